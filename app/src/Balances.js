@@ -1,12 +1,26 @@
 import React, { useEffect, useState } from 'react';
 import { Table, Grid, Button } from 'semantic-ui-react';
 import { CopyToClipboard } from 'react-copy-to-clipboard';
+import { formatBalance, u8aToString } from '@polkadot/util';
 import { useSubstrate } from './substrate-lib';
 
 export default function Main(props) {
   const { api, keyring } = useSubstrate();
   const accounts = keyring.getPairs();
   const [balances, setBalances] = useState({});
+  const [metadata, setMetadata] = useState(null);
+
+  useEffect(() => {
+    let unsubscribe = null;
+
+    api.query.tokens.metadata('Native', maybeMetadata => {
+      setMetadata(maybeMetadata.isSome ? maybeMetadata.unwrap() : null);
+    }).then(unsub => {
+      unsubscribe = unsub;
+    }).catch(console.error);
+
+    return () => unsubscribe && unsubscribe();
+  }, [api, setMetadata]);
 
   useEffect(() => {
     const addresses = keyring.getPairs().map(account => [account.address, "Native"]);
@@ -15,7 +29,7 @@ export default function Main(props) {
     api.query.tokens.balances
       .multi(addresses, balances => {
         const balancesMap = addresses.reduce((acc, addressAndCurrency, index) => ({
-          ...acc, [addressAndCurrency[0]]: balances[index].free.toHuman()
+          ...acc, [addressAndCurrency[0]]: balances[index].free
         }), {});
         setBalances(balancesMap);
       }).then(unsub => {
@@ -25,6 +39,13 @@ export default function Main(props) {
     return () => unsubscribeAll && unsubscribeAll();
   }, [api, keyring, setBalances]);
 
+  const displayBalance = balance => metadata
+    ? formatBalance(balance, {
+      decimals: metadata.decimals.toNumber(),
+      withUnit: u8aToString(metadata.symbol)
+    })
+    : balance.toHuman();
+
   return (
     <Grid.Column>
       <h1>Balances</h1>
@@ -61,7 +82,7 @@ export default function Main(props) {
               </Table.Cell>
               <Table.Cell width={3}>{
                 balances && balances[account.address] &&
-                balances[account.address]
+                displayBalance(balances[account.address])
               }</Table.Cell>
             </Table.Row>
           )}
@@ -18,6 +18,9 @@ function Main(props) {
     const [status, setStatus] = useState(null);
     // Which org address we chose
     const [orgAddress, setOrgAddress] = useState(null);
+    // Human readable context of the proposal
+    const [title, setTitle] = useState('');
+    const [description, setDescription] = useState('');
     // Rest is from the 'Interactor' component
     const [palletRPCs, setPalletRPCs] = useState([]);
     const [callables, setCallables] = useState([]);
@@ -158,12 +161,31 @@ function Main(props) {
                         }
                     </Form.Field>
                 )}
+                <Form.Field>
+                    <Input
+                        fluid
+                        type='text'
+                        label='Title'
+                        value={title}
+                        onChange={(_, { value }) => setTitle(value)}
+                    />
+                </Form.Field>
+                <Form.Field>
+                    <Input
+                        fluid
+                        type='text'
+                        label='Description or IPFS CID'
+                        value={description}
+                        onChange={(_, { value }) => setDescription(value)}
+                    />
+                </Form.Field>
                 <Form.Field style={{ textAlign: 'center' }}>
                     <TxButton
                         accountPair={accountPair}
                         label='Propose'
                         type='SIGNED-TX-FOR-ORG'
                         org={orgAddress}
+                        proposalMetadata={{ title, description, content_hash: null }}
                         setStatus={setStatus}
                         attrs={{
                             palletRpc, callable, inputParams, paramFields
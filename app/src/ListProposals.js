@@ -87,7 +87,9 @@ function Main(props) {
                 .map((prop) => ({
                     key: prop,
                     value: prop,
-                    text: parseCall(api, allProposals[prop].call),
+                    text: allProposals[prop].metadata.title.isEmpty
+                        ? allProposals[prop].calls.map((call) => parseCall(api, call)).join(', ')
+                        : allProposals[prop].metadata.title.toUtf8(),
                 }))
         );
 
@@ -231,9 +233,11 @@ function Main(props) {
 
         // a random address that we are using for our queries
         const ZERO_ACCOUNT = '5CAUdnwecHGxxyr5vABevAfZ34Fi4AaraDRMwfDQXQ52PXqg';
-        api.tx(api.createType('Call', allProposals[value].call))
-            .paymentInfo(ZERO_ACCOUNT)
-            .then(({ weight }) => setPropWeight(weight))
+        // closing requires a weight bound covering every call of the proposal
+        Promise.all(allProposals[value].calls.map((call) =>
+            api.tx(api.createType('Call', call)).paymentInfo(ZERO_ACCOUNT)
+        ))
+            .then((infos) => setPropWeight(infos.reduce((acc, { weight }) => acc + weight.toNumber(), 0)))
             .catch(console.error);
     };
 
@@ -61,6 +61,19 @@ const reducer = (state, action) => {
 ///
 // Connecting to the Substrate node
 
+// Custom signed extensions of the runtime, the fee currency defaults to the native one.
+// `ChargeSponsoredTransaction` replaces `ChargeTransactionPayment` and is encoded the same way.
+const signedExtensions = {
+  SetFeeCurrency: {
+    extrinsic: { feeCurrency: 'Option<CurrencyId>' },
+    payload: {}
+  },
+  ChargeSponsoredTransaction: {
+    extrinsic: { tip: 'Compact<Balance>' },
+    payload: {}
+  }
+};
+
 const connect = (state, dispatch) => {
   const { apiState, socket, jsonrpc, types } = state;
   // We only want this function to be performed once
@@ -69,7 +82,7 @@ const connect = (state, dispatch) => {
   dispatch({ type: 'CONNECT_INIT' });
 
   const provider = new WsProvider(socket);
-  const _api = new ApiPromise({ provider, types, rpc: jsonrpc });
+  const _api = new ApiPromise({ provider, types, rpc: jsonrpc, signedExtensions });
 
   // Set listeners for disconnection and reconnection event.
   _api.on('connected', () => {
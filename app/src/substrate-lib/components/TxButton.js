@@ -15,6 +15,7 @@ function TxButton({
   type = 'QUERY',
   attrs = null,
   org = null,
+  proposalMetadata = null,
   disabled = false
 }) {
   // Hooks
@@ -101,7 +102,8 @@ function TxButton({
     const txExecute = transformed
       ? api.tx[palletRpc][callable](...transformed)
       : api.tx[palletRpc][callable]();
-    const txOrg = api.tx.organizations.createProposal(org, txExecute);
+    const metadata = proposalMetadata || { title: '', description: '', content_hash: null };
+    const txOrg = api.tx.organizations.createProposal(org, [txExecute], 'AllOrNothing', metadata);
 
     const unsub = await txOrg.signAndSend(fromAcct, txResHandler)
       .catch(txErrHandler);
@@ -259,6 +261,11 @@ TxButton.propTypes = {
     paramFields: PropTypes.array
   }).isRequired,
   org: PropTypes.string,
+  proposalMetadata: PropTypes.shape({
+    title: PropTypes.string,
+    description: PropTypes.string,
+    content_hash: PropTypes.string
+  }),
 };
 
 function TxGroupButton(props) {
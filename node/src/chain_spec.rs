@@ -17,12 +17,13 @@
 use crate::helpers::core_org;
 use governance_os_pallet_coin_voting::VoteCountingStrategy;
 use governance_os_pallet_organizations::OrganizationDetails;
-use governance_os_pallet_tokens::CurrencyDetails;
-use governance_os_primitives::{AccountId, CurrencyId, Role, Signature};
+use governance_os_pallet_tokens::{CurrencyDetails, CurrencyMetadata};
+use governance_os_primitives::{AccountId, Balance, BlockNumber, CurrencyId, Role, Signature};
 use governance_os_runtime::{
     AuraConfig, AuraId, BylawsConfig, CoinVotingParameters, GenesisConfig, GrandpaConfig,
-    GrandpaId, NativeCurrencyId, OrganizationsConfig, RuntimeVotingParameters,
-    RuntimeVotingSystemId, SystemConfig, TokensConfig, WASM_BINARY,
+    GrandpaId, NativeCurrencyId, NativeExistentialDeposit, OrganizationsConfig,
+    RuntimeVotingParameters, RuntimeVotingSystemId, SystemConfig, TokensConfig, VestingConfig,
+    WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_core::{sr25519, Pair, Public};
@@ -58,10 +59,23 @@ fn testnet_genesis(
     wasm_binary: &[u8],
     initial_authorities: Vec<(AuraId, GrandpaId)>,
     endowed_accounts: Vec<AccountId>,
-    currencies: Option<Vec<(CurrencyId, CurrencyDetails<AccountId>)>>,
+    currencies: Option<
+        Vec<(
+            CurrencyId,
+            CurrencyDetails<AccountId, Balance, BlockNumber>,
+            Option<CurrencyMetadata>,
+        )>,
+    >,
     roles: Option<Vec<(Role, Option<AccountId>)>>,
     organizations: Option<
-        Vec<OrganizationDetails<AccountId, (RuntimeVotingSystemId, RuntimeVotingParameters)>>,
+        Vec<
+            OrganizationDetails<
+                AccountId,
+                (RuntimeVotingSystemId, RuntimeVotingParameters),
+                CurrencyId,
+                Balance,
+            >,
+        >,
     >,
 ) -> GenesisConfig {
     let chain_currencies = currencies.unwrap_or(vec![(
@@ -69,7 +83,12 @@ fn testnet_genesis(
         CurrencyDetails {
             owner: core_org(),
             transferable: true,
+            restricted_receivers: false,
+            existential_deposit: NativeExistentialDeposit::get(),
+            max_supply: None,
+            issuance: None,
         },
+        None,
     )]);
     let chain_roles = roles.unwrap_or(vec![
         (Role::Root, Some(core_org())),
@@ -86,8 +105,11 @@ fn testnet_genesis(
                 min_quorum: 50,
                 min_participation: 33,
                 vote_counting_strategy: VoteCountingStrategy::Simple,
+                snapshot: false,
             }),
         ),
+        guardians: None,
+        proposal_deposit: None,
     }]);
 
     GenesisConfig {
@@ -113,7 +135,11 @@ fn testnet_genesis(
                 .collect::<Vec<_>>(),
             currency_details: chain_currencies,
         }),
-        governance_os_pallet_bylaws: Some(BylawsConfig { roles: chain_roles }),
+        governance_os_pallet_vesting: Some(VestingConfig { vesting: vec![] }),
+        governance_os_pallet_bylaws: Some(BylawsConfig {
+            roles: chain_roles,
+            role_settings: vec![],
+        }),
         governance_os_pallet_organizations: Some(OrganizationsConfig {
             organizations: chain_orgs,
         }),
@@ -16,7 +16,8 @@
 
 //! Implement node specific RPC handlers.
 
-use governance_os_primitives::{AccountId, Balance, Block, Index};
+use governance_os_primitives::{AccountId, Balance, Block, Hash, Index};
+use governance_os_runtime::RuntimeProposalTally;
 use jsonrpc_core::IoHandler;
 pub use sc_rpc_api::DenyUnsafe;
 use sp_api::ProvideRuntimeApi;
@@ -43,9 +44,12 @@ where
     C: Send + Sync + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api:
+        governance_os_pallet_organizations_rpc::VotingRuntimeApi<Block, Hash, RuntimeProposalTally>,
     C::Api: BlockBuilder<Block>,
     P: TransactionPool + 'static,
 {
+    use governance_os_pallet_organizations_rpc::{Voting, VotingApi};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
     use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
@@ -66,6 +70,8 @@ where
         client.clone(),
     )));
 
+    io.extend_with(VotingApi::to_delegate(Voting::new(client)));
+
     // Extend this RPC with a custom API by using the following syntax.
     // `YourRpcStruct` should have a reference to a client, which is needed
     // to call into the runtime.
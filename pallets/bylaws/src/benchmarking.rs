@@ -41,7 +41,13 @@ fn prepare_benchmark<T: Config>(
         // `grant_role` would avoid duplicates so we have set this manually. Note that for those
         // benchmarks to be relevant the root role MUST be different from `T::Role::default()`.
         // We are simply trying to force the maximum number of iterations (worst case scenario).
-        Roles::<T>::mutate(Some(&target), |v| v.push(RoleBuilderOf::<T>::root()));
+        Roles::<T>::mutate(Some(&target), |v| {
+            v.push(RoleGrant {
+                role: RoleBuilderOf::<T>::root(),
+                valid_from: None,
+                valid_until: None,
+            })
+        });
     }
 
     drop(<Module<T> as RoleManager>::grant_role(
@@ -58,7 +64,18 @@ benchmarks! {
         let (root, target, target_lookup, role) = prepare_benchmark::<T>(b);
     }: _(RawOrigin::Signed(root), Some(target_lookup), role.clone())
     verify {
-        assert_eq!(Roles::<T>::get(Some(&target)).iter().any(|r| r.clone() == role), true);
+        assert_eq!(Roles::<T>::get(Some(&target)).iter().any(|g| g.role == role), true);
+    }
+
+    grant_role_with_validity {
+        let b in 0 .. T::MaxRoles::get();
+
+        let (root, target, target_lookup, role) = prepare_benchmark::<T>(b);
+        let valid_from = frame_system::Module::<T>::block_number();
+        let valid_until = valid_from + 10u32.into();
+    }: _(RawOrigin::Signed(root), Some(target_lookup), role.clone(), Some(valid_from), Some(valid_until))
+    verify {
+        assert_eq!(Roles::<T>::get(Some(&target)).iter().any(|g| g.role == role), true);
     }
 
     revoke_role {
@@ -68,7 +85,20 @@ benchmarks! {
         drop(<Module<T> as RoleManager>::grant_role(Some(&target), role.clone()));
     }: _(RawOrigin::Signed(root), Some(target_lookup), role.clone())
     verify {
-        assert_eq!(Roles::<T>::get(Some(&target)).iter().any(|r| r.clone() == role), false);
+        assert_eq!(Roles::<T>::get(Some(&target)).iter().any(|g| g.role == role), false);
+    }
+
+    set_role_settings {
+        let p in 0 .. T::MaxRoles::get();
+
+        let (root, _, _, role) = prepare_benchmark::<T>(0);
+        let settings = RoleSettings {
+            parents: vec![RoleBuilderOf::<T>::manage_roles(); p as usize],
+            admin: Some(RoleBuilderOf::<T>::manage_roles()),
+        };
+    }: _(RawOrigin::Signed(root), role.clone(), settings.clone())
+    verify {
+        assert_eq!(Settings::<T>::get(&role), settings);
     }
 }
 
@@ -28,10 +28,22 @@ impl crate::WeightInfo for () {
             .saturating_add(DbWeight::get().reads(7 as Weight))
             .saturating_add(DbWeight::get().writes(3 as Weight))
     }
+    fn grant_role_with_validity(b: u32) -> Weight {
+        (66_108_000 as Weight)
+            .saturating_add((185_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
     fn revoke_role(b: u32) -> Weight {
         (64_362_000 as Weight)
             .saturating_add((201_000 as Weight).saturating_mul(b as Weight))
             .saturating_add(DbWeight::get().reads(7 as Weight))
             .saturating_add(DbWeight::get().writes(3 as Weight))
     }
+    fn set_role_settings(p: u32) -> Weight {
+        (38_410_000 as Weight)
+            .saturating_add((1_082_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
 }
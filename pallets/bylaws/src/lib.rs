@@ -18,41 +18,52 @@
 //! A role is the equivalent of a UNIX role, it can be granted either to
 //! one or many account or even to all the accounts within the system.
 //! Pallets can then use it to define custom role requirements.
+//! Grants can optionally be limited to a range of blocks, expired grants are
+//! pruned whenever the roles of their target are modified.
+//! Roles can also be organized in a hierarchy: a role may be implied by one or
+//! many parent roles and may only be granted or revoked by its admin role.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{weights::Weight, Parameter};
+use frame_support::{traits::Get, weights::Weight, Parameter};
 use governance_os_support::traits::RoleManager;
 use sp_runtime::{
     traits::{MaybeSerializeDeserialize, Member, StaticLookup},
     DispatchResult,
 };
-use sp_std::prelude::Vec;
+use sp_std::{collections::btree_set::BTreeSet, prelude::Vec};
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 mod default_weights;
 #[cfg(test)]
 mod tests;
+mod types;
 
 pub use pallet::*;
+pub use types::{Releases, RoleGrant, RoleSettings};
 
 pub trait WeightInfo {
     fn grant_role(b: u32) -> Weight;
+    fn grant_role_with_validity(b: u32) -> Weight;
     fn revoke_role(b: u32) -> Weight;
+    fn set_role_settings(p: u32) -> Weight;
 }
 
 pub trait RoleBuilder {
     type Role;
 
-    /// Give access to the functions `grant_role` and `revoke_role`.
+    /// Default admin role, give access to the functions `grant_role` and `revoke_role`
+    /// for all the roles that do not have a custom admin, except `root`.
     fn manage_roles() -> Self::Role;
 
     /// This role would be the equivalent of a super role. If an account is granted it it can submit
     /// any other calls.
+    /// It is also the default admin of itself.
     fn root() -> Self::Role;
 }
 type RoleBuilderOf<T> = <T as Config>::RoleBuilder;
+type RoleGrantOf<T> = RoleGrant<<T as Config>::Role, <T as frame_system::Config>::BlockNumber>;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -79,6 +90,11 @@ pub mod pallet {
         /// to have.
         type MaxRoles: Get<u32>;
 
+        /// Maximum number of ancestors, across all the levels of the hierarchy, looked at when
+        /// checking if an account holds a role. Settings giving a role more ancestors are
+        /// rejected.
+        type MaxRoleAncestors: Get<u32>;
+
         /// Helper for the runtime to specify its custom roles.
         type RoleBuilder: RoleBuilder<Role = Self::Role>;
     }
@@ -89,18 +105,36 @@ pub mod pallet {
         RoleNotFound,
         /// Target was already granted this role.
         RoleAlreadyExists,
+        /// The validity window of the grant is empty or already over.
+        InvalidValidity,
+        /// A role can not have more than `MaxRoles` parents.
+        TooManyParents,
+        /// The role would have more than `MaxRoleAncestors` ancestors.
+        TooManyAncestors,
     }
 
     #[pallet::storage]
     #[pallet::getter(fn roles)]
     /// Roles granted to the different accounts. If a role is granted to `None` this means
-    /// it is granted to all accounts from the runtime.
+    /// it is granted to all accounts from the runtime. Grants are sorted by role.
     pub(super) type Roles<T: Config> =
-        StorageMap<_, Blake2_128Concat, Option<T::AccountId>, Vec<T::Role>, ValueQuery>;
+        StorageMap<_, Blake2_128Concat, Option<T::AccountId>, Vec<RoleGrantOf<T>>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn role_settings)]
+    /// Parents and admin of the roles. Roles without any entry have no parents and
+    /// use the default admin role.
+    pub(super) type Settings<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Role, RoleSettings<T::Role>, ValueQuery>;
+
+    #[pallet::storage]
+    /// Layout of the roles, used to know which migrations still need to run.
+    pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
 
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub roles: Vec<(T::Role, Option<T::AccountId>)>,
+        pub role_settings: Vec<(T::Role, RoleSettings<T::Role>)>,
     }
 
     #[cfg(feature = "std")]
@@ -108,6 +142,7 @@ pub mod pallet {
         fn default() -> Self {
             Self {
                 roles: Default::default(),
+                role_settings: Default::default(),
             }
         }
     }
@@ -115,6 +150,10 @@ pub mod pallet {
     #[pallet::genesis_build]
     impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
         fn build(&self) {
+            StorageVersion::<T>::put(Releases::V2);
+            self.role_settings
+                .iter()
+                .for_each(|(role, settings)| Settings::<T>::insert(role, settings));
             self.roles.iter().for_each(|(role, target)| {
                 drop(<Pallet<T> as RoleManager>::grant_role(
                     target.as_ref(),
@@ -126,28 +165,44 @@ pub mod pallet {
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
-    #[pallet::metadata(T::AccountId = "AccountId", T::Role = "Role")]
+    #[pallet::metadata(
+        T::AccountId = "AccountId",
+        T::Role = "Role",
+        T::BlockNumber = "BlockNumber"
+    )]
     pub enum Event<T: Config> {
-        /// A role has been granted to an account. \[account, role\]
-        RoleGranted(Option<T::AccountId>, T::Role),
+        /// A role has been granted to an account. \[account, role, valid_from, valid_until\]
+        RoleGranted(
+            Option<T::AccountId>,
+            T::Role,
+            Option<T::BlockNumber>,
+            Option<T::BlockNumber>,
+        ),
         /// A role has been revoked from an account. \[account, role\]
         RoleRevoked(Option<T::AccountId>, T::Role),
+        /// The parents and admin of a role were updated. \[role, parents, admin\]
+        RoleSettingsUpdated(T::Role, Vec<T::Role>, Option<T::Role>),
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            Self::migrate_to_v2()
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Add a `role` to a given account `who`. If `who` is set to `None` this
-        /// means that the role is granted to all the accounts of the chain.
-        #[pallet::weight(T::WeightInfo::grant_role(T::MaxRoles::get()))]
+        /// means that the role is granted to all the accounts of the chain. Can
+        /// only be called by holders of the admin role of `role`.
+        #[pallet::weight(T::WeightInfo::grant_role(T::MaxRoles::get()).saturating_add(Pallet::<T>::has_role_weight()))]
         pub(super) fn grant_role(
             origin: OriginFor<T>,
             who: Option<<T::Lookup as StaticLookup>::Source>,
             role: T::Role,
         ) -> DispatchResultWithPostInfo {
-            Self::ensure_has_role(origin, RoleBuilderOf::<T>::manage_roles())?;
+            Self::ensure_has_role(origin, Self::admin_of(&role))?;
 
             let target = match who {
                 Some(lookmeup) => Some(T::Lookup::lookup(lookmeup)?),
@@ -159,15 +214,44 @@ pub mod pallet {
             Ok(().into())
         }
 
+        /// Same as `grant_role` but the role is only effective between the blocks
+        /// `valid_from` and `valid_until` included. A `None` bound means that the
+        /// grant is effective immediately or never expires.
+        #[pallet::weight(T::WeightInfo::grant_role_with_validity(T::MaxRoles::get()).saturating_add(Pallet::<T>::has_role_weight()))]
+        pub(super) fn grant_role_with_validity(
+            origin: OriginFor<T>,
+            who: Option<<T::Lookup as StaticLookup>::Source>,
+            role: T::Role,
+            valid_from: Option<T::BlockNumber>,
+            valid_until: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            Self::ensure_has_role(origin, Self::admin_of(&role))?;
+
+            let target = match who {
+                Some(lookmeup) => Some(T::Lookup::lookup(lookmeup)?),
+                None => None,
+            };
+
+            <Self as RoleManager>::grant_role_with_validity(
+                target.as_ref(),
+                role,
+                valid_from,
+                valid_until,
+            )?;
+
+            Ok(().into())
+        }
+
         /// Remove a `role` from a given account `who`. If `who` is set to `None` this means
-        /// that the role is revoked for all the accounts of the chain.
-        #[pallet::weight(T::WeightInfo::revoke_role(T::MaxRoles::get()))]
+        /// that the role is revoked for all the accounts of the chain. Can only be called
+        /// by holders of the admin role of `role`.
+        #[pallet::weight(T::WeightInfo::revoke_role(T::MaxRoles::get()).saturating_add(Pallet::<T>::has_role_weight()))]
         pub(super) fn revoke_role(
             origin: OriginFor<T>,
             who: Option<<T::Lookup as StaticLookup>::Source>,
             role: T::Role,
         ) -> DispatchResultWithPostInfo {
-            Self::ensure_has_role(origin, RoleBuilderOf::<T>::manage_roles())?;
+            Self::ensure_has_role(origin, Self::admin_of(&role))?;
 
             let target = match who {
                 Some(lookmeup) => Some(T::Lookup::lookup(lookmeup)?),
@@ -178,31 +262,163 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Set the parents and the admin role of `role`. Holders of any of the parent
+        /// roles will implicitly be granted `role`. Can only be called by holders of
+        /// the current admin role of `role`.
+        #[pallet::weight(
+            T::WeightInfo::set_role_settings(settings.parents.len() as u32)
+                // One walk for the admin check and one to count the ancestors
+                .saturating_add(Pallet::<T>::has_role_weight().saturating_mul(2))
+        )]
+        pub(super) fn set_role_settings(
+            origin: OriginFor<T>,
+            role: T::Role,
+            settings: RoleSettings<T::Role>,
+        ) -> DispatchResultWithPostInfo {
+            Self::ensure_has_role(origin, Self::admin_of(&role))?;
+            ensure!(
+                settings.parents.len() as u32 <= T::MaxRoles::get(),
+                Error::<T>::TooManyParents
+            );
+            ensure!(
+                Self::ancestors_count(&role, &settings.parents) <= T::MaxRoleAncestors::get(),
+                Error::<T>::TooManyAncestors
+            );
+
+            Settings::<T>::insert(&role, &settings);
+            Self::deposit_event(Event::RoleSettingsUpdated(
+                role,
+                settings.parents,
+                settings.admin,
+            ));
+
+            Ok(().into())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Wrap the roles granted before grants had a validity window into `RoleGrant`s valid
+    /// forever, they would otherwise fail to decode and be lost. Only runs once.
+    fn migrate_to_v2() -> Weight {
+        if StorageVersion::<T>::get() != Releases::V1 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut targets: u64 = 0;
+        Roles::<T>::translate::<Vec<T::Role>, _>(|_target, roles| {
+            targets = targets.saturating_add(1);
+            Some(
+                roles
+                    .into_iter()
+                    .map(|role| RoleGrant {
+                        role,
+                        valid_from: None,
+                        valid_until: None,
+                    })
+                    .collect(),
+            )
+        });
+        StorageVersion::<T>::put(Releases::V2);
+
+        T::DbWeight::get().reads_writes(targets.saturating_add(1), targets.saturating_add(1))
+    }
+
+    /// Returns the role allowed to grant and revoke `role`.
+    pub fn admin_of(role: &T::Role) -> T::Role {
+        Settings::<T>::get(role).admin.unwrap_or_else(|| {
+            if *role == RoleBuilderOf::<T>::root() {
+                RoleBuilderOf::<T>::root()
+            } else {
+                RoleBuilderOf::<T>::manage_roles()
+            }
+        })
+    }
+
+    /// Returns the number of distinct ancestors `role` would have if it had the parents
+    /// `parents`. Counting stops once it goes past `MaxRoleAncestors` so that the number of
+    /// storage reads stays bounded.
+    fn ancestors_count(role: &T::Role, parents: &[T::Role]) -> u32 {
+        let max_ancestors = T::MaxRoleAncestors::get() as usize;
+        let mut ancestors: Vec<T::Role> = Vec::new();
+        for parent in parents {
+            if parent != role && !ancestors.contains(parent) {
+                ancestors.push(parent.clone());
+            }
+        }
+
+        let mut index = 0;
+        while index < ancestors.len() && ancestors.len() <= max_ancestors {
+            for parent in Settings::<T>::get(&ancestors[index]).parents {
+                if &parent != role && !ancestors.contains(&parent) {
+                    ancestors.push(parent);
+                }
+            }
+            index += 1;
+        }
+        ancestors.len() as u32
+    }
+
+    /// Weight of a role check that read the settings of `settings_reads` roles.
+    fn role_check_weight(settings_reads: u32) -> Weight {
+        T::DbWeight::get().reads(settings_reads.saturating_add(2).into())
     }
 }
 
 impl<T: Config> RoleManager for Pallet<T> {
     type AccountId = T::AccountId;
     type Role = T::Role;
+    type BlockNumber = T::BlockNumber;
+
+    fn grant_role_with_validity(
+        target: Option<&Self::AccountId>,
+        role: Self::Role,
+        valid_from: Option<Self::BlockNumber>,
+        valid_until: Option<Self::BlockNumber>,
+    ) -> DispatchResult {
+        let now = frame_system::Module::<T>::block_number();
+        let grant = RoleGrant {
+            role: role.clone(),
+            valid_from,
+            valid_until,
+        };
+        if grant.is_expired(&now) {
+            return Err(Error::<T>::InvalidValidity.into());
+        }
+        if let (Some(from), Some(until)) = (&grant.valid_from, &grant.valid_until) {
+            if from > until {
+                return Err(Error::<T>::InvalidValidity.into());
+            }
+        }
 
-    fn grant_role(target: Option<&Self::AccountId>, role: Self::Role) -> DispatchResult {
-        Roles::<T>::try_mutate(target, |v| match v.binary_search(&role.clone()) {
-            Ok(_) => Err(Error::<T>::RoleAlreadyExists.into()),
-            Err(index) => {
-                v.insert(index, role.clone());
-                Ok(())
+        Roles::<T>::try_mutate(target, |v| {
+            v.retain(|g| !g.is_expired(&now));
+            match v.binary_search_by(|g| g.role.cmp(&role)) {
+                Ok(_) => Err(Error::<T>::RoleAlreadyExists.into()),
+                Err(index) => {
+                    v.insert(index, grant);
+                    Ok(())
+                }
             }
         })
         .map(|result| {
-            Self::deposit_event(Event::RoleGranted(target.cloned(), role));
+            Self::deposit_event(Event::RoleGranted(
+                target.cloned(),
+                role,
+                valid_from,
+                valid_until,
+            ));
             result
         })
     }
 
     fn revoke_role(target: Option<&Self::AccountId>, role: Self::Role) -> DispatchResult {
+        let now = frame_system::Module::<T>::block_number();
         Roles::<T>::try_mutate_exists(target, |v| {
             let mut vec = v.take().unwrap_or_default();
-            match vec.binary_search(&role) {
+            vec.retain(|g| !g.is_expired(&now));
+            match vec.binary_search_by(|g| g.role.cmp(&role)) {
                 Ok(index) => {
                     vec.remove(index);
                     if vec.is_empty() {
@@ -222,12 +438,48 @@ impl<T: Config> RoleManager for Pallet<T> {
     }
 
     fn has_role(target: &Self::AccountId, role: Self::Role) -> bool {
-        Roles::<T>::get(Some(target))
+        Self::has_role_with_weight(target, role).0
+    }
+
+    fn has_role_weight() -> Weight {
+        // Settings are only read while less than `MaxRoleAncestors` ancestors were queued.
+        Self::role_check_weight(T::MaxRoleAncestors::get())
+    }
+
+    fn has_role_with_weight(target: &Self::AccountId, role: Self::Role) -> (bool, Weight) {
+        let now = frame_system::Module::<T>::block_number();
+        let granted: BTreeSet<T::Role> = Roles::<T>::get(Some(target))
             .into_iter()
             .chain(Roles::<T>::get(None as Option<T::AccountId>).into_iter())
-            .find(|r| {
-                return *r == role || *r == RoleBuilderOf::<T>::root();
-            })
-            .is_some()
+            .filter(|g| g.is_active(&now))
+            .map(|g| g.role)
+            .collect();
+        if granted.contains(&RoleBuilderOf::<T>::root()) {
+            return (true, Self::role_check_weight(0));
+        }
+
+        // Walk up the role hierarchy breadth first, skipping the roles we already queued in
+        // case the settings contain a cycle. At most `MaxRoleAncestors` ancestors are looked
+        // at, however the hierarchy fans out, so that the walk stays bounded.
+        let max_roles = T::MaxRoleAncestors::get().saturating_add(1) as usize;
+        let mut to_visit = sp_std::vec![role];
+        let mut settings_reads = 0;
+        let mut index = 0;
+        while index < to_visit.len() {
+            if granted.contains(&to_visit[index]) {
+                return (true, Self::role_check_weight(settings_reads));
+            }
+            if to_visit.len() < max_roles {
+                settings_reads += 1;
+                for parent in Settings::<T>::get(&to_visit[index]).parents {
+                    if to_visit.len() < max_roles && !to_visit.contains(&parent) {
+                        to_visit.push(parent);
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        (false, Self::role_check_weight(settings_reads))
     }
 }
@@ -15,7 +15,8 @@
  */
 
 use super::mock::*;
-use frame_support::assert_ok;
+use crate::{Error, RoleGrant};
+use frame_support::{assert_noop, assert_ok};
 use frame_system::RawOrigin;
 use governance_os_support::{
     testing::{ALICE, ROOT},
@@ -48,3 +49,114 @@ fn revoke_role() {
             assert!(!Bylaws::has_role(&ALICE, MockRoles::Root));
         })
 }
+
+#[test]
+fn grant_role_with_validity_respects_window() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Bylaws::grant_role_with_validity(
+            RawOrigin::Signed(ROOT).into(),
+            Some(ALICE),
+            MockRoles::RemarkOnly,
+            Some(5),
+            Some(10),
+        ));
+        assert!(!Bylaws::has_role(&ALICE, MockRoles::RemarkOnly));
+
+        System::set_block_number(5);
+        assert!(Bylaws::has_role(&ALICE, MockRoles::RemarkOnly));
+
+        System::set_block_number(10);
+        assert!(Bylaws::has_role(&ALICE, MockRoles::RemarkOnly));
+
+        System::set_block_number(11);
+        assert!(!Bylaws::has_role(&ALICE, MockRoles::RemarkOnly));
+    })
+}
+
+#[test]
+fn grant_role_with_validity_rejects_invalid_windows() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(5);
+
+        assert_noop!(
+            Bylaws::grant_role_with_validity(
+                RawOrigin::Signed(ROOT).into(),
+                Some(ALICE),
+                MockRoles::RemarkOnly,
+                Some(10),
+                Some(8),
+            ),
+            Error::<Test>::InvalidValidity
+        );
+        assert_noop!(
+            Bylaws::grant_role_with_validity(
+                RawOrigin::Signed(ROOT).into(),
+                Some(ALICE),
+                MockRoles::RemarkOnly,
+                None,
+                Some(4),
+            ),
+            Error::<Test>::InvalidValidity
+        );
+    })
+}
+
+#[test]
+fn expired_grants_are_pruned_on_mutation() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Bylaws::grant_role_with_validity(
+            RawOrigin::Signed(ROOT).into(),
+            Some(ALICE),
+            MockRoles::RemarkOnly,
+            None,
+            Some(2),
+        ));
+        assert_noop!(
+            Bylaws::grant_role(
+                RawOrigin::Signed(ROOT).into(),
+                Some(ALICE),
+                MockRoles::RemarkOnly,
+            ),
+            Error::<Test>::RoleAlreadyExists
+        );
+
+        System::set_block_number(3);
+        assert_noop!(
+            Bylaws::revoke_role(
+                RawOrigin::Signed(ROOT).into(),
+                Some(ALICE),
+                MockRoles::RemarkOnly,
+            ),
+            Error::<Test>::RoleNotFound
+        );
+        assert_ok!(Bylaws::grant_role(
+            RawOrigin::Signed(ROOT).into(),
+            Some(ALICE),
+            MockRoles::RemarkOnly,
+        ));
+        assert_eq!(
+            Bylaws::roles(Some(ALICE)),
+            vec![RoleGrant {
+                role: MockRoles::RemarkOnly,
+                valid_from: None,
+                valid_until: None,
+            }]
+        );
+    })
+}
+
+#[test]
+fn expired_root_grant_does_not_imply_other_roles() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(<Bylaws as RoleManager>::grant_role_with_validity(
+            Some(&ALICE),
+            MockRoles::Root,
+            None,
+            Some(3),
+        ));
+        assert!(Bylaws::has_role(&ALICE, MockRoles::RemarkOnly));
+
+        System::set_block_number(4);
+        assert!(!Bylaws::has_role(&ALICE, MockRoles::RemarkOnly));
+    })
+}
@@ -0,0 +1,301 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{Error, RoleSettings};
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use governance_os_support::{
+    errors::AclError,
+    testing::{ALICE, BOB, CHARLIE, ROOT},
+    traits::RoleManager,
+};
+
+#[test]
+fn parents_imply_their_children() {
+    ExtBuilder::default()
+        .with_role(MockRoles::ManageRemarks, Some(ALICE))
+        .with_role_settings(MockRoles::RemarkOnly, vec![MockRoles::ManageRemarks], None)
+        .build()
+        .execute_with(|| {
+            assert!(Bylaws::has_role(&ALICE, MockRoles::RemarkOnly));
+            assert!(!Bylaws::has_role(&BOB, MockRoles::RemarkOnly));
+            assert!(!Bylaws::has_role(&ALICE, MockRoles::ManageRoles));
+        })
+}
+
+#[test]
+fn parents_are_resolved_recursively() {
+    ExtBuilder::default()
+        .with_role(MockRoles::ManageRoles, Some(ALICE))
+        .with_role_settings(MockRoles::RemarkOnly, vec![MockRoles::ManageRemarks], None)
+        .with_role_settings(MockRoles::ManageRemarks, vec![MockRoles::ManageRoles], None)
+        .build()
+        .execute_with(|| {
+            assert!(Bylaws::has_role(&ALICE, MockRoles::ManageRemarks));
+            assert!(Bylaws::has_role(&ALICE, MockRoles::RemarkOnly));
+        })
+}
+
+#[test]
+fn cycles_in_the_hierarchy_are_handled() {
+    ExtBuilder::default()
+        .with_role(MockRoles::RemarkOnly, Some(ALICE))
+        .with_role_settings(MockRoles::ManageRemarks, vec![MockRoles::ManageRoles], None)
+        .with_role_settings(MockRoles::ManageRoles, vec![MockRoles::ManageRemarks], None)
+        .build()
+        .execute_with(|| {
+            assert!(!Bylaws::has_role(&ALICE, MockRoles::ManageRemarks));
+        })
+}
+
+#[test]
+fn custom_admin_can_grant_and_revoke() {
+    ExtBuilder::default()
+        .with_role(MockRoles::ManageRemarks, Some(ALICE))
+        .with_role_settings(
+            MockRoles::RemarkOnly,
+            vec![],
+            Some(MockRoles::ManageRemarks),
+        )
+        .build()
+        .execute_with(|| {
+            assert_ok!(Bylaws::grant_role(
+                RawOrigin::Signed(ALICE).into(),
+                Some(BOB),
+                MockRoles::RemarkOnly,
+            ));
+            assert!(Bylaws::has_role(&BOB, MockRoles::RemarkOnly));
+
+            assert_ok!(Bylaws::revoke_role(
+                RawOrigin::Signed(ALICE).into(),
+                Some(BOB),
+                MockRoles::RemarkOnly,
+            ));
+            assert!(!Bylaws::has_role(&BOB, MockRoles::RemarkOnly));
+
+            assert_noop!(
+                Bylaws::grant_role(RawOrigin::Signed(ALICE).into(), Some(BOB), MockRoles::Root),
+                AclError::MissingRole
+            );
+        })
+}
+
+#[test]
+fn custom_admin_replaces_manage_roles() {
+    ExtBuilder::default()
+        .with_role(MockRoles::ManageRoles, Some(ALICE))
+        .with_role_settings(
+            MockRoles::RemarkOnly,
+            vec![],
+            Some(MockRoles::ManageRemarks),
+        )
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Bylaws::grant_role(
+                    RawOrigin::Signed(ALICE).into(),
+                    Some(BOB),
+                    MockRoles::RemarkOnly,
+                ),
+                AclError::MissingRole
+            );
+        })
+}
+
+#[test]
+fn manage_roles_can_not_grant_root() {
+    ExtBuilder::default()
+        .with_role(MockRoles::ManageRoles, Some(ALICE))
+        .build()
+        .execute_with(|| {
+            assert_ok!(Bylaws::grant_role(
+                RawOrigin::Signed(ALICE).into(),
+                Some(BOB),
+                MockRoles::ManageRemarks,
+            ));
+            assert_noop!(
+                Bylaws::grant_role(RawOrigin::Signed(ALICE).into(), Some(BOB), MockRoles::Root),
+                AclError::MissingRole
+            );
+            assert_ok!(Bylaws::grant_role(
+                RawOrigin::Signed(ROOT).into(),
+                Some(BOB),
+                MockRoles::Root,
+            ));
+        })
+}
+
+#[test]
+fn set_role_settings() {
+    ExtBuilder::default()
+        .with_role(MockRoles::ManageRoles, Some(ALICE))
+        .with_role(MockRoles::ManageRemarks, Some(CHARLIE))
+        .build()
+        .execute_with(|| {
+            let settings = RoleSettings {
+                parents: vec![MockRoles::ManageRemarks],
+                admin: Some(MockRoles::ManageRemarks),
+            };
+            assert_ok!(Bylaws::set_role_settings(
+                RawOrigin::Signed(ALICE).into(),
+                MockRoles::RemarkOnly,
+                settings.clone(),
+            ));
+            assert_eq!(Bylaws::role_settings(MockRoles::RemarkOnly), settings);
+            assert!(Bylaws::has_role(&CHARLIE, MockRoles::RemarkOnly));
+
+            // Only the new admin can update the settings now.
+            assert_noop!(
+                Bylaws::set_role_settings(
+                    RawOrigin::Signed(ALICE).into(),
+                    MockRoles::RemarkOnly,
+                    RoleSettings::default(),
+                ),
+                AclError::MissingRole
+            );
+        })
+}
+
+#[test]
+fn set_role_settings_of_root_requires_root() {
+    ExtBuilder::default()
+        .with_role(MockRoles::ManageRoles, Some(ALICE))
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Bylaws::set_role_settings(
+                    RawOrigin::Signed(ALICE).into(),
+                    MockRoles::Root,
+                    RoleSettings {
+                        parents: vec![MockRoles::ManageRoles],
+                        admin: None,
+                    },
+                ),
+                AclError::MissingRole
+            );
+        })
+}
+
+#[test]
+fn set_role_settings_fails_if_too_many_parents() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Bylaws::set_role_settings(
+                RawOrigin::Signed(ROOT).into(),
+                MockRoles::RemarkOnly,
+                RoleSettings {
+                    parents: vec![MockRoles::ManageRemarks; MaxRoles::get() as usize + 1],
+                    admin: None,
+                },
+            ),
+            Error::<Test>::TooManyParents
+        );
+    })
+}
+
+#[test]
+fn set_role_settings_fails_if_too_many_ancestors() {
+    ExtBuilder::default()
+        .with_role_settings(MockRoles::ManageRemarks, vec![MockRoles::ManageRoles], None)
+        .with_role_settings(MockRoles::ManageRoles, vec![MockRoles::Root], None)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Bylaws::set_role_settings(
+                    RawOrigin::Signed(ROOT).into(),
+                    MockRoles::RemarkOnly,
+                    RoleSettings {
+                        parents: vec![MockRoles::ManageRemarks],
+                        admin: None,
+                    },
+                ),
+                Error::<Test>::TooManyAncestors
+            );
+
+            // Two ancestors are fine
+            assert_ok!(Bylaws::set_role_settings(
+                RawOrigin::Signed(ROOT).into(),
+                MockRoles::RemarkOnly,
+                RoleSettings {
+                    parents: vec![MockRoles::ManageRoles],
+                    admin: None,
+                },
+            ));
+        })
+}
+
+#[test]
+fn set_role_settings_counts_ancestors_across_parents() {
+    ExtBuilder::default().build().execute_with(|| {
+        // A single level can fan out past the limit too
+        assert_noop!(
+            Bylaws::set_role_settings(
+                RawOrigin::Signed(ROOT).into(),
+                MockRoles::RemarkOnly,
+                RoleSettings {
+                    parents: vec![
+                        MockRoles::ManageRemarks,
+                        MockRoles::ManageRoles,
+                        MockRoles::Root
+                    ],
+                    admin: None,
+                },
+            ),
+            Error::<Test>::TooManyAncestors
+        );
+    })
+}
+
+#[test]
+fn has_role_does_not_walk_past_max_ancestors() {
+    ExtBuilder::default()
+        .with_role(MockRoles::ManageRoles, Some(ALICE))
+        .with_role_settings(MockRoles::RemarkOnly, vec![MockRoles::ManageRemarks], None)
+        .with_role_settings(MockRoles::ManageRemarks, vec![MockRoles::Root], None)
+        .with_role_settings(MockRoles::Root, vec![MockRoles::ManageRoles], None)
+        .build()
+        .execute_with(|| {
+            assert!(Bylaws::has_role(&ALICE, MockRoles::Root));
+            assert!(Bylaws::has_role(&ALICE, MockRoles::ManageRemarks));
+            assert!(!Bylaws::has_role(&ALICE, MockRoles::RemarkOnly));
+        })
+}
+
+#[test]
+fn has_role_weight_covers_the_reads_made() {
+    ExtBuilder::default()
+        .with_role(MockRoles::ManageRoles, Some(ALICE))
+        .with_role_settings(MockRoles::RemarkOnly, vec![MockRoles::ManageRemarks], None)
+        .with_role_settings(MockRoles::ManageRemarks, vec![MockRoles::ManageRoles], None)
+        .build()
+        .execute_with(|| {
+            let read = <Test as frame_system::Config>::DbWeight::get().reads(1);
+            assert_eq!(
+                Bylaws::has_role_with_weight(&ALICE, MockRoles::ManageRoles),
+                (true, 2 * read)
+            );
+            assert_eq!(
+                Bylaws::has_role_with_weight(&ALICE, MockRoles::RemarkOnly),
+                (true, 4 * read)
+            );
+            assert_eq!(
+                Bylaws::has_role_with_weight(&BOB, MockRoles::RemarkOnly),
+                (false, 4 * read)
+            );
+            assert_eq!(Bylaws::has_role_weight(), 4 * read);
+        })
+}
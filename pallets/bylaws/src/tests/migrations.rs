@@ -0,0 +1,60 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{Releases, RoleGrant, Roles, StorageVersion};
+use frame_support::{storage::unhashed, traits::OnRuntimeUpgrade};
+use governance_os_support::{
+    testing::{primitives::AccountId, ALICE, BOB},
+    traits::RoleManager,
+};
+
+#[test]
+fn genesis_uses_latest_storage_version() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(StorageVersion::<Test>::get(), Releases::V2);
+    })
+}
+
+#[test]
+fn migration_to_v2_keeps_existing_roles() {
+    ExtBuilder::default().build().execute_with(|| {
+        StorageVersion::<Test>::put(Releases::V1);
+        unhashed::put(
+            &Roles::<Test>::hashed_key_for(Some(ALICE)),
+            &vec![MockRoles::Root],
+        );
+        unhashed::put(
+            &Roles::<Test>::hashed_key_for(None::<AccountId>),
+            &vec![MockRoles::RemarkOnly],
+        );
+
+        Bylaws::on_runtime_upgrade();
+
+        assert_eq!(StorageVersion::<Test>::get(), Releases::V2);
+        assert_eq!(
+            Bylaws::roles(Some(ALICE)),
+            vec![RoleGrant {
+                role: MockRoles::Root,
+                valid_from: None,
+                valid_until: None,
+            }]
+        );
+        assert!(Bylaws::has_role(&ALICE, MockRoles::Root));
+        assert!(Bylaws::has_role(&BOB, MockRoles::RemarkOnly));
+        assert!(!Bylaws::has_role(&BOB, MockRoles::Root));
+    })
+}
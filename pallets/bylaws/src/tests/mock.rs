@@ -15,8 +15,11 @@
  */
 
 use crate as governance_os_pallet_bylaws;
+use crate::RoleSettings;
 use codec::{Decode, Encode};
-use frame_support::{construct_runtime, parameter_types, traits::GenesisBuild};
+use frame_support::{
+    construct_runtime, parameter_types, traits::GenesisBuild, weights::constants::RocksDbWeight,
+};
 use governance_os_support::{
     impl_enum_default,
     testing::{
@@ -52,7 +55,7 @@ impl frame_system::Config for Test {
     type BaseCallFilter = ();
     type BlockWeights = BlockWeights;
     type BlockLength = ();
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type Origin = Origin;
     type Index = u64;
     type BlockNumber = BlockNumber;
@@ -87,6 +90,8 @@ impl frame_system::Config for Test {
 )]
 pub enum MockRoles {
     Root,
+    ManageRoles,
+    ManageRemarks,
     RemarkOnly,
 }
 impl_enum_default!(MockRoles, RemarkOnly);
@@ -94,7 +99,7 @@ impl governance_os_pallet_bylaws::RoleBuilder for MockRoles {
     type Role = MockRoles;
 
     fn manage_roles() -> MockRoles {
-        Self::root()
+        MockRoles::ManageRoles
     }
 
     fn root() -> MockRoles {
@@ -105,6 +110,7 @@ impl governance_os_pallet_bylaws::RoleBuilder for MockRoles {
 parameter_types! {
     pub const RootRole: MockRoles = MockRoles::Root;
     pub const MaxRoles: u32 = 5;
+    pub const MaxRoleAncestors: u32 = 2;
 }
 
 impl governance_os_pallet_bylaws::Config for Test {
@@ -112,17 +118,20 @@ impl governance_os_pallet_bylaws::Config for Test {
     type Role = MockRoles;
     type WeightInfo = ();
     type MaxRoles = MaxRoles;
+    type MaxRoleAncestors = MaxRoleAncestors;
     type RoleBuilder = MockRoles;
 }
 
 pub struct ExtBuilder {
     roles: Vec<(MockRoles, Option<AccountId>)>,
+    role_settings: Vec<(MockRoles, RoleSettings<MockRoles>)>,
 }
 
 impl Default for ExtBuilder {
     fn default() -> Self {
         Self {
             roles: vec![(MockRoles::Root, Some(ROOT))],
+            role_settings: vec![],
         }
     }
 }
@@ -133,14 +142,28 @@ impl ExtBuilder {
         self
     }
 
+    pub fn with_role_settings(
+        mut self,
+        role: MockRoles,
+        parents: Vec<MockRoles>,
+        admin: Option<MockRoles>,
+    ) -> Self {
+        self.role_settings
+            .push((role, RoleSettings { parents, admin }));
+        self
+    }
+
     pub fn build(self) -> sp_io::TestExternalities {
         let mut t = frame_system::GenesisConfig::default()
             .build_storage::<Test>()
             .unwrap();
 
-        governance_os_pallet_bylaws::GenesisConfig::<Test> { roles: self.roles }
-            .assimilate_storage(&mut t)
-            .unwrap();
+        governance_os_pallet_bylaws::GenesisConfig::<Test> {
+            roles: self.roles,
+            role_settings: self.role_settings,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
 
         let mut ext = sp_io::TestExternalities::new(t);
         ext.execute_with(|| System::set_block_number(1));
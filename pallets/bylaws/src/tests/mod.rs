@@ -16,4 +16,6 @@
 
 mod dispatchable;
 mod genesis;
+mod hierarchy;
+mod migrations;
 pub mod mock;
@@ -0,0 +1,78 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Type definitions for the bylaws pallet.
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A role granted to an account, optionally restricted to a range of blocks.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RoleGrant<Role, BlockNumber> {
+    /// The role that was granted.
+    pub role: Role,
+    /// First block at which the grant is effective. `None` means it is
+    /// effective immediately.
+    pub valid_from: Option<BlockNumber>,
+    /// Last block at which the grant is effective. `None` means it never
+    /// expires.
+    pub valid_until: Option<BlockNumber>,
+}
+
+impl<Role, BlockNumber: PartialOrd> RoleGrant<Role, BlockNumber> {
+    /// Returns `true` if the grant is effective at block `now`.
+    pub fn is_active(&self, now: &BlockNumber) -> bool {
+        let started = match &self.valid_from {
+            Some(from) => now >= from,
+            None => true,
+        };
+        started && !self.is_expired(now)
+    }
+
+    /// Returns `true` if the grant will never be effective again after block `now`.
+    pub fn is_expired(&self, now: &BlockNumber) -> bool {
+        match &self.valid_until {
+            Some(until) => now > until,
+            None => false,
+        }
+    }
+}
+
+/// Storage layouts of the pallet.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub enum Releases {
+    /// Roles are stored without any validity window.
+    #[default]
+    V1,
+    /// Roles are stored as `RoleGrant`s.
+    V2,
+}
+
+/// Describes how a role relates to the other roles of the system.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RoleSettings<Role> {
+    /// Roles whose holders are implicitly granted this role. This is resolved
+    /// recursively, parents of the parents are considered too.
+    pub parents: Vec<Role>,
+    /// The only role allowed to grant or revoke this role through the pallet's
+    /// extrinsics. Defaults to the `manage_roles` role if `None`.
+    pub admin: Option<Role>,
+}
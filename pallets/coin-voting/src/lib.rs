@@ -17,16 +17,23 @@
 //! This pallet can be used to deploy a coin voting system. It supports direct,
 //! simple coin voting as well as the possibility to configure it to support
 //! quadratic voting.
+//! Proposals can also be configured to count votes from the balances held when
+//! they were created, in which case voters do not have to lock their coins.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{decl_error, decl_module, decl_storage, ensure, traits::LockIdentifier};
+use frame_support::{
+    decl_error, decl_module, decl_storage, ensure,
+    traits::{Get, LockIdentifier},
+    weights::Weight,
+};
 use governance_os_support::traits::{
-    Currencies, LockableCurrencies, ProposalResult, StandardizedVoting,
+    voting_lock_reasons, CheckpointedCurrencies, Currencies, Delegations, LockableCurrencies,
+    OnDelegationChanged, ProposalResult, ProposalTally, StandardizedVoting,
 };
 use sp_runtime::{
-    traits::{Saturating, Zero},
-    DispatchError, DispatchResult, Perbill,
+    traits::{One, Saturating, Zero},
+    DispatchError, DispatchResult,
 };
 use sp_std::{prelude::*, vec::Vec};
 use types::ProposalState;
@@ -40,8 +47,17 @@ pub use types::{VoteCountingStrategy, VoteData, VotingParameters};
 pub const COIN_VOTING_LOCK_ID: LockIdentifier = *b"coinvote";
 
 pub trait Config: frame_system::Config {
-    /// Pallet in charge of currencies. Used so that we can lock tokens etc...
-    type Currencies: LockableCurrencies<Self::AccountId>;
+    /// Pallet in charge of currencies. Used so that we can lock tokens or read the
+    /// balances snapshot proposals are counted from.
+    type Currencies: LockableCurrencies<Self::AccountId>
+        + CheckpointedCurrencies<Self::AccountId, Self::BlockNumber>;
+
+    /// Used to count the voting power delegated to the voters.
+    type Delegations: Delegations<Self::AccountId, CurrencyIdOf<Self>, BalanceOf<Self>>;
+
+    /// Maximum number of open proposals an account can vote on with the same currency. Bounds
+    /// the number of proposals recounted when a delegation changes.
+    type MaxActiveVotes: Get<u32>;
 }
 
 type BalanceOf<T> =
@@ -56,11 +72,13 @@ type LockDataOf<T> = (
 );
 type LockIdentifierOf<T> = (CurrencyIdOf<T>, <T as frame_system::Config>::AccountId);
 type CoinProposalStateOf<T> = ProposalState<
+    <T as frame_system::Config>::AccountId,
     BalanceOf<T>,
     <T as frame_system::Config>::BlockNumber,
     CurrencyIdOf<T>,
     LockIdentifierOf<T>,
 >;
+type ProposalTallyOf<T> = ProposalTally<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
 
 decl_storage! {
     trait Store for Module<T: Config> as CoinVoting {
@@ -68,6 +86,15 @@ decl_storage! {
         pub Proposals get(fn proposals): map hasher(blake2_128_concat) T::Hash => CoinProposalStateOf<T>;
         /// Keeps track of locks set on user's balances and to which proposal they were linked to.
         pub Locks get(fn locks): map hasher(blake2_128_concat) LockIdentifierOf<T> => Vec<LockDataOf<T>>;
+        /// Voting power delegated to voters and counted in the proposals' tallies. Keyed by delegator
+        /// and proposal, values are the delegate, its support and the amount counted.
+        pub DelegatedVotes get(fn delegated_votes): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::Hash => Option<(T::AccountId, bool, BalanceOf<T>)>;
+        /// Votes casted on snapshot proposals, which do not lock any coins. Values are the support
+        /// and power of the vote.
+        pub SnapshotVotes get(fn snapshot_votes): double_map hasher(blake2_128_concat) T::Hash, hasher(blake2_128_concat) T::AccountId => Option<(bool, BalanceOf<T>)>;
+        /// Open proposals an account voted on with a given currency. Used to find the proposals to
+        /// recount when a delegation changes.
+        pub ActiveVotes get(fn active_votes): map hasher(blake2_128_concat) (CurrencyIdOf<T>, T::AccountId) => Vec<T::Hash>;
     }
 }
 
@@ -78,6 +105,11 @@ decl_error! {
         NotEnoughBalance,
         /// Proposal cannot be closed yet, it is likely too early.
         CannotClose,
+        /// Snapshot proposals require the voting currency to record balance checkpoints
+        /// since before their creation.
+        NoCheckpoints,
+        /// The voter already voted on `MaxActiveVotes` open proposals with this currency.
+        TooManyVotes,
     }
 }
 
@@ -91,19 +123,38 @@ impl<T: Config> StandardizedVoting for Module<T> {
     type Parameters = VotingParameters<T::BlockNumber, CurrencyIdOf<T>>;
     type VoteData = VoteData<BalanceOf<T>>;
     type AccountId = T::AccountId;
+    type Tally = ProposalTallyOf<T>;
+    type BlockNumber = T::BlockNumber;
 
     /// Register the proposal in our storage. Does not make any attempt at preventing duplicates
     /// as we assume this is handled by the calling pallet.
-    fn initiate(proposal: Self::ProposalId, parameters: Self::Parameters) -> DispatchResult {
+    fn initiate(
+        proposal: Self::ProposalId,
+        organization: &Self::AccountId,
+        parameters: Self::Parameters,
+    ) -> DispatchResult {
+        let state = ProposalState {
+            parameters,
+            organization: organization.clone(),
+            total_against: Zero::zero(),
+            total_favorable: Zero::zero(),
+            locks: vec![],
+            delegators: vec![],
+            created_on: Self::now(),
+        };
+        ensure!(
+            !state.parameters.snapshot
+                || T::Currencies::total_issuance_at(
+                    state.parameters.voting_currency,
+                    Self::snapshot_block(&state)
+                )
+                .is_some(),
+            Error::<T>::NoCheckpoints
+        );
+
         Proposals::<T>::try_mutate_exists(proposal, |maybe_existing_state| -> DispatchResult {
             // no duplicates, we can create a new state
-            *maybe_existing_state = Some(ProposalState {
-                parameters,
-                total_against: Zero::zero(),
-                total_favorable: Zero::zero(),
-                locks: vec![],
-                created_on: Self::now(),
-            });
+            *maybe_existing_state = Some(state);
 
             Ok(())
         })?;
@@ -111,43 +162,70 @@ impl<T: Config> StandardizedVoting for Module<T> {
         Ok(())
     }
 
-    /// Record a new vote and set any locks or reserved coins in place.
+    /// Record a new vote and set any locks or reserved coins in place, votes on snapshot
+    /// proposals are only limited by the voter's balance at the time of the snapshot. The
+    /// voting power delegated to the voter is counted too, unless the delegators voted
+    /// themselves.
     fn vote(
         proposal: Self::ProposalId,
         voter: &Self::AccountId,
         data: Self::VoteData,
     ) -> DispatchResult {
         let mut state = Self::proposals(proposal);
+        let mut active_votes = ActiveVotes::<T>::get((state.parameters.voting_currency, voter));
+        if !active_votes.contains(&proposal) {
+            ensure!(
+                (active_votes.len() as u32) < T::MaxActiveVotes::get(),
+                Error::<T>::TooManyVotes
+            );
+            active_votes.push(proposal);
+        }
 
-        // We want to prevent votes for user with less coins than they'd like to lock.
-        ensure!(
-            T::Currencies::free_balance(state.parameters.voting_currency, voter) >= data.power,
-            Error::<T>::NotEnoughBalance
-        );
-        let mut this_vote_is_a_duplicate = false;
-        Self::update_locks(
-            state.parameters.voting_currency,
-            voter,
-            proposal,
-            data.in_support,
-            data.power,
-            state.parameters.vote_counting_strategy,
-            |_proposal, old_support, old_power, _strategy| {
-                // We found a duplicated vote, thus we need to remove it from our precomputed
-                // state to avoid mistakes
+        if state.parameters.snapshot {
+            ensure!(
+                Self::snapshot_balance(&state, voter) >= data.power,
+                Error::<T>::NotEnoughBalance
+            );
+            if let Some((old_support, old_power)) = SnapshotVotes::<T>::get(proposal, voter) {
                 state.unrecord_vote(old_support, old_power);
-                this_vote_is_a_duplicate = true;
-            },
-        )?;
-
-        if !this_vote_is_a_duplicate {
-            state
-                .locks
-                .push((state.parameters.voting_currency, voter.clone()));
+            }
+            SnapshotVotes::<T>::insert(proposal, voter, (data.in_support, data.power));
+        } else {
+            // We want to prevent votes for user with less coins than they'd like to lock.
+            ensure!(
+                T::Currencies::free_balance(state.parameters.voting_currency, voter) >= data.power,
+                Error::<T>::NotEnoughBalance
+            );
+            let mut this_vote_is_a_duplicate = false;
+            Self::update_locks(
+                state.parameters.voting_currency,
+                voter,
+                proposal,
+                data.in_support,
+                data.power,
+                state.parameters.vote_counting_strategy,
+                |_proposal, old_support, old_power, _strategy| {
+                    // We found a duplicated vote, thus we need to remove it from our precomputed
+                    // state to avoid mistakes
+                    state.unrecord_vote(old_support, old_power);
+                    this_vote_is_a_duplicate = true;
+                },
+            )?;
+
+            if !this_vote_is_a_duplicate {
+                state
+                    .locks
+                    .push((state.parameters.voting_currency, voter.clone()));
+            }
         }
 
+        // A direct vote overrides any delegation.
+        Self::uncount_delegated_vote(&mut state, proposal, voter);
+
         state.record_vote(data.in_support, data.power);
+        Self::count_delegated_votes(&mut state, proposal, voter, data.in_support);
 
+        ActiveVotes::<T>::insert((state.parameters.voting_currency, voter), active_votes);
         Proposals::<T>::insert(proposal, state);
 
         Ok(())
@@ -157,42 +235,280 @@ impl<T: Config> StandardizedVoting for Module<T> {
     /// frees any storage associated to it.
     fn veto(proposal: Self::ProposalId) -> DispatchResult {
         // note the use of take instead of get which also deletes the storage
-        Self::unlock(Proposals::<T>::take(proposal).locks, proposal)
+        let state = Proposals::<T>::take(proposal);
+        Self::clear_delegated_votes(proposal, &state.delegators);
+        Self::clear_active_votes(proposal, &state);
+        SnapshotVotes::<T>::remove_prefix(proposal);
+        Self::unlock(state.locks, proposal)
     }
 
     /// Checks wether a proposal is passing or not. Then unlock all coins related to it
     /// and clean the storage.
     fn close(proposal: Self::ProposalId) -> Result<ProposalResult, DispatchError> {
         let state = Proposals::<T>::get(proposal);
+        let result = Self::compute_tally(&state)
+            .close_result
+            .ok_or(Error::<T>::CannotClose)?;
 
-        let total_supply = T::Currencies::total_issuance(state.parameters.voting_currency);
-        let total_participation = state.total_against.saturating_add(state.total_favorable);
+        Self::clear_delegated_votes(proposal, &state.delegators);
+        Self::clear_active_votes(proposal, &state);
+        SnapshotVotes::<T>::remove_prefix(proposal);
+        Self::unlock(state.locks, proposal)?;
 
-        let enough_participation = total_participation
-            > Perbill::from_percent(state.parameters.min_participation) * total_supply;
-        let enough_quorum = state.total_favorable
-            > Perbill::from_percent(state.parameters.min_quorum) * total_participation;
+        Proposals::<T>::remove(proposal);
+        Ok(result)
+    }
 
-        let result = if enough_participation && enough_quorum {
-            ProposalResult::Passing
-        } else {
-            ProposalResult::Failing
-        };
+    /// Compute the tally from the proposal's current vote counts.
+    fn tally(proposal: Self::ProposalId) -> Option<Self::Tally> {
+        Proposals::<T>::try_get(proposal)
+            .ok()
+            .map(|state| Self::compute_tally(&state))
+    }
 
-        let can_close = state.created_on.saturating_add(state.parameters.ttl) < Self::now();
-        ensure!(
-            can_close || result == ProposalResult::Passing,
-            Error::<T>::CannotClose
-        );
+    /// A proposal expires once its ttl is over.
+    fn expiry(proposal: Self::ProposalId) -> Option<Self::BlockNumber> {
+        Proposals::<T>::try_get(proposal)
+            .ok()
+            .map(|state| Self::expiry_of(&state))
+    }
 
-        Self::unlock(state.locks, proposal)?;
+    fn can_vote(proposal: Self::ProposalId, voter: &Self::AccountId) -> bool {
+        match Proposals::<T>::try_get(proposal) {
+            Ok(state) if state.parameters.snapshot => {
+                !Self::snapshot_balance(&state, voter).is_zero()
+            }
+            Ok(state) => {
+                !T::Currencies::free_balance(state.parameters.voting_currency, voter).is_zero()
+            }
+            Err(_) => false,
+        }
+    }
 
-        Proposals::<T>::remove(proposal);
-        Ok(result)
+    fn vote_weight() -> Weight {
+        // We read the proposal, the active votes, locks and snapshot vote of the voter, its
+        // balance and delegated vote, then write them back. For each delegator we take its
+        // delegated vote, read its locks, snapshot vote and snapshot balance and write its
+        // new delegated vote.
+        let delegators = T::Delegations::max_delegators_of();
+        T::DbWeight::get()
+            .reads_writes(6, 5)
+            .saturating_add(T::Delegations::delegators_of_weight())
+            .saturating_add(T::DbWeight::get().reads_writes(
+                delegators.saturating_mul(4).into(),
+                delegators.saturating_mul(2).into(),
+            ))
+    }
+}
+
+impl<T: Config> OnDelegationChanged<T::AccountId, CurrencyIdOf<T>> for Module<T> {
+    /// Recount the voting power of `delegator` in the proposals `delegates` voted on, it
+    /// may now be delegated to another account or not be delegated at all.
+    fn on_delegation_changed(
+        currency: CurrencyIdOf<T>,
+        delegator: &T::AccountId,
+        delegates: &[T::AccountId],
+    ) -> DispatchResult {
+        Self::proposals_voted_by(currency, delegates)
+            .into_iter()
+            .for_each(|proposal| {
+                let mut state = Proposals::<T>::get(proposal);
+
+                Self::uncount_delegated_vote(&mut state, proposal, delegator);
+                // A direct vote overrides any delegation.
+                if Self::vote_of(currency, proposal, delegator).is_none() {
+                    if let Some((delegate, amount)) =
+                        T::Delegations::delegation_of(currency, &state.organization, delegator)
+                    {
+                        if let Some(support) = Self::vote_of(currency, proposal, &delegate) {
+                            Self::count_delegated_vote(
+                                &mut state, proposal, delegator, delegate, support, amount,
+                            );
+                        }
+                    }
+                }
+
+                Proposals::<T>::insert(proposal, state);
+            });
+
+        Ok(())
+    }
+
+    fn on_delegation_changed_weight(delegates: u32) -> Weight {
+        // For each proposal we read it, the delegated vote, both delegations of the delegator,
+        // the votes of the delegator and its delegate and the delegator's snapshot balance.
+        // Then write the proposal and the delegated vote.
+        let proposals = T::MaxActiveVotes::get().saturating_mul(delegates);
+        T::DbWeight::get()
+            .reads(delegates.into())
+            .saturating_add(T::DbWeight::get().reads_writes(
+                proposals.saturating_mul(12).into(),
+                proposals.saturating_mul(2).into(),
+            ))
     }
 }
 
 impl<T: Config> Module<T> {
+    /// Count the voting power delegated to `delegate` in the tally of `proposal`, replacing
+    /// any power previously counted for it. Delegators that voted themselves are ignored.
+    /// Delegation changes are recounted as they happen so the power previously counted for
+    /// `delegate` can only come from its current delegators.
+    fn count_delegated_votes(
+        state: &mut CoinProposalStateOf<T>,
+        proposal: T::Hash,
+        delegate: &T::AccountId,
+        support: bool,
+    ) {
+        let currency = state.parameters.voting_currency;
+
+        T::Delegations::delegators_of(currency, &state.organization, delegate)
+            .into_iter()
+            .for_each(|(delegator, amount)| {
+                Self::uncount_delegated_vote(state, proposal, &delegator);
+                if Self::vote_of(currency, proposal, &delegator).is_none() {
+                    Self::count_delegated_vote(
+                        state,
+                        proposal,
+                        &delegator,
+                        delegate.clone(),
+                        support,
+                        amount,
+                    )
+                }
+            });
+    }
+
+    /// Add the power of `delegator` to the tally of `proposal`. On snapshot proposals it can
+    /// not exceed the delegator's balance at the time of the snapshot, otherwise coins moved
+    /// after it could be counted twice.
+    fn count_delegated_vote(
+        state: &mut CoinProposalStateOf<T>,
+        proposal: T::Hash,
+        delegator: &T::AccountId,
+        delegate: T::AccountId,
+        support: bool,
+        amount: BalanceOf<T>,
+    ) {
+        let amount = if state.parameters.snapshot {
+            amount.min(Self::snapshot_balance(state, delegator))
+        } else {
+            amount
+        };
+        state.record_vote(support, amount);
+        DelegatedVotes::<T>::insert(delegator, proposal, (delegate, support, amount));
+        if !state.delegators.contains(delegator) {
+            state.delegators.push(delegator.clone());
+        }
+    }
+
+    /// Remove the power of `delegator` from the tally of `proposal`, if it was counted.
+    fn uncount_delegated_vote(
+        state: &mut CoinProposalStateOf<T>,
+        proposal: T::Hash,
+        delegator: &T::AccountId,
+    ) {
+        if let Some((_delegate, support, amount)) = DelegatedVotes::<T>::take(delegator, proposal) {
+            state.unrecord_vote(support, amount);
+        }
+    }
+
+    /// Forget about the votes casted on `proposal`, it is being closed or vetoed.
+    fn clear_active_votes(proposal: T::Hash, state: &CoinProposalStateOf<T>) {
+        let currency = state.parameters.voting_currency;
+        state
+            .locks
+            .iter()
+            .map(|(_currency, voter)| voter.clone())
+            .chain(SnapshotVotes::<T>::iter_prefix(proposal).map(|(voter, _)| voter))
+            .for_each(|voter| {
+                ActiveVotes::<T>::mutate_exists((currency, voter), |maybe_proposals| {
+                    let mut proposals = maybe_proposals.take().unwrap_or_default();
+                    proposals.retain(|p| p != &proposal);
+                    if !proposals.is_empty() {
+                        *maybe_proposals = Some(proposals);
+                    }
+                })
+            });
+    }
+
+    /// Open proposals on which any of `voters` voted with `currency`.
+    fn proposals_voted_by(currency: CurrencyIdOf<T>, voters: &[T::AccountId]) -> Vec<T::Hash> {
+        let mut proposals: Vec<T::Hash> = voters
+            .iter()
+            .flat_map(|voter| ActiveVotes::<T>::get((currency, voter)))
+            .collect();
+        proposals.sort();
+        proposals.dedup();
+        proposals
+    }
+
+    /// Forget about the delegated power counted in `proposal`.
+    fn clear_delegated_votes(proposal: T::Hash, delegators: &[T::AccountId]) {
+        delegators
+            .iter()
+            .for_each(|delegator| DelegatedVotes::<T>::remove(delegator, proposal));
+    }
+
+    /// Returns the support of the vote `who` casted on `proposal`, if any.
+    fn vote_of(currency: CurrencyIdOf<T>, proposal: T::Hash, who: &T::AccountId) -> Option<bool> {
+        Locks::<T>::get((currency, who))
+            .into_iter()
+            .find(|(locked_proposal, _, _, _)| locked_proposal == &proposal)
+            .map(|(_, support, _, _)| support)
+            .or_else(|| SnapshotVotes::<T>::get(proposal, who).map(|(support, _)| support))
+    }
+
+    /// Block whose balances are used by snapshot proposals. Coins can still move during the
+    /// block the proposal was created in, so we use the one preceding it.
+    fn snapshot_block(state: &CoinProposalStateOf<T>) -> T::BlockNumber {
+        state.created_on.saturating_sub(One::one())
+    }
+
+    /// Balance `who` can vote with on a snapshot proposal. Zero if the voting currency
+    /// stopped recording checkpoints.
+    fn snapshot_balance(state: &CoinProposalStateOf<T>, who: &T::AccountId) -> BalanceOf<T> {
+        T::Currencies::balance_at(
+            state.parameters.voting_currency,
+            who,
+            Self::snapshot_block(state),
+        )
+        .unwrap_or_else(Zero::zero)
+    }
+
+    /// Supply participation is computed from, the one at the time of the snapshot for snapshot
+    /// proposals.
+    fn voting_supply(state: &CoinProposalStateOf<T>) -> BalanceOf<T> {
+        let currency = state.parameters.voting_currency;
+        if state.parameters.snapshot {
+            if let Some(issuance) =
+                T::Currencies::total_issuance_at(currency, Self::snapshot_block(state))
+            {
+                return issuance;
+            }
+        }
+        T::Currencies::total_issuance(currency)
+    }
+
+    /// Compute the tally of a proposal. Shared by `tally` and `close` so that both always
+    /// agree on the outcome.
+    fn compute_tally(state: &CoinProposalStateOf<T>) -> ProposalTallyOf<T> {
+        ProposalTally::new(
+            state.total_favorable,
+            state.total_against,
+            Self::voting_supply(state),
+            state.parameters.min_participation,
+            state.parameters.min_quorum,
+            Self::expiry_of(state),
+            Self::now(),
+        )
+    }
+
+    /// Last block during which a proposal can still receive votes before being considered
+    /// as failing.
+    fn expiry_of(state: &CoinProposalStateOf<T>) -> T::BlockNumber {
+        state.created_on.saturating_add(state.parameters.ttl)
+    }
+
     /// Modify the locks related to the `voter` and `proposal`. We provide a hook `on_duplicate_vote_found`
     /// used to handle cases where we have a similar lock in place.
     fn update_locks<F>(
@@ -210,7 +526,7 @@ impl<T: Config> Module<T> {
         Locks::<T>::try_mutate((voting_currency, voter), |locks| -> DispatchResult {
             // because we use iterators we have to first create a vec for
             // a use with chain() later on
-            let locks_addition = vec![(proposal, support, power, strategy)];
+            let locks_addition = [(proposal, support, power, strategy)];
 
             // Filter and remove any duplicate votes
             *locks = locks
@@ -304,7 +620,13 @@ impl<T: Config> Module<T> {
         if max_to_lock == Zero::zero() {
             T::Currencies::remove_lock(voting_currency, COIN_VOTING_LOCK_ID, who)?;
         } else {
-            T::Currencies::set_lock(voting_currency, COIN_VOTING_LOCK_ID, who, max_to_lock)?;
+            T::Currencies::set_lock_with_reasons(
+                voting_currency,
+                COIN_VOTING_LOCK_ID,
+                who,
+                max_to_lock,
+                voting_lock_reasons(),
+            )?;
         }
 
         Ok(())
@@ -0,0 +1,282 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{types::VoteData, ActiveVotes, DelegatedVotes, Error, Proposals};
+use frame_support::{assert_noop, assert_ok, StorageDoubleMap, StorageMap};
+use governance_os_support::{
+    testing::{ALICE, BOB, CHARLIE, TEST_ORGANIZATION, TEST_TOKEN_ID},
+    traits::StandardizedVoting,
+};
+use sp_core::H256;
+
+fn balances_for_everyone() -> ExtBuilder {
+    ExtBuilder::default().balances(vec![
+        (TEST_TOKEN_ID, ALICE, 100),
+        (TEST_TOKEN_ID, BOB, 100),
+        (TEST_TOKEN_ID, CHARLIE, 100),
+    ])
+}
+
+fn vote(voter: u128, in_support: bool, power: u128) {
+    assert_ok!(<CoinVoting as StandardizedVoting>::vote(
+        H256::default(),
+        &voter,
+        VoteData { in_support, power }
+    ));
+}
+
+fn totals() -> (u128, u128) {
+    let state = CoinVoting::proposals(H256::default());
+    (state.total_favorable, state.total_against)
+}
+
+fn setup_alice_delegates_to_bob() {
+    assert_ok!(Delegation::delegate(
+        Origin::signed(ALICE),
+        TEST_TOKEN_ID,
+        None,
+        BOB,
+        50
+    ));
+    assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
+        H256::default(),
+        &TEST_ORGANIZATION,
+        mock_voting_parameters()
+    ));
+}
+
+#[test]
+fn delegated_power_is_counted_when_delegate_votes() {
+    balances_for_everyone().build().execute_with(|| {
+        setup_alice_delegates_to_bob();
+        vote(BOB, true, 10);
+
+        assert_eq!(totals(), (60, 0));
+        assert_eq!(
+            CoinVoting::delegated_votes(ALICE, H256::default()),
+            Some((BOB, true, 50))
+        );
+    })
+}
+
+#[test]
+fn delegated_power_follows_the_delegate_vote() {
+    balances_for_everyone().build().execute_with(|| {
+        setup_alice_delegates_to_bob();
+        vote(BOB, true, 10);
+        vote(BOB, false, 10);
+
+        assert_eq!(totals(), (0, 60));
+    })
+}
+
+#[test]
+fn direct_vote_overrides_delegation() {
+    balances_for_everyone().build().execute_with(|| {
+        setup_alice_delegates_to_bob();
+        vote(BOB, true, 10);
+        vote(ALICE, false, 20);
+
+        assert_eq!(totals(), (10, 20));
+        assert_eq!(CoinVoting::delegated_votes(ALICE, H256::default()), None);
+
+        // Even if the delegate votes again
+        vote(BOB, true, 15);
+        assert_eq!(totals(), (15, 20));
+    })
+}
+
+#[test]
+fn undelegating_recomputes_tally() {
+    balances_for_everyone().build().execute_with(|| {
+        setup_alice_delegates_to_bob();
+        vote(BOB, true, 10);
+
+        assert_ok!(Delegation::undelegate(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            None
+        ));
+
+        assert_eq!(totals(), (10, 0));
+        assert_eq!(CoinVoting::delegated_votes(ALICE, H256::default()), None);
+    })
+}
+
+#[test]
+fn redelegating_moves_power_to_new_delegate() {
+    balances_for_everyone().build().execute_with(|| {
+        setup_alice_delegates_to_bob();
+        vote(BOB, true, 10);
+        vote(CHARLIE, false, 5);
+
+        assert_ok!(Delegation::delegate(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            None,
+            CHARLIE,
+            40
+        ));
+
+        assert_eq!(totals(), (10, 45));
+        assert_eq!(
+            CoinVoting::delegated_votes(ALICE, H256::default()),
+            Some((CHARLIE, false, 40))
+        );
+    })
+}
+
+#[test]
+fn organization_delegation_takes_precedence() {
+    balances_for_everyone().build().execute_with(|| {
+        setup_alice_delegates_to_bob();
+        assert_ok!(Delegation::delegate(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            Some(TEST_ORGANIZATION),
+            CHARLIE,
+            30
+        ));
+
+        vote(BOB, true, 10);
+        assert_eq!(totals(), (10, 0));
+
+        vote(CHARLIE, false, 5);
+        assert_eq!(totals(), (10, 35));
+    })
+}
+
+#[test]
+fn close_clears_delegated_votes() {
+    balances_for_everyone().build().execute_with(|| {
+        setup_alice_delegates_to_bob();
+        vote(BOB, true, 10);
+
+        assert_ok!(<CoinVoting as StandardizedVoting>::close(H256::default()));
+
+        assert!(!Proposals::<Test>::contains_key(H256::default()));
+        assert!(!DelegatedVotes::<Test>::contains_key(
+            ALICE,
+            H256::default()
+        ));
+    })
+}
+
+#[test]
+fn delegating_after_the_delegate_voted_is_counted() {
+    balances_for_everyone().build().execute_with(|| {
+        assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
+            H256::default(),
+            &TEST_ORGANIZATION,
+            mock_voting_parameters()
+        ));
+        vote(BOB, true, 10);
+
+        assert_ok!(Delegation::delegate(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            None,
+            BOB,
+            50
+        ));
+
+        assert_eq!(totals(), (60, 0));
+        assert_eq!(
+            CoinVoting::delegated_votes(ALICE, H256::default()),
+            Some((BOB, true, 50))
+        );
+    })
+}
+
+#[test]
+fn organization_delegation_after_votes_replaces_the_generic_one() {
+    balances_for_everyone().build().execute_with(|| {
+        setup_alice_delegates_to_bob();
+        vote(BOB, true, 10);
+        vote(CHARLIE, false, 5);
+        assert_eq!(totals(), (60, 5));
+
+        assert_ok!(Delegation::delegate(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            Some(TEST_ORGANIZATION),
+            CHARLIE,
+            30
+        ));
+        assert_eq!(totals(), (10, 35));
+
+        assert_ok!(Delegation::undelegate(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            Some(TEST_ORGANIZATION)
+        ));
+        assert_eq!(totals(), (60, 5));
+    })
+}
+
+#[test]
+fn votes_are_limited_to_max_active_votes() {
+    balances_for_everyone().build().execute_with(|| {
+        (0..=MaxActiveVotes::get()).for_each(|i| {
+            assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
+                H256::repeat_byte(i as u8),
+                &TEST_ORGANIZATION,
+                mock_voting_parameters()
+            ));
+        });
+        (0..MaxActiveVotes::get()).for_each(|i| {
+            assert_ok!(<CoinVoting as StandardizedVoting>::vote(
+                H256::repeat_byte(i as u8),
+                &ALICE,
+                VoteData {
+                    in_support: true,
+                    power: 10
+                }
+            ));
+        });
+
+        let last = H256::repeat_byte(MaxActiveVotes::get() as u8);
+        assert_noop!(
+            <CoinVoting as StandardizedVoting>::vote(
+                last,
+                &ALICE,
+                VoteData {
+                    in_support: true,
+                    power: 10
+                }
+            ),
+            Error::<Test>::TooManyVotes
+        );
+
+        // Closing a proposal frees a slot
+        assert_ok!(<CoinVoting as StandardizedVoting>::veto(H256::repeat_byte(
+            0
+        )));
+        assert_eq!(
+            ActiveVotes::<Test>::get((TEST_TOKEN_ID, ALICE)).len() as u32,
+            MaxActiveVotes::get() - 1
+        );
+        assert_ok!(<CoinVoting as StandardizedVoting>::vote(
+            last,
+            &ALICE,
+            VoteData {
+                in_support: true,
+                power: 10
+            }
+        ));
+    })
+}
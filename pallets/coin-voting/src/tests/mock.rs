@@ -40,7 +40,7 @@ use governance_os_support::{
     impl_enum_default,
     testing::{
         primitives::{AccountId, Balance, BlockNumber, CurrencyId},
-        ALICE, BOB, TEST_TOKEN_ID, TEST_TOKEN_OWNER,
+        AutoCurrencyIds, ALICE, BOB, TEST_TOKEN_ID, TEST_TOKEN_OWNER,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -59,6 +59,7 @@ construct_runtime!(
         System: frame_system::{Module, Call, Config, Storage, Event<T>},
         Bylaws: governance_os_pallet_bylaws::{Module, Call, Storage, Event<T>},
         Tokens: governance_os_pallet_tokens::{Module, Call, Storage, Event<T>},
+        Delegation: governance_os_pallet_delegation::{Module, Call, Storage, Event<T>},
         CoinVoting: governance_os_pallet_coin_voting::{Module, Call, Storage},
     }
 );
@@ -110,6 +111,7 @@ pub enum MockRoles {
     Root,
     CreateCurrencies,
     TransferCurrency(CurrencyId),
+    ReceiveCurrency(CurrencyId),
     ManageCurrency(CurrencyId),
 }
 impl_enum_default!(MockRoles, Root);
@@ -128,6 +130,7 @@ impl governance_os_pallet_bylaws::RoleBuilder for MockRoles {
 parameter_types! {
     pub const RootRole: MockRoles = MockRoles::Root;
     pub const MaxRoles: u32 = 5;
+    pub const MaxRoleAncestors: u32 = 4;
 }
 
 impl governance_os_pallet_bylaws::Config for Test {
@@ -135,6 +138,7 @@ impl governance_os_pallet_bylaws::Config for Test {
     type Role = MockRoles;
     type WeightInfo = ();
     type MaxRoles = MaxRoles;
+    type MaxRoleAncestors = MaxRoleAncestors;
     type RoleBuilder = MockRoles;
 }
 
@@ -146,6 +150,10 @@ impl governance_os_pallet_tokens::RoleBuilder for MockRoles {
         Self::TransferCurrency(id)
     }
 
+    fn receive_currency(id: CurrencyId) -> Self {
+        Self::ReceiveCurrency(id)
+    }
+
     fn manage_currency(id: CurrencyId) -> Self {
         Self::ManageCurrency(id)
     }
@@ -153,6 +161,15 @@ impl governance_os_pallet_tokens::RoleBuilder for MockRoles {
     fn create_currencies() -> Self {
         Self::CreateCurrencies
     }
+
+    fn manage_fee_rates() -> Self {
+        Self::Root
+    }
+}
+
+parameter_types! {
+    pub const MaxCurrencyIdSkips: u32 = 16;
+    pub const MaxCheckpoints: u32 = 16;
 }
 
 impl governance_os_pallet_tokens::Config for Test {
@@ -160,17 +177,47 @@ impl governance_os_pallet_tokens::Config for Test {
     type CurrencyId = CurrencyId;
     type Balance = Balance;
     type WeightInfo = ();
+    type NativeCurrencyId = ();
+    type NativeExistentialDeposit = ();
+    type AutoCurrencyId = AutoCurrencyIds;
+    type MaxCurrencyIdSkips = MaxCurrencyIdSkips;
+    type CurrencyDeposit = ();
+    type MetadataDepositPerByte = ();
+    type MaxMetadataLength = ();
+    type MaxIssuanceSchedules = ();
+    type MaxBatchSize = ();
+    type MaxCheckpoints = MaxCheckpoints;
+    type OnDust = ();
+    type OnBalanceChanged = ();
     type RoleManager = Bylaws;
     type RoleBuilder = MockRoles;
 }
 
+parameter_types! {
+    pub const MaxDelegators: u32 = 10;
+}
+
+impl governance_os_pallet_delegation::Config for Test {
+    type Event = Event;
+    type Currencies = Tokens;
+    type OnDelegationChanged = CoinVoting;
+    type MaxDelegators = MaxDelegators;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const MaxActiveVotes: u32 = 3;
+}
+
 impl governance_os_pallet_coin_voting::Config for Test {
     type Currencies = Tokens;
+    type Delegations = Delegation;
+    type MaxActiveVotes = MaxActiveVotes;
 }
 
 pub struct ExtBuilder {
     endowed_accounts: Vec<(CurrencyId, AccountId, Balance)>,
-    test_token_details: CurrencyDetails<AccountId>,
+    test_token_details: CurrencyDetails<AccountId, Balance, BlockNumber>,
 }
 
 impl Default for ExtBuilder {
@@ -180,6 +227,10 @@ impl Default for ExtBuilder {
             test_token_details: CurrencyDetails {
                 owner: TEST_TOKEN_OWNER,
                 transferable: true,
+                restricted_receivers: false,
+                existential_deposit: 0,
+                max_supply: None,
+                issuance: None,
             },
         }
     }
@@ -202,13 +253,14 @@ impl ExtBuilder {
 
         governance_os_pallet_bylaws::GenesisConfig::<Test> {
             roles: vec![(MockRoles::CreateCurrencies, None)], // Everybody can create currencies
+            role_settings: vec![],
         }
         .assimilate_storage(&mut t)
         .unwrap();
 
         governance_os_pallet_tokens::GenesisConfig::<Test> {
             endowed_accounts: self.endowed_accounts,
-            currency_details: vec![(TEST_TOKEN_ID, self.test_token_details)],
+            currency_details: vec![(TEST_TOKEN_ID, self.test_token_details, None)],
         }
         .assimilate_storage(&mut t)
         .unwrap();
@@ -226,6 +278,7 @@ pub fn mock_voting_parameters() -> VotingParameters<BlockNumber, CurrencyIdOf<Te
         min_quorum: 0,
         min_participation: 0,
         vote_counting_strategy: VoteCountingStrategy::Simple,
+        snapshot: false,
     }
 }
 
@@ -236,5 +289,20 @@ pub fn mock_quadratic_voting_parameters() -> VotingParameters<BlockNumber, Curre
         min_quorum: 0,
         min_participation: 0,
         vote_counting_strategy: VoteCountingStrategy::Quadratic,
+        snapshot: false,
     }
 }
+
+pub fn mock_snapshot_voting_parameters() -> VotingParameters<BlockNumber, CurrencyIdOf<Test>> {
+    VotingParameters {
+        snapshot: true,
+        ..mock_voting_parameters()
+    }
+}
+
+/// Record checkpoints for the test token from the current block and move to the next one so
+/// that proposals can be created from them.
+pub fn enable_checkpoints() {
+    assert!(Tokens::set_checkpoints(Origin::signed(TEST_TOKEN_OWNER), TEST_TOKEN_ID, true).is_ok());
+    System::set_block_number(System::block_number() + 1);
+}
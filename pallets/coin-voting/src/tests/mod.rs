@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+mod delegation;
 mod mock;
 mod quadratic;
 mod simple;
+mod snapshot;
@@ -18,7 +18,7 @@ use super::mock::*;
 use crate::types::{VoteCountingStrategy, VoteData};
 use frame_support::assert_ok;
 use governance_os_support::{
-    testing::{primitives::AccountId, ALICE, TEST_TOKEN_ID},
+    testing::{primitives::AccountId, ALICE, TEST_ORGANIZATION, TEST_TOKEN_ID},
     traits::{LockableCurrencies, StandardizedVoting},
 };
 use sp_core::H256;
@@ -33,6 +33,7 @@ fn registers_the_correct_amount_of_coins_locked() {
 
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_quadratic_voting_parameters()
             ));
 
@@ -67,6 +68,7 @@ fn increase_counters_quadratically() {
 
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_quadratic_voting_parameters()
             ));
 
@@ -95,6 +97,7 @@ fn decrease_counters_correctly() {
 
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_quadratic_voting_parameters()
             ));
 
@@ -132,6 +135,7 @@ fn unlock_the_right_amount_of_coins() {
 
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_quadratic_voting_parameters()
             ));
 
@@ -172,10 +176,12 @@ fn locks_not_shared_between_proposals() {
 
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash_1,
+                &TEST_ORGANIZATION,
                 mock_quadratic_voting_parameters()
             ));
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash_2,
+                &TEST_ORGANIZATION,
                 mock_quadratic_voting_parameters()
             ));
 
@@ -21,8 +21,8 @@ use crate::{
 };
 use frame_support::{assert_noop, assert_ok, StorageMap};
 use governance_os_support::{
-    testing::{primitives::AccountId, ALICE, BOB, TEST_TOKEN_ID},
-    traits::{LockableCurrencies, ProposalResult, StandardizedVoting},
+    testing::{primitives::AccountId, ALICE, BOB, CHARLIE, TEST_ORGANIZATION, TEST_TOKEN_ID},
+    traits::{LockableCurrencies, ProposalResult, ProposalTally, StandardizedVoting},
 };
 use sp_core::H256;
 
@@ -36,12 +36,38 @@ fn initialize_registers_proposal_hash() {
 
         assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
             mock_hash,
+            &TEST_ORGANIZATION,
             Default::default()
         ));
         assert!(Proposals::<Test>::contains_key(mock_hash));
     })
 }
 
+#[test]
+fn can_vote_requires_voting_currency() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let mock_hash = H256::default();
+            assert!(!<CoinVoting as StandardizedVoting>::can_vote(
+                mock_hash, &ALICE
+            ));
+
+            assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
+                mock_hash,
+                &TEST_ORGANIZATION,
+                mock_voting_parameters()
+            ));
+            assert!(<CoinVoting as StandardizedVoting>::can_vote(
+                mock_hash, &ALICE
+            ));
+            assert!(!<CoinVoting as StandardizedVoting>::can_vote(
+                mock_hash, &CHARLIE
+            ));
+        })
+}
+
 #[test]
 fn vote_lock_tokens() {
     ExtBuilder::default()
@@ -52,6 +78,7 @@ fn vote_lock_tokens() {
 
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_voting_parameters()
             ));
 
@@ -81,6 +108,7 @@ fn vote_edit_previous_vote() {
 
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_voting_parameters()
             ));
 
@@ -123,6 +151,7 @@ fn votes_saved_correctly() {
 
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_voting_parameters()
             ));
 
@@ -186,6 +215,7 @@ fn vote_other_proposals_extend_locks() {
 
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash_1,
+                &TEST_ORGANIZATION,
                 mock_voting_parameters()
             ));
             assert_ok!(<CoinVoting as StandardizedVoting>::vote(
@@ -199,6 +229,7 @@ fn vote_other_proposals_extend_locks() {
 
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash_2,
+                &TEST_ORGANIZATION,
                 mock_voting_parameters()
             ));
             assert_ok!(<CoinVoting as StandardizedVoting>::vote(
@@ -232,6 +263,7 @@ fn vote_fail_if_not_enough_tokens() {
 
         assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
             mock_hash,
+            &TEST_ORGANIZATION,
             mock_voting_parameters()
         ));
 
@@ -261,6 +293,7 @@ macro_rules! test_close_or_veto {
 
                     assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                         mock_hash,
+                        &TEST_ORGANIZATION,
                         mock_voting_parameters()
                     ));
 
@@ -311,6 +344,7 @@ macro_rules! test_close_or_veto {
 
                     assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                         mock_hash,
+                        &TEST_ORGANIZATION,
                         mock_voting_parameters()
                     ));
 
@@ -354,10 +388,12 @@ macro_rules! test_close_or_veto {
 
                     assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                         mock_hash_1,
+                        &TEST_ORGANIZATION,
                         mock_voting_parameters()
                     ));
                     assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                         mock_hash_2,
+                        &TEST_ORGANIZATION,
                         mock_voting_parameters()
                     ));
 
@@ -430,12 +466,14 @@ fn close_error_if_early() {
 
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 VotingParameters {
                     voting_currency: TEST_TOKEN_ID,
                     ttl: CoinVoting::now() + 10,
                     min_quorum: 33,
                     min_participation: 50,
-                    vote_counting_strategy: VoteCountingStrategy::Simple
+                    vote_counting_strategy: VoteCountingStrategy::Simple,
+                    snapshot: false,
                 }
             ));
 
@@ -459,12 +497,14 @@ fn close_failing() {
 
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 VotingParameters {
                     voting_currency: TEST_TOKEN_ID,
                     ttl: CoinVoting::now() + 10,
                     min_quorum: 33,
                     min_participation: 50,
-                    vote_counting_strategy: VoteCountingStrategy::Simple
+                    vote_counting_strategy: VoteCountingStrategy::Simple,
+                    snapshot: false,
                 }
             ));
 
@@ -487,12 +527,14 @@ fn close_passing_early() {
 
             assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 VotingParameters {
                     voting_currency: TEST_TOKEN_ID,
                     ttl: CoinVoting::now() + 10,
                     min_quorum: 50,
                     min_participation: 33,
-                    vote_counting_strategy: VoteCountingStrategy::Simple
+                    vote_counting_strategy: VoteCountingStrategy::Simple,
+                    snapshot: false,
                 }
             ));
 
@@ -511,3 +553,99 @@ fn close_passing_early() {
             );
         })
 }
+
+#[test]
+fn tally_reports_state_without_closing() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let mock_hash = H256::default();
+
+            assert_eq!(CoinVoting::tally(mock_hash), None);
+            assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
+                mock_hash,
+                &TEST_ORGANIZATION,
+                VotingParameters {
+                    voting_currency: TEST_TOKEN_ID,
+                    ttl: 10,
+                    min_quorum: 50,
+                    min_participation: 33,
+                    vote_counting_strategy: VoteCountingStrategy::Simple,
+                    snapshot: false,
+                }
+            ));
+            assert_ok!(<CoinVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &ALICE,
+                VoteData {
+                    in_support: true,
+                    power: 99
+                }
+            ));
+            assert_ok!(<CoinVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &BOB,
+                VoteData {
+                    in_support: false,
+                    power: 11
+                }
+            ));
+
+            assert_eq!(
+                CoinVoting::tally(mock_hash),
+                Some(ProposalTally {
+                    favorable: 99,
+                    against: 11,
+                    participation: 110,
+                    participation_threshold: 66,
+                    min_participation: 33,
+                    quorum_threshold: 55,
+                    min_quorum: 50,
+                    expiry: 11,
+                    close_result: Some(ProposalResult::Passing),
+                })
+            );
+            assert!(Proposals::<Test>::contains_key(mock_hash));
+            assert_eq!(
+                <Tokens as LockableCurrencies<AccountId>>::locked_balance(TEST_TOKEN_ID, &ALICE),
+                99
+            );
+        })
+}
+
+#[test]
+fn tally_close_result_follows_expiry() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let mock_hash = H256::default();
+
+            assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
+                mock_hash,
+                &TEST_ORGANIZATION,
+                VotingParameters {
+                    voting_currency: TEST_TOKEN_ID,
+                    ttl: 10,
+                    min_quorum: 33,
+                    min_participation: 50,
+                    vote_counting_strategy: VoteCountingStrategy::Simple,
+                    snapshot: false,
+                }
+            ));
+
+            assert_eq!(CoinVoting::expiry(mock_hash), Some(11));
+            assert_eq!(CoinVoting::tally(mock_hash).unwrap().close_result, None);
+
+            System::set_block_number(12);
+            assert_eq!(
+                CoinVoting::tally(mock_hash).unwrap().close_result,
+                Some(ProposalResult::Failing)
+            );
+            assert_eq!(
+                CoinVoting::close(mock_hash).expect("proposal shall fail with no error"),
+                ProposalResult::Failing
+            );
+        })
+}
@@ -0,0 +1,269 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use super::mock::*;
+use crate::{types::VoteData, Error, SnapshotVotes};
+use frame_support::{assert_noop, assert_ok, StorageDoubleMap};
+use governance_os_support::{
+    testing::{
+        primitives::AccountId, ALICE, BOB, CHARLIE, TEST_ORGANIZATION, TEST_TOKEN_ID,
+        TEST_TOKEN_OWNER,
+    },
+    traits::{Currencies, LockableCurrencies, StandardizedVoting},
+};
+use sp_core::H256;
+
+#[test]
+fn initiate_requires_checkpoints() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                <CoinVoting as StandardizedVoting>::initiate(
+                    H256::default(),
+                    &TEST_ORGANIZATION,
+                    mock_snapshot_voting_parameters()
+                ),
+                Error::<Test>::NoCheckpoints
+            );
+
+            // Checkpoints must cover the block preceding the creation of the proposal.
+            assert_ok!(Tokens::set_checkpoints(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                true
+            ));
+            assert_noop!(
+                <CoinVoting as StandardizedVoting>::initiate(
+                    H256::default(),
+                    &TEST_ORGANIZATION,
+                    mock_snapshot_voting_parameters()
+                ),
+                Error::<Test>::NoCheckpoints
+            );
+        })
+}
+
+#[test]
+fn votes_use_snapshot_balances_without_locks() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let mock_hash = H256::default();
+            enable_checkpoints();
+            assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
+                mock_hash,
+                &TEST_ORGANIZATION,
+                mock_snapshot_voting_parameters()
+            ));
+
+            // Coins moved after the snapshot can not be used to vote twice.
+            assert_ok!(<Tokens as Currencies<AccountId>>::transfer(
+                TEST_TOKEN_ID,
+                &ALICE,
+                &BOB,
+                100
+            ));
+            assert_ok!(<CoinVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &ALICE,
+                VoteData {
+                    in_support: true,
+                    power: 100
+                }
+            ));
+            assert_noop!(
+                <CoinVoting as StandardizedVoting>::vote(
+                    mock_hash,
+                    &BOB,
+                    VoteData {
+                        in_support: false,
+                        power: 101
+                    }
+                ),
+                Error::<Test>::NotEnoughBalance
+            );
+            assert_ok!(<CoinVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &BOB,
+                VoteData {
+                    in_support: false,
+                    power: 100
+                }
+            ));
+
+            assert_eq!(
+                <Tokens as LockableCurrencies<AccountId>>::locked_balance(TEST_TOKEN_ID, &ALICE),
+                0
+            );
+            assert_eq!(
+                <Tokens as LockableCurrencies<AccountId>>::locked_balance(TEST_TOKEN_ID, &BOB),
+                0
+            );
+            assert_eq!(CoinVoting::proposals(mock_hash).total_favorable, 100);
+            assert_eq!(CoinVoting::proposals(mock_hash).total_against, 100);
+        })
+}
+
+#[test]
+fn votes_can_be_changed() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let mock_hash = H256::default();
+            enable_checkpoints();
+            assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
+                mock_hash,
+                &TEST_ORGANIZATION,
+                mock_snapshot_voting_parameters()
+            ));
+
+            assert_ok!(<CoinVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &ALICE,
+                VoteData {
+                    in_support: true,
+                    power: 60
+                }
+            ));
+            assert_ok!(<CoinVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &ALICE,
+                VoteData {
+                    in_support: false,
+                    power: 40
+                }
+            ));
+
+            assert_eq!(CoinVoting::proposals(mock_hash).total_favorable, 0);
+            assert_eq!(CoinVoting::proposals(mock_hash).total_against, 40);
+            assert_eq!(
+                SnapshotVotes::<Test>::get(mock_hash, ALICE),
+                Some((false, 40))
+            );
+        })
+}
+
+#[test]
+fn participation_uses_snapshot_issuance() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let mock_hash = H256::default();
+            enable_checkpoints();
+            assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
+                mock_hash,
+                &TEST_ORGANIZATION,
+                mock_snapshot_voting_parameters()
+            ));
+            let tally = CoinVoting::tally(mock_hash);
+
+            assert_ok!(<Tokens as Currencies<AccountId>>::mint(
+                TEST_TOKEN_ID,
+                &ALICE,
+                1_000
+            ));
+            assert_eq!(CoinVoting::tally(mock_hash), tally);
+        })
+}
+
+#[test]
+fn close_clears_snapshot_votes() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let mock_hash = H256::default();
+            enable_checkpoints();
+            assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
+                mock_hash,
+                &TEST_ORGANIZATION,
+                mock_snapshot_voting_parameters()
+            ));
+            assert_ok!(<CoinVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &ALICE,
+                VoteData {
+                    in_support: true,
+                    power: 100
+                }
+            ));
+
+            System::set_block_number(System::block_number() + mock_voting_parameters().ttl + 1);
+            assert_ok!(<CoinVoting as StandardizedVoting>::close(mock_hash));
+            assert!(!SnapshotVotes::<Test>::contains_key(mock_hash, ALICE));
+        })
+}
+
+#[test]
+fn delegated_power_is_capped_by_snapshot_balances() {
+    ExtBuilder::default()
+        .balances(vec![
+            (TEST_TOKEN_ID, ALICE, 100),
+            (TEST_TOKEN_ID, BOB, 10),
+            (TEST_TOKEN_ID, CHARLIE, 10),
+        ])
+        .build()
+        .execute_with(|| {
+            let mock_hash = H256::default();
+            enable_checkpoints();
+            assert_ok!(<CoinVoting as StandardizedVoting>::initiate(
+                mock_hash,
+                &TEST_ORGANIZATION,
+                mock_snapshot_voting_parameters()
+            ));
+            assert_ok!(<CoinVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &ALICE,
+                VoteData {
+                    in_support: true,
+                    power: 100
+                }
+            ));
+
+            // Alice's coins were already counted, Bob can only delegate his snapshot balance.
+            assert_ok!(<Tokens as Currencies<AccountId>>::transfer(
+                TEST_TOKEN_ID,
+                &ALICE,
+                &BOB,
+                100
+            ));
+            assert_ok!(Delegation::delegate(
+                Origin::signed(BOB),
+                TEST_TOKEN_ID,
+                None,
+                CHARLIE,
+                110
+            ));
+            assert_ok!(<CoinVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &CHARLIE,
+                VoteData {
+                    in_support: true,
+                    power: 10
+                }
+            ));
+
+            assert_eq!(CoinVoting::proposals(mock_hash).total_favorable, 120);
+            assert_eq!(
+                CoinVoting::delegated_votes(BOB, mock_hash),
+                Some((CHARLIE, true, 10))
+            );
+        })
+}
@@ -50,6 +50,11 @@ pub struct VotingParameters<BlockNumber, CurrencyId> {
 
     /// Defines how we are going to count and register votes.
     pub vote_counting_strategy: VoteCountingStrategy,
+
+    /// If set, votes are counted from the balances held at the end of the block
+    /// preceding the proposal's creation and no coins are locked. The voting
+    /// currency must record balance checkpoints.
+    pub snapshot: bool,
 }
 
 /// Various strategies we support to count votes.
@@ -74,9 +79,12 @@ pub struct VoteData<Balance> {
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, Default)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct ProposalState<Balance, BlockNumber, CurrencyId, LockIdentifier> {
+pub struct ProposalState<AccountId, Balance, BlockNumber, CurrencyId, LockIdentifier> {
     /// Parameters that this proposal was created with.
     pub parameters: VotingParameters<BlockNumber, CurrencyId>,
+    /// Organization the proposal was submitted to. Used to find which
+    /// delegations apply to it.
+    pub organization: AccountId,
     /// Total amount of votes staked in favor of this proposal.
     pub total_favorable: Balance,
     /// Total amount of votes staked against this proposal.
@@ -84,14 +92,22 @@ pub struct ProposalState<Balance, BlockNumber, CurrencyId, LockIdentifier> {
 
     /// Used to list all opened locks on coins to later free those.
     pub locks: Vec<LockIdentifier>,
+    /// Accounts whose delegated voting power was counted in this proposal.
+    /// Used to later clean the associated storage.
+    pub delegators: Vec<AccountId>,
 
     /// Record when the proposal was created. Used to know when it is expired
     /// (when the ttl is over).
     pub created_on: BlockNumber,
 }
 
-impl<Balance: Saturating + Copy + IntegerSquareRoot, BlockNumber, CurrencyId, LockIdentifier>
-    ProposalState<Balance, BlockNumber, CurrencyId, LockIdentifier>
+impl<
+        AccountId,
+        Balance: Saturating + Copy + IntegerSquareRoot,
+        BlockNumber,
+        CurrencyId,
+        LockIdentifier,
+    > ProposalState<AccountId, Balance, BlockNumber, CurrencyId, LockIdentifier>
 {
     /// Record a new vote based on wether it is favorable or not.
     pub fn record_vote(&mut self, favorable: bool, power: Balance) {
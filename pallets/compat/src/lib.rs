@@ -80,7 +80,12 @@ pub mod pallet {
         /// Dispatches the given call with a `Root` origin.
         #[pallet::weight({
             let dispatch_info = call.get_dispatch_info();
-            (dispatch_info.weight.saturating_add(10_000), dispatch_info.class)
+            (
+                dispatch_info.weight
+                    .saturating_add(10_000)
+                    .saturating_add(RoleManagerOf::<T>::has_role_weight()),
+                dispatch_info.class
+            )
         })]
         pub(crate) fn sudo(
             origin: OriginFor<T>,
@@ -115,8 +120,7 @@ pub mod pallet {
                     .saturating_add(10_000)
                     // AccountData for inner call origin accountdata.
                     .saturating_add(T::DbWeight::get().reads_writes(1, 1))
-                    // Two read performed by ensure_has_role
-                    .saturating_add(T::DbWeight::get().reads(2)),
+                    .saturating_add(RoleManagerOf::<T>::has_role_weight()),
                 dispatch_info.class
             )
         })]
@@ -103,6 +103,7 @@ impl governance_os_pallet_bylaws::RoleBuilder for MockRoles {
 parameter_types! {
     pub const RootRole: MockRoles = MockRoles::Root;
     pub const MaxRoles: u32 = 5;
+    pub const MaxRoleAncestors: u32 = 4;
 }
 
 impl governance_os_pallet_bylaws::Config for Test {
@@ -110,6 +111,7 @@ impl governance_os_pallet_bylaws::Config for Test {
     type Role = MockRoles;
     type WeightInfo = ();
     type MaxRoles = MaxRoles;
+    type MaxRoleAncestors = MaxRoleAncestors;
     type RoleBuilder = MockRoles;
 }
 
@@ -145,6 +147,7 @@ impl ExtBuilder {
         if self.root.is_some() {
             governance_os_pallet_bylaws::GenesisConfig::<Test> {
                 roles: vec![(MockRoles::Root, self.root)],
+                role_settings: vec![],
             }
             .assimilate_storage(&mut t)
             .unwrap();
@@ -23,19 +23,23 @@
 //! slowly accumulate to whatever the user staked on the proposal. This
 //! means that even if they stake a lot of coins they won't be accounted
 //! immediately.
+//! Proposals can also be configured to limit convictions to the balances held
+//! when they were created, in which case voters do not have to lock their coins.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::{
     decl_error, decl_module, decl_storage, ensure,
     traits::{Get, LockIdentifier},
+    weights::Weight,
 };
 use governance_os_support::traits::{
-    Currencies, LockableCurrencies, ProposalResult, StandardizedVoting,
+    voting_lock_reasons, CheckpointedCurrencies, Currencies, Delegations, LockableCurrencies,
+    OnDelegationChanged, ProposalResult, ProposalTally, StandardizedVoting,
 };
 use sp_runtime::{
-    traits::{Saturating, Zero},
-    DispatchError, DispatchResult, Perbill,
+    traits::{One, Saturating, Zero},
+    DispatchError, DispatchResult,
 };
 use sp_std::prelude::*;
 
@@ -48,8 +52,10 @@ pub use types::{Conviction, ProposalState, VotingParameters};
 pub const CONVICTION_VOTING_LOCK_ID: LockIdentifier = *b"convvote";
 
 pub trait Config: frame_system::Config {
-    /// Pallet in charge of currencies. Used so that we can lock tokens etc...
-    type Currencies: LockableCurrencies<Self::AccountId>;
+    /// Pallet in charge of currencies. Used so that we can lock tokens or read the
+    /// balances snapshot proposals are counted from.
+    type Currencies: LockableCurrencies<Self::AccountId>
+        + CheckpointedCurrencies<Self::AccountId, Self::BlockNumber>;
     /// Decay constant as part of the conviction voting / decay curve formula.
     /// Shared among all organizations relying on this pallet.
     /// Must be expressed in the form `Decay = a * D` where `D` is `10` and
@@ -57,6 +63,11 @@ pub trait Config: frame_system::Config {
     /// [document](https://hackmd.io/@EtCgawsxS2mC6-Q0rCqhAw/rJMvfgOv4?type=view#Solidity-implementation)
     /// for better explanations.
     type Decay: Get<BalanceOf<Self>>;
+    /// Used to count the voting power delegated to the voters.
+    type Delegations: Delegations<Self::AccountId, CurrencyIdOf<Self>, BalanceOf<Self>>;
+    /// Maximum number of open proposals an account can vote on with the same currency. Bounds
+    /// the number of proposals recounted when a delegation changes.
+    type MaxActiveVotes: Get<u32>;
 }
 
 type BalanceOf<T> =
@@ -69,11 +80,18 @@ type ConvictionProposalStateOf<T> = ProposalState<
     <T as frame_system::Config>::BlockNumber,
     CurrencyIdOf<T>,
 >;
+type ProposalTallyOf<T> = ProposalTally<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
 
 decl_storage! {
     trait Store for Module<T: Config> as PlcrVoting {
         pub Proposals get(fn proposals): map hasher(blake2_128_concat) T::Hash => ConvictionProposalStateOf<T>;
         pub Locks get(fn locks): map hasher(blake2_128_concat) (CurrencyIdOf<T>, T::AccountId) => Vec<(T::Hash, bool, BalanceOf<T>)>;
+        /// Voting power delegated to voters and counted in the proposals' convictions. Keyed by delegator
+        /// and proposal, values are the delegate, its support and the amount counted.
+        pub DelegatedVotes get(fn delegated_votes): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::Hash => Option<(T::AccountId, bool, BalanceOf<T>)>;
+        /// Open proposals an account voted on with a given currency. Used to find the proposals to
+        /// recount when a delegation changes.
+        pub ActiveVotes get(fn active_votes): map hasher(blake2_128_concat) (CurrencyIdOf<T>, T::AccountId) => Vec<T::Hash>;
     }
 }
 
@@ -86,6 +104,12 @@ decl_error! {
         /// met. This typically happens if the proposal did not receive
         /// enough support and is not yet expired.
         CannotClose,
+        /// Snapshot proposals require the voting currency to record balance
+        /// checkpoints since before their creation.
+        NoCheckpoints,
+        /// The voter already voted on `MaxActiveVotes` open proposals with
+        /// this currency.
+        TooManyVotes,
     }
 }
 
@@ -99,15 +123,33 @@ impl<T: Config> StandardizedVoting for Module<T> {
     type Parameters = VotingParameters<T::BlockNumber, CurrencyIdOf<T>>;
     type VoteData = Conviction<BalanceOf<T>>;
     type AccountId = T::AccountId;
+    type Tally = ProposalTallyOf<T>;
+    type BlockNumber = T::BlockNumber;
+
+    fn initiate(
+        proposal: Self::ProposalId,
+        organization: &Self::AccountId,
+        parameters: Self::Parameters,
+    ) -> DispatchResult {
+        let state = ProposalState {
+            parameters,
+            organization: organization.clone(),
+            created_on: Self::now(),
+            ..Default::default()
+        };
+        ensure!(
+            !state.parameters.snapshot
+                || T::Currencies::total_issuance_at(
+                    state.parameters.voting_currency,
+                    Self::snapshot_block(&state)
+                )
+                .is_some(),
+            Error::<T>::NoCheckpoints
+        );
 
-    fn initiate(proposal: Self::ProposalId, parameters: Self::Parameters) -> DispatchResult {
         Proposals::<T>::try_mutate_exists(proposal, |maybe_existing_state| -> DispatchResult {
             // no duplicates, we can create a new state
-            *maybe_existing_state = Some(ProposalState {
-                parameters,
-                created_on: Self::now(),
-                ..Default::default()
-            });
+            *maybe_existing_state = Some(state);
 
             Ok(())
         })?;
@@ -126,21 +168,37 @@ impl<T: Config> StandardizedVoting for Module<T> {
         data: Self::VoteData,
     ) -> DispatchResult {
         let mut state = Proposals::<T>::get(proposal);
+        let mut active_votes = ActiveVotes::<T>::get((state.parameters.voting_currency, voter));
+        if !active_votes.contains(&proposal) {
+            ensure!(
+                (active_votes.len() as u32) < T::MaxActiveVotes::get(),
+                Error::<T>::TooManyVotes
+            );
+            active_votes.push(proposal);
+        }
 
-        ensure!(
-            data.power < T::Currencies::free_balance(state.parameters.voting_currency, voter),
-            Error::<T>::NotEnoughBalance
-        );
-
-        let mut locks = Locks::<T>::get((state.parameters.voting_currency, voter));
-        locks = locks
-            .iter()
-            .cloned()
-            .filter(|(proposal_hash, _support, _power)| *proposal_hash != proposal)
-            .collect::<Vec<_>>();
-        locks.push((proposal, data.in_support, data.power));
-
-        Self::rejig_locks(state.parameters.voting_currency, voter, locks)?;
+        if state.parameters.snapshot {
+            // Snapshot proposals do not lock any coins.
+            ensure!(
+                data.power <= Self::snapshot_balance(&state, voter),
+                Error::<T>::NotEnoughBalance
+            );
+        } else {
+            ensure!(
+                data.power < T::Currencies::free_balance(state.parameters.voting_currency, voter),
+                Error::<T>::NotEnoughBalance
+            );
+
+            let mut locks = Locks::<T>::get((state.parameters.voting_currency, voter));
+            locks = locks
+                .iter()
+                .cloned()
+                .filter(|(proposal_hash, _support, _power)| *proposal_hash != proposal)
+                .collect::<Vec<_>>();
+            locks.push((proposal, data.in_support, data.power));
+
+            Self::rejig_locks(state.parameters.voting_currency, voter, locks)?;
+        }
 
         let mut maybe_previous_conviction = None;
         let mut filtered_convictions = Vec::new();
@@ -176,8 +234,13 @@ impl<T: Config> StandardizedVoting for Module<T> {
             state.conviction_against = state.conviction_against.saturating_add(data.power);
         }
 
+        // A direct vote overrides any delegation.
+        Self::uncount_delegated_vote(&mut state, proposal, voter);
+        Self::count_delegated_votes(&mut state, proposal, voter, data.in_support);
+
         // Refresh conviction snapshot
         state.mutate_conviction_snapshot(Self::now(), T::Decay::get())?;
+        ActiveVotes::<T>::insert((state.parameters.voting_currency, voter), active_votes);
         Proposals::<T>::insert(proposal, state);
 
         Ok(())
@@ -185,36 +248,102 @@ impl<T: Config> StandardizedVoting for Module<T> {
 
     fn close(proposal: Self::ProposalId) -> Result<ProposalResult, DispatchError> {
         let mut state = Proposals::<T>::get(proposal);
-
-        let total_supply = T::Currencies::total_issuance(state.parameters.voting_currency);
-        state.mutate_conviction_snapshot(Self::now(), T::Decay::get())?;
-        let total_participation = state
-            .snapshot
-            .favorable
-            .saturating_add(state.snapshot.against);
-
-        let enough_participation = total_participation
-            > Perbill::from_percent(state.parameters.min_participation) * total_supply;
-        let enough_quorum = state.snapshot.favorable
-            > Perbill::from_percent(state.parameters.min_quorum) * total_participation;
-
-        let result = if enough_participation && enough_quorum {
-            ProposalResult::Passing
-        } else {
-            ProposalResult::Failing
-        };
-
-        let can_close = state.created_on.saturating_add(state.parameters.ttl) < Self::now();
-        ensure!(
-            can_close || result == ProposalResult::Passing,
-            Error::<T>::CannotClose
-        );
+        let result = Self::refresh_tally(&mut state)?
+            .close_result
+            .ok_or(Error::<T>::CannotClose)?;
 
         Self::finalize(proposal, state)?;
         Proposals::<T>::remove(proposal);
 
         Ok(result)
     }
+
+    /// Compute the tally with an up to date conviction snapshot. The refreshed snapshot
+    /// is not saved.
+    fn tally(proposal: Self::ProposalId) -> Option<Self::Tally> {
+        let mut state = Proposals::<T>::try_get(proposal).ok()?;
+        Self::refresh_tally(&mut state).ok()
+    }
+
+    /// A proposal expires once its ttl is over.
+    fn expiry(proposal: Self::ProposalId) -> Option<Self::BlockNumber> {
+        Proposals::<T>::try_get(proposal)
+            .ok()
+            .map(|state| Self::expiry_of(&state))
+    }
+
+    fn can_vote(proposal: Self::ProposalId, voter: &Self::AccountId) -> bool {
+        match Proposals::<T>::try_get(proposal) {
+            Ok(state) if state.parameters.snapshot => {
+                !Self::snapshot_balance(&state, voter).is_zero()
+            }
+            Ok(state) => {
+                !T::Currencies::free_balance(state.parameters.voting_currency, voter).is_zero()
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn vote_weight() -> Weight {
+        // We read the proposal, the active votes and locks of the voter, its balance and
+        // delegated vote, then write them back. For each delegator we take its delegated
+        // vote, read its snapshot balance and write its new delegated vote.
+        let delegators = T::Delegations::max_delegators_of();
+        T::DbWeight::get()
+            .reads_writes(5, 4)
+            .saturating_add(T::Delegations::delegators_of_weight())
+            .saturating_add(T::DbWeight::get().reads_writes(
+                delegators.saturating_mul(2).into(),
+                delegators.saturating_mul(2).into(),
+            ))
+    }
+}
+
+impl<T: Config> OnDelegationChanged<T::AccountId, CurrencyIdOf<T>> for Module<T> {
+    /// Recount the voting power of `delegator` in the proposals `delegates` voted on, it
+    /// may now be delegated to another account or not be delegated at all.
+    fn on_delegation_changed(
+        currency: CurrencyIdOf<T>,
+        delegator: &T::AccountId,
+        delegates: &[T::AccountId],
+    ) -> DispatchResult {
+        Self::proposals_voted_by(currency, delegates)
+            .into_iter()
+            .try_for_each(|proposal| -> DispatchResult {
+                let mut state = Proposals::<T>::get(proposal);
+
+                Self::uncount_delegated_vote(&mut state, proposal, delegator);
+                // A direct vote overrides any delegation.
+                if Self::vote_of(&state, delegator).is_none() {
+                    if let Some((delegate, amount)) =
+                        T::Delegations::delegation_of(currency, &state.organization, delegator)
+                    {
+                        if let Some(support) = Self::vote_of(&state, &delegate) {
+                            Self::count_delegated_vote(
+                                &mut state, proposal, delegator, delegate, support, amount,
+                            );
+                        }
+                    }
+                }
+
+                state.mutate_conviction_snapshot(Self::now(), T::Decay::get())?;
+                Proposals::<T>::insert(proposal, state);
+
+                Ok(())
+            })
+    }
+
+    fn on_delegation_changed_weight(delegates: u32) -> Weight {
+        // For each proposal we read it, the delegated vote, both delegations of the delegator
+        // and its snapshot balance. Then write the proposal and the delegated vote.
+        let proposals = T::MaxActiveVotes::get().saturating_mul(delegates);
+        T::DbWeight::get()
+            .reads(delegates.into())
+            .saturating_add(T::DbWeight::get().reads_writes(
+                proposals.saturating_mul(8).into(),
+                proposals.saturating_mul(2).into(),
+            ))
+    }
 }
 
 impl<T: Config> Module<T> {
@@ -223,6 +352,61 @@ impl<T: Config> Module<T> {
         frame_system::Module::<T>::block_number()
     }
 
+    /// Refresh the conviction snapshot of `state` and compute its tally from it. Shared
+    /// by `tally` and `close` so that both always agree on the outcome.
+    fn refresh_tally(
+        state: &mut ConvictionProposalStateOf<T>,
+    ) -> Result<ProposalTallyOf<T>, DispatchError> {
+        state.mutate_conviction_snapshot(Self::now(), T::Decay::get())?;
+
+        Ok(ProposalTally::new(
+            state.snapshot.favorable,
+            state.snapshot.against,
+            Self::voting_supply(state),
+            state.parameters.min_participation,
+            state.parameters.min_quorum,
+            Self::expiry_of(state),
+            Self::now(),
+        ))
+    }
+
+    /// Last block during which a proposal can still receive votes before being considered
+    /// as failing.
+    fn expiry_of(state: &ConvictionProposalStateOf<T>) -> T::BlockNumber {
+        state.created_on.saturating_add(state.parameters.ttl)
+    }
+
+    /// Block whose balances are used by snapshot proposals. Coins can still move during the
+    /// block the proposal was created in, so we use the one preceding it.
+    fn snapshot_block(state: &ConvictionProposalStateOf<T>) -> T::BlockNumber {
+        state.created_on.saturating_sub(One::one())
+    }
+
+    /// Balance `who` can stake on a snapshot proposal. Zero if the voting currency stopped
+    /// recording checkpoints.
+    fn snapshot_balance(state: &ConvictionProposalStateOf<T>, who: &T::AccountId) -> BalanceOf<T> {
+        T::Currencies::balance_at(
+            state.parameters.voting_currency,
+            who,
+            Self::snapshot_block(state),
+        )
+        .unwrap_or_else(Zero::zero)
+    }
+
+    /// Supply participation is computed from, the one at the time of the snapshot for snapshot
+    /// proposals.
+    fn voting_supply(state: &ConvictionProposalStateOf<T>) -> BalanceOf<T> {
+        let currency = state.parameters.voting_currency;
+        if state.parameters.snapshot {
+            if let Some(issuance) =
+                T::Currencies::total_issuance_at(currency, Self::snapshot_block(state))
+            {
+                return issuance;
+            }
+        }
+        T::Currencies::total_issuance(currency)
+    }
+
     /// Iterates the `locks` vector and lock the maximum amount of coins needed for
     /// the pair `voting_currency` and `voter`.
     fn rejig_locks(
@@ -243,7 +427,13 @@ impl<T: Config> Module<T> {
         if max == Zero::zero() {
             T::Currencies::remove_lock(voting_currency, CONVICTION_VOTING_LOCK_ID, voter)?;
         } else {
-            T::Currencies::set_lock(voting_currency, CONVICTION_VOTING_LOCK_ID, voter, max)?;
+            T::Currencies::set_lock_with_reasons(
+                voting_currency,
+                CONVICTION_VOTING_LOCK_ID,
+                voter,
+                max,
+                voting_lock_reasons(),
+            )?;
         }
         if locks.is_empty() {
             Locks::<T>::remove((voting_currency, voter));
@@ -254,9 +444,115 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Count the voting power delegated to `delegate` in the convictions of `proposal`,
+    /// replacing any power previously counted for it. Delegators that voted themselves
+    /// are ignored. Delegation changes are recounted as they happen so the power previously
+    /// counted for `delegate` can only come from its current delegators.
+    fn count_delegated_votes(
+        state: &mut ConvictionProposalStateOf<T>,
+        proposal: T::Hash,
+        delegate: &T::AccountId,
+        support: bool,
+    ) {
+        T::Delegations::delegators_of(
+            state.parameters.voting_currency,
+            &state.organization,
+            delegate,
+        )
+        .into_iter()
+        .for_each(|(delegator, amount)| {
+            Self::uncount_delegated_vote(state, proposal, &delegator);
+            if Self::vote_of(state, &delegator).is_none() {
+                Self::count_delegated_vote(
+                    state,
+                    proposal,
+                    &delegator,
+                    delegate.clone(),
+                    support,
+                    amount,
+                )
+            }
+        });
+    }
+
+    /// Add the power of `delegator` to the convictions of `proposal`. On snapshot proposals
+    /// it can not exceed the delegator's balance at the time of the snapshot.
+    fn count_delegated_vote(
+        state: &mut ConvictionProposalStateOf<T>,
+        proposal: T::Hash,
+        delegator: &T::AccountId,
+        delegate: T::AccountId,
+        support: bool,
+        amount: BalanceOf<T>,
+    ) {
+        let amount = if state.parameters.snapshot {
+            amount.min(Self::snapshot_balance(state, delegator))
+        } else {
+            amount
+        };
+        state.add_conviction(support, amount);
+        DelegatedVotes::<T>::insert(delegator, proposal, (delegate, support, amount));
+        if !state.delegators.contains(delegator) {
+            state.delegators.push(delegator.clone());
+        }
+    }
+
+    /// Remove the power of `delegator` from the convictions of `proposal`, if it was counted.
+    fn uncount_delegated_vote(
+        state: &mut ConvictionProposalStateOf<T>,
+        proposal: T::Hash,
+        delegator: &T::AccountId,
+    ) {
+        if let Some((_delegate, support, amount)) = DelegatedVotes::<T>::take(delegator, proposal) {
+            state.remove_conviction(support, amount);
+        }
+    }
+
+    /// Returns the support of the vote `who` casted on the proposal, if any.
+    fn vote_of(state: &ConvictionProposalStateOf<T>, who: &T::AccountId) -> Option<bool> {
+        state
+            .convictions
+            .iter()
+            .find(|(participant, _when, _conviction)| participant == who)
+            .map(|(_participant, _when, conviction)| conviction.in_support)
+    }
+
+    /// Open proposals on which any of `voters` voted with `currency`.
+    fn proposals_voted_by(currency: CurrencyIdOf<T>, voters: &[T::AccountId]) -> Vec<T::Hash> {
+        let mut proposals: Vec<T::Hash> = voters
+            .iter()
+            .flat_map(|voter| ActiveVotes::<T>::get((currency, voter)))
+            .collect();
+        proposals.sort();
+        proposals.dedup();
+        proposals
+    }
+
     /// Goes through all the elements related to the passed proposal state and cleans
     /// up any associated storage elements such as locks.
     fn finalize(proposal: T::Hash, state: ConvictionProposalStateOf<T>) -> DispatchResult {
+        state
+            .delegators
+            .iter()
+            .for_each(|delegator| DelegatedVotes::<T>::remove(delegator, proposal));
+        state
+            .convictions
+            .iter()
+            .for_each(|(voter, _when, _conviction)| {
+                ActiveVotes::<T>::mutate_exists(
+                    (state.parameters.voting_currency, voter),
+                    |maybe_proposals| {
+                        let mut proposals = maybe_proposals.take().unwrap_or_default();
+                        proposals.retain(|p| p != &proposal);
+                        if !proposals.is_empty() {
+                            *maybe_proposals = Some(proposals);
+                        }
+                    },
+                )
+            });
+        if state.parameters.snapshot {
+            return Ok(());
+        }
         state
             .convictions
             .iter()
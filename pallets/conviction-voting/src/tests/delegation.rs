@@ -0,0 +1,212 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{Conviction, DelegatedVotes, Error};
+use frame_support::{assert_noop, assert_ok, StorageDoubleMap};
+use governance_os_support::{
+    testing::{ALICE, BOB, CHARLIE, TEST_ORGANIZATION, TEST_TOKEN_ID},
+    traits::StandardizedVoting,
+};
+use sp_core::H256;
+
+fn vote(voter: u128, in_support: bool, power: u128) {
+    assert_ok!(<ConvictionVoting as StandardizedVoting>::vote(
+        H256::default(),
+        &voter,
+        Conviction { in_support, power }
+    ));
+}
+
+fn convictions() -> (u128, u128) {
+    let state = ConvictionVoting::proposals(H256::default());
+    (state.conviction_for, state.conviction_against)
+}
+
+fn with_alice_delegating_to_bob(test: impl FnOnce()) {
+    ExtBuilder::default()
+        .balances(vec![
+            (TEST_TOKEN_ID, ALICE, 100),
+            (TEST_TOKEN_ID, BOB, 100),
+            (TEST_TOKEN_ID, CHARLIE, 100),
+        ])
+        .build()
+        .execute_with(|| {
+            assert_ok!(Delegation::delegate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                None,
+                BOB,
+                50
+            ));
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
+                H256::default(),
+                &TEST_ORGANIZATION,
+                mock_voting_parameters()
+            ));
+
+            test()
+        })
+}
+
+#[test]
+fn delegated_power_is_counted_when_delegate_votes() {
+    with_alice_delegating_to_bob(|| {
+        vote(BOB, true, 10);
+
+        assert_eq!(convictions(), (60, 0));
+        assert_eq!(
+            ConvictionVoting::delegated_votes(ALICE, H256::default()),
+            Some((BOB, true, 50))
+        );
+    })
+}
+
+#[test]
+fn direct_vote_overrides_delegation() {
+    with_alice_delegating_to_bob(|| {
+        vote(BOB, true, 10);
+        vote(ALICE, false, 20);
+        vote(BOB, true, 15);
+
+        assert_eq!(convictions(), (15, 20));
+        assert_eq!(
+            ConvictionVoting::delegated_votes(ALICE, H256::default()),
+            None
+        );
+    })
+}
+
+#[test]
+fn undelegating_recomputes_convictions() {
+    with_alice_delegating_to_bob(|| {
+        vote(BOB, true, 10);
+        vote(CHARLIE, false, 5);
+
+        assert_ok!(Delegation::delegate(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            None,
+            CHARLIE,
+            40
+        ));
+        assert_eq!(convictions(), (10, 45));
+
+        assert_ok!(Delegation::undelegate(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            None
+        ));
+        assert_eq!(convictions(), (10, 5));
+    })
+}
+
+#[test]
+fn veto_clears_delegated_votes() {
+    with_alice_delegating_to_bob(|| {
+        vote(BOB, true, 10);
+
+        assert_ok!(<ConvictionVoting as StandardizedVoting>::veto(
+            H256::default()
+        ));
+
+        assert!(!DelegatedVotes::<Test>::contains_key(
+            ALICE,
+            H256::default()
+        ));
+    })
+}
+
+#[test]
+fn delegating_after_the_delegate_voted_is_counted() {
+    ExtBuilder::default()
+        .balances(vec![(TEST_TOKEN_ID, ALICE, 100), (TEST_TOKEN_ID, BOB, 100)])
+        .build()
+        .execute_with(|| {
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
+                H256::default(),
+                &TEST_ORGANIZATION,
+                mock_voting_parameters()
+            ));
+            vote(BOB, true, 10);
+
+            assert_ok!(Delegation::delegate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                None,
+                BOB,
+                50
+            ));
+
+            assert_eq!(convictions(), (60, 0));
+            assert_eq!(
+                ConvictionVoting::delegated_votes(ALICE, H256::default()),
+                Some((BOB, true, 50))
+            );
+        })
+}
+
+#[test]
+fn votes_are_limited_to_max_active_votes() {
+    ExtBuilder::default()
+        .balances(vec![(TEST_TOKEN_ID, ALICE, 100)])
+        .build()
+        .execute_with(|| {
+            (0..=MaxActiveVotes::get()).for_each(|i| {
+                assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
+                    H256::repeat_byte(i as u8),
+                    &TEST_ORGANIZATION,
+                    mock_voting_parameters()
+                ));
+            });
+            (0..MaxActiveVotes::get()).for_each(|i| {
+                assert_ok!(<ConvictionVoting as StandardizedVoting>::vote(
+                    H256::repeat_byte(i as u8),
+                    &ALICE,
+                    Conviction {
+                        in_support: true,
+                        power: 10
+                    }
+                ));
+            });
+
+            let last = H256::repeat_byte(MaxActiveVotes::get() as u8);
+            assert_noop!(
+                <ConvictionVoting as StandardizedVoting>::vote(
+                    last,
+                    &ALICE,
+                    Conviction {
+                        in_support: true,
+                        power: 10
+                    }
+                ),
+                Error::<Test>::TooManyVotes
+            );
+
+            // Vetoing a proposal frees a slot
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::veto(
+                H256::repeat_byte(0)
+            ));
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::vote(
+                last,
+                &ALICE,
+                Conviction {
+                    in_support: true,
+                    power: 10
+                }
+            ));
+        })
+}
@@ -15,5 +15,7 @@
  */
 
 mod conviction;
+mod delegation;
 mod mock;
+mod snapshot;
 mod spec;
@@ -0,0 +1,161 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use super::mock::*;
+use crate::{Conviction, Error, Locks, Proposals};
+use frame_support::{assert_noop, assert_ok, StorageMap};
+use governance_os_support::{
+    testing::{
+        primitives::AccountId, ALICE, BOB, TEST_ORGANIZATION, TEST_TOKEN_ID, TEST_TOKEN_OWNER,
+    },
+    traits::{Currencies, LockableCurrencies, StandardizedVoting},
+};
+use sp_core::H256;
+
+#[test]
+fn initiate_requires_checkpoints() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                <ConvictionVoting as StandardizedVoting>::initiate(
+                    H256::default(),
+                    &TEST_ORGANIZATION,
+                    mock_snapshot_voting_parameters()
+                ),
+                Error::<Test>::NoCheckpoints
+            );
+
+            assert_ok!(Tokens::set_checkpoints(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                true
+            ));
+            assert_noop!(
+                <ConvictionVoting as StandardizedVoting>::initiate(
+                    H256::default(),
+                    &TEST_ORGANIZATION,
+                    mock_snapshot_voting_parameters()
+                ),
+                Error::<Test>::NoCheckpoints
+            );
+        })
+}
+
+#[test]
+fn votes_use_snapshot_balances_without_locks() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let mock_hash = H256::default();
+            enable_checkpoints();
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
+                mock_hash,
+                &TEST_ORGANIZATION,
+                mock_snapshot_voting_parameters()
+            ));
+
+            // Coins moved after the snapshot can not be used to vote twice.
+            assert_ok!(<Tokens as Currencies<AccountId>>::transfer(
+                TEST_TOKEN_ID,
+                &ALICE,
+                &BOB,
+                100
+            ));
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &ALICE,
+                Conviction {
+                    in_support: true,
+                    power: 100
+                }
+            ));
+            assert_noop!(
+                <ConvictionVoting as StandardizedVoting>::vote(
+                    mock_hash,
+                    &BOB,
+                    Conviction {
+                        in_support: false,
+                        power: 101
+                    }
+                ),
+                Error::<Test>::NotEnoughBalance
+            );
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &BOB,
+                Conviction {
+                    in_support: false,
+                    power: 100
+                }
+            ));
+
+            assert_eq!(
+                <Tokens as LockableCurrencies<AccountId>>::locked_balance(TEST_TOKEN_ID, &ALICE),
+                0
+            );
+            assert!(!Locks::<Test>::contains_key((TEST_TOKEN_ID, BOB)));
+            assert_eq!(Proposals::<Test>::get(mock_hash).conviction_for, 100);
+            assert_eq!(Proposals::<Test>::get(mock_hash).conviction_against, 100);
+        })
+}
+
+#[test]
+fn veto_keeps_other_locks() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let locked_hash = H256::repeat_byte(1);
+            let snapshot_hash = H256::repeat_byte(2);
+            enable_checkpoints();
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
+                locked_hash,
+                &TEST_ORGANIZATION,
+                mock_voting_parameters()
+            ));
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
+                snapshot_hash,
+                &TEST_ORGANIZATION,
+                mock_snapshot_voting_parameters()
+            ));
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::vote(
+                locked_hash,
+                &ALICE,
+                Conviction {
+                    in_support: true,
+                    power: 50
+                }
+            ));
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::vote(
+                snapshot_hash,
+                &ALICE,
+                Conviction {
+                    in_support: true,
+                    power: 100
+                }
+            ));
+
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::veto(
+                snapshot_hash
+            ));
+            assert_eq!(
+                <Tokens as LockableCurrencies<AccountId>>::locked_balance(TEST_TOKEN_ID, &ALICE),
+                50
+            );
+        })
+}
@@ -18,7 +18,7 @@ use super::mock::*;
 use crate::{Conviction, Error, Locks, Proposals};
 use frame_support::{assert_noop, assert_ok, StorageMap};
 use governance_os_support::{
-    testing::{primitives::AccountId, ALICE, BOB, TEST_TOKEN_ID},
+    testing::{primitives::AccountId, ALICE, BOB, TEST_ORGANIZATION, TEST_TOKEN_ID},
     traits::{LockableCurrencies, ProposalResult, StandardizedVoting},
 };
 use sp_core::H256;
@@ -33,6 +33,7 @@ fn initialize_registers_proposal_hash() {
 
         assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
             mock_hash,
+            &TEST_ORGANIZATION,
             Default::default()
         ));
         assert!(Proposals::<Test>::contains_key(mock_hash));
@@ -46,6 +47,7 @@ fn initiate_logs_block_number() {
 
         assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
             mock_hash,
+            &TEST_ORGANIZATION,
             Default::default()
         ));
         assert_eq!(
@@ -65,6 +67,7 @@ fn vote_lock_tokens() {
 
             assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_voting_parameters()
             ));
 
@@ -96,6 +99,7 @@ macro_rules! test_vote_edit {
 
                     assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
                         mock_hash,
+                        &TEST_ORGANIZATION,
                         mock_voting_parameters()
                     ));
 
@@ -160,6 +164,7 @@ fn vote_edit_change_our_mind() {
 
             assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_voting_parameters()
             ));
 
@@ -220,6 +225,7 @@ fn votes_saved_correctly() {
 
             assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_voting_parameters()
             ));
 
@@ -293,6 +299,7 @@ fn vote_other_proposals_extend_locks() {
 
             assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
                 mock_hash_1,
+                &TEST_ORGANIZATION,
                 mock_voting_parameters()
             ));
             assert_ok!(<ConvictionVoting as StandardizedVoting>::vote(
@@ -306,6 +313,7 @@ fn vote_other_proposals_extend_locks() {
 
             assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
                 mock_hash_2,
+                &TEST_ORGANIZATION,
                 mock_voting_parameters()
             ));
             assert_ok!(<ConvictionVoting as StandardizedVoting>::vote(
@@ -336,6 +344,7 @@ fn vote_fail_if_not_enough_tokens() {
 
         assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
             mock_hash,
+            &TEST_ORGANIZATION,
             mock_voting_parameters()
         ));
 
@@ -365,6 +374,7 @@ macro_rules! test_close_or_veto {
 
                     assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
                         mock_hash,
+                        &TEST_ORGANIZATION,
                         mock_voting_parameters()
                     ));
 
@@ -415,6 +425,7 @@ macro_rules! test_close_or_veto {
 
                     assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
                         mock_hash,
+                        &TEST_ORGANIZATION,
                         mock_voting_parameters()
                     ));
 
@@ -458,10 +469,12 @@ macro_rules! test_close_or_veto {
 
                     assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
                         mock_hash_1,
+                        &TEST_ORGANIZATION,
                         mock_voting_parameters()
                     ));
                     assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
                         mock_hash_2,
+                        &TEST_ORGANIZATION,
                         mock_voting_parameters()
                     ));
 
@@ -534,6 +547,7 @@ fn close_error_if_early() {
 
             assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_voting_parameters()
             ));
 
@@ -560,6 +574,7 @@ fn close_failing() {
 
             assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_voting_parameters()
             ));
 
@@ -582,6 +597,7 @@ fn close_passing_early() {
 
             assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_voting_parameters()
             ));
 
@@ -604,3 +620,47 @@ fn close_passing_early() {
             );
         })
 }
+
+#[test]
+fn tally_refreshes_conviction_without_saving_it() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let mock_hash = H256::default();
+
+            assert_eq!(ConvictionVoting::tally(mock_hash), None);
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::initiate(
+                mock_hash,
+                &TEST_ORGANIZATION,
+                mock_voting_parameters()
+            ));
+            assert_ok!(<ConvictionVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &ALICE,
+                Conviction {
+                    in_support: true,
+                    power: 90,
+                }
+            ));
+
+            let tally = ConvictionVoting::tally(mock_hash).expect("proposal exists");
+            assert_eq!(tally.favorable, 0);
+            assert_eq!(tally.close_result, None);
+            assert_eq!(tally.expiry, 1_001);
+            assert_eq!(ConvictionVoting::expiry(mock_hash), Some(1_001));
+
+            System::set_block_number(ConvictionVoting::now() + 200);
+
+            let tally = ConvictionVoting::tally(mock_hash).expect("proposal exists");
+            assert_eq!(tally.favorable, 100);
+            assert_eq!(tally.participation_threshold, 66);
+            assert_eq!(tally.close_result, Some(ProposalResult::Passing));
+            assert_eq!(ConvictionVoting::proposals(mock_hash).snapshot.favorable, 0);
+
+            assert_eq!(
+                ConvictionVoting::close(mock_hash).expect("proposal shall pass"),
+                ProposalResult::Passing
+            );
+        })
+}
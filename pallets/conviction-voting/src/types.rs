@@ -44,6 +44,11 @@ pub struct VotingParameters<BlockNumber, CurrencyId> {
     /// is set to 33% it means that at least 33% + 1 of the `voting_currency`
     /// total supply need to have been used in votes for or against the proposal.
     pub min_participation: u32,
+
+    /// If set, convictions are limited by the balances held at the end of the
+    /// block preceding the proposal's creation and no coins are locked. The
+    /// voting currency must record balance checkpoints.
+    pub snapshot: bool,
 }
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
@@ -69,6 +74,9 @@ pub struct ConvictionSnapshot<Balance> {
 pub struct ProposalState<AccountId, Balance, BlockNumber, CurrencyId> {
     /// Parameters that this proposal was created with.
     pub parameters: VotingParameters<BlockNumber, CurrencyId>,
+    /// Organization the proposal was submitted to. Used to find which
+    /// delegations apply to it.
+    pub organization: AccountId,
     /// Record when the proposal was created. Used to know when it is expired
     /// (when the ttl is over).
     pub created_on: BlockNumber,
@@ -81,6 +89,9 @@ pub struct ProposalState<AccountId, Balance, BlockNumber, CurrencyId> {
     /// Snapshot of our different conviction records. Used to compute
     /// the current conviction progressively.
     pub snapshot: ConvictionSnapshot<Balance>,
+    /// Accounts whose delegated voting power was counted in this proposal.
+    /// Used to later clean the associated storage.
+    pub delegators: Vec<AccountId>,
 }
 impl<
         AccountId: Clone,
@@ -89,6 +100,24 @@ impl<
         CurrencyId,
     > ProposalState<AccountId, Balance, BlockNumber, CurrencyId>
 {
+    /// Add `power` to the tokens staked for or against the proposal.
+    pub fn add_conviction(&mut self, in_support: bool, power: Balance) {
+        if in_support {
+            self.conviction_for = self.conviction_for.saturating_add(power);
+        } else {
+            self.conviction_against = self.conviction_against.saturating_add(power);
+        }
+    }
+
+    /// Remove `power` from the tokens staked for or against the proposal.
+    pub fn remove_conviction(&mut self, in_support: bool, power: Balance) {
+        if in_support {
+            self.conviction_for = self.conviction_for.saturating_sub(power);
+        } else {
+            self.conviction_against = self.conviction_against.saturating_sub(power);
+        }
+    }
+
     /// Compute the current amount of conviction for or against the proposal
     /// and save its latest value in the proposal state. `now` should be the
     /// current block number. `decay` is the decay variable of the half life
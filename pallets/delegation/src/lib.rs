@@ -0,0 +1,316 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! This pallet lets token holders delegate their voting power to a representative
+//! without transferring their tokens. Delegations are made per currency and can
+//! either apply to all the organizations or to a specific one, the latter taking
+//! precedence. Delegated coins are locked until the delegation is removed.
+//!
+//! Voting pallets consult this pallet through the `Delegations` trait to count the
+//! delegated power when a delegate votes, and are notified of any delegation change
+//! through the `OnDelegationChanged` hook so that they can update their tallies.
+//! Delegations are not transitive: power delegated to an account that delegated its
+//! own power is only counted when that account votes itself.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    traits::{Get, LockIdentifier},
+    weights::Weight,
+};
+use frame_system::ensure_signed;
+use governance_os_support::traits::{
+    voting_lock_reasons, Currencies, Delegations as DelegationsT, LockableCurrencies,
+    OnDelegationChanged,
+};
+use sp_runtime::{
+    traits::{StaticLookup, Zero},
+    DispatchResult,
+};
+use sp_std::prelude::*;
+
+mod default_weights;
+#[cfg(test)]
+mod tests;
+mod types;
+
+pub use types::Delegation;
+
+pub const DELEGATION_LOCK_ID: LockIdentifier = *b"delegate";
+
+pub trait WeightInfo {
+    fn delegate() -> Weight;
+    fn undelegate() -> Weight;
+}
+
+pub trait Config: frame_system::Config {
+    /// Because this pallet emits events, it depends on the runtime's definition of an event.
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
+
+    /// Pallet in charge of currencies. Used so that we can lock the delegated coins.
+    type Currencies: LockableCurrencies<Self::AccountId>;
+
+    /// Called every time a delegation is created, updated or removed. Typically used
+    /// by voting pallets to recompute their tallies.
+    type OnDelegationChanged: OnDelegationChanged<Self::AccountId, CurrencyIdOf<Self>>;
+
+    /// Maximum number of accounts that can delegate to the same delegate for a given
+    /// currency and organization. Bounds the work voting pallets have to do when a
+    /// delegate votes.
+    type MaxDelegators: Get<u32>;
+
+    /// Weight values for this pallet.
+    type WeightInfo: WeightInfo;
+}
+
+type BalanceOf<T> =
+    <<T as Config>::Currencies as Currencies<<T as frame_system::Config>::AccountId>>::Balance;
+type CurrencyIdOf<T> =
+    <<T as Config>::Currencies as Currencies<<T as frame_system::Config>::AccountId>>::CurrencyId;
+type DelegationOf<T> = Delegation<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+decl_storage! {
+    trait Store for Module<T: Config> as Delegation {
+        /// Delegations made by an account for a given currency, keyed by the organization they apply
+        /// to. `None` means that the delegation applies to all the organizations.
+        pub Delegations get(fn delegations): double_map hasher(blake2_128_concat) (CurrencyIdOf<T>, T::AccountId), hasher(blake2_128_concat) Option<T::AccountId> => Option<DelegationOf<T>>;
+        /// Reverse index of `Delegations`, list the accounts that delegated to a given delegate for
+        /// a currency and organization.
+        pub Delegators get(fn delegators): double_map hasher(blake2_128_concat) (CurrencyIdOf<T>, Option<T::AccountId>), hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        AccountId = <T as frame_system::Config>::AccountId,
+        Balance = BalanceOf<T>,
+        CurrencyId = CurrencyIdOf<T>,
+    {
+        /// An account delegated some voting power. \[delegator, currency, organization, delegate, amount\]
+        Delegated(AccountId, CurrencyId, Option<AccountId>, AccountId, Balance),
+        /// An account removed one of its delegations. \[delegator, currency, organization\]
+        Undelegated(AccountId, CurrencyId, Option<AccountId>),
+    }
+);
+
+decl_error! {
+    pub enum Error for Module<T: Config> {
+        /// Accounts can not delegate to themselves.
+        SelfDelegation,
+        /// Delegations need a non null amount of coins.
+        ZeroAmount,
+        /// There are not enough coins in the delegator's balance.
+        NotEnoughBalance,
+        /// The delegate has reached the maximum number of delegators.
+        TooManyDelegators,
+        /// There is no delegation to remove.
+        NotDelegated,
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Config> for enum Call where origin: T::Origin {
+        fn deposit_event() = default;
+
+        /// Delegate `amount` of the caller's `currency` voting power to `delegate`. If `organization`
+        /// is set the delegation only applies to the proposals of this organization, otherwise it
+        /// applies to all of them. Any previous delegation for the same currency and organization
+        /// is replaced.
+        #[weight = T::WeightInfo::delegate().saturating_add(T::OnDelegationChanged::on_delegation_changed_weight(3))]
+        pub fn delegate(
+            origin,
+            currency: CurrencyIdOf<T>,
+            organization: Option<<T::Lookup as StaticLookup>::Source>,
+            delegate: <T::Lookup as StaticLookup>::Source,
+            amount: BalanceOf<T>,
+        ) {
+            let who = ensure_signed(origin)?;
+            let organization = Self::lookup_organization(organization)?;
+            let delegate = T::Lookup::lookup(delegate)?;
+
+            ensure!(who != delegate, Error::<T>::SelfDelegation);
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            ensure!(T::Currencies::free_balance(currency, &who) >= amount, Error::<T>::NotEnoughBalance);
+
+            let mut affected = Self::generic_delegate(currency, &who, &organization);
+            if let Some(previous) = Delegations::<T>::get((currency, &who), &organization) {
+                Self::remove_delegator(currency, &organization, &previous.delegate, &who);
+                affected.push(previous.delegate);
+            }
+            affected.push(delegate.clone());
+            Delegators::<T>::try_mutate((currency, &organization), &delegate, |delegators| -> DispatchResult {
+                ensure!((delegators.len() as u32) < T::MaxDelegators::get(), Error::<T>::TooManyDelegators);
+                delegators.push(who.clone());
+                Ok(())
+            })?;
+            Delegations::<T>::insert((currency, &who), &organization, Delegation {
+                delegate: delegate.clone(),
+                amount,
+            });
+
+            Self::rejig_lock(currency, &who)?;
+            T::OnDelegationChanged::on_delegation_changed(currency, &who, &affected)?;
+
+            Self::deposit_event(RawEvent::Delegated(who, currency, organization, delegate, amount));
+        }
+
+        /// Remove the caller's delegation for `currency` and `organization`. Delegated coins are
+        /// unlocked and removed from the tallies of the proposals they were counted in.
+        #[weight = T::WeightInfo::undelegate().saturating_add(T::OnDelegationChanged::on_delegation_changed_weight(2))]
+        pub fn undelegate(
+            origin,
+            currency: CurrencyIdOf<T>,
+            organization: Option<<T::Lookup as StaticLookup>::Source>,
+        ) {
+            let who = ensure_signed(origin)?;
+            let organization = Self::lookup_organization(organization)?;
+
+            let previous = Delegations::<T>::take((currency, &who), &organization).ok_or(Error::<T>::NotDelegated)?;
+            Self::remove_delegator(currency, &organization, &previous.delegate, &who);
+            let mut affected = Self::generic_delegate(currency, &who, &organization);
+            affected.push(previous.delegate);
+
+            Self::rejig_lock(currency, &who)?;
+            T::OnDelegationChanged::on_delegation_changed(currency, &who, &affected)?;
+
+            Self::deposit_event(RawEvent::Undelegated(who, currency, organization));
+        }
+    }
+}
+
+impl<T: Config> Module<T> {
+    fn lookup_organization(
+        organization: Option<<T::Lookup as StaticLookup>::Source>,
+    ) -> Result<Option<T::AccountId>, sp_runtime::DispatchError> {
+        match organization {
+            Some(lookmeup) => Ok(Some(T::Lookup::lookup(lookmeup)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// When `organization` is set, returns the delegate of the delegation `delegator` made for
+    /// all the organizations, if any. Its votes on the proposals of `organization` are affected
+    /// by any change to the delegation made specifically for `organization`.
+    fn generic_delegate(
+        currency: CurrencyIdOf<T>,
+        delegator: &T::AccountId,
+        organization: &Option<T::AccountId>,
+    ) -> Vec<T::AccountId> {
+        if organization.is_none() {
+            return Vec::new();
+        }
+        Delegations::<T>::get((currency, delegator), None as Option<T::AccountId>)
+            .map(|delegation| sp_std::vec![delegation.delegate])
+            .unwrap_or_default()
+    }
+
+    /// Remove `delegator` from the list of accounts delegating to `delegate`.
+    fn remove_delegator(
+        currency: CurrencyIdOf<T>,
+        organization: &Option<T::AccountId>,
+        delegate: &T::AccountId,
+        delegator: &T::AccountId,
+    ) {
+        Delegators::<T>::mutate_exists((currency, organization), delegate, |maybe_delegators| {
+            let mut delegators = maybe_delegators.take().unwrap_or_default();
+            delegators.retain(|d| d != delegator);
+            if !delegators.is_empty() {
+                *maybe_delegators = Some(delegators);
+            }
+        });
+    }
+
+    /// Lock the highest amount `who` delegated for `currency` across all the organizations.
+    fn rejig_lock(currency: CurrencyIdOf<T>, who: &T::AccountId) -> DispatchResult {
+        let max = Delegations::<T>::iter_prefix_values((currency, who)).fold(
+            Zero::zero(),
+            |acc, delegation| {
+                if delegation.amount > acc {
+                    delegation.amount
+                } else {
+                    acc
+                }
+            },
+        );
+
+        if max == Zero::zero() {
+            T::Currencies::remove_lock(currency, DELEGATION_LOCK_ID, who)
+        } else {
+            T::Currencies::set_lock_with_reasons(
+                currency,
+                DELEGATION_LOCK_ID,
+                who,
+                max,
+                voting_lock_reasons(),
+            )
+        }
+    }
+}
+
+impl<T: Config> DelegationsT<T::AccountId, CurrencyIdOf<T>, BalanceOf<T>> for Module<T> {
+    fn delegation_of(
+        currency: CurrencyIdOf<T>,
+        organization: &T::AccountId,
+        delegator: &T::AccountId,
+    ) -> Option<(T::AccountId, BalanceOf<T>)> {
+        Delegations::<T>::get((currency, delegator), Some(organization))
+            .or_else(|| Delegations::<T>::get((currency, delegator), None as Option<T::AccountId>))
+            .map(|delegation| (delegation.delegate, delegation.amount))
+    }
+
+    fn delegators_of(
+        currency: CurrencyIdOf<T>,
+        organization: &T::AccountId,
+        delegate: &T::AccountId,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        let specific = Delegators::<T>::get((currency, Some(organization)), delegate)
+            .into_iter()
+            .filter_map(|delegator| {
+                Delegations::<T>::get((currency, &delegator), Some(organization))
+                    .map(|delegation| (delegator, delegation.amount))
+            });
+        // Delegations made for this organization take precedence over the generic ones.
+        let generic = Delegators::<T>::get((currency, None as Option<T::AccountId>), delegate)
+            .into_iter()
+            .filter(|delegator| {
+                !Delegations::<T>::contains_key((currency, delegator), Some(organization))
+            })
+            .filter_map(|delegator| {
+                Delegations::<T>::get((currency, &delegator), None as Option<T::AccountId>)
+                    .map(|delegation| (delegator, delegation.amount))
+            });
+
+        specific.chain(generic).collect()
+    }
+
+    /// Delegators for the organization and for all the organizations are bounded separately.
+    fn max_delegators_of() -> u32 {
+        T::MaxDelegators::get().saturating_mul(2)
+    }
+
+    fn delegators_of_weight() -> Weight {
+        // Both lists of delegators, then one delegation per specific delegator and up to two
+        // per generic one.
+        T::DbWeight::get().reads(
+            T::MaxDelegators::get()
+                .saturating_mul(3)
+                .saturating_add(2)
+                .into(),
+        )
+    }
+}
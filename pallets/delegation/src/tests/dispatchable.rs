@@ -0,0 +1,271 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{Delegation as DelegationDetails, Error, DELEGATION_LOCK_ID};
+use frame_support::{assert_noop, assert_ok};
+use governance_os_support::{
+    testing::{ALICE, BOB, CHARLIE, EVE, TEST_ORGANIZATION, TEST_TOKEN_ID},
+    traits::{Delegations, LockableCurrencies},
+};
+
+#[test]
+fn delegate_locks_coins() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Delegation::delegate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                None,
+                BOB,
+                60
+            ));
+
+            assert_eq!(
+                Delegation::delegations((TEST_TOKEN_ID, ALICE), None as Option<u128>),
+                Some(DelegationDetails {
+                    delegate: BOB,
+                    amount: 60
+                })
+            );
+            assert_eq!(
+                Delegation::delegators((TEST_TOKEN_ID, None as Option<u128>), BOB),
+                vec![ALICE]
+            );
+            assert_eq!(Tokens::locked_balance(TEST_TOKEN_ID, &ALICE), 60);
+            assert_eq!(delegation_changes(), vec![(TEST_TOKEN_ID, ALICE)]);
+        })
+}
+
+#[test]
+fn delegate_replaces_previous_delegation() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Delegation::delegate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                None,
+                BOB,
+                60
+            ));
+            assert_ok!(Delegation::delegate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                None,
+                CHARLIE,
+                40
+            ));
+
+            assert_eq!(
+                Delegation::delegators((TEST_TOKEN_ID, None as Option<u128>), BOB),
+                Vec::<u128>::new()
+            );
+            assert_eq!(
+                Delegation::delegators((TEST_TOKEN_ID, None as Option<u128>), CHARLIE),
+                vec![ALICE]
+            );
+            assert_eq!(Tokens::locked_balance(TEST_TOKEN_ID, &ALICE), 40);
+        })
+}
+
+#[test]
+fn lock_covers_the_biggest_delegation() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Delegation::delegate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                None,
+                BOB,
+                30
+            ));
+            assert_ok!(Delegation::delegate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                Some(TEST_ORGANIZATION),
+                CHARLIE,
+                70
+            ));
+            assert_eq!(Tokens::locked_balance(TEST_TOKEN_ID, &ALICE), 70);
+
+            assert_ok!(Delegation::undelegate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                Some(TEST_ORGANIZATION)
+            ));
+            assert_eq!(Tokens::locked_balance(TEST_TOKEN_ID, &ALICE), 30);
+        })
+}
+
+#[test]
+fn undelegate_unlocks_coins() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Delegation::delegate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                None,
+                BOB,
+                60
+            ));
+            assert_ok!(Delegation::undelegate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                None
+            ));
+
+            assert_eq!(
+                Delegation::delegations((TEST_TOKEN_ID, ALICE), None as Option<u128>),
+                None
+            );
+            assert_eq!(
+                Delegation::delegators((TEST_TOKEN_ID, None as Option<u128>), BOB),
+                Vec::<u128>::new()
+            );
+            assert_eq!(
+                Tokens::locks((ALICE, TEST_TOKEN_ID), DELEGATION_LOCK_ID),
+                None
+            );
+            assert_eq!(
+                delegation_changes(),
+                vec![(TEST_TOKEN_ID, ALICE), (TEST_TOKEN_ID, ALICE)]
+            );
+        })
+}
+
+#[test]
+fn undelegate_fails_if_not_delegated() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Delegation::undelegate(Origin::signed(ALICE), TEST_TOKEN_ID, None),
+            Error::<Test>::NotDelegated
+        );
+    })
+}
+
+#[test]
+fn delegate_fails_for_invalid_delegations() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Delegation::delegate(Origin::signed(ALICE), TEST_TOKEN_ID, None, ALICE, 10),
+                Error::<Test>::SelfDelegation
+            );
+            assert_noop!(
+                Delegation::delegate(Origin::signed(ALICE), TEST_TOKEN_ID, None, BOB, 0),
+                Error::<Test>::ZeroAmount
+            );
+            assert_noop!(
+                Delegation::delegate(Origin::signed(ALICE), TEST_TOKEN_ID, None, BOB, 101),
+                Error::<Test>::NotEnoughBalance
+            );
+        })
+}
+
+#[test]
+fn delegate_fails_if_too_many_delegators() {
+    ExtBuilder::default()
+        .balances(vec![
+            (TEST_TOKEN_ID, ALICE, 100),
+            (TEST_TOKEN_ID, CHARLIE, 100),
+            (TEST_TOKEN_ID, EVE, 100),
+        ])
+        .build()
+        .execute_with(|| {
+            assert_ok!(Delegation::delegate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                None,
+                BOB,
+                10
+            ));
+            assert_ok!(Delegation::delegate(
+                Origin::signed(CHARLIE),
+                TEST_TOKEN_ID,
+                None,
+                BOB,
+                10
+            ));
+            assert_noop!(
+                Delegation::delegate(Origin::signed(EVE), TEST_TOKEN_ID, None, BOB, 10),
+                Error::<Test>::TooManyDelegators
+            );
+
+            // Delegations for a specific organization are tracked separately
+            assert_ok!(Delegation::delegate(
+                Origin::signed(EVE),
+                TEST_TOKEN_ID,
+                Some(TEST_ORGANIZATION),
+                BOB,
+                10
+            ));
+        })
+}
+
+#[test]
+fn organization_delegations_take_precedence() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Delegation::delegate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                None,
+                BOB,
+                30
+            ));
+            assert_ok!(Delegation::delegate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                Some(TEST_ORGANIZATION),
+                CHARLIE,
+                70
+            ));
+
+            assert_eq!(
+                Delegation::delegation_of(TEST_TOKEN_ID, &TEST_ORGANIZATION, &ALICE),
+                Some((CHARLIE, 70))
+            );
+            assert_eq!(
+                Delegation::delegation_of(TEST_TOKEN_ID, &EVE, &ALICE),
+                Some((BOB, 30))
+            );
+
+            assert_eq!(
+                Delegation::delegators_of(TEST_TOKEN_ID, &TEST_ORGANIZATION, &CHARLIE),
+                vec![(ALICE, 70)]
+            );
+            assert_eq!(
+                Delegation::delegators_of(TEST_TOKEN_ID, &TEST_ORGANIZATION, &BOB),
+                vec![]
+            );
+            assert_eq!(
+                Delegation::delegators_of(TEST_TOKEN_ID, &EVE, &BOB),
+                vec![(ALICE, 30)]
+            );
+        })
+}
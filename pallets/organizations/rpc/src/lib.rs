@@ -0,0 +1,91 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! RPC interface for the organizations pallet.
+
+pub use self::gen_client::Client as VotingClient;
+use codec::Codec;
+pub use governance_os_pallet_organizations_rpc_runtime_api::VotingApi as VotingRuntimeApi;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+#[rpc]
+pub trait VotingApi<BlockHash, ProposalId, Tally> {
+    /// Return the current tally of an open proposal, `null` if it does not exist.
+    #[rpc(name = "organizations_proposalTally")]
+    fn proposal_tally(&self, proposal: ProposalId, at: Option<BlockHash>) -> Result<Option<Tally>>;
+}
+
+/// A struct that implements the [`VotingApi`].
+pub struct Voting<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Voting<C, B> {
+    /// Create a new `Voting` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+    /// The call to the runtime failed.
+    RuntimeError,
+}
+
+impl From<Error> for i64 {
+    fn from(e: Error) -> i64 {
+        match e {
+            Error::RuntimeError => 1,
+        }
+    }
+}
+
+impl<C, Block, ProposalId, Tally> VotingApi<<Block as BlockT>::Hash, ProposalId, Tally>
+    for Voting<C, Block>
+where
+    Block: BlockT,
+    C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: VotingRuntimeApi<Block, ProposalId, Tally>,
+    ProposalId: Codec,
+    Tally: Codec,
+{
+    fn proposal_tally(
+        &self,
+        proposal: ProposalId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<Tally>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        api.proposal_tally(&at, proposal).map_err(|e| RpcError {
+            code: ErrorCode::ServerError(Error::RuntimeError.into()),
+            message: "Unable to query proposal tally.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+}
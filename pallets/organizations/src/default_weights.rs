@@ -57,6 +57,13 @@ impl crate::WeightInfo for () {
             .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(b as Weight)))
             .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
     }
+    fn guardian_veto(b: u32, c: u32, d: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((500_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(Self::veto_proposal(c, d))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
     fn decide_on_proposal(b: u32) -> Weight {
         (126_842_000 as Weight)
             .saturating_add((1_272_000 as Weight).saturating_mul(b as Weight))
@@ -75,4 +82,20 @@ impl crate::WeightInfo for () {
             .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(b as Weight)))
             .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
     }
+    fn set_spending_limit(b: u32) -> Weight {
+        (35_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(b as Weight)))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn spend() -> Weight {
+        (80_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    fn set_sponsorship() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
 }
@@ -23,7 +23,7 @@ use sp_std::prelude::Vec;
 /// This structure is used to encode metadata about an organization.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct OrganizationDetails<AccountId, VotingSystem> {
+pub struct OrganizationDetails<AccountId, VotingSystem, CurrencyId, Balance> {
     /// A set of accounts that have access to the `apply_as` function
     /// of an organization.
     pub executors: Vec<AccountId>,
@@ -31,20 +31,134 @@ pub struct OrganizationDetails<AccountId, VotingSystem> {
     /// Which voting system is in place. `executors` do not need to go
     /// through it due to their higher privilege permission.
     pub voting: VotingSystem,
+
+    /// Optional set of accounts that can jointly veto pending proposals
+    /// without going through `executors` or a new vote.
+    pub guardians: Option<Guardians<AccountId>>,
+
+    /// Optional deposit required to submit a proposal to the organization.
+    /// It is returned when the proposal is closed but given to the
+    /// organization if the proposal is vetoed.
+    pub proposal_deposit: Option<ProposalDeposit<CurrencyId, Balance>>,
 }
 
-impl<AccountId: Ord, VotingSystem> OrganizationDetails<AccountId, VotingSystem> {
+impl<AccountId: Ord, VotingSystem, CurrencyId, Balance>
+    OrganizationDetails<AccountId, VotingSystem, CurrencyId, Balance>
+{
     /// Sort all the vectors inside the strutcture.
     pub fn sort(&mut self) {
         self.executors.sort();
+        if let Some(guardians) = self.guardians.as_mut() {
+            guardians.accounts.sort();
+        }
+    }
+}
+
+/// A M-of-N configuration of accounts allowed to veto an organization's proposals.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Guardians<AccountId> {
+    /// Accounts that can approve a veto.
+    pub accounts: Vec<AccountId>,
+
+    /// How many of the `accounts` need to approve a veto before it
+    /// is applied.
+    pub threshold: u32,
+}
+
+impl<AccountId> Guardians<AccountId> {
+    /// A threshold of zero would let anybody veto proposals and one above
+    /// the number of guardians could never be reached.
+    pub fn is_valid(&self) -> bool {
+        self.threshold > 0 && self.threshold as usize <= self.accounts.len()
     }
 }
 
+/// An amount of a given currency to reserve from the proposers.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProposalDeposit<CurrencyId, Balance> {
+    pub currency_id: CurrencyId,
+    pub amount: Balance,
+}
+
+/// How the calls of a proposal are executed once it passed.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ExecutionMode {
+    /// Stop at the first failing call and revert the ones executed before it,
+    /// similar to `batch_all`.
+    AllOrNothing,
+    /// Execute every call regardless of the failures of the previous ones.
+    BestEffort,
+}
+
+/// Human readable context attached to a proposal.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProposalMetadata<Hash> {
+    pub title: Vec<u8>,
+    /// A description of the proposal, or an IPFS CID pointing to it.
+    pub description: Vec<u8>,
+    /// Hash of an off-chain document detailing the proposal, if any.
+    pub content_hash: Option<Hash>,
+}
+
 /// Represent a proposal as stored by the pallet.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct Proposal<Call, OrganizationId, VotingSystem> {
-    pub org: OrganizationId,
-    pub call: Call,
+pub struct Proposal<Call, AccountId, VotingSystem, Hash, CurrencyId, Balance> {
+    pub org: AccountId,
+    /// Account that submitted the proposal and paid its deposits.
+    pub proposer: AccountId,
+    /// Calls to execute, in order, if the proposal passes.
+    pub calls: Vec<Call>,
+    pub mode: ExecutionMode,
     pub voting: VotingSystem,
+    pub metadata: ProposalMetadata<Hash>,
+    /// Reserved to pay for the storage used by the proposal. It is returned
+    /// once the proposal is closed or vetoed.
+    pub storage_deposit: Balance,
+    /// Deposit required by the organization when the proposal was submitted.
+    pub proposal_deposit: Option<ProposalDeposit<CurrencyId, Balance>>,
+}
+
+/// Maximum amount of a currency the executors of an organization can spend
+/// without going through a vote.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SpendingLimit<Balance, BlockNumber> {
+    /// How much can be spent per period.
+    pub amount: Balance,
+    /// Length of a period, in blocks.
+    pub period: BlockNumber,
+}
+
+/// Tracks what the executors of an organization spent during the current period.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Spending<Balance, BlockNumber> {
+    pub period_start: BlockNumber,
+    pub spent: Balance,
+}
+
+/// Calls an organization can pay the transaction fees of.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SponsoredCall {
+    /// Votes on the proposals of the organization.
+    DecideOnProposal,
+}
+
+/// Transaction fees an organization accepts to pay on behalf of the signers of some calls.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Sponsorship<Balance, BlockNumber> {
+    pub calls: Vec<SponsoredCall>,
+    /// How many transactions of each account are sponsored per period.
+    pub calls_per_account: u32,
+    /// How much the organization pays in fees per period, across all accounts.
+    pub budget: Balance,
+    /// Length of a period, in blocks.
+    pub period: BlockNumber,
 }
@@ -18,6 +18,9 @@
 //! to a set of executors that can call its `apply_as` function to execute calls as if
 //! it came from it.
 //! For instance, a voting contract could be deployed and registered as an executor.
+//! Organizations can pay the transaction fees of some calls made on their proposals, such as
+//! votes, within per account and per period quotas. Only accounts that can vote on the proposal
+//! are sponsored. Those are charged by the `ChargeSponsoredTransaction` signed extension.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -26,23 +29,32 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchResultWithPostInfo, Dispatchable, Parameter, PostDispatchInfo},
     ensure,
-    traits::Get,
+    storage::{with_transaction, TransactionOutcome},
+    traits::{BalanceStatus, Currency, Get, OnUnbalanced},
     weights::{GetDispatchInfo, Weight},
 };
 use frame_system::ensure_signed;
-use governance_os_support::traits::{ProposalResult, RoleManager, VotingRouter};
+use governance_os_support::traits::{
+    AllowanceCurrencies, Currencies, ProposalResult, ReservableCurrencies, RoleManager,
+    VotingRouter,
+};
 use sp_runtime::{
-    traits::{AccountIdConversion, Hash, StaticLookup},
+    traits::{AccountIdConversion, Hash, One, Saturating, StaticLookup, Zero},
     DispatchError, DispatchResult, ModuleId,
 };
 use sp_std::{boxed::Box, prelude::Vec};
 
 mod default_weights;
 mod details;
+mod sponsorship;
 #[cfg(test)]
 mod tests;
 
-pub use details::{OrganizationDetails, Proposal};
+pub use details::{
+    ExecutionMode, Guardians, OrganizationDetails, Proposal, ProposalDeposit, ProposalMetadata,
+    Spending, SpendingLimit, SponsoredCall, Sponsorship,
+};
+pub use sponsorship::ChargeSponsoredTransaction;
 
 pub trait RoleBuilder {
     type OrganizationId;
@@ -61,8 +73,12 @@ pub trait WeightInfo {
     fn mutate(b: u32, c: u32) -> Weight;
     fn create_proposal() -> Weight;
     fn veto_proposal(b: u32, c: u32) -> Weight;
+    fn guardian_veto(b: u32, c: u32, d: u32) -> Weight;
     fn decide_on_proposal(b: u32) -> Weight;
     fn close_proposal(b: u32, c: u32) -> Weight;
+    fn set_spending_limit(b: u32) -> Weight;
+    fn spend() -> Weight;
+    fn set_sponsorship() -> Weight;
 }
 
 pub trait Config: frame_system::Config {
@@ -88,7 +104,28 @@ pub trait Config: frame_system::Config {
 
     /// VotingRouter implementation to choose between voting systems and route those
     /// to the right pallets.
-    type VotingRouter: VotingRouter<AccountId = Self::AccountId, ProposalId = ProposalIdOf<Self>>;
+    type VotingRouter: VotingRouter<
+        AccountId = Self::AccountId,
+        ProposalId = ProposalIdOf<Self>,
+        BlockNumber = Self::BlockNumber,
+    >;
+
+    /// Currencies implementation used to reserve proposal deposits and to check the funds
+    /// executors make available to other accounts.
+    type Currencies: ReservableCurrencies<Self::AccountId> + AllowanceCurrencies<Self::AccountId>;
+
+    /// Currency in which proposal deposits are reserved.
+    type DepositCurrencyId: Get<CurrencyIdOf<Self>>;
+
+    /// Amount reserved from the submitter of a proposal for each byte it occupies in
+    /// our storage.
+    type ProposalDepositPerByte: Get<BalanceOf<Self>>;
+
+    /// Maximum length of a proposal's title.
+    type MaxTitleLength: Get<u32>;
+
+    /// Maximum length of a proposal's description.
+    type MaxDescriptionLength: Get<u32>;
 
     /// Mostly used for weight computations and not actually enforced. The maximum number
     /// of votes in favor or against we can expect a proposal to have.
@@ -98,6 +135,28 @@ pub trait Config: frame_system::Config {
     /// executors we expect to be configured for an organization.
     type MaxExecutors: Get<u32>;
 
+    /// Mostly used for weight computations and not actually enforced. Maximum numbers of
+    /// guardians we expect to be configured for an organization.
+    type MaxGuardians: Get<u32>;
+
+    /// Maximum number of currencies an organization can set a spending limit for. Each
+    /// of them adds some overhead to `apply_as`.
+    type MaxSpendingLimits: Get<u32>;
+
+    /// Maximum weight we can spend per block to automatically close and execute expired
+    /// proposals. Proposals that do not fit are carried over to the next blocks.
+    type MaxAutoCloseWeight: Get<Weight>;
+
+    /// Maximum number of calls an organization sponsors for a given account and proposal,
+    /// whatever its sponsorship quotas.
+    type MaxSponsoredCallsPerProposal: Get<u32>;
+
+    /// Currency in which the transaction fees sponsored by organizations are paid.
+    type NativeCurrency: Currency<Self::AccountId>;
+
+    /// Handler for the transaction fees paid by organizations.
+    type OnSponsoredFees: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
     /// Weight values for this pallet
     type WeightInfo: WeightInfo;
 }
@@ -105,12 +164,39 @@ pub trait Config: frame_system::Config {
 type OrganizationDetailsOf<T> = OrganizationDetails<
     <T as frame_system::Config>::AccountId,
     (VotingSystemIdOf<T>, VotingParametersOf<T>),
+    CurrencyIdOf<T>,
+    BalanceOf<T>,
 >;
+type BalanceOf<T> =
+    <<T as Config>::Currencies as Currencies<<T as frame_system::Config>::AccountId>>::Balance;
+type CurrencyIdOf<T> =
+    <<T as Config>::Currencies as Currencies<<T as frame_system::Config>::AccountId>>::CurrencyId;
+type NativeBalanceOf<T> =
+    <<T as Config>::NativeCurrency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+type NegativeImbalanceOf<T> = <<T as Config>::NativeCurrency as Currency<
+    <T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
 pub type OrganizationsCounter = u32;
 type ProposalIdOf<T> = <T as frame_system::Config>::Hash;
-type ProposalOf<T> = Proposal<Vec<u8>, <T as frame_system::Config>::AccountId, VotingSystemIdOf<T>>;
+type ProposalMetadataOf<T> = ProposalMetadata<<T as frame_system::Config>::Hash>;
+type ProposalOf<T> = Proposal<
+    Vec<u8>,
+    <T as frame_system::Config>::AccountId,
+    VotingSystemIdOf<T>,
+    <T as frame_system::Config>::Hash,
+    CurrencyIdOf<T>,
+    BalanceOf<T>,
+>;
 type RoleBuilderOf<T> = <T as Config>::RoleBuilder;
+type SpendingLimitOf<T> = SpendingLimit<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+type SpendingOf<T> = Spending<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+type LimitedBalanceOf<T> = (CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>);
+type SponsoredSpendingOf<T> =
+    Spending<NativeBalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+type SponsoredUsageOf<T> = (<T as frame_system::Config>::BlockNumber, u32);
+type SponsorshipOf<T> = Sponsorship<NativeBalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
 type RoleManagerOf<T> = <T as Config>::RoleManager;
+type TallyOf<T> = <<T as Config>::VotingRouter as VotingRouter>::Tally;
 type VoteDataOf<T> = <<T as Config>::VotingRouter as VotingRouter>::VoteData;
 type VotingParametersOf<T> = <<T as Config>::VotingRouter as VotingRouter>::Parameters;
 type VotingSystemIdOf<T> = <<T as Config>::VotingRouter as VotingRouter>::VotingSystemId;
@@ -122,6 +208,31 @@ decl_storage! {
         pub Counter get(fn counter): OrganizationsCounter = 0;
         pub Parameters get(fn parameters): map hasher(blake2_128_concat) T::AccountId => Option<OrganizationDetailsOf<T>>;
         pub Proposals get(fn proposals): map hasher(blake2_128_concat) ProposalIdOf<T> => Option<ProposalOf<T>>;
+        /// Guardians that approved the veto of a pending proposal.
+        pub GuardianApprovals get(fn guardian_approvals): map hasher(blake2_128_concat) ProposalIdOf<T> => Vec<T::AccountId>;
+        /// Proposals to automatically close at a given block, that is the block right after
+        /// their voting period ends.
+        pub ExpiryQueue get(fn expiry_queue): map hasher(twox_64_concat) T::BlockNumber => Vec<ProposalIdOf<T>>;
+        /// Next block whose `ExpiryQueue` has to be processed. Lags behind the current block
+        /// when we did not have enough weight to close all the expired proposals.
+        pub ExpiryCursor get(fn expiry_cursor): Option<T::BlockNumber>;
+        /// How much executors can spend from an organization's account per currency without
+        /// going through a vote.
+        pub SpendingLimits get(fn spending_limits): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) CurrencyIdOf<T> => Option<SpendingLimitOf<T>>;
+        /// What executors spent from an organization's account during the current period.
+        pub Spendings get(fn spendings): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) CurrencyIdOf<T> => Option<SpendingOf<T>>;
+        /// Executor dispatching a call on behalf of an organization through `apply_as`. Only set
+        /// while the call is being dispatched.
+        pub ActingExecutors get(fn acting_executors): map hasher(blake2_128_concat) T::AccountId => Option<T::AccountId>;
+        /// Transaction fees an organization pays on behalf of the signers of some calls.
+        pub Sponsorships get(fn sponsorships): map hasher(blake2_128_concat) T::AccountId => Option<SponsorshipOf<T>>;
+        /// Fees an organization paid for others during the current period.
+        pub SponsoredSpendings get(fn sponsored_spendings): map hasher(blake2_128_concat) T::AccountId => Option<SponsoredSpendingOf<T>>;
+        /// Start of the period during which an account last had a transaction sponsored by an
+        /// organization and how many were sponsored since.
+        pub SponsoredCalls get(fn sponsored_calls): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => SponsoredUsageOf<T>;
+        /// How many calls related to a proposal were sponsored for an account.
+        pub SponsoredProposalCalls get(fn sponsored_proposal_calls): double_map hasher(blake2_128_concat) ProposalIdOf<T>, hasher(blake2_128_concat) T::AccountId => u32;
     }
     add_extra_genesis {
         config(organizations): Vec<OrganizationDetailsOf<T>>;
@@ -138,8 +249,14 @@ decl_event!(
     pub enum Event<T>
     where
         AccountId = <T as frame_system::Config>::AccountId,
+        Balance = BalanceOf<T>,
+        CurrencyId = CurrencyIdOf<T>,
+        NativeBalance = NativeBalanceOf<T>,
         OrganizationDetails = OrganizationDetailsOf<T>,
         ProposalId = ProposalIdOf<T>,
+        ProposalMetadata = ProposalMetadataOf<T>,
+        SpendingLimit = SpendingLimitOf<T>,
+        Sponsorship = SponsorshipOf<T>,
         VoteData = VoteDataOf<T>,
     {
         /// An organization was created with the following parameters. \[org. address, details\]
@@ -148,16 +265,42 @@ decl_event!(
         OrganizationExecuted(AccountId, DispatchResult),
         /// An organization parameters have been modified. \[org. address, old details, new details\]
         OrganizationMutated(AccountId, OrganizationDetails, OrganizationDetails),
-        /// A proposal has been submitted to an organization. \[org. address, proposal id\]
-        ProposalSubmitted(AccountId, ProposalId),
+        /// A proposal has been submitted to an organization. \[org. address, proposal id, metadata\]
+        ProposalSubmitted(AccountId, ProposalId, ProposalMetadata),
         /// A proposal has been vetoed and removed from the queue of open proposals. \[proposal id\]
         ProposalVetoed(ProposalId),
+        /// The proposal deposit of a vetoed proposal was given to its organization.
+        /// \[proposal id, proposer, amount\]
+        ProposalDepositRepatriated(ProposalId, AccountId, Balance),
+        /// A guardian approved the veto of a proposal. \[proposal id, guardian, approvals count\]
+        GuardianVetoApproved(ProposalId, AccountId, u32),
         /// Somebody just voted on a proposal. \[proposal id, voter, vote data\]
         ProposalVoteCasted(ProposalId, AccountId, VoteData),
-        /// A proposal has been executed with the following result. \[proposal id, result\]
+        /// A proposal has been executed with the following result, that is the first error met
+        /// if any. \[proposal id, result\]
         ProposalExecuted(ProposalId, DispatchResult),
+        /// One of the calls of a proposal was executed. Calls of an `AllOrNothing` proposal were
+        /// reverted if the proposal execution failed. \[proposal id, call index, result\]
+        ProposalCallExecuted(ProposalId, u32, DispatchResult),
         /// A proposal was closed. \[proposal id, wether it passed or not\]
         ProposalClosed(ProposalId, ProposalResult),
+        /// An expired proposal could not be closed automatically and has to be closed manually.
+        /// \[proposal id, error\]
+        ProposalAutoCloseFailed(ProposalId, DispatchError),
+        /// An organization changed the spending limit of its executors for a currency.
+        /// \[org. address, currency id, new limit\]
+        SpendingLimitSet(AccountId, CurrencyId, Option<SpendingLimit>),
+        /// An executor spent funds from an organization's account via `apply_as`.
+        /// \[org. address, executor, currency id, amount\]
+        ExecutorSpent(AccountId, AccountId, CurrencyId, Balance),
+        /// Funds were sent from an organization's treasury.
+        /// \[org. address, pot name if any, currency id, destination, amount\]
+        TreasurySpent(AccountId, Option<Vec<u8>>, CurrencyId, AccountId, Balance),
+        /// An organization changed the transaction fees it pays for others.
+        /// \[org. address, new sponsorship\]
+        SponsorshipSet(AccountId, Option<Sponsorship>),
+        /// An organization paid the fees of a transaction. \[org. address, signer, fee\]
+        TransactionSponsored(AccountId, AccountId, NativeBalance),
     }
 );
 
@@ -170,6 +313,14 @@ decl_error! {
         NotAnOrganization,
         /// A similar proposal already exists.
         ProposalDuplicate,
+        /// A proposal needs at least one call.
+        ProposalEmpty,
+        /// The proposal's title is longer than `MaxTitleLength`.
+        TitleTooLong,
+        /// The proposal's description is longer than `MaxDescriptionLength`.
+        DescriptionTooLong,
+        /// The caller can not afford the deposit of the proposal.
+        InsufficientDepositBalance,
         /// The proposal is not linked to this organization.
         ProposalNotForOrganization,
         /// The proposal does not exists, maybe it was already closed.
@@ -181,6 +332,21 @@ decl_error! {
         ProposalDecodingFailure,
         /// The weight passed to the function is too small.
         TooSmallWeightBound,
+        /// The guardians threshold is zero or above the number of guardians.
+        InvalidGuardians,
+        /// The caller is not a guardian of the organization.
+        NotAGuardian,
+        /// The guardian already approved the veto of this proposal.
+        GuardianAlreadyApproved,
+        /// The organization already has `MaxSpendingLimits` spending limits configured.
+        TooManySpendingLimits,
+        /// This spend would exceed the executors' spending limit for the current period.
+        SpendingLimitExceeded,
+        /// Sponsorship periods can not be empty.
+        InvalidSponsorship,
+        /// Spending limits and sponsorships can only be changed through a proposal, not by the
+        /// executors.
+        ExecutorsCannotChangeLimits,
     }
 }
 
@@ -188,9 +354,14 @@ decl_module! {
     pub struct Module<T: Config> for enum Call where origin: T::Origin {
         fn deposit_event() = default;
 
+        /// Close and execute the proposals that expired, up to `MaxAutoCloseWeight`.
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            Self::close_expired_proposals(now)
+        }
+
         /// Create an organization with the given parameters. An event will be triggered with
         /// the organization's address.
-        #[weight = T::WeightInfo::create(details.executors.len() as u32)]
+        #[weight = T::WeightInfo::create(details.executors.len() as u32).saturating_add(RoleManagerOf::<T>::has_role_weight())]
         fn create(origin, details: OrganizationDetailsOf<T>) {
             RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::create_organizations())?;
             Self::do_create(details)?;
@@ -202,14 +373,34 @@ decl_module! {
                 .saturating_add(10_000)
                 // AccountData for inner call origin accountdata.
                 .saturating_add(T::DbWeight::get().reads_writes(1, 1))
-                // Two read performed by ensure_has_role
-                .saturating_add(T::DbWeight::get().reads(2))
+                .saturating_add(RoleManagerOf::<T>::has_role_weight())
+                // Acting executor
+                .saturating_add(T::DbWeight::get().reads_writes(1, 2))
+                // Balances, allowances and spendings of the currencies with a spending limit
+                .saturating_add(T::DbWeight::get().reads_writes(
+                    T::MaxSpendingLimits::get().saturating_mul(4).saturating_add(1).into(),
+                    T::MaxSpendingLimits::get().into(),
+                ))
         ]
         fn apply_as(origin, org_id: <T::Lookup as StaticLookup>::Source, call: Box<<T as Config>::Call>) {
             let target_org_id = T::Lookup::lookup(org_id)?;
-            RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::apply_as_organization(&target_org_id))?;
+            let executor = RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::apply_as_organization(&target_org_id))?;
 
-            let res = call.dispatch(frame_system::RawOrigin::Signed(target_org_id.clone()).into());
+            // Any funds leaving the organization's account or made available to other accounts
+            // through allowances are checked against its spending limits, the call is reverted if
+            // they are exceeded. Spends from the organization's pots are checked by `spend`.
+            let balances_before = Self::limited_balances(&target_org_id);
+            let previous_executor = ActingExecutors::<T>::get(&target_org_id);
+            ActingExecutors::<T>::insert(&target_org_id, &executor);
+            let res = with_transaction(|| {
+                let res = call.dispatch(frame_system::RawOrigin::Signed(target_org_id.clone()).into());
+                match Self::record_executor_spendings(&target_org_id, &executor, balances_before) {
+                    Ok(()) => TransactionOutcome::Commit(Ok(res)),
+                    Err(e) => TransactionOutcome::Rollback(Err(e)),
+                }
+            });
+            Self::set_acting_executor(&target_org_id, previous_executor);
+            let res = res?;
             Self::deposit_event(RawEvent::OrganizationExecuted(target_org_id, res.map(|_| ()).map_err(|e| e.error)));
         }
 
@@ -217,6 +408,7 @@ decl_module! {
         #[weight = T::WeightInfo::mutate(new_details.executors.len() as u32, T::MaxExecutors::get())]
         fn mutate(origin, new_details: OrganizationDetailsOf<T>) -> DispatchResultWithPostInfo {
             let (org_id, old_details) = Self::ensure_org(origin)?;
+            Self::ensure_valid_guardians(&new_details)?;
 
             // Make sure everything is sorted for optimization purposes
             let mut new_details = new_details;
@@ -238,29 +430,58 @@ decl_module! {
             Ok(Some(T::WeightInfo::mutate(roles_granted, roles_revoked)).into())
         }
 
-        /// Create a proposal for a given organization
-        #[weight = T::WeightInfo::create_proposal()]
-        fn create_proposal(origin, org_id: <T::Lookup as StaticLookup>::Source, call: Box<<T as Config>::Call>) {
-            let _who = ensure_signed(origin)?;
+        /// Create a proposal for a given organization. If it passes `calls` will be executed in order
+        /// according to `mode`. A deposit proportional to the size of the proposal is reserved from the
+        /// caller until the proposal is closed or vetoed. If the organization requires it, its proposal
+        /// deposit is reserved too, it is returned when the proposal is closed but given to the organization
+        /// if the proposal is vetoed.
+        #[weight = T::WeightInfo::create_proposal()
+            // Registration in the expiry queue
+            .saturating_add(T::DbWeight::get().writes(1))
+            // Deposits reservation
+            .saturating_add(T::DbWeight::get().reads_writes(2, 2))
+        ]
+        fn create_proposal(origin, org_id: <T::Lookup as StaticLookup>::Source, calls: Vec<<T as Config>::Call>, mode: ExecutionMode, metadata: ProposalMetadataOf<T>) {
+            let who = ensure_signed(origin)?;
+            ensure!(!calls.is_empty(), Error::<T>::ProposalEmpty);
+            ensure!(metadata.title.len() <= T::MaxTitleLength::get() as usize, Error::<T>::TitleTooLong);
+            ensure!(metadata.description.len() <= T::MaxDescriptionLength::get() as usize, Error::<T>::DescriptionTooLong);
             let target_org_id = T::Lookup::lookup(org_id)?;
             let details = Self::try_get_parameters(&target_org_id)?;
-            let proposal_id = Self::proposal_id(&target_org_id, call.clone());
+            let proposal_id = Self::proposal_id(&target_org_id, &calls);
             if Proposals::<T>::contains_key(proposal_id) {
                 return Err(Error::<T>::ProposalDuplicate.into());
             }
 
-            T::VotingRouter::initiate(details.voting.0.clone(), proposal_id, details.voting.1)?;
+            let encoded_calls: Vec<Vec<u8>> = calls.iter().map(|call| call.encode()).collect();
+            let storage_deposit = T::ProposalDepositPerByte::get()
+                .saturating_mul((encoded_calls.encoded_size().saturating_add(metadata.encoded_size()) as u32).into());
+            Self::ensure_can_reserve_deposits(&who, storage_deposit, &details.proposal_deposit)?;
+
+            T::VotingRouter::initiate(details.voting.0.clone(), proposal_id, &target_org_id, details.voting.1)?;
+            T::Currencies::reserve(T::DepositCurrencyId::get(), &who, storage_deposit)?;
+            if let Some(proposal_deposit) = &details.proposal_deposit {
+                T::Currencies::reserve(proposal_deposit.currency_id, &who, proposal_deposit.amount)?;
+            }
+            if let Some(expiry) = T::VotingRouter::expiry(details.voting.0.clone(), proposal_id) {
+                ExpiryQueue::<T>::append(expiry.saturating_add(One::one()), proposal_id);
+            }
 
-            Proposals::<T>::insert(&proposal_id, Proposal{
+            Proposals::<T>::insert(proposal_id, Proposal{
                 org: target_org_id.clone(),
-                call: call.encode(),
+                proposer: who,
+                calls: encoded_calls,
+                mode,
                 // Not only does this save us future read weights but it also cover
                 // the case where an org change voting systems but still has pending
                 // proposals.
                 voting: details.voting.0,
+                metadata: metadata.clone(),
+                storage_deposit,
+                proposal_deposit: details.proposal_deposit,
             });
 
-            Self::deposit_event(RawEvent::ProposalSubmitted(target_org_id, proposal_id));
+            Self::deposit_event(RawEvent::ProposalSubmitted(target_org_id, proposal_id, metadata));
         }
 
         /// Remove a proposal from the batch of active ones. Has to be called by the organization itself,
@@ -271,15 +492,39 @@ decl_module! {
             let proposal = Self::try_get_proposal(proposal_id)?;
             ensure!(proposal.org == org_id, Error::<T>::ProposalNotForOrganization);
 
-            T::VotingRouter::veto(proposal.voting, proposal_id)?;
-            Proposals::<T>::remove(proposal_id);
+            Self::do_veto(proposal_id, proposal)?;
+        }
+
+        /// Approve the veto of a proposal as one of the guardians of its organization. The proposal
+        /// is vetoed as soon as the guardians' threshold is reached.
+        #[weight = T::WeightInfo::guardian_veto(T::MaxGuardians::get(), T::MaxVotes::get(), T::MaxVotes::get())]
+        fn guardian_veto(origin, proposal_id: ProposalIdOf<T>) {
+            let guardian = ensure_signed(origin)?;
+            let proposal = Self::try_get_proposal(proposal_id)?;
+            let guardians = Self::try_get_parameters(&proposal.org)?
+                .guardians
+                .ok_or(Error::<T>::NotAGuardian)?;
+            ensure!(guardians.accounts.binary_search(&guardian).is_ok(), Error::<T>::NotAGuardian);
+
+            let mut approvals = Self::guardian_approvals(proposal_id);
+            ensure!(!approvals.contains(&guardian), Error::<T>::GuardianAlreadyApproved);
+            // The guardians may have been changed since the first approvals
+            approvals.retain(|approver| guardians.accounts.binary_search(approver).is_ok());
+            approvals.push(guardian.clone());
+
+            let approvals_count = approvals.len() as u32;
+            if approvals_count >= guardians.threshold {
+                Self::do_veto(proposal_id, proposal)?;
+            } else {
+                GuardianApprovals::<T>::insert(proposal_id, approvals);
+            }
 
-            Self::deposit_event(RawEvent::ProposalVetoed(proposal_id));
+            Self::deposit_event(RawEvent::GuardianVetoApproved(proposal_id, guardian, approvals_count));
         }
 
         /// Vote for or against a given proposal. The caller can choose how much voting power is dedicated
         /// to it via the `power` parameter.
-        #[weight = T::WeightInfo::decide_on_proposal(T::MaxVotes::get())]
+        #[weight = T::WeightInfo::decide_on_proposal(T::MaxVotes::get()).saturating_add(T::VotingRouter::vote_weight())]
         fn decide_on_proposal(origin, proposal_id: ProposalIdOf<T>, vote_data: VoteDataOf<T>) {
             let voter = ensure_signed(origin)?;
             let proposal = Self::try_get_proposal(proposal_id)?;
@@ -290,29 +535,103 @@ decl_module! {
 
         /// If a proposal passed or failed but is not longer awaiting or waiting for votes it can be closed. Closing
         /// a proposal means executing it if it passed, freeing all funds locked and erasing it from the local storage.
-        /// `proposal_weight_bound` has to be at least equal to the weight of the call that will be executed would the
-        /// proposal pass.
-        #[weight = T::WeightInfo::close_proposal(T::MaxVotes::get(), T::MaxVotes::get()).saturating_add(*proposal_weight_bound)]
+        /// `proposal_weight_bound` has to be at least equal to the weight of all the calls that will be executed would
+        /// the proposal pass.
+        #[weight = Module::<T>::close_proposal_weight().saturating_add(*proposal_weight_bound)]
         fn close_proposal(origin, proposal_id: ProposalIdOf<T>, proposal_weight_bound: Weight) -> DispatchResultWithPostInfo {
             let _ = ensure_signed(origin)?;
             let proposal = Self::try_get_proposal(proposal_id)?;
-            let decoded_call = <T as Config>::Call::decode(&mut &proposal.clone().call[..]).map_err(|_| Error::<T>::ProposalDecodingFailure)?;
-            let decoded_call_weight = decoded_call.get_dispatch_info().weight;
-            ensure!(proposal_weight_bound >= decoded_call_weight, Error::<T>::TooSmallWeightBound);
+            let decoded_calls = Self::decode_calls(&proposal)?;
+            ensure!(proposal_weight_bound >= Self::calls_weight(&decoded_calls), Error::<T>::TooSmallWeightBound);
+
+            Self::unqueue_expiry(proposal_id, &proposal);
+            let external_weight = Self::do_close(proposal_id, proposal, decoded_calls)?;
 
-            let proposal_result = T::VotingRouter::close(proposal.voting.clone(), proposal_id)?;
+            Ok(Some(Self::close_proposal_weight().saturating_add(external_weight)).into())
+        }
+
+        /// Set or remove how much of `currency_id` the executors can spend per period without going
+        /// through a vote. Only an organization can call this on itself through a proposal, executors
+        /// can not change it via `apply_as`. This resets the current period.
+        #[weight = T::WeightInfo::set_spending_limit(T::MaxSpendingLimits::get())
+            .saturating_add(T::DbWeight::get().reads(1))]
+        fn set_spending_limit(origin, currency_id: CurrencyIdOf<T>, limit: Option<SpendingLimitOf<T>>) {
+            let (org_id, _details) = Self::ensure_org(origin)?;
+            ensure!(!ActingExecutors::<T>::contains_key(&org_id), Error::<T>::ExecutorsCannotChangeLimits);
+
+            if limit.is_some() && !SpendingLimits::<T>::contains_key(&org_id, currency_id) {
+                let configured = SpendingLimits::<T>::iter_prefix(&org_id).count();
+                ensure!(configured < T::MaxSpendingLimits::get() as usize, Error::<T>::TooManySpendingLimits);
+            }
+
+            match &limit {
+                Some(limit) => SpendingLimits::<T>::insert(&org_id, currency_id, limit),
+                None => SpendingLimits::<T>::remove(&org_id, currency_id),
+            }
+            Spendings::<T>::remove(&org_id, currency_id);
+
+            Self::deposit_event(RawEvent::SpendingLimitSet(org_id, currency_id, limit));
+        }
+
+        /// Send funds from an organization's main account or from one of its named pots. When called
+        /// by the organization itself through a proposal, no limit applies. Executors can call it
+        /// too, directly or via `apply_as`, but are bound by the organization's spending limits.
+        #[weight = T::WeightInfo::spend()
+            .saturating_add(RoleManagerOf::<T>::has_role_weight())
+            .saturating_add(T::DbWeight::get().reads(1))]
+        fn spend(
+            origin,
+            org_id: <T::Lookup as StaticLookup>::Source,
+            pot: Option<Vec<u8>>,
+            currency_id: CurrencyIdOf<T>,
+            dest: <T::Lookup as StaticLookup>::Source,
+            amount: BalanceOf<T>
+        ) {
+            let who = ensure_signed(origin.clone())?;
+            let target_org_id = T::Lookup::lookup(org_id)?;
+            let dest = T::Lookup::lookup(dest)?;
+            let _ = Self::try_get_parameters(&target_org_id)?;
+
+            let new_spending = if who == target_org_id {
+                match (&pot, ActingExecutors::<T>::contains_key(&target_org_id)) {
+                    // Funds leaving the main account are already checked by `apply_as`.
+                    (Some(_), true) => Self::try_spend(&target_org_id, currency_id, amount)?,
+                    _ => None,
+                }
+            } else {
+                RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::apply_as_organization(&target_org_id))?;
+                Self::try_spend(&target_org_id, currency_id, amount)?
+            };
 
-            let mut external_weight: Weight = 0;
-            if proposal_result == ProposalResult::Passing {
-                let res = decoded_call.dispatch(frame_system::RawOrigin::Signed(proposal.clone().org).into());
-                Self::deposit_event(RawEvent::ProposalExecuted(proposal_id, res.map(|_| ()).map_err(|e| e.error)));
-                external_weight = external_weight.saturating_add(Self::get_result_weight(res).unwrap_or(decoded_call_weight));
+            let source = match &pot {
+                Some(name) => Self::pot_account(&target_org_id, name),
+                None => target_org_id.clone(),
+            };
+            T::Currencies::transfer(currency_id, &source, &dest, amount)?;
+            if let Some(spending) = new_spending {
+                Spendings::<T>::insert(&target_org_id, currency_id, spending);
             }
-            Proposals::<T>::remove(proposal_id);
 
-            Self::deposit_event(RawEvent::ProposalClosed(proposal_id, proposal_result));
+            Self::deposit_event(RawEvent::TreasurySpent(target_org_id, pot, currency_id, dest, amount));
+        }
+
+        /// Set or remove the transaction fees the organization pays for others. Only an organization
+        /// can call this on itself, not through its executors, this resets the current period.
+        #[weight = T::WeightInfo::set_sponsorship().saturating_add(T::DbWeight::get().reads(1))]
+        fn set_sponsorship(origin, sponsorship: Option<SponsorshipOf<T>>) {
+            let (org_id, _details) = Self::ensure_org(origin)?;
+            ensure!(!ActingExecutors::<T>::contains_key(&org_id), Error::<T>::ExecutorsCannotChangeLimits);
+
+            match &sponsorship {
+                Some(sponsorship) => {
+                    ensure!(!sponsorship.period.is_zero(), Error::<T>::InvalidSponsorship);
+                    Sponsorships::<T>::insert(&org_id, sponsorship);
+                }
+                None => Sponsorships::<T>::remove(&org_id),
+            }
+            SponsoredSpendings::<T>::remove(&org_id);
 
-            Ok(Some(T::WeightInfo::close_proposal(T::MaxVotes::get(), T::MaxVotes::get()).saturating_add(external_weight)).into())
+            Self::deposit_event(RawEvent::SponsorshipSet(org_id, sponsorship));
         }
     }
 }
@@ -346,11 +665,109 @@ impl<T: Config> Module<T> {
         })
     }
 
+    /// Fetch the current tally of a proposal from the voting system it was created with.
+    /// Returns `None` if the proposal does not exist.
+    pub fn proposal_tally(proposal_id: ProposalIdOf<T>) -> Option<TallyOf<T>> {
+        Self::proposals(proposal_id)
+            .and_then(|proposal| T::VotingRouter::tally(proposal.voting, proposal_id))
+    }
+
     /// Given any counter return the associated organization id
     pub fn org_id_for(counter: u32) -> T::AccountId {
         ORGS_MODULE_ID.into_sub_account(counter)
     }
 
+    /// Account of a named pot of an organization's treasury. Pots do not need to be registered,
+    /// they can be funded like any other account and spent from via `spend`.
+    pub fn pot_account(org_id: &T::AccountId, name: &[u8]) -> T::AccountId {
+        ORGS_MODULE_ID.into_sub_account(T::Hashing::hash_of(&(org_id, name)))
+    }
+
+    /// Compute what the executors of `org_id` will have spent of `currency_id` in the current
+    /// period if they spend `amount` more. Returns `None` if there is no limit for this currency.
+    fn try_spend(
+        org_id: &T::AccountId,
+        currency_id: CurrencyIdOf<T>,
+        amount: BalanceOf<T>,
+    ) -> Result<Option<SpendingOf<T>>, DispatchError> {
+        let limit = match Self::spending_limits(org_id, currency_id) {
+            Some(limit) => limit,
+            None => return Ok(None),
+        };
+
+        let now = frame_system::Module::<T>::block_number();
+        let mut spending = match Self::spendings(org_id, currency_id) {
+            Some(spending) if now < spending.period_start.saturating_add(limit.period) => spending,
+            _ => Spending {
+                period_start: now,
+                spent: Zero::zero(),
+            },
+        };
+        spending.spent = spending.spent.saturating_add(amount);
+        ensure!(
+            spending.spent <= limit.amount,
+            Error::<T>::SpendingLimitExceeded
+        );
+
+        Ok(Some(spending))
+    }
+
+    /// Total balances and allowances given to other accounts of `org_id` for all the currencies
+    /// it set a spending limit for.
+    fn limited_balances(org_id: &T::AccountId) -> Vec<LimitedBalanceOf<T>> {
+        SpendingLimits::<T>::iter_prefix(org_id)
+            .map(|(currency_id, _limit)| {
+                (
+                    currency_id,
+                    T::Currencies::total_balance(currency_id, org_id),
+                    T::Currencies::total_allowance(currency_id, org_id),
+                )
+            })
+            .collect()
+    }
+
+    /// Compare the balances and allowances of `org_id` with the ones it had before `executor`
+    /// dispatched a call and record the balance decreases and allowance increases against the
+    /// spending limits. Errors if a limit is exceeded.
+    fn record_executor_spendings(
+        org_id: &T::AccountId,
+        executor: &T::AccountId,
+        balances_before: Vec<LimitedBalanceOf<T>>,
+    ) -> DispatchResult {
+        balances_before.into_iter().try_for_each(
+            |(currency_id, balance_before, allowance_before)| {
+                let spent = balance_before
+                    .saturating_sub(T::Currencies::total_balance(currency_id, org_id))
+                    .saturating_add(
+                        T::Currencies::total_allowance(currency_id, org_id)
+                            .saturating_sub(allowance_before),
+                    );
+                if spent.is_zero() {
+                    return Ok(());
+                }
+
+                if let Some(spending) = Self::try_spend(org_id, currency_id, spent)? {
+                    Spendings::<T>::insert(org_id, currency_id, spending);
+                }
+                Self::deposit_event(RawEvent::ExecutorSpent(
+                    org_id.clone(),
+                    executor.clone(),
+                    currency_id,
+                    spent,
+                ));
+                Ok(())
+            },
+        )
+    }
+
+    /// Restore the executor acting on behalf of `org_id` once a call it dispatched is over.
+    fn set_acting_executor(org_id: &T::AccountId, executor: Option<T::AccountId>) {
+        match executor {
+            Some(executor) => ActingExecutors::<T>::insert(org_id, executor),
+            None => ActingExecutors::<T>::remove(org_id),
+        }
+    }
+
     /// Makes sure that the `origin` is a registered organization
     fn ensure_org(
         origin: T::Origin,
@@ -382,11 +799,289 @@ impl<T: Config> Module<T> {
         }
     }
 
-    /// Hash the block number, org id and proposal together to generate its id
-    fn proposal_id(org_id: &T::AccountId, proposal: Box<<T as Config>::Call>) -> ProposalIdOf<T> {
+    /// Makes sure that the guardians configuration of an organization, if any, can be used.
+    fn ensure_valid_guardians(details: &OrganizationDetailsOf<T>) -> DispatchResult {
+        match &details.guardians {
+            Some(guardians) if !guardians.is_valid() => Err(Error::<T>::InvalidGuardians.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Makes sure that `who` can afford both the storage deposit and the organization's
+    /// proposal deposit, if any.
+    fn ensure_can_reserve_deposits(
+        who: &T::AccountId,
+        storage_deposit: BalanceOf<T>,
+        proposal_deposit: &Option<ProposalDeposit<CurrencyIdOf<T>, BalanceOf<T>>>,
+    ) -> DispatchResult {
+        let storage_currency = T::DepositCurrencyId::get();
+        let can_reserve = match proposal_deposit {
+            Some(deposit) if deposit.currency_id == storage_currency => T::Currencies::can_reserve(
+                storage_currency,
+                who,
+                storage_deposit.saturating_add(deposit.amount),
+            ),
+            Some(deposit) => {
+                T::Currencies::can_reserve(storage_currency, who, storage_deposit)
+                    && T::Currencies::can_reserve(deposit.currency_id, who, deposit.amount)
+            }
+            None => T::Currencies::can_reserve(storage_currency, who, storage_deposit),
+        };
+        ensure!(can_reserve, Error::<T>::InsufficientDepositBalance);
+        Ok(())
+    }
+
+    /// Veto a proposal through its voting system and clean it from our storage. The proposal
+    /// deposit, if any, is given to the organization.
+    fn do_veto(proposal_id: ProposalIdOf<T>, proposal: ProposalOf<T>) -> DispatchResult {
+        T::VotingRouter::veto(proposal.voting.clone(), proposal_id)?;
+        Self::remove_proposal(proposal_id, &proposal);
+
+        if let Some(deposit) = &proposal.proposal_deposit {
+            // Would the organization account not be able to receive the funds they are
+            // simply burned.
+            let not_repatriated = T::Currencies::repatriate_reserved(
+                deposit.currency_id,
+                &proposal.proposer,
+                &proposal.org,
+                deposit.amount,
+                BalanceStatus::Free,
+            )
+            .unwrap_or(deposit.amount);
+            T::Currencies::slash_reserved(deposit.currency_id, &proposal.proposer, not_repatriated);
+
+            Self::deposit_event(RawEvent::ProposalDepositRepatriated(
+                proposal_id,
+                proposal.proposer.clone(),
+                deposit.amount,
+            ));
+        }
+
+        Self::deposit_event(RawEvent::ProposalVetoed(proposal_id));
+        Ok(())
+    }
+
+    /// Decode the calls attached to a proposal.
+    fn decode_calls(proposal: &ProposalOf<T>) -> Result<Vec<<T as Config>::Call>, DispatchError> {
+        proposal
+            .calls
+            .iter()
+            .map(|call| <T as Config>::Call::decode(&mut &call[..]))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Error::<T>::ProposalDecodingFailure.into())
+    }
+
+    /// Sum of the weights of all the `calls`.
+    fn calls_weight(calls: &[<T as Config>::Call]) -> Weight {
+        calls.iter().fold(0, |acc: Weight, call| {
+            acc.saturating_add(call.get_dispatch_info().weight)
+        })
+    }
+
+    /// Close a proposal through its voting system, execute it if it passed and clean it
+    /// from our storage. Returns the weight consumed by the execution of `decoded_calls`.
+    fn do_close(
+        proposal_id: ProposalIdOf<T>,
+        proposal: ProposalOf<T>,
+        decoded_calls: Vec<<T as Config>::Call>,
+    ) -> Result<Weight, DispatchError> {
+        let proposal_result = T::VotingRouter::close(proposal.voting.clone(), proposal_id)?;
+
+        let mut external_weight: Weight = 0;
+        if proposal_result == ProposalResult::Passing {
+            let (results, weight) =
+                Self::execute_calls(&proposal.org, proposal.mode, decoded_calls);
+            results.iter().enumerate().for_each(|(index, res)| {
+                Self::deposit_event(RawEvent::ProposalCallExecuted(
+                    proposal_id,
+                    index as u32,
+                    *res,
+                ))
+            });
+            let first_error = results.into_iter().find(|res| res.is_err());
+            Self::deposit_event(RawEvent::ProposalExecuted(
+                proposal_id,
+                first_error.unwrap_or(Ok(())),
+            ));
+            external_weight = external_weight.saturating_add(weight);
+        }
+        Self::remove_proposal(proposal_id, &proposal);
+        if let Some(deposit) = &proposal.proposal_deposit {
+            T::Currencies::unreserve(deposit.currency_id, &proposal.proposer, deposit.amount);
+        }
+
+        Self::deposit_event(RawEvent::ProposalClosed(proposal_id, proposal_result));
+
+        Ok(external_weight)
+    }
+
+    /// Weight of `close_proposal` without the weight of the proposal calls. Accounts for the
+    /// removal of the proposal from the `ExpiryQueue`.
+    fn close_proposal_weight() -> Weight {
+        T::WeightInfo::close_proposal(T::MaxVotes::get(), T::MaxVotes::get())
+            .saturating_add(T::DbWeight::get().reads_writes(2, 1))
+    }
+
+    /// Remove a proposal from the `ExpiryQueue` so that we do not have to process it once it
+    /// expires.
+    fn unqueue_expiry(proposal_id: ProposalIdOf<T>, proposal: &ProposalOf<T>) {
+        if let Some(expiry) = T::VotingRouter::expiry(proposal.voting.clone(), proposal_id) {
+            ExpiryQueue::<T>::mutate_exists(expiry.saturating_add(One::one()), |maybe_queue| {
+                if let Some(queue) = maybe_queue {
+                    queue.retain(|id| *id != proposal_id);
+                    if queue.is_empty() {
+                        *maybe_queue = None;
+                    }
+                }
+            });
+        }
+    }
+
+    /// Clean a proposal from our storage and return its storage deposit.
+    fn remove_proposal(proposal_id: ProposalIdOf<T>, proposal: &ProposalOf<T>) {
+        Proposals::<T>::remove(proposal_id);
+        GuardianApprovals::<T>::remove(proposal_id);
+        SponsoredProposalCalls::<T>::remove_prefix(proposal_id);
+
+        T::Currencies::unreserve(
+            T::DepositCurrencyId::get(),
+            &proposal.proposer,
+            proposal.storage_deposit,
+        );
+    }
+
+    /// Dispatch `calls` in order as if they came from `org_id`. When using `AllOrNothing` we
+    /// stop at the first failure and revert the calls executed before it. Returns the result
+    /// of every dispatched call and the weight they consumed.
+    fn execute_calls(
+        org_id: &T::AccountId,
+        mode: ExecutionMode,
+        calls: Vec<<T as Config>::Call>,
+    ) -> (Vec<DispatchResult>, Weight) {
+        // Calls approved by a proposal are not bound by the limits of an executor that may
+        // have triggered the execution.
+        let acting_executor = ActingExecutors::<T>::take(org_id);
+        let dispatch = || {
+            let mut results = Vec::with_capacity(calls.len());
+            let mut weight: Weight = 0;
+            for call in calls {
+                let call_weight = call.get_dispatch_info().weight;
+                let res = call.dispatch(frame_system::RawOrigin::Signed(org_id.clone()).into());
+                weight = weight.saturating_add(Self::get_result_weight(res).unwrap_or(call_weight));
+
+                let res = res.map(|_| ()).map_err(|e| e.error);
+                results.push(res);
+                if res.is_err() && mode == ExecutionMode::AllOrNothing {
+                    break;
+                }
+            }
+            (results, weight)
+        };
+
+        let outcome = match mode {
+            ExecutionMode::BestEffort => dispatch(),
+            ExecutionMode::AllOrNothing => with_transaction(|| {
+                let (results, weight) = dispatch();
+                if results.iter().any(|res| res.is_err()) {
+                    TransactionOutcome::Rollback((results, weight))
+                } else {
+                    TransactionOutcome::Commit((results, weight))
+                }
+            }),
+        };
+        Self::set_acting_executor(org_id, acting_executor);
+
+        outcome
+    }
+
+    /// Close the proposals that expired by `now`, starting from the oldest block we did not
+    /// fully process yet, and stop as soon as we would go above `MaxAutoCloseWeight`. What we
+    /// did not process stays in the `ExpiryQueue` for the next blocks. Proposals that were
+    /// already closed or vetoed are skipped. Proposals that fail to close, or that are too
+    /// heavy to ever fit in our budget, are dropped from the queue and have to be closed
+    /// manually.
+    fn close_expired_proposals(now: T::BlockNumber) -> Weight {
+        let budget = T::MaxAutoCloseWeight::get();
+        let close_weight = T::WeightInfo::close_proposal(T::MaxVotes::get(), T::MaxVotes::get());
+        let db = T::DbWeight::get();
+
+        // Reading and writing the cursor
+        let mut consumed = db.reads_writes(1, 1);
+        let mut block = Self::expiry_cursor().unwrap_or(now);
+        while block <= now {
+            // Reading the queue and writing back what we could not process
+            let queue_weight = db.reads_writes(1, 1);
+            if consumed.saturating_add(queue_weight) > budget {
+                break;
+            }
+            consumed = consumed.saturating_add(queue_weight);
+
+            let mut queue = ExpiryQueue::<T>::take(block);
+            let mut processed = 0;
+            for proposal_id in queue.iter() {
+                if consumed.saturating_add(db.reads(1)) > budget {
+                    break;
+                }
+                consumed = consumed.saturating_add(db.reads(1));
+
+                let proposal = match Self::proposals(proposal_id) {
+                    Some(proposal) => proposal,
+                    // Already closed or vetoed
+                    None => {
+                        processed += 1;
+                        continue;
+                    }
+                };
+                let decoded_calls = match Self::decode_calls(&proposal) {
+                    Ok(decoded_calls) => decoded_calls,
+                    Err(e) => {
+                        Self::deposit_event(RawEvent::ProposalAutoCloseFailed(*proposal_id, e));
+                        processed += 1;
+                        continue;
+                    }
+                };
+
+                let max_weight = close_weight.saturating_add(Self::calls_weight(&decoded_calls));
+                if max_weight > budget {
+                    processed += 1;
+                    continue;
+                }
+                if consumed.saturating_add(max_weight) > budget {
+                    break;
+                }
+
+                let external_weight = with_transaction(|| {
+                    match Self::do_close(*proposal_id, proposal, decoded_calls) {
+                        Ok(weight) => TransactionOutcome::Commit(Ok(weight)),
+                        Err(e) => TransactionOutcome::Rollback(Err(e)),
+                    }
+                })
+                .unwrap_or_else(|e| {
+                    Self::deposit_event(RawEvent::ProposalAutoCloseFailed(*proposal_id, e));
+                    0
+                });
+                consumed = consumed
+                    .saturating_add(close_weight)
+                    .saturating_add(external_weight);
+                processed += 1;
+            }
+
+            if processed < queue.len() {
+                ExpiryQueue::<T>::insert(block, queue.split_off(processed));
+                break;
+            }
+            block = block.saturating_add(One::one());
+        }
+        ExpiryCursor::<T>::put(block);
+
+        consumed
+    }
+
+    /// Hash the org id and proposal calls together to generate its id
+    fn proposal_id(org_id: &T::AccountId, calls: &[<T as Config>::Call]) -> ProposalIdOf<T> {
         // Proposals are organization specific and there can not be two identical proposals opened
         // in the same organization.
-        T::Hashing::hash_of(&[org_id.encode(), proposal.encode()])
+        T::Hashing::hash_of(&[org_id.encode(), calls.encode()])
     }
 
     /// Return the weight of a dispatch call result as an `Option`.
@@ -419,6 +1114,8 @@ impl<T: Config> Module<T> {
         details: OrganizationDetailsOf<T>,
         org_id: T::AccountId,
     ) -> DispatchResult {
+        Self::ensure_valid_guardians(&details)?;
+
         // Sorting details allows us to be more efficient when updating them later on.
         let mut details = details;
         details.sort();
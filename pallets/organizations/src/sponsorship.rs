@@ -0,0 +1,281 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{
+    Call, Config, Module, NativeBalanceOf, NegativeImbalanceOf, ProposalIdOf, RawEvent, Spending,
+    SponsoredCall, SponsoredCalls, SponsoredProposalCalls, SponsoredSpendingOf, SponsoredSpendings,
+    SponsoredUsageOf,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+    dispatch::DispatchResult,
+    traits::{
+        Currency, ExistenceRequirement, Get, Imbalance, IsSubType, OnUnbalanced, WithdrawReasons,
+    },
+    weights::{DispatchInfo, PostDispatchInfo},
+    StorageDoubleMap, StorageMap,
+};
+use governance_os_support::traits::VotingRouter;
+use pallet_transaction_payment::{ChargeTransactionPayment, OnChargeTransaction};
+use sp_runtime::{
+    traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, Saturating, SignedExtension, Zero},
+    transaction_validity::{
+        InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
+    FixedPointOperand,
+};
+
+type FeeBalanceOf<T> = <<T as pallet_transaction_payment::Config>::OnChargeTransaction as OnChargeTransaction<T>>::Balance;
+type InnerPreOf<T> = <ChargeTransactionPayment<T> as SignedExtension>::Pre;
+type SponsoredPreOf<T> = (
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::AccountId,
+    NegativeImbalanceOf<T>,
+    ProposalIdOf<T>,
+    SponsoredUsageOf<T>,
+);
+
+impl<T: Config> Module<T> {
+    /// Proposal `call` targets, the organization it belongs to and which kind of sponsored
+    /// call it is. Only accounts that could vote on the proposal are candidates. Closing
+    /// proposals is never sponsored as its weight depends on the calls of the proposal.
+    fn sponsor_candidate(
+        call: &Call<T>,
+        who: &T::AccountId,
+    ) -> Option<(ProposalIdOf<T>, T::AccountId, SponsoredCall)> {
+        let (proposal_id, kind) = match call {
+            Call::decide_on_proposal(proposal_id, _) => {
+                (*proposal_id, SponsoredCall::DecideOnProposal)
+            }
+            _ => return None,
+        };
+
+        let proposal = Self::proposals(proposal_id)?;
+        if !T::VotingRouter::can_vote(proposal.voting, proposal_id, who) {
+            return None;
+        }
+        if Self::sponsored_proposal_calls(proposal_id, who)
+            >= T::MaxSponsoredCallsPerProposal::get()
+        {
+            return None;
+        }
+
+        Some((proposal_id, proposal.org, kind))
+    }
+
+    /// Check that `org_id` sponsors calls of type `kind` and can pay `fee` for `who` without
+    /// exceeding its quotas. Returns the updated organization spending and account usage.
+    fn try_sponsor(
+        org_id: &T::AccountId,
+        who: &T::AccountId,
+        kind: SponsoredCall,
+        fee: NativeBalanceOf<T>,
+    ) -> Option<(SponsoredSpendingOf<T>, SponsoredUsageOf<T>)> {
+        let sponsorship = Self::sponsorships(org_id).filter(|s| s.calls.contains(&kind))?;
+
+        let now = frame_system::Module::<T>::block_number();
+        let mut spending = match Self::sponsored_spendings(org_id) {
+            Some(spending) if now < spending.period_start.saturating_add(sponsorship.period) => {
+                spending
+            }
+            _ => Spending {
+                period_start: now,
+                spent: Zero::zero(),
+            },
+        };
+        spending.spent = spending.spent.saturating_add(fee);
+        if spending.spent > sponsorship.budget {
+            return None;
+        }
+
+        // Accounts' usage is reset whenever the organization starts a new period.
+        let (period_start, count) = Self::sponsored_calls(org_id, who);
+        let count = if period_start == spending.period_start {
+            count
+        } else {
+            0
+        };
+        if count >= sponsorship.calls_per_account {
+            return None;
+        }
+
+        let period_start = spending.period_start;
+        Some((spending, (period_start, count.saturating_add(1))))
+    }
+}
+
+/// Replacement for `ChargeTransactionPayment` that lets organizations pay the fees of the calls
+/// they sponsor, within their quotas. Other transactions, or the ones the organization can not
+/// pay for, are charged to their signer as usual. Tips are never sponsored and sponsored
+/// transactions get the lowest priority.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ChargeSponsoredTransaction<T: Config + pallet_transaction_payment::Config>(
+    pub ChargeTransactionPayment<T>,
+);
+
+impl<T> ChargeSponsoredTransaction<T>
+where
+    T: Config + pallet_transaction_payment::Config,
+    T::NativeCurrency: Currency<T::AccountId, Balance = FeeBalanceOf<T>>,
+    <T as frame_system::Config>::Call:
+        Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + IsSubType<Call<T>>,
+    FeeBalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+{
+    /// Utility constructor, `tip` is only paid when the transaction is not sponsored.
+    pub fn from(tip: FeeBalanceOf<T>) -> Self {
+        Self(ChargeTransactionPayment::from(tip))
+    }
+
+    /// Withdraw the fee of `call` from the organization sponsoring it, if any, and set it
+    /// aside from its budget. The calls are only counted once they succeed.
+    fn withdraw_sponsored_fee(
+        who: &T::AccountId,
+        call: &<T as frame_system::Config>::Call,
+        info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+        len: usize,
+    ) -> Option<SponsoredPreOf<T>> {
+        let (proposal_id, org_id, kind) = Module::<T>::sponsor_candidate(call.is_sub_type()?, who)?;
+        let fee =
+            pallet_transaction_payment::Module::<T>::compute_fee(len as u32, info, Zero::zero());
+        if fee.is_zero() {
+            return None;
+        }
+
+        let (spending, usage) = Module::<T>::try_sponsor(&org_id, who, kind, fee)?;
+        let imbalance = T::NativeCurrency::withdraw(
+            &org_id,
+            fee,
+            WithdrawReasons::TRANSACTION_PAYMENT,
+            ExistenceRequirement::KeepAlive,
+        )
+        .ok()?;
+        SponsoredSpendings::<T>::insert(&org_id, spending);
+
+        Some((org_id, who.clone(), imbalance, proposal_id, usage))
+    }
+}
+
+impl<T: Config + pallet_transaction_payment::Config> sp_std::fmt::Debug
+    for ChargeSponsoredTransaction<T>
+{
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "ChargeSponsoredTransaction<{:?}>", self.0)
+    }
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T> SignedExtension for ChargeSponsoredTransaction<T>
+where
+    T: Config + pallet_transaction_payment::Config + Send + Sync,
+    T::NativeCurrency: Currency<T::AccountId, Balance = FeeBalanceOf<T>>,
+    <T as frame_system::Config>::Call:
+        Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + IsSubType<Call<T>>,
+    FeeBalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+{
+    const IDENTIFIER: &'static str = "ChargeSponsoredTransaction";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::Call;
+    type AdditionalSigned = ();
+    type Pre = (
+        // sponsoring organization, signer and fee it paid
+        Option<SponsoredPreOf<T>>,
+        // fee paid by the signer
+        Option<InnerPreOf<T>>,
+    );
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> TransactionValidity {
+        match Self::withdraw_sponsored_fee(who, call, info, len) {
+            Some(_) => Ok(ValidTransaction::default()),
+            None => self.0.validate(who, call, info, len),
+        }
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        match Self::withdraw_sponsored_fee(who, call, info, len) {
+            Some(sponsored) => Ok((Some(sponsored), None)),
+            None => Ok((None, Some(self.0.pre_dispatch(who, call, info, len)?))),
+        }
+    }
+
+    fn post_dispatch(
+        pre: Self::Pre,
+        info: &DispatchInfoOf<Self::Call>,
+        post_info: &PostDispatchInfoOf<Self::Call>,
+        len: usize,
+        result: &DispatchResult,
+    ) -> Result<(), TransactionValidityError> {
+        let (sponsored, charged) = pre;
+        if let Some(pre) = charged {
+            ChargeTransactionPayment::<T>::post_dispatch(pre, info, post_info, len, result)?;
+        }
+
+        if let Some((org_id, who, paid, proposal_id, usage)) = sponsored {
+            // Failed calls are refunded in full and not counted against the quotas.
+            let actual_fee = if result.is_ok() {
+                pallet_transaction_payment::Module::<T>::compute_actual_fee(
+                    len as u32,
+                    info,
+                    post_info,
+                    Zero::zero(),
+                )
+            } else {
+                Zero::zero()
+            };
+            let refund = paid.peek().saturating_sub(actual_fee);
+            let refund_imbalance = T::NativeCurrency::deposit_into_existing(&org_id, refund)
+                .unwrap_or_else(|_| Imbalance::zero());
+            let fee = paid
+                .offset(refund_imbalance)
+                .map_err(|_| InvalidTransaction::Payment)?;
+
+            SponsoredSpendings::<T>::mutate(&org_id, |maybe_spending| {
+                if let Some(spending) = maybe_spending {
+                    spending.spent = spending.spent.saturating_sub(refund);
+                }
+            });
+            if result.is_ok() {
+                SponsoredCalls::<T>::insert(&org_id, &who, usage);
+                SponsoredProposalCalls::<T>::mutate(proposal_id, &who, |count| {
+                    *count = count.saturating_add(1)
+                });
+                Module::<T>::deposit_event(RawEvent::TransactionSponsored(org_id, who, fee.peek()));
+            }
+            T::OnSponsoredFees::on_unbalanced(fee);
+        }
+
+        Ok(())
+    }
+}
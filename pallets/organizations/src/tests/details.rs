@@ -14,15 +14,35 @@
  * limitations under the License.
  */
 
-use crate::OrganizationDetails;
-use governance_os_support::testing::{ALICE, BOB};
+use crate::{Guardians, OrganizationDetails};
+use governance_os_support::testing::{
+    primitives::{Balance, CurrencyId},
+    ALICE, BOB,
+};
 
 #[test]
 fn sort() {
-    let mut details = OrganizationDetails {
+    let mut details: OrganizationDetails<_, _, CurrencyId, Balance> = OrganizationDetails {
         executors: vec![BOB, ALICE],
         voting: (),
+        guardians: None,
+        proposal_deposit: None,
     };
     details.sort();
     assert_eq!(details.executors, vec![ALICE, BOB]);
 }
+
+#[test]
+fn sort_guardians() {
+    let mut details: OrganizationDetails<_, _, CurrencyId, Balance> = OrganizationDetails {
+        executors: vec![],
+        voting: (),
+        guardians: Some(Guardians {
+            accounts: vec![BOB, ALICE],
+            threshold: 1,
+        }),
+        proposal_deposit: None,
+    };
+    details.sort();
+    assert_eq!(details.guardians.unwrap().accounts, vec![ALICE, BOB]);
+}
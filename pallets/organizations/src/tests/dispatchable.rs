@@ -15,17 +15,27 @@
  */
 
 use super::mock::{
-    make_proposal, Bylaws, ExtBuilder, MockRoles, MockVotingSystemId, Organizations, Test,
+    make_call, make_proposal, proposal_weight, Bylaws, Call, Event, ExtBuilder,
+    MaxDescriptionLength, MaxTitleLength, MockRoles, MockVotingSystemId, Organizations,
+    ProposalDepositPerByte, System, Test, Tokens, ALICE_BALANCE,
+};
+use crate::{
+    Error, ExecutionMode, GuardianApprovals, Guardians, OrganizationDetails, Proposal,
+    ProposalDeposit, ProposalMetadata, Proposals, RawEvent, RoleBuilder,
 };
-use crate::{Error, OrganizationDetails, Proposal, Proposals, RoleBuilder};
 use codec::Encode;
-use frame_support::{assert_noop, assert_ok, weights::GetDispatchInfo, StorageMap};
+use frame_support::{assert_noop, assert_ok, StorageMap};
 use frame_system::RawOrigin;
 use governance_os_support::{
     errors::AclError,
-    testing::{ALICE, BOB, CHARLIE},
-    traits::{ProposalResult, RoleManager},
+    testing::{
+        primitives::{AccountId, Balance},
+        ALICE, BOB, CHARLIE, EVE, TEST_TOKEN_ID,
+    },
+    traits::{Currencies, ProposalResult, ReservableCurrencies, RoleManager},
 };
+use sp_core::H256;
+use sp_runtime::DispatchError;
 
 #[test]
 fn create_increments_counter_and_save_details_and_configure_roles() {
@@ -39,6 +49,8 @@ fn create_increments_counter_and_save_details_and_configure_roles() {
                     // We intentionally make it unsorted for the test
                     executors: vec![CHARLIE, BOB],
                     voting: (MockVotingSystemId::WithResult(ProposalResult::Passing), ()),
+                    guardians: None,
+                    proposal_deposit: None,
                 }
             ));
             assert_eq!(Organizations::counter(), 1);
@@ -70,12 +82,14 @@ fn apply_as() {
                 OrganizationDetails {
                     executors: vec![ALICE],
                     voting: (MockVotingSystemId::WithResult(ProposalResult::Passing), ()),
+                    guardians: None,
+                    proposal_deposit: None,
                 }
             ));
             assert_ok!(Organizations::apply_as(
                 RawOrigin::Signed(ALICE).into(),
                 Organizations::org_id_for(0),
-                make_proposal(),
+                Box::new(make_call()),
             ));
         })
 }
@@ -91,6 +105,8 @@ fn mutate_save_details_and_update_roles() {
                 OrganizationDetails {
                     executors: vec![ALICE, BOB],
                     voting: (MockVotingSystemId::WithResult(ProposalResult::Passing), ()),
+                    guardians: None,
+                    proposal_deposit: None,
                 }
             ));
             let org_id = Organizations::org_id_for(0);
@@ -99,6 +115,8 @@ fn mutate_save_details_and_update_roles() {
                 OrganizationDetails {
                     executors: vec![ALICE, CHARLIE],
                     voting: (MockVotingSystemId::WithResult(ProposalResult::Passing), ()),
+                    guardians: None,
+                    proposal_deposit: None,
                 },
             ));
 
@@ -134,6 +152,8 @@ fn create_fail_if_not_corect_role() {
                 OrganizationDetails {
                     executors: vec![],
                     voting: (MockVotingSystemId::WithResult(ProposalResult::Passing), ()),
+                    guardians: None,
+                    proposal_deposit: None,
                 }
             ),
             AclError::MissingRole
@@ -151,7 +171,7 @@ fn apply_as_fail_if_not_correct_role() {
                 Organizations::apply_as(
                     RawOrigin::Signed(ALICE).into(),
                     Organizations::org_id_for(0),
-                    make_proposal()
+                    Box::new(make_call())
                 ),
                 AclError::MissingRole
             );
@@ -170,6 +190,8 @@ fn mutate_fail_if_not_the_org_itself() {
                     OrganizationDetails {
                         executors: vec![],
                         voting: (MockVotingSystemId::WithResult(ProposalResult::Passing), ()),
+                        guardians: None,
+                        proposal_deposit: None,
                     }
                 ),
                 Error::<Test>::NotAnOrganization,
@@ -185,12 +207,14 @@ fn create_proposal() {
         .execute_with(|| {
             let org_id = Organizations::org_id_for(0);
             let proposal = make_proposal();
-            let proposal_id = Organizations::proposal_id(&org_id, proposal.clone());
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
 
             assert_ok!(Organizations::create_proposal(
                 RawOrigin::Signed(ALICE).into(),
                 org_id,
-                proposal
+                proposal,
+                ExecutionMode::AllOrNothing,
+                Default::default()
             ));
 
             assert!(Proposals::<Test>::contains_key(proposal_id));
@@ -215,12 +239,20 @@ fn create_proposal_fail_if_duplicate() {
             assert_ok!(Organizations::create_proposal(
                 RawOrigin::Signed(ALICE).into(),
                 org_id,
-                proposal.clone()
+                proposal.clone(),
+                ExecutionMode::AllOrNothing,
+                Default::default()
             ));
 
             // Block number did not change so we'd end up generating the same hash
             assert_noop!(
-                Organizations::create_proposal(RawOrigin::Signed(ALICE).into(), org_id, proposal),
+                Organizations::create_proposal(
+                    RawOrigin::Signed(ALICE).into(),
+                    org_id,
+                    proposal,
+                    ExecutionMode::AllOrNothing,
+                    Default::default()
+                ),
                 Error::<Test>::ProposalDuplicate
             );
         })
@@ -233,7 +265,9 @@ fn create_proposal_fail_if_target_is_not_an_org() {
             Organizations::create_proposal(
                 RawOrigin::Signed(ALICE).into(),
                 Organizations::org_id_for(0),
-                make_proposal()
+                make_proposal(),
+                ExecutionMode::AllOrNothing,
+                Default::default()
             ),
             Error::<Test>::NotAnOrganization
         );
@@ -246,6 +280,8 @@ fn create_proposal_fail_if_hook_fail() {
         .with_org(OrganizationDetails {
             executors: vec![],
             voting: (MockVotingSystemId::FailInitiate, ()),
+            guardians: None,
+            proposal_deposit: None,
         })
         .build()
         .execute_with(|| {
@@ -254,7 +290,9 @@ fn create_proposal_fail_if_hook_fail() {
                 Organizations::create_proposal(
                     RawOrigin::Signed(ALICE).into(),
                     org_id,
-                    make_proposal()
+                    make_proposal(),
+                    ExecutionMode::AllOrNothing,
+                    Default::default()
                 ),
                 "fail"
             );
@@ -270,12 +308,14 @@ fn veto_proposal() {
         .execute_with(|| {
             let org_id = Organizations::org_id_for(0);
             let proposal = make_proposal();
-            let proposal_id = Organizations::proposal_id(&org_id, proposal.clone());
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
 
             assert_ok!(Organizations::create_proposal(
                 RawOrigin::Signed(ALICE).into(),
                 org_id,
-                proposal
+                proposal,
+                ExecutionMode::AllOrNothing,
+                Default::default()
             ));
 
             assert_ok!(Organizations::veto_proposal(
@@ -293,7 +333,7 @@ fn veto_proposal_fail_if_not_called_by_an_org() {
         assert_noop!(
             Organizations::veto_proposal(
                 RawOrigin::Signed(ALICE).into(),
-                Organizations::proposal_id(&Organizations::org_id_for(0), make_proposal()),
+                Organizations::proposal_id(&Organizations::org_id_for(0), &make_proposal()),
             ),
             Error::<Test>::NotAnOrganization,
         );
@@ -310,12 +350,14 @@ fn veto_proposal_fail_if_called_on_wrong_proposal() {
         .execute_with(|| {
             let org_id = Organizations::org_id_for(0);
             let proposal = make_proposal();
-            let proposal_id = Organizations::proposal_id(&org_id, proposal.clone());
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
 
             assert_ok!(Organizations::create_proposal(
                 RawOrigin::Signed(ALICE).into(),
                 org_id,
-                proposal.clone()
+                proposal.clone(),
+                ExecutionMode::AllOrNothing,
+                Default::default()
             ));
 
             // Not the right org id
@@ -330,7 +372,7 @@ fn veto_proposal_fail_if_called_on_wrong_proposal() {
             assert_noop!(
                 Organizations::veto_proposal(
                     RawOrigin::Signed(org_id).into(),
-                    Organizations::proposal_id(&Organizations::org_id_for(1), proposal)
+                    Organizations::proposal_id(&Organizations::org_id_for(1), &proposal)
                 ),
                 Error::<Test>::ProposalNotFound
             );
@@ -345,15 +387,20 @@ fn veto_proposal_fail_if_hook_fail() {
         .execute_with(|| {
             let org_id = Organizations::org_id_for(0);
             let proposal = make_proposal();
-            let proposal_id = Organizations::proposal_id(&org_id, proposal);
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
 
             // We have to insert a fake proposal and bypass the hook for the `None` voting system
             Proposals::<Test>::insert(
                 &proposal_id,
                 Proposal {
                     org: org_id,
-                    call: vec![],
+                    calls: vec![],
+                    mode: ExecutionMode::AllOrNothing,
                     voting: MockVotingSystemId::FailVeto,
+                    metadata: Default::default(),
+                    proposer: ALICE,
+                    storage_deposit: 0,
+                    proposal_deposit: None,
                 },
             );
 
@@ -364,6 +411,186 @@ fn veto_proposal_fail_if_hook_fail() {
         })
 }
 
+// guardian_veto
+#[test]
+fn guardian_veto_applies_once_threshold_is_reached() {
+    ExtBuilder::default()
+        .with_guarded_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal = make_proposal();
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                proposal,
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+
+            assert_ok!(Organizations::guardian_veto(
+                RawOrigin::Signed(ALICE).into(),
+                proposal_id
+            ));
+            assert!(Proposals::<Test>::contains_key(proposal_id));
+            assert_eq!(Organizations::guardian_approvals(proposal_id), vec![ALICE]);
+
+            assert_ok!(Organizations::guardian_veto(
+                RawOrigin::Signed(BOB).into(),
+                proposal_id
+            ));
+            assert!(!Proposals::<Test>::contains_key(proposal_id));
+            assert!(!GuardianApprovals::<Test>::contains_key(proposal_id));
+        })
+}
+
+#[test]
+fn guardian_veto_fail_if_not_a_guardian() {
+    ExtBuilder::default()
+        .with_guarded_org()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let guarded_proposal_id =
+                Organizations::proposal_id(&Organizations::org_id_for(0), &make_proposal());
+            let unguarded_proposal_id =
+                Organizations::proposal_id(&Organizations::org_id_for(1), &make_proposal());
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                Organizations::org_id_for(0),
+                make_proposal(),
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                Organizations::org_id_for(1),
+                make_proposal(),
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+
+            assert_noop!(
+                Organizations::guardian_veto(RawOrigin::Signed(EVE).into(), guarded_proposal_id),
+                Error::<Test>::NotAGuardian
+            );
+            assert_noop!(
+                Organizations::guardian_veto(
+                    RawOrigin::Signed(ALICE).into(),
+                    unguarded_proposal_id
+                ),
+                Error::<Test>::NotAGuardian
+            );
+        })
+}
+
+#[test]
+fn guardian_veto_fail_if_already_approved() {
+    ExtBuilder::default()
+        .with_guarded_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal = make_proposal();
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                proposal,
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+            assert_ok!(Organizations::guardian_veto(
+                RawOrigin::Signed(ALICE).into(),
+                proposal_id
+            ));
+
+            assert_noop!(
+                Organizations::guardian_veto(RawOrigin::Signed(ALICE).into(), proposal_id),
+                Error::<Test>::GuardianAlreadyApproved
+            );
+        })
+}
+
+#[test]
+fn guardian_veto_ignores_approvals_of_removed_guardians() {
+    ExtBuilder::default()
+        .with_guarded_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal = make_proposal();
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                proposal,
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+            assert_ok!(Organizations::guardian_veto(
+                RawOrigin::Signed(ALICE).into(),
+                proposal_id
+            ));
+
+            assert_ok!(Organizations::mutate(
+                RawOrigin::Signed(org_id).into(),
+                OrganizationDetails {
+                    executors: vec![],
+                    voting: (MockVotingSystemId::WithResult(ProposalResult::Passing), ()),
+                    guardians: Some(Guardians {
+                        accounts: vec![BOB, CHARLIE],
+                        threshold: 2,
+                    }),
+                    proposal_deposit: None,
+                },
+            ));
+
+            assert_ok!(Organizations::guardian_veto(
+                RawOrigin::Signed(BOB).into(),
+                proposal_id
+            ));
+            assert!(Proposals::<Test>::contains_key(proposal_id));
+            assert_eq!(Organizations::guardian_approvals(proposal_id), vec![BOB]);
+        })
+}
+
+#[test]
+fn create_and_mutate_fail_if_guardians_are_invalid() {
+    ExtBuilder::default()
+        .alice_can_create_orgs()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let invalid_details = |threshold| OrganizationDetails {
+                executors: vec![],
+                voting: (MockVotingSystemId::WithResult(ProposalResult::Passing), ()),
+                guardians: Some(Guardians {
+                    accounts: vec![ALICE, BOB],
+                    threshold,
+                }),
+                proposal_deposit: None,
+            };
+
+            assert_noop!(
+                Organizations::create(RawOrigin::Signed(ALICE).into(), invalid_details(0)),
+                Error::<Test>::InvalidGuardians
+            );
+            assert_noop!(
+                Organizations::mutate(
+                    RawOrigin::Signed(Organizations::org_id_for(0)).into(),
+                    invalid_details(3)
+                ),
+                Error::<Test>::InvalidGuardians
+            );
+        })
+}
+
 #[test]
 fn decide_on_proposal() {
     ExtBuilder::default()
@@ -372,12 +599,14 @@ fn decide_on_proposal() {
         .execute_with(|| {
             let org_id = Organizations::org_id_for(0);
             let proposal = make_proposal();
-            let proposal_id = Organizations::proposal_id(&org_id, proposal.clone());
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
 
             assert_ok!(Organizations::create_proposal(
                 RawOrigin::Signed(ALICE).into(),
                 org_id,
-                proposal
+                proposal,
+                ExecutionMode::AllOrNothing,
+                Default::default()
             ));
 
             assert_ok!(Organizations::decide_on_proposal(
@@ -392,7 +621,7 @@ fn decide_on_proposal() {
 fn decide_on_proposal_fails_if_does_not_exists() {
     ExtBuilder::default().build().execute_with(|| {
         let proposal_id =
-            Organizations::proposal_id(&Organizations::org_id_for(0), make_proposal());
+            Organizations::proposal_id(&Organizations::org_id_for(0), &make_proposal());
 
         assert_noop!(
             Organizations::decide_on_proposal(RawOrigin::Signed(ALICE).into(), proposal_id, ()),
@@ -409,15 +638,20 @@ fn decide_on_proposal_fails_if_hook_fails() {
         .execute_with(|| {
             let org_id = Organizations::org_id_for(0);
             let proposal = make_proposal();
-            let proposal_id = Organizations::proposal_id(&org_id, proposal);
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
 
             // We have to insert a fake proposal and bypass the hook for the `None` voting system
             Proposals::<Test>::insert(
                 &proposal_id,
                 Proposal {
                     org: org_id,
-                    call: vec![],
+                    calls: vec![],
+                    mode: ExecutionMode::AllOrNothing,
                     voting: MockVotingSystemId::FailVote,
+                    metadata: Default::default(),
+                    proposer: ALICE,
+                    storage_deposit: 0,
+                    proposal_deposit: None,
                 },
             );
 
@@ -443,23 +677,27 @@ macro_rules! test_close_proposal {
                         MockVotingSystemId::WithResult(ProposalResult::$proposal_result),
                         (),
                     ),
+                    guardians: None,
+                    proposal_deposit: None,
                 })
                 .build()
                 .execute_with(|| {
                     let org_id = Organizations::org_id_for(0);
                     let proposal = make_proposal();
-                    let proposal_id = Organizations::proposal_id(&org_id, proposal.clone());
+                    let proposal_id = Organizations::proposal_id(&org_id, &proposal);
 
                     assert_ok!(Organizations::create_proposal(
                         RawOrigin::Signed(ALICE).into(),
                         org_id,
-                        proposal.clone()
+                        proposal.clone(),
+                        ExecutionMode::AllOrNothing,
+                        Default::default()
                     ));
 
                     assert_ok!(Organizations::close_proposal(
                         RawOrigin::Signed(ALICE).into(),
                         proposal_id,
-                        proposal.get_dispatch_info().weight
+                        proposal_weight(&proposal)
                     ));
 
                     // Deleted the proposal
@@ -480,19 +718,21 @@ fn close_fails_if_proposal_weight_bound_is_too_small() {
         .execute_with(|| {
             let org_id = Organizations::org_id_for(0);
             let proposal = make_proposal();
-            let proposal_id = Organizations::proposal_id(&org_id, proposal.clone());
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
 
             assert_ok!(Organizations::create_proposal(
                 RawOrigin::Signed(ALICE).into(),
                 org_id,
-                proposal.clone()
+                proposal.clone(),
+                ExecutionMode::AllOrNothing,
+                Default::default()
             ));
 
             assert_noop!(
                 Organizations::close_proposal(
                     RawOrigin::Signed(ALICE).into(),
                     proposal_id,
-                    proposal.get_dispatch_info().weight - 1
+                    proposal_weight(&proposal) - 1
                 ),
                 Error::<Test>::TooSmallWeightBound
             );
@@ -503,7 +743,7 @@ fn close_fails_if_proposal_weight_bound_is_too_small() {
 fn close_fails_if_proposal_does_not_exists() {
     ExtBuilder::default().build().execute_with(|| {
         let proposal_id =
-            Organizations::proposal_id(&Organizations::org_id_for(0), make_proposal());
+            Organizations::proposal_id(&Organizations::org_id_for(0), &make_proposal());
 
         assert_noop!(
             Organizations::close_proposal(RawOrigin::Signed(ALICE).into(), proposal_id, 0),
@@ -517,15 +757,20 @@ fn close_fails_if_hook_fails() {
     ExtBuilder::default().build().execute_with(|| {
         let org_id = Organizations::org_id_for(0);
         let proposal = make_proposal();
-        let proposal_id = Organizations::proposal_id(&org_id, proposal.clone());
+        let proposal_id = Organizations::proposal_id(&org_id, &proposal);
 
         // We have to insert a fake proposal and bypass the hook for the `None` voting system
         Proposals::<Test>::insert(
             &proposal_id,
             Proposal {
                 org: org_id,
-                call: make_proposal().encode(),
+                calls: vec![make_call().encode()],
+                mode: ExecutionMode::AllOrNothing,
                 voting: MockVotingSystemId::FailClose,
+                metadata: Default::default(),
+                proposer: ALICE,
+                storage_deposit: 0,
+                proposal_deposit: None,
             },
         );
 
@@ -533,9 +778,415 @@ fn close_fails_if_hook_fails() {
             Organizations::close_proposal(
                 RawOrigin::Signed(ALICE).into(),
                 proposal_id,
-                proposal.get_dispatch_info().weight
+                proposal_weight(&proposal)
             ),
             "fail"
         );
     })
 }
+
+// Proposals made of several calls.
+fn mutate_and_fail_proposal(org_id: AccountId) -> Vec<Call> {
+    vec![
+        Call::Organizations(crate::Call::mutate(OrganizationDetails {
+            executors: vec![ALICE],
+            voting: (MockVotingSystemId::WithResult(ProposalResult::Passing), ()),
+            guardians: None,
+            proposal_deposit: None,
+        })),
+        // Requires root, will fail
+        Call::System(frame_system::Call::set_heap_pages(0)),
+        Call::System(frame_system::Call::remark(vec![org_id as u8])),
+    ]
+}
+
+fn create_and_close(calls: Vec<Call>, mode: ExecutionMode) -> H256 {
+    let org_id = Organizations::org_id_for(0);
+    let proposal_id = Organizations::proposal_id(&org_id, &calls);
+
+    assert_ok!(Organizations::create_proposal(
+        RawOrigin::Signed(ALICE).into(),
+        org_id,
+        calls.clone(),
+        mode,
+        Default::default()
+    ));
+    assert_ok!(Organizations::close_proposal(
+        RawOrigin::Signed(ALICE).into(),
+        proposal_id,
+        proposal_weight(&calls)
+    ));
+
+    proposal_id
+}
+
+fn organizations_events() -> Vec<crate::Event<Test>> {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            Event::governance_os_pallet_organizations(event) => Some(event),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn create_proposal_fail_if_empty() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Organizations::create_proposal(
+                    RawOrigin::Signed(ALICE).into(),
+                    Organizations::org_id_for(0),
+                    vec![],
+                    ExecutionMode::AllOrNothing,
+                    Default::default()
+                ),
+                Error::<Test>::ProposalEmpty
+            );
+        })
+}
+
+#[test]
+fn close_all_or_nothing_proposal_reverts_on_failure() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal_id = create_and_close(
+                mutate_and_fail_proposal(org_id),
+                ExecutionMode::AllOrNothing,
+            );
+
+            // First call was reverted
+            assert!(Organizations::parameters(org_id)
+                .unwrap()
+                .executors
+                .is_empty());
+
+            let events = organizations_events();
+            assert!(events.contains(&RawEvent::ProposalCallExecuted(proposal_id, 0, Ok(()))));
+            assert!(events.contains(&RawEvent::ProposalCallExecuted(
+                proposal_id,
+                1,
+                Err(DispatchError::BadOrigin)
+            )));
+            // Stopped at the first failure
+            assert!(!events
+                .iter()
+                .any(|event| matches!(event, RawEvent::ProposalCallExecuted(_, 2, _))));
+            assert!(events.contains(&RawEvent::ProposalExecuted(
+                proposal_id,
+                Err(DispatchError::BadOrigin)
+            )));
+        })
+}
+
+#[test]
+fn close_best_effort_proposal_executes_every_call() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal_id =
+                create_and_close(mutate_and_fail_proposal(org_id), ExecutionMode::BestEffort);
+
+            assert_eq!(
+                Organizations::parameters(org_id).unwrap().executors,
+                vec![ALICE]
+            );
+
+            let events = organizations_events();
+            assert!(events.contains(&RawEvent::ProposalCallExecuted(proposal_id, 0, Ok(()))));
+            assert!(events.contains(&RawEvent::ProposalCallExecuted(
+                proposal_id,
+                1,
+                Err(DispatchError::BadOrigin)
+            )));
+            assert!(events.contains(&RawEvent::ProposalCallExecuted(proposal_id, 2, Ok(()))));
+            assert!(events.contains(&RawEvent::ProposalExecuted(
+                proposal_id,
+                Err(DispatchError::BadOrigin)
+            )));
+        })
+}
+
+#[test]
+fn close_fails_if_weight_bound_does_not_cover_all_calls() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let calls = mutate_and_fail_proposal(org_id);
+            let proposal_id = Organizations::proposal_id(&org_id, &calls);
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                calls.clone(),
+                ExecutionMode::BestEffort,
+                Default::default()
+            ));
+
+            assert_noop!(
+                Organizations::close_proposal(
+                    RawOrigin::Signed(ALICE).into(),
+                    proposal_id,
+                    proposal_weight(&calls[..1])
+                ),
+                Error::<Test>::TooSmallWeightBound
+            );
+        })
+}
+
+// Proposal metadata and deposits.
+fn mock_metadata() -> ProposalMetadata<H256> {
+    ProposalMetadata {
+        title: b"Treasury".to_vec(),
+        description: b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpH".to_vec(),
+        content_hash: Some(H256::repeat_byte(1)),
+    }
+}
+
+fn expected_deposit(calls: &[Call], metadata: &ProposalMetadata<H256>) -> Balance {
+    let encoded_calls: Vec<Vec<u8>> = calls.iter().map(|call| call.encode()).collect();
+    ProposalDepositPerByte::get()
+        * (encoded_calls.encoded_size() + metadata.encoded_size()) as Balance
+}
+
+#[test]
+fn create_proposal_stores_metadata_and_reserves_deposit() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal = make_proposal();
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
+            let deposit = expected_deposit(&proposal, &mock_metadata());
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                proposal,
+                ExecutionMode::AllOrNothing,
+                mock_metadata()
+            ));
+
+            let stored = Organizations::proposals(proposal_id).unwrap();
+            assert_eq!(stored.metadata, mock_metadata());
+            assert_eq!(stored.proposer, ALICE);
+            assert_eq!(stored.storage_deposit, deposit);
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE), deposit);
+            assert!(
+                organizations_events().contains(&RawEvent::ProposalSubmitted(
+                    org_id,
+                    proposal_id,
+                    mock_metadata()
+                ))
+            );
+        })
+}
+
+#[test]
+fn create_proposal_fail_if_metadata_too_long() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let mut metadata = mock_metadata();
+            metadata.title = vec![0; MaxTitleLength::get() as usize + 1];
+            assert_noop!(
+                Organizations::create_proposal(
+                    RawOrigin::Signed(ALICE).into(),
+                    Organizations::org_id_for(0),
+                    make_proposal(),
+                    ExecutionMode::AllOrNothing,
+                    metadata
+                ),
+                Error::<Test>::TitleTooLong
+            );
+
+            let mut metadata = mock_metadata();
+            metadata.description = vec![0; MaxDescriptionLength::get() as usize + 1];
+            assert_noop!(
+                Organizations::create_proposal(
+                    RawOrigin::Signed(ALICE).into(),
+                    Organizations::org_id_for(0),
+                    make_proposal(),
+                    ExecutionMode::AllOrNothing,
+                    metadata
+                ),
+                Error::<Test>::DescriptionTooLong
+            );
+        })
+}
+
+#[test]
+fn create_proposal_fail_if_deposit_can_not_be_paid() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Organizations::create_proposal(
+                    RawOrigin::Signed(BOB).into(),
+                    Organizations::org_id_for(0),
+                    make_proposal(),
+                    ExecutionMode::AllOrNothing,
+                    mock_metadata()
+                ),
+                Error::<Test>::InsufficientDepositBalance
+            );
+        })
+}
+
+#[test]
+fn close_proposal_returns_deposit() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            create_and_close(make_proposal(), ExecutionMode::AllOrNothing);
+
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE), 0);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), ALICE_BALANCE);
+        })
+}
+
+#[test]
+fn veto_proposal_returns_deposit() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal = make_proposal();
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                proposal,
+                ExecutionMode::AllOrNothing,
+                mock_metadata()
+            ));
+            assert_ok!(Organizations::veto_proposal(
+                RawOrigin::Signed(org_id).into(),
+                proposal_id
+            ));
+
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE), 0);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), ALICE_BALANCE);
+        })
+}
+
+#[test]
+fn create_proposal_reserves_organization_deposit() {
+    ExtBuilder::default()
+        .with_deposit_org(100)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal = make_proposal();
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
+            let storage_deposit = expected_deposit(&proposal, &mock_metadata());
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                proposal,
+                ExecutionMode::AllOrNothing,
+                mock_metadata()
+            ));
+
+            assert_eq!(
+                Organizations::proposals(proposal_id)
+                    .unwrap()
+                    .proposal_deposit,
+                Some(ProposalDeposit {
+                    currency_id: TEST_TOKEN_ID,
+                    amount: 100
+                })
+            );
+            assert_eq!(
+                Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE),
+                storage_deposit + 100
+            );
+        })
+}
+
+#[test]
+fn create_proposal_fail_if_organization_deposit_can_not_be_paid() {
+    ExtBuilder::default()
+        .with_deposit_org(ALICE_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Organizations::create_proposal(
+                    RawOrigin::Signed(ALICE).into(),
+                    Organizations::org_id_for(0),
+                    make_proposal(),
+                    ExecutionMode::AllOrNothing,
+                    mock_metadata()
+                ),
+                Error::<Test>::InsufficientDepositBalance
+            );
+        })
+}
+
+#[test]
+fn close_proposal_returns_organization_deposit() {
+    ExtBuilder::default()
+        .with_deposit_org(100)
+        .build()
+        .execute_with(|| {
+            create_and_close(make_proposal(), ExecutionMode::AllOrNothing);
+
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE), 0);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), ALICE_BALANCE);
+        })
+}
+
+#[test]
+fn veto_proposal_gives_organization_deposit_to_the_organization() {
+    ExtBuilder::default()
+        .with_deposit_org(100)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal = make_proposal();
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                proposal,
+                ExecutionMode::AllOrNothing,
+                mock_metadata()
+            ));
+            assert_ok!(Organizations::veto_proposal(
+                RawOrigin::Signed(org_id).into(),
+                proposal_id
+            ));
+
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE), 0);
+            assert_eq!(
+                Tokens::free_balance(TEST_TOKEN_ID, &ALICE),
+                ALICE_BALANCE - 100
+            );
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &org_id), 100);
+            assert!(
+                organizations_events().contains(&RawEvent::ProposalDepositRepatriated(
+                    proposal_id,
+                    ALICE,
+                    100
+                ))
+            );
+        })
+}
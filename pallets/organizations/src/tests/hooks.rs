@@ -0,0 +1,259 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::{
+    make_call, make_proposal, proposal_weight, Call, Event, ExtBuilder, MaxAutoCloseWeight,
+    MaxVotes, MockVotingSystemId, Organizations, System, Test, MOCK_VOTING_TTL,
+};
+use crate::{ExecutionMode, ExpiryCursor, ExpiryQueue, Proposal, Proposals, RawEvent, WeightInfo};
+use codec::Encode;
+use frame_support::{assert_ok, traits::OnInitialize, StorageMap, StorageValue};
+use frame_system::RawOrigin;
+use governance_os_support::testing::{primitives::BlockNumber, ALICE};
+
+const EXPIRED_ON: BlockNumber = 1 + MOCK_VOTING_TTL + 1;
+
+fn close_weight(calls: &[Call]) -> u64 {
+    <() as WeightInfo>::close_proposal(MaxVotes::get(), MaxVotes::get())
+        .saturating_add(proposal_weight(calls))
+}
+
+fn other_proposal() -> Vec<Call> {
+    vec![Call::System(frame_system::Call::remark(vec![42]))]
+}
+
+#[test]
+fn create_proposal_registers_expiry() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal = make_proposal();
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                proposal,
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+
+            assert_eq!(Organizations::expiry_queue(EXPIRED_ON), vec![proposal_id]);
+        })
+}
+
+#[test]
+fn on_initialize_closes_expired_proposals() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal = make_proposal();
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                proposal,
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+
+            Organizations::on_initialize(EXPIRED_ON - 1);
+            assert!(Proposals::<Test>::contains_key(proposal_id));
+
+            Organizations::on_initialize(EXPIRED_ON);
+            assert!(!Proposals::<Test>::contains_key(proposal_id));
+            assert!(!ExpiryQueue::<Test>::contains_key(EXPIRED_ON));
+            assert_eq!(Organizations::expiry_cursor(), Some(EXPIRED_ON + 1));
+        })
+}
+
+#[test]
+fn on_initialize_carries_over_what_does_not_fit() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let first_id = Organizations::proposal_id(&org_id, &make_proposal());
+            let second_id = Organizations::proposal_id(&org_id, &other_proposal());
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                make_proposal(),
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                other_proposal(),
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+
+            // Enough for one proposal but not two
+            MaxAutoCloseWeight::set(close_weight(&other_proposal()) * 3 / 2);
+
+            Organizations::on_initialize(EXPIRED_ON);
+            assert!(!Proposals::<Test>::contains_key(first_id));
+            assert!(Proposals::<Test>::contains_key(second_id));
+            assert_eq!(Organizations::expiry_queue(EXPIRED_ON), vec![second_id]);
+            assert_eq!(Organizations::expiry_cursor(), Some(EXPIRED_ON));
+
+            Organizations::on_initialize(EXPIRED_ON + 1);
+            assert!(!Proposals::<Test>::contains_key(second_id));
+            assert!(!ExpiryQueue::<Test>::contains_key(EXPIRED_ON));
+            assert_eq!(Organizations::expiry_cursor(), Some(EXPIRED_ON + 2));
+        })
+}
+
+#[test]
+fn on_initialize_skips_closed_proposals() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal = make_proposal();
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                proposal.clone(),
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+            assert_ok!(Organizations::close_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                proposal_id,
+                proposal_weight(&proposal)
+            ));
+            assert!(!ExpiryQueue::<Test>::contains_key(EXPIRED_ON));
+
+            Organizations::on_initialize(EXPIRED_ON);
+            assert_eq!(Organizations::expiry_cursor(), Some(EXPIRED_ON + 1));
+        })
+}
+
+#[test]
+fn on_initialize_drops_proposals_heavier_than_budget() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal = make_proposal();
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                proposal.clone(),
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+
+            MaxAutoCloseWeight::set(close_weight(&proposal) - 1);
+
+            Organizations::on_initialize(EXPIRED_ON);
+            assert!(Proposals::<Test>::contains_key(proposal_id));
+            assert!(!ExpiryQueue::<Test>::contains_key(EXPIRED_ON));
+        })
+}
+
+#[test]
+fn on_initialize_drops_proposals_failing_to_close() {
+    ExtBuilder::default().build().execute_with(|| {
+        let org_id = Organizations::org_id_for(0);
+        let proposal_id = Organizations::proposal_id(&org_id, &make_proposal());
+
+        // We have to insert a fake proposal and bypass the hook for the `None` voting system
+        Proposals::<Test>::insert(
+            proposal_id,
+            Proposal {
+                org: org_id,
+                calls: vec![make_call().encode()],
+                mode: ExecutionMode::AllOrNothing,
+                voting: MockVotingSystemId::FailClose,
+                metadata: Default::default(),
+                proposer: ALICE,
+                storage_deposit: 0,
+                proposal_deposit: None,
+            },
+        );
+        ExpiryQueue::<Test>::insert(EXPIRED_ON, vec![proposal_id]);
+
+        Organizations::on_initialize(EXPIRED_ON);
+        assert!(Proposals::<Test>::contains_key(proposal_id));
+        assert!(!ExpiryQueue::<Test>::contains_key(EXPIRED_ON));
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            Event::governance_os_pallet_organizations(RawEvent::ProposalAutoCloseFailed(id, _))
+                if id == proposal_id
+        )));
+    })
+}
+
+#[test]
+fn on_initialize_catches_up_with_late_blocks() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let first_id = Organizations::proposal_id(&org_id, &make_proposal());
+            let second_id = Organizations::proposal_id(&org_id, &other_proposal());
+
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                make_proposal(),
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                other_proposal(),
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+            // Pretend the second proposal expires one block later and that we did not
+            // process any block yet
+            ExpiryQueue::<Test>::insert(EXPIRED_ON, vec![first_id]);
+            ExpiryQueue::<Test>::insert(EXPIRED_ON + 1, vec![second_id]);
+            ExpiryCursor::<Test>::put(EXPIRED_ON);
+
+            // Enough for one proposal but not two
+            MaxAutoCloseWeight::set(close_weight(&other_proposal()) * 3 / 2);
+            Organizations::on_initialize(EXPIRED_ON + 1);
+            assert!(!Proposals::<Test>::contains_key(first_id));
+            assert!(Proposals::<Test>::contains_key(second_id));
+            assert_eq!(Organizations::expiry_cursor(), Some(EXPIRED_ON + 1));
+
+            Organizations::on_initialize(EXPIRED_ON + 2);
+            assert!(!Proposals::<Test>::contains_key(second_id));
+            assert!(!ExpiryQueue::<Test>::contains_key(EXPIRED_ON + 1));
+            assert_eq!(Organizations::expiry_cursor(), Some(EXPIRED_ON + 3));
+        })
+}
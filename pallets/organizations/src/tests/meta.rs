@@ -14,7 +14,12 @@
  * limitations under the License.
  */
 
-use super::mock::Organizations;
+use super::mock::{make_proposal, ExtBuilder, Organizations};
+use crate::ExecutionMode;
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+use governance_os_support::{testing::ALICE, traits::ProposalResult};
+use sp_core::H256;
 use sp_std::collections::btree_set::BTreeSet;
 
 #[test]
@@ -28,3 +33,35 @@ fn org_ids_are_different() {
         all_ids.insert(id);
     }
 }
+
+#[test]
+fn proposal_tally_routes_to_voting_system() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let proposal = make_proposal();
+            let proposal_id = Organizations::proposal_id(&org_id, &proposal);
+
+            assert_eq!(Organizations::proposal_tally(proposal_id), None);
+            assert_ok!(Organizations::create_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                proposal,
+                ExecutionMode::AllOrNothing,
+                Default::default()
+            ));
+            assert_eq!(
+                Organizations::proposal_tally(proposal_id),
+                Some(ProposalResult::Passing)
+            );
+        })
+}
+
+#[test]
+fn proposal_tally_is_none_for_unknown_proposals() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(Organizations::proposal_tally(H256::default()), None);
+    })
+}
@@ -15,8 +15,8 @@
  */
 
 use crate::{
-    self as governance_os_pallet_organizations, OrganizationDetails, OrganizationDetailsOf,
-    VotingRouter,
+    self as governance_os_pallet_organizations, Error, Guardians, OrganizationDetails,
+    OrganizationDetailsOf, ProposalDeposit, RawEvent, VotingRouter,
 };
 use codec::{Decode, Encode};
 use frame_support::{
@@ -24,18 +24,24 @@ use frame_support::{
     dispatch::{DispatchError, DispatchResult},
     parameter_types,
     traits::GenesisBuild,
+    weights::{GetDispatchInfo, IdentityFee, Weight},
 };
+use governance_os_pallet_tokens::{CurrencyDetails, NativeCurrencyAdapter};
 use governance_os_support::{
     impl_enum_default,
     testing::{
-        primitives::{AccountId, BlockNumber},
-        ALICE,
+        primitives::{AccountId, Balance, BlockNumber, CurrencyId},
+        AutoCurrencyIds, ALICE, BOB, CHARLIE, TEST_TOKEN_ID, TEST_TOKEN_OWNER,
     },
-    traits::ProposalResult,
+    traits::{Currencies, ProposalResult},
 };
 use serde::{Deserialize, Serialize};
 use sp_core::H256;
-use sp_runtime::{testing::Header, traits::IdentityLookup, RuntimeDebug};
+use sp_runtime::{
+    testing::Header,
+    traits::{IdentityLookup, Zero},
+    RuntimeDebug,
+};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -48,7 +54,9 @@ construct_runtime!(
     {
         System: frame_system::{Module, Call, Config, Storage, Event<T>},
         Bylaws: governance_os_pallet_bylaws::{Module, Call, Storage, Event<T>},
+        Tokens: governance_os_pallet_tokens::{Module, Call, Storage, Event<T>},
         Organizations: governance_os_pallet_organizations::{Module, Call, Storage, Event<T>},
+        TransactionPayment: pallet_transaction_payment::{Module, Storage},
     }
 );
 
@@ -100,6 +108,10 @@ pub enum MockRoles {
     RemarkOnly,
     CreateOrganizations,
     ApplyAsOrganization(AccountId),
+    CreateCurrencies,
+    TransferCurrency(CurrencyId),
+    ReceiveCurrency(CurrencyId),
+    ManageCurrency(CurrencyId),
 }
 impl_enum_default!(MockRoles, RemarkOnly);
 impl governance_os_pallet_bylaws::RoleBuilder for MockRoles {
@@ -117,6 +129,7 @@ impl governance_os_pallet_bylaws::RoleBuilder for MockRoles {
 parameter_types! {
     pub const RootRole: MockRoles = MockRoles::Root;
     pub const MaxRoles: u32 = 5;
+    pub const MaxRoleAncestors: u32 = 4;
 }
 
 impl governance_os_pallet_bylaws::Config for Test {
@@ -124,14 +137,77 @@ impl governance_os_pallet_bylaws::Config for Test {
     type Role = MockRoles;
     type WeightInfo = ();
     type MaxRoles = MaxRoles;
+    type MaxRoleAncestors = MaxRoleAncestors;
     type RoleBuilder = MockRoles;
 }
 
+impl governance_os_pallet_tokens::RoleBuilder for MockRoles {
+    type CurrencyId = CurrencyId;
+    type Role = Self;
+
+    fn transfer_currency(id: CurrencyId) -> Self {
+        Self::TransferCurrency(id)
+    }
+
+    fn receive_currency(id: CurrencyId) -> Self {
+        Self::ReceiveCurrency(id)
+    }
+
+    fn manage_currency(id: CurrencyId) -> Self {
+        Self::ManageCurrency(id)
+    }
+
+    fn create_currencies() -> Self {
+        Self::CreateCurrencies
+    }
+
+    fn manage_fee_rates() -> Self {
+        Self::Root
+    }
+}
+
 parameter_types! {
+    pub const MaxCurrencyIdSkips: u32 = 16;
+    pub const MaxCheckpoints: u32 = 16;
+}
+
+impl governance_os_pallet_tokens::Config for Test {
+    type Event = Event;
+    type CurrencyId = CurrencyId;
+    type Balance = Balance;
+    type WeightInfo = ();
+    type NativeCurrencyId = ();
+    type NativeExistentialDeposit = ();
+    type AutoCurrencyId = AutoCurrencyIds;
+    type MaxCurrencyIdSkips = MaxCurrencyIdSkips;
+    type CurrencyDeposit = ();
+    type MetadataDepositPerByte = ();
+    type MaxMetadataLength = ();
+    type MaxIssuanceSchedules = ();
+    type MaxBatchSize = ();
+    type MaxCheckpoints = MaxCheckpoints;
+    type OnDust = ();
+    type OnBalanceChanged = ();
+    type RoleManager = Bylaws;
+    type RoleBuilder = MockRoles;
+}
+
+parameter_types! {
+    pub const DepositCurrencyId: CurrencyId = TEST_TOKEN_ID;
+    pub const ProposalDepositPerByte: Balance = 1;
+    pub const MaxTitleLength: u32 = 16;
+    pub const MaxDescriptionLength: u32 = 32;
     pub const MaxVotes: u32 = 100;
     pub const MaxExecutors: u32 = 100;
+    pub const MaxGuardians: u32 = 100;
+    pub const MaxSpendingLimits: u32 = 2;
+    pub const MaxSponsoredCallsPerProposal: u32 = 2;
+    pub static MaxAutoCloseWeight: Weight = Weight::max_value();
 }
 
+/// Number of blocks the mock voting systems accept votes for.
+pub const MOCK_VOTING_TTL: BlockNumber = 10;
+
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, Serialize, Deserialize)]
 pub enum MockVotingSystemId {
     WithResult(ProposalResult),
@@ -148,10 +224,13 @@ impl VotingRouter for MockVotingRouter {
     type Parameters = ();
     type ProposalId = H256;
     type VoteData = ();
+    type Tally = ProposalResult;
+    type BlockNumber = BlockNumber;
 
     fn initiate(
         voting_system: Self::VotingSystemId,
         _proposal: Self::ProposalId,
+        _organization: &Self::AccountId,
         _parameters: Self::Parameters,
     ) -> DispatchResult {
         match voting_system {
@@ -189,6 +268,36 @@ impl VotingRouter for MockVotingRouter {
             _ => Ok(ProposalResult::Passing),
         }
     }
+
+    fn tally(
+        voting_system: Self::VotingSystemId,
+        _proposal: Self::ProposalId,
+    ) -> Option<Self::Tally> {
+        match voting_system {
+            MockVotingSystemId::WithResult(res) => Some(res),
+            _ => None,
+        }
+    }
+
+    fn expiry(
+        _voting_system: Self::VotingSystemId,
+        _proposal: Self::ProposalId,
+    ) -> Option<Self::BlockNumber> {
+        // Tests manually close proposals in the block they were created in
+        Some(System::block_number() + MOCK_VOTING_TTL)
+    }
+
+    fn can_vote(
+        _voting_system: Self::VotingSystemId,
+        _proposal: Self::ProposalId,
+        voter: &Self::AccountId,
+    ) -> bool {
+        !Tokens::free_balance(TEST_TOKEN_ID, voter).is_zero()
+    }
+
+    fn vote_weight() -> Weight {
+        0
+    }
 }
 
 impl governance_os_pallet_organizations::RoleBuilder for MockRoles {
@@ -204,20 +313,48 @@ impl governance_os_pallet_organizations::RoleBuilder for MockRoles {
     }
 }
 
+pub type NativeCurrency = NativeCurrencyAdapter<Test, DepositCurrencyId>;
+
+parameter_types! {
+    pub const TransactionByteFee: Balance = 1;
+}
+
+impl pallet_transaction_payment::Config for Test {
+    type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<NativeCurrency, ()>;
+    type TransactionByteFee = TransactionByteFee;
+    type WeightToFee = IdentityFee<Balance>;
+    type FeeMultiplierUpdate = ();
+}
+
 impl governance_os_pallet_organizations::Config for Test {
     type Event = Event;
     type Call = Call;
     type RoleManager = Bylaws;
     type RoleBuilder = MockRoles;
     type VotingRouter = MockVotingRouter;
+    type Currencies = Tokens;
+    type DepositCurrencyId = DepositCurrencyId;
+    type ProposalDepositPerByte = ProposalDepositPerByte;
+    type MaxTitleLength = MaxTitleLength;
+    type MaxDescriptionLength = MaxDescriptionLength;
     type MaxVotes = MaxVotes;
     type MaxExecutors = MaxExecutors;
+    type MaxGuardians = MaxGuardians;
+    type MaxSpendingLimits = MaxSpendingLimits;
+    type MaxAutoCloseWeight = MaxAutoCloseWeight;
+    type MaxSponsoredCallsPerProposal = MaxSponsoredCallsPerProposal;
+    type NativeCurrency = NativeCurrency;
+    type OnSponsoredFees = ();
     type WeightInfo = ();
 }
 
+/// Balance of `ALICE` at genesis, used to pay for proposal deposits.
+pub const ALICE_BALANCE: Balance = 1_000;
+
 pub struct ExtBuilder {
     can_create: Vec<AccountId>,
     orgs: Vec<OrganizationDetailsOf<Test>>,
+    endowments: Vec<(AccountId, Balance)>,
 }
 
 impl Default for ExtBuilder {
@@ -225,6 +362,7 @@ impl Default for ExtBuilder {
         Self {
             can_create: vec![],
             orgs: vec![],
+            endowments: vec![],
         }
     }
 }
@@ -244,10 +382,54 @@ impl ExtBuilder {
         self.orgs.push(OrganizationDetails {
             executors: vec![],
             voting: (MockVotingSystemId::WithResult(ProposalResult::Passing), ()),
+            guardians: None,
+            proposal_deposit: None,
         });
         self
     }
 
+    pub fn with_guarded_org(mut self) -> Self {
+        self.orgs.push(OrganizationDetails {
+            executors: vec![],
+            voting: (MockVotingSystemId::WithResult(ProposalResult::Passing), ()),
+            guardians: Some(Guardians {
+                accounts: vec![ALICE, BOB, CHARLIE],
+                threshold: 2,
+            }),
+            proposal_deposit: None,
+        });
+        self
+    }
+
+    pub fn with_deposit_org(self, amount: Balance) -> Self {
+        self.with_org(OrganizationDetails {
+            executors: vec![],
+            voting: (MockVotingSystemId::WithResult(ProposalResult::Passing), ()),
+            guardians: None,
+            proposal_deposit: Some(ProposalDeposit {
+                currency_id: TEST_TOKEN_ID,
+                amount,
+            }),
+        })
+    }
+
+    /// Create an organization with `ALICE` as executor and `balance` tokens in its treasury.
+    pub fn with_treasury_org(self, balance: Balance) -> Self {
+        let org_id = Organizations::org_id_for(self.orgs.len() as u32);
+        self.with_endowment(org_id, balance)
+            .with_org(OrganizationDetails {
+                executors: vec![ALICE],
+                voting: (MockVotingSystemId::WithResult(ProposalResult::Passing), ()),
+                guardians: None,
+                proposal_deposit: None,
+            })
+    }
+
+    pub fn with_endowment(mut self, who: AccountId, balance: Balance) -> Self {
+        self.endowments.push((who, balance));
+        self
+    }
+
     pub fn build(self) -> sp_io::TestExternalities {
         let mut t = frame_system::GenesisConfig::default()
             .build_storage::<Test>()
@@ -259,6 +441,29 @@ impl ExtBuilder {
                 .into_iter()
                 .map(|account| (MockRoles::CreateOrganizations, Some(account)))
                 .collect::<Vec<_>>(),
+            role_settings: vec![],
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        governance_os_pallet_tokens::GenesisConfig::<Test> {
+            endowed_accounts: vec![(ALICE, ALICE_BALANCE)]
+                .into_iter()
+                .chain(self.endowments)
+                .map(|(who, balance)| (TEST_TOKEN_ID, who, balance))
+                .collect(),
+            currency_details: vec![(
+                TEST_TOKEN_ID,
+                CurrencyDetails {
+                    owner: TEST_TOKEN_OWNER,
+                    transferable: true,
+                    restricted_receivers: false,
+                    existential_deposit: 0,
+                    max_supply: None,
+                    issuance: None,
+                },
+                None,
+            )],
         }
         .assimilate_storage(&mut t)
         .unwrap();
@@ -275,6 +480,39 @@ impl ExtBuilder {
     }
 }
 
-pub fn make_proposal() -> Box<Call> {
-    Box::new(Call::System(frame_system::Call::remark(vec![])))
+pub fn make_call() -> Call {
+    Call::System(frame_system::Call::remark(vec![]))
+}
+
+pub fn make_proposal() -> Vec<Call> {
+    vec![make_call()]
+}
+
+pub fn proposal_weight(calls: &[Call]) -> Weight {
+    calls
+        .iter()
+        .map(|call| call.get_dispatch_info().weight)
+        .sum()
+}
+
+/// Whether an `OrganizationExecuted` event reports `expected`. Error messages are not kept
+/// in the events so we only compare the module and error indexes.
+pub fn execution_failed_with(expected: Error<Test>) -> bool {
+    let expected: DispatchError = expected.into();
+    System::events()
+        .iter()
+        .any(|record| match (&record.event, expected) {
+            (
+                Event::governance_os_pallet_organizations(RawEvent::OrganizationExecuted(
+                    _,
+                    Err(DispatchError::Module { index, error, .. }),
+                )),
+                DispatchError::Module {
+                    index: expected_index,
+                    error: expected_error,
+                    ..
+                },
+            ) => *index == expected_index && *error == expected_error,
+            _ => false,
+        })
 }
@@ -17,5 +17,8 @@
 mod details;
 mod dispatchable;
 mod genesis;
+mod hooks;
 mod meta;
 pub mod mock;
+mod sponsorship;
+mod treasury;
@@ -0,0 +1,376 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::{
+    execution_failed_with, make_call, make_proposal, proposal_weight, Call, Event, ExtBuilder,
+    Organizations, System, Test, Tokens, TransactionPayment,
+};
+use crate::{
+    ChargeSponsoredTransaction, Error, ExecutionMode, RawEvent, SponsoredCall, Sponsorship,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::DispatchResult,
+    weights::{GetDispatchInfo, PostDispatchInfo},
+};
+use frame_system::RawOrigin;
+use governance_os_support::{
+    testing::{
+        primitives::{AccountId, Balance},
+        ALICE, BOB, CHARLIE, TEST_TOKEN_ID,
+    },
+    traits::Currencies,
+};
+use sp_core::H256;
+use sp_runtime::{
+    traits::SignedExtension,
+    transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+
+/// Enough to pay for the fees of a few transactions.
+const FUNDS: Balance = 1_000_000_000_000;
+/// Enough to vote but not to pay for any fee.
+const VOTER_FUNDS: Balance = 1;
+const LEN: usize = 10;
+
+fn sponsorship(calls_per_account: u32, budget: Balance) -> Sponsorship<Balance, u64> {
+    Sponsorship {
+        calls: vec![SponsoredCall::DecideOnProposal],
+        calls_per_account,
+        budget,
+        period: 10,
+    }
+}
+
+fn sponsor(org_id: AccountId, calls_per_account: u32, budget: Balance) {
+    assert_ok!(Organizations::set_sponsorship(
+        RawOrigin::Signed(org_id).into(),
+        Some(sponsorship(calls_per_account, budget))
+    ));
+}
+
+fn create_proposal(org_id: AccountId) -> H256 {
+    let proposal = make_proposal();
+    let proposal_id = Organizations::proposal_id(&org_id, &proposal);
+    assert_ok!(Organizations::create_proposal(
+        RawOrigin::Signed(ALICE).into(),
+        org_id,
+        proposal,
+        ExecutionMode::AllOrNothing,
+        Default::default()
+    ));
+    proposal_id
+}
+
+fn vote_call(proposal_id: H256) -> Call {
+    Call::Organizations(crate::Call::decide_on_proposal(proposal_id, ()))
+}
+
+fn fee_of(call: &Call) -> Balance {
+    TransactionPayment::compute_fee(LEN as u32, &call.get_dispatch_info(), 0)
+}
+
+fn charge(
+    who: AccountId,
+    call: &Call,
+) -> Result<<ChargeSponsoredTransaction<Test> as SignedExtension>::Pre, TransactionValidityError> {
+    ChargeSponsoredTransaction::<Test>::from(0).pre_dispatch(
+        &who,
+        call,
+        &call.get_dispatch_info(),
+        LEN,
+    )
+}
+
+/// Charge `call` to `who` and settle it as if it dispatched with `result`.
+fn charge_and_dispatch(who: AccountId, call: &Call, result: DispatchResult) {
+    let info = call.get_dispatch_info();
+    let pre = charge(who, call).unwrap();
+    assert_ok!(ChargeSponsoredTransaction::<Test>::post_dispatch(
+        pre,
+        &info,
+        &PostDispatchInfo::default(),
+        LEN,
+        &result
+    ));
+}
+
+#[test]
+fn set_sponsorship_works() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            sponsor(org_id, 2, 100);
+            assert_eq!(
+                Organizations::sponsorships(org_id),
+                Some(sponsorship(2, 100))
+            );
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_organizations(RawEvent::SponsorshipSet(
+                    org_id,
+                    Some(sponsorship(2, 100))
+                ))));
+
+            assert_ok!(Organizations::set_sponsorship(
+                RawOrigin::Signed(org_id).into(),
+                None
+            ));
+            assert_eq!(Organizations::sponsorships(org_id), None);
+        })
+}
+
+#[test]
+fn set_sponsorship_checks_origin_and_period() {
+    ExtBuilder::default()
+        .with_default_org()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Organizations::set_sponsorship(
+                    RawOrigin::Signed(ALICE).into(),
+                    Some(sponsorship(1, 100))
+                ),
+                Error::<Test>::NotAnOrganization
+            );
+
+            let mut invalid = sponsorship(1, 100);
+            invalid.period = 0;
+            assert_noop!(
+                Organizations::set_sponsorship(
+                    RawOrigin::Signed(Organizations::org_id_for(0)).into(),
+                    Some(invalid)
+                ),
+                Error::<Test>::InvalidSponsorship
+            );
+        })
+}
+
+#[test]
+fn apply_as_can_not_change_sponsorships() {
+    ExtBuilder::default()
+        .with_treasury_org(FUNDS)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            assert_ok!(Organizations::apply_as(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                Box::new(Call::Organizations(crate::Call::set_sponsorship(Some(
+                    sponsorship(5, FUNDS)
+                ))))
+            ));
+
+            assert_eq!(Organizations::sponsorships(org_id), None);
+            assert!(execution_failed_with(
+                Error::<Test>::ExecutorsCannotChangeLimits
+            ));
+        })
+}
+
+#[test]
+fn sponsored_votes_are_paid_by_the_organization() {
+    ExtBuilder::default()
+        .with_treasury_org(FUNDS)
+        .with_endowment(BOB, VOTER_FUNDS)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            sponsor(org_id, 1, FUNDS);
+            let call = vote_call(create_proposal(org_id));
+            let fee = fee_of(&call);
+
+            let pre = charge(BOB, &call).unwrap();
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &org_id), FUNDS - fee);
+            // Calls are only counted once they succeed
+            assert_eq!(Organizations::sponsored_calls(org_id, BOB), (0, 0));
+
+            // The organization is refunded if the call used less weight than expected
+            let info = call.get_dispatch_info();
+            let post_info = PostDispatchInfo {
+                actual_weight: Some(0),
+                pays_fee: Default::default(),
+            };
+            let actual_fee =
+                TransactionPayment::compute_actual_fee(LEN as u32, &info, &post_info, 0);
+            assert_ok!(ChargeSponsoredTransaction::<Test>::post_dispatch(
+                pre,
+                &info,
+                &post_info,
+                LEN,
+                &Ok(())
+            ));
+            assert_eq!(Organizations::sponsored_calls(org_id, BOB), (1, 1));
+            assert_eq!(
+                Tokens::free_balance(TEST_TOKEN_ID, &org_id),
+                FUNDS - actual_fee
+            );
+            assert_eq!(
+                Organizations::sponsored_spendings(org_id).unwrap().spent,
+                actual_fee
+            );
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_organizations(RawEvent::TransactionSponsored(
+                    org_id, BOB, actual_fee
+                ))));
+        })
+}
+
+#[test]
+fn signers_pay_once_their_quota_is_used() {
+    ExtBuilder::default()
+        .with_treasury_org(FUNDS)
+        .with_endowment(BOB, VOTER_FUNDS)
+        .with_endowment(CHARLIE, FUNDS)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            sponsor(org_id, 1, FUNDS);
+            let call = vote_call(create_proposal(org_id));
+            let fee = fee_of(&call);
+
+            charge_and_dispatch(BOB, &call, Ok(()));
+            assert_eq!(
+                charge(BOB, &call).err(),
+                Some(TransactionValidityError::Invalid(
+                    InvalidTransaction::Payment
+                ))
+            );
+
+            charge_and_dispatch(CHARLIE, &call, Ok(()));
+            assert!(charge(CHARLIE, &call).is_ok());
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &CHARLIE), FUNDS - fee);
+            assert_eq!(
+                Tokens::free_balance(TEST_TOKEN_ID, &org_id),
+                FUNDS - 2 * fee
+            );
+        })
+}
+
+#[test]
+fn budget_is_reset_every_period() {
+    ExtBuilder::default()
+        .with_treasury_org(FUNDS)
+        .with_endowment(BOB, VOTER_FUNDS)
+        .with_endowment(CHARLIE, VOTER_FUNDS)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            let call = vote_call(create_proposal(org_id));
+            let fee = fee_of(&call);
+            sponsor(org_id, 5, fee);
+
+            assert!(charge(BOB, &call).is_ok());
+            assert!(charge(CHARLIE, &call).is_err());
+
+            System::set_block_number(11);
+            assert!(charge(CHARLIE, &call).is_ok());
+            assert_eq!(
+                Tokens::free_balance(TEST_TOKEN_ID, &org_id),
+                FUNDS - 2 * fee
+            );
+        })
+}
+
+#[test]
+fn other_calls_are_not_sponsored() {
+    ExtBuilder::default()
+        .with_treasury_org(FUNDS)
+        .with_endowment(BOB, VOTER_FUNDS)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            sponsor(org_id, 5, FUNDS);
+            let proposal_id = create_proposal(org_id);
+
+            let close_call = Call::Organizations(crate::Call::close_proposal(proposal_id, 0));
+            assert!(charge(BOB, &close_call).is_err());
+            assert!(charge(BOB, &make_call()).is_err());
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &org_id), FUNDS);
+        })
+}
+
+#[test]
+fn accounts_that_can_not_vote_are_not_sponsored() {
+    ExtBuilder::default()
+        .with_treasury_org(FUNDS)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            sponsor(org_id, 5, FUNDS);
+            let call = vote_call(create_proposal(org_id));
+
+            assert!(charge(BOB, &call).is_err());
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &org_id), FUNDS);
+        })
+}
+
+#[test]
+fn sponsored_calls_are_capped_per_proposal() {
+    ExtBuilder::default()
+        .with_treasury_org(FUNDS)
+        .with_endowment(BOB, VOTER_FUNDS)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            sponsor(org_id, 5, FUNDS);
+            let proposal_id = create_proposal(org_id);
+            let call = vote_call(proposal_id);
+
+            // The mock runtime sponsors up to two calls per account and proposal
+            charge_and_dispatch(BOB, &call, Ok(()));
+            charge_and_dispatch(BOB, &call, Ok(()));
+            assert!(charge(BOB, &call).is_err());
+            assert_eq!(Organizations::sponsored_proposal_calls(proposal_id, BOB), 2);
+
+            // The count is removed along with the proposal
+            assert_ok!(Organizations::close_proposal(
+                RawOrigin::Signed(ALICE).into(),
+                proposal_id,
+                proposal_weight(&make_proposal())
+            ));
+            assert_eq!(Organizations::sponsored_proposal_calls(proposal_id, BOB), 0);
+        })
+}
+
+#[test]
+fn failed_calls_are_not_charged_nor_counted() {
+    ExtBuilder::default()
+        .with_treasury_org(FUNDS)
+        .with_endowment(BOB, VOTER_FUNDS)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            sponsor(org_id, 1, FUNDS);
+            let proposal_id = create_proposal(org_id);
+            let call = vote_call(proposal_id);
+
+            charge_and_dispatch(BOB, &call, Err(Error::<Test>::ProposalNotFound.into()));
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &org_id), FUNDS);
+            assert_eq!(Organizations::sponsored_spendings(org_id).unwrap().spent, 0);
+            assert_eq!(Organizations::sponsored_calls(org_id, BOB), (0, 0));
+            assert_eq!(Organizations::sponsored_proposal_calls(proposal_id, BOB), 0);
+            assert!(!System::events().iter().any(|record| matches!(
+                record.event,
+                Event::governance_os_pallet_organizations(RawEvent::TransactionSponsored(..))
+            )));
+
+            // The quota is still available
+            charge_and_dispatch(BOB, &call, Ok(()));
+            assert_eq!(Organizations::sponsored_calls(org_id, BOB), (1, 1));
+        })
+}
@@ -0,0 +1,467 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::{
+    execution_failed_with, Call, Event, ExtBuilder, Organizations, System, Test, Tokens,
+};
+use crate::{Error, RawEvent, Spending, SpendingLimit};
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use governance_os_support::{
+    errors::AclError,
+    testing::{ALICE, BOB, TEST_TOKEN_ID},
+    traits::Currencies,
+};
+
+fn transfer_call(amount: u128) -> Box<Call> {
+    Box::new(Call::Tokens(governance_os_pallet_tokens::Call::transfer(
+        TEST_TOKEN_ID,
+        BOB,
+        amount,
+    )))
+}
+
+fn limit_to(amount: u128) -> SpendingLimit<u128, u64> {
+    SpendingLimit { amount, period: 10 }
+}
+
+#[test]
+fn set_spending_limit_saves_limit_and_resets_spendings() {
+    ExtBuilder::default()
+        .with_treasury_org(100)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            assert_ok!(Organizations::set_spending_limit(
+                RawOrigin::Signed(org_id).into(),
+                TEST_TOKEN_ID,
+                Some(limit_to(50))
+            ));
+            assert_ok!(Organizations::apply_as(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                transfer_call(20)
+            ));
+            assert_eq!(
+                Organizations::spendings(org_id, TEST_TOKEN_ID)
+                    .unwrap()
+                    .spent,
+                20
+            );
+
+            assert_ok!(Organizations::set_spending_limit(
+                RawOrigin::Signed(org_id).into(),
+                TEST_TOKEN_ID,
+                Some(limit_to(60))
+            ));
+            assert_eq!(
+                Organizations::spending_limits(org_id, TEST_TOKEN_ID),
+                Some(limit_to(60))
+            );
+            assert_eq!(Organizations::spendings(org_id, TEST_TOKEN_ID), None);
+
+            assert_ok!(Organizations::set_spending_limit(
+                RawOrigin::Signed(org_id).into(),
+                TEST_TOKEN_ID,
+                None
+            ));
+            assert_eq!(Organizations::spending_limits(org_id, TEST_TOKEN_ID), None);
+        })
+}
+
+#[test]
+fn set_spending_limit_fail_if_not_org() {
+    ExtBuilder::default()
+        .with_treasury_org(100)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Organizations::set_spending_limit(
+                    RawOrigin::Signed(ALICE).into(),
+                    TEST_TOKEN_ID,
+                    Some(limit_to(50))
+                ),
+                Error::<Test>::NotAnOrganization
+            );
+        })
+}
+
+#[test]
+fn set_spending_limit_fail_if_too_many_limits() {
+    ExtBuilder::default()
+        .with_treasury_org(100)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            for currency_id in 1..=2 {
+                assert_ok!(Organizations::set_spending_limit(
+                    RawOrigin::Signed(org_id).into(),
+                    currency_id,
+                    Some(limit_to(50))
+                ));
+            }
+
+            assert_noop!(
+                Organizations::set_spending_limit(
+                    RawOrigin::Signed(org_id).into(),
+                    3,
+                    Some(limit_to(50))
+                ),
+                Error::<Test>::TooManySpendingLimits
+            );
+            // Updating an existing limit is still possible
+            assert_ok!(Organizations::set_spending_limit(
+                RawOrigin::Signed(org_id).into(),
+                2,
+                Some(limit_to(60))
+            ));
+        })
+}
+
+#[test]
+fn apply_as_records_executor_spendings() {
+    ExtBuilder::default()
+        .with_treasury_org(100)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            assert_ok!(Organizations::set_spending_limit(
+                RawOrigin::Signed(org_id).into(),
+                TEST_TOKEN_ID,
+                Some(limit_to(50))
+            ));
+
+            assert_ok!(Organizations::apply_as(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                transfer_call(30)
+            ));
+
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &BOB), 30);
+            assert_eq!(
+                Organizations::spendings(org_id, TEST_TOKEN_ID),
+                Some(Spending {
+                    period_start: 1,
+                    spent: 30
+                })
+            );
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_organizations(RawEvent::ExecutorSpent(
+                    org_id,
+                    ALICE,
+                    TEST_TOKEN_ID,
+                    30
+                ))));
+        })
+}
+
+#[test]
+fn apply_as_fail_if_spending_limit_exceeded() {
+    ExtBuilder::default()
+        .with_treasury_org(100)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            assert_ok!(Organizations::set_spending_limit(
+                RawOrigin::Signed(org_id).into(),
+                TEST_TOKEN_ID,
+                Some(limit_to(50))
+            ));
+            assert_ok!(Organizations::apply_as(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                transfer_call(30)
+            ));
+
+            assert_noop!(
+                Organizations::apply_as(RawOrigin::Signed(ALICE).into(), org_id, transfer_call(21)),
+                Error::<Test>::SpendingLimitExceeded
+            );
+        })
+}
+
+#[test]
+fn apply_as_spending_limit_resets_after_period() {
+    ExtBuilder::default()
+        .with_treasury_org(100)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            assert_ok!(Organizations::set_spending_limit(
+                RawOrigin::Signed(org_id).into(),
+                TEST_TOKEN_ID,
+                Some(limit_to(50))
+            ));
+            assert_ok!(Organizations::apply_as(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                transfer_call(50)
+            ));
+
+            System::set_block_number(11);
+            assert_ok!(Organizations::apply_as(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                transfer_call(40)
+            ));
+            assert_eq!(
+                Organizations::spendings(org_id, TEST_TOKEN_ID),
+                Some(Spending {
+                    period_start: 11,
+                    spent: 40
+                })
+            );
+        })
+}
+
+#[test]
+fn spend_from_pot_as_org_ignores_limits() {
+    let org_id = Organizations::org_id_for(0);
+    let pot = Organizations::pot_account(&org_id, b"grants");
+    ExtBuilder::default()
+        .with_treasury_org(0)
+        .with_endowment(pot, 100)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Organizations::set_spending_limit(
+                RawOrigin::Signed(org_id).into(),
+                TEST_TOKEN_ID,
+                Some(limit_to(50))
+            ));
+
+            assert_ok!(Organizations::spend(
+                RawOrigin::Signed(org_id).into(),
+                org_id,
+                Some(b"grants".to_vec()),
+                TEST_TOKEN_ID,
+                BOB,
+                80
+            ));
+
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &pot), 20);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &BOB), 80);
+            assert_eq!(Organizations::spendings(org_id, TEST_TOKEN_ID), None);
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_organizations(RawEvent::TreasurySpent(
+                    org_id,
+                    Some(b"grants".to_vec()),
+                    TEST_TOKEN_ID,
+                    BOB,
+                    80
+                ))));
+        })
+}
+
+#[test]
+fn spend_as_executor_checks_limits() {
+    ExtBuilder::default()
+        .with_treasury_org(100)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            assert_ok!(Organizations::set_spending_limit(
+                RawOrigin::Signed(org_id).into(),
+                TEST_TOKEN_ID,
+                Some(limit_to(50))
+            ));
+
+            assert_ok!(Organizations::spend(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                None,
+                TEST_TOKEN_ID,
+                BOB,
+                50
+            ));
+            assert_eq!(
+                Organizations::spendings(org_id, TEST_TOKEN_ID)
+                    .unwrap()
+                    .spent,
+                50
+            );
+
+            assert_noop!(
+                Organizations::spend(
+                    RawOrigin::Signed(ALICE).into(),
+                    org_id,
+                    None,
+                    TEST_TOKEN_ID,
+                    BOB,
+                    1
+                ),
+                Error::<Test>::SpendingLimitExceeded
+            );
+        })
+}
+
+#[test]
+fn spend_fail_if_not_executor() {
+    ExtBuilder::default()
+        .with_treasury_org(100)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            assert_noop!(
+                Organizations::spend(
+                    RawOrigin::Signed(BOB).into(),
+                    org_id,
+                    None,
+                    TEST_TOKEN_ID,
+                    BOB,
+                    1
+                ),
+                AclError::MissingRole
+            );
+        })
+}
+
+#[test]
+fn apply_as_can_not_change_spending_limits() {
+    ExtBuilder::default()
+        .with_treasury_org(100)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            assert_ok!(Organizations::set_spending_limit(
+                RawOrigin::Signed(org_id).into(),
+                TEST_TOKEN_ID,
+                Some(limit_to(50))
+            ));
+
+            assert_ok!(Organizations::apply_as(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                Box::new(Call::Organizations(crate::Call::set_spending_limit(
+                    TEST_TOKEN_ID,
+                    None
+                )))
+            ));
+
+            assert_eq!(
+                Organizations::spending_limits(org_id, TEST_TOKEN_ID),
+                Some(limit_to(50))
+            );
+            assert!(execution_failed_with(
+                Error::<Test>::ExecutorsCannotChangeLimits
+            ));
+            assert_eq!(Organizations::acting_executors(org_id), None);
+        })
+}
+
+#[test]
+fn apply_as_spend_from_pot_checks_limits() {
+    let org_id = Organizations::org_id_for(0);
+    let pot = Organizations::pot_account(&org_id, b"grants");
+    let spend_call = |amount| {
+        Box::new(Call::Organizations(crate::Call::spend(
+            org_id,
+            Some(b"grants".to_vec()),
+            TEST_TOKEN_ID,
+            BOB,
+            amount,
+        )))
+    };
+    ExtBuilder::default()
+        .with_treasury_org(0)
+        .with_endowment(pot, 100)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Organizations::set_spending_limit(
+                RawOrigin::Signed(org_id).into(),
+                TEST_TOKEN_ID,
+                Some(limit_to(50))
+            ));
+
+            assert_ok!(Organizations::apply_as(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                spend_call(30)
+            ));
+            assert_eq!(
+                Organizations::spendings(org_id, TEST_TOKEN_ID)
+                    .unwrap()
+                    .spent,
+                30
+            );
+
+            assert_ok!(Organizations::apply_as(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                spend_call(21)
+            ));
+            assert!(execution_failed_with(Error::<Test>::SpendingLimitExceeded));
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &pot), 70);
+        })
+}
+
+#[test]
+fn apply_as_counts_allowances_against_limits() {
+    let approve_call = |amount| {
+        Box::new(Call::Tokens(governance_os_pallet_tokens::Call::approve(
+            TEST_TOKEN_ID,
+            BOB,
+            amount,
+        )))
+    };
+    ExtBuilder::default()
+        .with_treasury_org(100)
+        .build()
+        .execute_with(|| {
+            let org_id = Organizations::org_id_for(0);
+            assert_ok!(Organizations::set_spending_limit(
+                RawOrigin::Signed(org_id).into(),
+                TEST_TOKEN_ID,
+                Some(limit_to(50))
+            ));
+
+            assert_noop!(
+                Organizations::apply_as(RawOrigin::Signed(ALICE).into(), org_id, approve_call(60)),
+                Error::<Test>::SpendingLimitExceeded
+            );
+
+            assert_ok!(Organizations::apply_as(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                approve_call(30)
+            ));
+            assert_eq!(
+                Organizations::spendings(org_id, TEST_TOKEN_ID)
+                    .unwrap()
+                    .spent,
+                30
+            );
+
+            // Spending the allowance is not counted a second time
+            assert_ok!(Tokens::transfer_from(
+                RawOrigin::Signed(BOB).into(),
+                TEST_TOKEN_ID,
+                org_id,
+                BOB,
+                30
+            ));
+            assert_ok!(Organizations::apply_as(
+                RawOrigin::Signed(ALICE).into(),
+                org_id,
+                transfer_call(20)
+            ));
+            assert_eq!(
+                Organizations::spendings(org_id, TEST_TOKEN_ID)
+                    .unwrap()
+                    .spent,
+                50
+            );
+        })
+}
@@ -18,18 +18,23 @@
 //! voting, votes are hashed before being revealed and counted. This
 //! allows people to hide their votes until everyone places theirs as
 //! to prevent collusion between participants or typical human biases.
+//! Proposals can also be configured to count votes from the balances held
+//! when they were created, in which case voters do not have to lock their coins.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage, ensure, traits::LockIdentifier,
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    traits::{Get, LockIdentifier},
+    weights::Weight,
 };
 use governance_os_support::traits::{
-    Currencies, LockableCurrencies, ProposalResult, StandardizedVoting,
+    voting_lock_reasons, CheckpointedCurrencies, Currencies, Delegations, LockableCurrencies,
+    OnDelegationChanged, ProposalResult, ProposalTally, StandardizedVoting,
 };
 use sp_runtime::{
-    traits::{Hash, Saturating, Zero},
-    DispatchError, DispatchResult, Perbill,
+    traits::{Hash, One, Saturating, Zero},
+    DispatchError, DispatchResult,
 };
 use sp_std::prelude::*;
 use types::ProposalState;
@@ -45,17 +50,29 @@ pub const PLCR_VOTING_LOCK_ID: LockIdentifier = *b"plcrvote";
 pub trait Config: frame_system::Config {
     /// Because this pallet emits events, it depends on the runtime's definition of an event.
     type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
-    /// Pallet in charge of currencies. Used so that we can lock tokens etc...
-    type Currencies: LockableCurrencies<Self::AccountId>;
+    /// Pallet in charge of currencies. Used so that we can lock tokens or read the
+    /// balances snapshot proposals are counted from.
+    type Currencies: LockableCurrencies<Self::AccountId>
+        + CheckpointedCurrencies<Self::AccountId, Self::BlockNumber>;
+    /// Used to count the voting power delegated to the voters.
+    type Delegations: Delegations<Self::AccountId, CurrencyIdOf<Self>, BalanceOf<Self>>;
+    /// Maximum number of open proposals an account can reveal votes on with the same currency.
+    /// Bounds the number of proposals recounted when a delegation changes.
+    type MaxActiveVotes: Get<u32>;
 }
 
 type BalanceOf<T> =
     <<T as Config>::Currencies as Currencies<<T as frame_system::Config>::AccountId>>::Balance;
 type CurrencyIdOf<T> =
     <<T as Config>::Currencies as Currencies<<T as frame_system::Config>::AccountId>>::CurrencyId;
-type PlcrProposalStateOf<T> =
-    ProposalState<BalanceOf<T>, <T as frame_system::Config>::BlockNumber, CurrencyIdOf<T>>;
+type PlcrProposalStateOf<T> = ProposalState<
+    <T as frame_system::Config>::AccountId,
+    BalanceOf<T>,
+    <T as frame_system::Config>::BlockNumber,
+    CurrencyIdOf<T>,
+>;
 type PlcrVoteData<Balance, Hash> = VoteData<Balance, Hash>;
+type ProposalTallyOf<T> = ProposalTally<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
 
 decl_storage! {
     trait Store for Module<T: Config> as PlcrVoting {
@@ -65,6 +82,12 @@ decl_storage! {
         pub Locks get(fn locks): map hasher(blake2_128_concat) (CurrencyIdOf<T>, T::AccountId) => Vec<(T::Hash, BalanceOf<T>)>;
         /// Keeps track of the votes casted in favor of or against the different proposals.
         pub Votes get(fn votes): double_map hasher(blake2_128_concat) T::Hash, hasher(blake2_128_concat) T::AccountId => PlcrVoteData<BalanceOf<T>, T::Hash>;
+        /// Voting power delegated to voters and counted in the proposals' tallies. Keyed by delegator
+        /// and proposal, values are the delegate, its support and the amount counted.
+        pub DelegatedVotes get(fn delegated_votes): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::Hash => Option<(T::AccountId, bool, BalanceOf<T>)>;
+        /// Open proposals an account revealed a vote on with a given currency. Used to find the
+        /// proposals to recount when a delegation changes.
+        pub ActiveVotes get(fn active_votes): map hasher(blake2_128_concat) (CurrencyIdOf<T>, T::AccountId) => Vec<T::Hash>;
     }
 }
 
@@ -85,6 +108,12 @@ decl_error! {
         Phase,
         /// We cannot close the vote now and have to wait
         TooEarly,
+        /// Snapshot proposals require the voting currency to record balance
+        /// checkpoints since before their creation.
+        NoCheckpoints,
+        /// The voter already revealed votes on `MaxActiveVotes` open proposals
+        /// with this currency.
+        TooManyVotes,
     }
 }
 
@@ -112,19 +141,39 @@ impl<T: Config> StandardizedVoting for Module<T> {
     type Parameters = VotingParameters<T::BlockNumber, CurrencyIdOf<T>>;
     type VoteData = VoteData<BalanceOf<T>, T::Hash>;
     type AccountId = T::AccountId;
+    type Tally = ProposalTallyOf<T>;
+    type BlockNumber = T::BlockNumber;
 
     /// Register the proposal in our storage. Does not make any attempt at preventing duplicates
     /// as we assume this is handled by the calling pallet.
-    fn initiate(proposal: Self::ProposalId, parameters: Self::Parameters) -> DispatchResult {
+    fn initiate(
+        proposal: Self::ProposalId,
+        organization: &Self::AccountId,
+        parameters: Self::Parameters,
+    ) -> DispatchResult {
+        let state = ProposalState {
+            parameters,
+            organization: organization.clone(),
+            created_on: Self::now(),
+
+            revealed_against: Zero::zero(),
+            revealed_favorable: Zero::zero(),
+
+            delegators: vec![],
+        };
+        ensure!(
+            !state.parameters.snapshot
+                || T::Currencies::total_issuance_at(
+                    state.parameters.voting_currency,
+                    Self::snapshot_block(&state)
+                )
+                .is_some(),
+            Error::<T>::NoCheckpoints
+        );
+
         Proposals::<T>::try_mutate_exists(proposal, |maybe_existing_state| -> DispatchResult {
             // no duplicates, we can create a new state
-            *maybe_existing_state = Some(ProposalState {
-                parameters,
-                created_on: Self::now(),
-
-                revealed_against: Zero::zero(),
-                revealed_favorable: Zero::zero(),
-            });
+            *maybe_existing_state = Some(state);
 
             Ok(())
         })?;
@@ -138,7 +187,8 @@ impl<T: Config> StandardizedVoting for Module<T> {
     }
 
     /// Handle votes depending on the different phases. We prevent revealing votes before the
-    /// reveal phase and idem for commit votes.
+    /// reveal phase and idem for commit votes. When revealing, the voting power delegated to
+    /// the voter is counted too, except for the delegators that committed a vote themselves.
     fn vote(
         proposal: Self::ProposalId,
         voter: &Self::AccountId,
@@ -169,17 +219,31 @@ impl<T: Config> StandardizedVoting for Module<T> {
                     );
                     // We want to prevent votes for user with less coins than they'd like to lock.
                     ensure!(
-                        T::Currencies::free_balance(state.parameters.voting_currency, voter)
-                            >= balance,
+                        Self::available_balance(&state, voter) >= balance,
                         Error::<T>::NotEnoughBalance
                     );
 
                     let hashed_reveal = T::Hashing::hash_of(&(balance, support, salt));
                     ensure!(hashed_reveal == hash, Error::<T>::RevealCommitMismatch);
 
-                    Self::lock(proposal, state.parameters.voting_currency, voter, balance)?;
+                    let mut active_votes =
+                        ActiveVotes::<T>::get((state.parameters.voting_currency, voter));
+                    ensure!(
+                        (active_votes.len() as u32) < T::MaxActiveVotes::get(),
+                        Error::<T>::TooManyVotes
+                    );
+                    active_votes.push(proposal);
+
+                    if !state.parameters.snapshot {
+                        Self::lock(proposal, state.parameters.voting_currency, voter, balance)?;
+                    }
 
                     state.add_support(support, balance);
+                    Self::count_delegated_votes(&mut state, proposal, voter, support);
+                    ActiveVotes::<T>::insert(
+                        (state.parameters.voting_currency, voter),
+                        active_votes,
+                    );
                     Proposals::<T>::insert(proposal, state);
 
                     Self::deposit_event(RawEvent::VoteRevealed(voter.clone(), proposal, hash));
@@ -197,40 +261,210 @@ impl<T: Config> StandardizedVoting for Module<T> {
     /// the voters' coins.
     fn close(proposal: Self::ProposalId) -> Result<ProposalResult, DispatchError> {
         let state = Self::proposals(proposal);
-        let proposal_expired = Self::now()
-            > state
-                .created_on
-                .saturating_add(state.parameters.commit_duration)
-                .saturating_add(state.parameters.reveal_duration);
-
-        let total_supply = T::Currencies::total_issuance(state.parameters.voting_currency);
-        let total_participation = state
-            .revealed_against
-            .saturating_add(state.revealed_favorable);
-        let participation_met = total_participation
-            > Perbill::from_percent(state.parameters.min_participation) * total_supply;
-        let quorum_met = state.revealed_favorable
-            > Perbill::from_percent(state.parameters.min_quorum) * total_participation;
-        let proposal_passing = quorum_met && participation_met;
-
-        ensure!(proposal_expired || proposal_passing, Error::<T>::TooEarly);
+        let result = Self::compute_tally(&state)
+            .close_result
+            .ok_or(Error::<T>::TooEarly)?;
 
         Self::finalize_proposal(proposal, state)?;
-        Ok(if proposal_passing {
-            ProposalResult::Passing
-        } else {
-            ProposalResult::Failing
-        })
+        Ok(result)
+    }
+
+    /// Compute the tally from the revealed votes only, commits are not counted.
+    fn tally(proposal: Self::ProposalId) -> Option<Self::Tally> {
+        Proposals::<T>::try_get(proposal)
+            .ok()
+            .map(|state| Self::compute_tally(&state))
+    }
+
+    /// A proposal expires once both its commit and reveal phases are over.
+    fn expiry(proposal: Self::ProposalId) -> Option<Self::BlockNumber> {
+        Proposals::<T>::try_get(proposal)
+            .ok()
+            .map(|state| Self::expiry_of(&state))
+    }
+
+    fn can_vote(proposal: Self::ProposalId, voter: &Self::AccountId) -> bool {
+        match Proposals::<T>::try_get(proposal) {
+            Ok(state) => !Self::available_balance(&state, voter).is_zero(),
+            Err(_) => false,
+        }
+    }
+
+    fn vote_weight() -> Weight {
+        // We read the proposal, the vote, active votes and locks of the voter and its balance,
+        // then write them back. For each delegator we check if it committed a vote and write
+        // its delegated vote.
+        let delegators = T::Delegations::max_delegators_of();
+        T::DbWeight::get()
+            .reads_writes(5, 4)
+            .saturating_add(T::Delegations::delegators_of_weight())
+            .saturating_add(T::DbWeight::get().reads_writes(delegators.into(), delegators.into()))
+    }
+}
+
+impl<T: Config> OnDelegationChanged<T::AccountId, CurrencyIdOf<T>> for Module<T> {
+    /// Recount the voting power of `delegator` in the proposals `delegates` revealed a vote
+    /// on, it may now be delegated to another account or not be delegated at all.
+    fn on_delegation_changed(
+        currency: CurrencyIdOf<T>,
+        delegator: &T::AccountId,
+        delegates: &[T::AccountId],
+    ) -> DispatchResult {
+        Self::proposals_voted_by(currency, delegates)
+            .into_iter()
+            .for_each(|proposal| {
+                let mut state = Self::proposals(proposal);
+
+                Self::uncount_delegated_vote(&mut state, proposal, delegator);
+                // Delegators that committed a vote themselves are ignored.
+                if !Votes::<T>::contains_key(proposal, delegator) {
+                    if let Some((delegate, amount)) =
+                        T::Delegations::delegation_of(currency, &state.organization, delegator)
+                    {
+                        if let VoteData::Reveal(_, support, _) = Self::votes(proposal, &delegate) {
+                            Self::count_delegated_vote(
+                                &mut state, proposal, delegator, delegate, support, amount,
+                            );
+                        }
+                    }
+                }
+
+                Proposals::<T>::insert(proposal, state);
+            });
+
+        Ok(())
+    }
+
+    fn on_delegation_changed_weight(delegates: u32) -> Weight {
+        // For each proposal we read it, the delegated vote, both delegations of the delegator
+        // and the votes of the delegator and its delegate. Then write the proposal and the
+        // delegated vote.
+        let proposals = T::MaxActiveVotes::get().saturating_mul(delegates);
+        T::DbWeight::get()
+            .reads(delegates.into())
+            .saturating_add(T::DbWeight::get().reads_writes(
+                proposals.saturating_mul(6).into(),
+                proposals.saturating_mul(2).into(),
+            ))
     }
 }
 
 impl<T: Config> Module<T> {
+    /// Count the voting power delegated to `delegate` in the tally of `proposal`. Delegators
+    /// that committed a vote themselves are ignored.
+    fn count_delegated_votes(
+        state: &mut PlcrProposalStateOf<T>,
+        proposal: T::Hash,
+        delegate: &T::AccountId,
+        support: bool,
+    ) {
+        T::Delegations::delegators_of(
+            state.parameters.voting_currency,
+            &state.organization,
+            delegate,
+        )
+        .into_iter()
+        .filter(|(delegator, _amount)| !Votes::<T>::contains_key(proposal, delegator))
+        .for_each(|(delegator, amount)| {
+            Self::count_delegated_vote(
+                state,
+                proposal,
+                &delegator,
+                delegate.clone(),
+                support,
+                amount,
+            )
+        });
+    }
+
+    /// Add the power of `delegator` to the tally of `proposal`.
+    fn count_delegated_vote(
+        state: &mut PlcrProposalStateOf<T>,
+        proposal: T::Hash,
+        delegator: &T::AccountId,
+        delegate: T::AccountId,
+        support: bool,
+        amount: BalanceOf<T>,
+    ) {
+        state.add_support(support, amount);
+        DelegatedVotes::<T>::insert(delegator, proposal, (delegate, support, amount));
+        if !state.delegators.contains(delegator) {
+            state.delegators.push(delegator.clone());
+        }
+    }
+
+    /// Remove the power of `delegator` from the tally of `proposal`, if it was counted.
+    fn uncount_delegated_vote(
+        state: &mut PlcrProposalStateOf<T>,
+        proposal: T::Hash,
+        delegator: &T::AccountId,
+    ) {
+        if let Some((_delegate, support, amount)) = DelegatedVotes::<T>::take(delegator, proposal) {
+            state.remove_support(support, amount);
+        }
+    }
+
     /// Just a helper function to return the current block number. Simply sexier
     /// than calling the actual `frame_system::Module::<T>::block_number()` function.
     fn now() -> T::BlockNumber {
         frame_system::Module::<T>::block_number()
     }
 
+    /// Compute the tally of a proposal, it expires once both the commit and reveal phases
+    /// are over. Shared by `tally` and `close` so that both always agree on the outcome.
+    fn compute_tally(state: &PlcrProposalStateOf<T>) -> ProposalTallyOf<T> {
+        ProposalTally::new(
+            state.revealed_favorable,
+            state.revealed_against,
+            Self::voting_supply(state),
+            state.parameters.min_participation,
+            state.parameters.min_quorum,
+            Self::expiry_of(state),
+            Self::now(),
+        )
+    }
+
+    /// Last block of the reveal phase of a proposal.
+    fn expiry_of(state: &PlcrProposalStateOf<T>) -> T::BlockNumber {
+        state
+            .created_on
+            .saturating_add(state.parameters.commit_duration)
+            .saturating_add(state.parameters.reveal_duration)
+    }
+
+    /// Block whose balances are used by snapshot proposals. Coins can still move during the
+    /// block the proposal was created in, so we use the one preceding it.
+    fn snapshot_block(state: &PlcrProposalStateOf<T>) -> T::BlockNumber {
+        state.created_on.saturating_sub(One::one())
+    }
+
+    /// Balance `who` can reveal a vote with: its free balance, or for snapshot proposals the
+    /// balance it held at the time of the snapshot. Zero if the voting currency stopped
+    /// recording checkpoints.
+    fn available_balance(state: &PlcrProposalStateOf<T>, who: &T::AccountId) -> BalanceOf<T> {
+        let currency = state.parameters.voting_currency;
+        if state.parameters.snapshot {
+            T::Currencies::balance_at(currency, who, Self::snapshot_block(state))
+                .unwrap_or_else(Zero::zero)
+        } else {
+            T::Currencies::free_balance(currency, who)
+        }
+    }
+
+    /// Supply participation is computed from, the one at the time of the snapshot for snapshot
+    /// proposals.
+    fn voting_supply(state: &PlcrProposalStateOf<T>) -> BalanceOf<T> {
+        let currency = state.parameters.voting_currency;
+        if state.parameters.snapshot {
+            if let Some(issuance) =
+                T::Currencies::total_issuance_at(currency, Self::snapshot_block(state))
+            {
+                return issuance;
+            }
+        }
+        T::Currencies::total_issuance(currency)
+    }
+
     /// Register a new lock of `amount` for `currency` linked to `proposal` for `who`.
     /// If a lock with similar parameters but a different `amount` exists we will
     /// replace it and lock or unlock the difference.
@@ -304,19 +538,56 @@ impl<T: Config> Module<T> {
         if max_to_lock == Zero::zero() {
             T::Currencies::remove_lock(currency, PLCR_VOTING_LOCK_ID, who)?;
         } else {
-            T::Currencies::set_lock(currency, PLCR_VOTING_LOCK_ID, who, max_to_lock)?;
+            T::Currencies::set_lock_with_reasons(
+                currency,
+                PLCR_VOTING_LOCK_ID,
+                who,
+                max_to_lock,
+                voting_lock_reasons(),
+            )?;
         }
 
         Ok(())
     }
 
+    /// Open proposals on which any of `voters` revealed a vote with `currency`.
+    fn proposals_voted_by(currency: CurrencyIdOf<T>, voters: &[T::AccountId]) -> Vec<T::Hash> {
+        let mut proposals: Vec<T::Hash> = voters
+            .iter()
+            .flat_map(|voter| ActiveVotes::<T>::get((currency, voter)))
+            .collect();
+        proposals.sort();
+        proposals.dedup();
+        proposals
+    }
+
     /// Clear all the storage related to a proposal.
     fn finalize_proposal(proposal: T::Hash, state: PlcrProposalStateOf<T>) -> DispatchResult {
-        Votes::<T>::iter_prefix(proposal).try_for_each(|(account, _vote)| -> DispatchResult {
-            Self::unlock(proposal, state.parameters.voting_currency, &account)?;
-
-            Ok(())
-        })?;
+        state
+            .delegators
+            .iter()
+            .for_each(|delegator| DelegatedVotes::<T>::remove(delegator, proposal));
+        Votes::<T>::iter_prefix(proposal).for_each(|(voter, _vote)| {
+            ActiveVotes::<T>::mutate_exists(
+                (state.parameters.voting_currency, voter),
+                |maybe_proposals| {
+                    let mut proposals = maybe_proposals.take().unwrap_or_default();
+                    proposals.retain(|p| p != &proposal);
+                    if !proposals.is_empty() {
+                        *maybe_proposals = Some(proposals);
+                    }
+                },
+            )
+        });
+        if !state.parameters.snapshot {
+            Votes::<T>::iter_prefix(proposal).try_for_each(
+                |(account, _vote)| -> DispatchResult {
+                    Self::unlock(proposal, state.parameters.voting_currency, &account)?;
+
+                    Ok(())
+                },
+            )?;
+        }
 
         Proposals::<T>::remove(proposal);
         Votes::<T>::remove_prefix(proposal);
@@ -0,0 +1,250 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{DelegatedVotes, Error, Proposals};
+use frame_support::{assert_noop, assert_ok, StorageDoubleMap, StorageMap};
+use governance_os_support::{
+    testing::{ALICE, BOB, CHARLIE, TEST_ORGANIZATION, TEST_TOKEN_ID},
+    traits::StandardizedVoting,
+};
+use sp_core::H256;
+
+fn balances_for_everyone() -> ExtBuilder {
+    ExtBuilder::default().balances(vec![
+        (TEST_TOKEN_ID, ALICE, 100),
+        (TEST_TOKEN_ID, BOB, 100),
+        (TEST_TOKEN_ID, CHARLIE, 100),
+    ])
+}
+
+fn commit_and_reveal(voter: u128, support: bool, power: u128) {
+    let (commit, reveal) = mock_vote(power, support, 42);
+    assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+        H256::default(),
+        &voter,
+        commit
+    ));
+    advance_blocks(mock_parameters().commit_duration + 1);
+    assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+        H256::default(),
+        &voter,
+        reveal
+    ));
+}
+
+fn totals() -> (u128, u128) {
+    let state = PlcrVoting::proposals(H256::default());
+    (state.revealed_favorable, state.revealed_against)
+}
+
+fn setup_alice_delegates_to_bob() {
+    assert_ok!(Delegation::delegate(
+        Origin::signed(ALICE),
+        TEST_TOKEN_ID,
+        None,
+        BOB,
+        50
+    ));
+    assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
+        H256::default(),
+        &TEST_ORGANIZATION,
+        mock_parameters()
+    ));
+}
+
+#[test]
+fn delegated_power_is_counted_when_delegate_reveals() {
+    balances_for_everyone().build().execute_with(|| {
+        setup_alice_delegates_to_bob();
+        commit_and_reveal(BOB, true, 10);
+
+        assert_eq!(totals(), (60, 0));
+        assert_eq!(
+            PlcrVoting::delegated_votes(ALICE, H256::default()),
+            Some((BOB, true, 50))
+        );
+    })
+}
+
+#[test]
+fn direct_commit_overrides_delegation() {
+    balances_for_everyone().build().execute_with(|| {
+        setup_alice_delegates_to_bob();
+
+        let (commit, reveal) = mock_vote(20, false, 1);
+        assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+            H256::default(),
+            &ALICE,
+            commit
+        ));
+        commit_and_reveal(BOB, true, 10);
+        assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+            H256::default(),
+            &ALICE,
+            reveal
+        ));
+
+        assert_eq!(totals(), (10, 20));
+        assert_eq!(PlcrVoting::delegated_votes(ALICE, H256::default()), None);
+    })
+}
+
+#[test]
+fn undelegating_recomputes_tally() {
+    balances_for_everyone().build().execute_with(|| {
+        setup_alice_delegates_to_bob();
+        commit_and_reveal(BOB, true, 10);
+
+        assert_ok!(Delegation::undelegate(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            None
+        ));
+
+        assert_eq!(totals(), (10, 0));
+        assert_eq!(PlcrVoting::delegated_votes(ALICE, H256::default()), None);
+    })
+}
+
+#[test]
+fn redelegating_moves_power_to_new_delegate() {
+    balances_for_everyone().build().execute_with(|| {
+        setup_alice_delegates_to_bob();
+
+        let (bob_commit, bob_reveal) = mock_vote(10, true, 42);
+        let (charlie_commit, charlie_reveal) = mock_vote(5, false, 42);
+        assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+            H256::default(),
+            &BOB,
+            bob_commit
+        ));
+        assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+            H256::default(),
+            &CHARLIE,
+            charlie_commit
+        ));
+        advance_blocks(mock_parameters().commit_duration + 1);
+        assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+            H256::default(),
+            &BOB,
+            bob_reveal
+        ));
+        assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+            H256::default(),
+            &CHARLIE,
+            charlie_reveal
+        ));
+        assert_eq!(totals(), (60, 5));
+
+        assert_ok!(Delegation::delegate(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            None,
+            CHARLIE,
+            50
+        ));
+
+        assert_eq!(totals(), (10, 55));
+        assert_eq!(
+            PlcrVoting::delegated_votes(ALICE, H256::default()),
+            Some((CHARLIE, false, 50))
+        );
+    })
+}
+
+#[test]
+fn close_cleans_delegated_votes() {
+    balances_for_everyone().build().execute_with(|| {
+        setup_alice_delegates_to_bob();
+        commit_and_reveal(BOB, true, 10);
+
+        advance_blocks(mock_parameters().reveal_duration + 1);
+        assert_ok!(<PlcrVoting as StandardizedVoting>::close(H256::default()));
+
+        assert!(!Proposals::<Test>::contains_key(H256::default()));
+        assert!(!DelegatedVotes::<Test>::contains_key(
+            ALICE,
+            H256::default()
+        ));
+    })
+}
+
+#[test]
+fn delegating_after_the_delegate_revealed_is_counted() {
+    balances_for_everyone().build().execute_with(|| {
+        assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
+            H256::default(),
+            &TEST_ORGANIZATION,
+            mock_parameters()
+        ));
+        commit_and_reveal(BOB, true, 10);
+
+        assert_ok!(Delegation::delegate(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            None,
+            BOB,
+            50
+        ));
+
+        assert_eq!(totals(), (60, 0));
+        assert_eq!(
+            PlcrVoting::delegated_votes(ALICE, H256::default()),
+            Some((BOB, true, 50))
+        );
+    })
+}
+
+#[test]
+fn reveals_are_limited_to_max_active_votes() {
+    balances_for_everyone().build().execute_with(|| {
+        let (commit, reveal) = mock_vote(10, true, 42);
+        (0..=MaxActiveVotes::get()).for_each(|i| {
+            let proposal = H256::repeat_byte(i as u8);
+            assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
+                proposal,
+                &TEST_ORGANIZATION,
+                mock_parameters()
+            ));
+            assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+                proposal, &ALICE, commit
+            ));
+        });
+        advance_blocks(mock_parameters().commit_duration + 1);
+
+        (0..MaxActiveVotes::get()).for_each(|i| {
+            assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+                H256::repeat_byte(i as u8),
+                &ALICE,
+                reveal
+            ));
+        });
+        let last = H256::repeat_byte(MaxActiveVotes::get() as u8);
+        assert_noop!(
+            <PlcrVoting as StandardizedVoting>::vote(last, &ALICE, reveal),
+            Error::<Test>::TooManyVotes
+        );
+
+        // Vetoing a proposal frees a slot
+        assert_ok!(<PlcrVoting as StandardizedVoting>::veto(H256::repeat_byte(
+            0
+        )));
+        assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+            last, &ALICE, reveal
+        ));
+    })
+}
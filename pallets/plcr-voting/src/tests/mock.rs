@@ -24,7 +24,7 @@ use governance_os_support::{
     impl_enum_default,
     testing::{
         primitives::{AccountId, Balance, BlockNumber, CurrencyId},
-        ALICE, BOB, TEST_TOKEN_ID, TEST_TOKEN_OWNER,
+        AutoCurrencyIds, ALICE, BOB, TEST_TOKEN_ID, TEST_TOKEN_OWNER,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -47,6 +47,7 @@ construct_runtime!(
         System: frame_system::{Module, Call, Config, Storage, Event<T>},
         Bylaws: governance_os_pallet_bylaws::{Module, Call, Storage, Event<T>},
         Tokens: governance_os_pallet_tokens::{Module, Call, Storage, Event<T>},
+        Delegation: governance_os_pallet_delegation::{Module, Call, Storage, Event<T>},
         PlcrVoting: governance_os_pallet_plcr_voting::{Module, Call, Storage, Event<T>},
     }
 );
@@ -98,6 +99,7 @@ pub enum MockRoles {
     Root,
     CreateCurrencies,
     TransferCurrency(CurrencyId),
+    ReceiveCurrency(CurrencyId),
     ManageCurrency(CurrencyId),
 }
 impl_enum_default!(MockRoles, Root);
@@ -116,6 +118,7 @@ impl governance_os_pallet_bylaws::RoleBuilder for MockRoles {
 parameter_types! {
     pub const RootRole: MockRoles = MockRoles::Root;
     pub const MaxRoles: u32 = 5;
+    pub const MaxRoleAncestors: u32 = 4;
 }
 
 impl governance_os_pallet_bylaws::Config for Test {
@@ -123,6 +126,7 @@ impl governance_os_pallet_bylaws::Config for Test {
     type Role = MockRoles;
     type WeightInfo = ();
     type MaxRoles = MaxRoles;
+    type MaxRoleAncestors = MaxRoleAncestors;
     type RoleBuilder = MockRoles;
 }
 
@@ -134,6 +138,10 @@ impl governance_os_pallet_tokens::RoleBuilder for MockRoles {
         Self::TransferCurrency(id)
     }
 
+    fn receive_currency(id: CurrencyId) -> Self {
+        Self::ReceiveCurrency(id)
+    }
+
     fn manage_currency(id: CurrencyId) -> Self {
         Self::ManageCurrency(id)
     }
@@ -141,6 +149,15 @@ impl governance_os_pallet_tokens::RoleBuilder for MockRoles {
     fn create_currencies() -> Self {
         Self::CreateCurrencies
     }
+
+    fn manage_fee_rates() -> Self {
+        Self::Root
+    }
+}
+
+parameter_types! {
+    pub const MaxCurrencyIdSkips: u32 = 16;
+    pub const MaxCheckpoints: u32 = 16;
 }
 
 impl governance_os_pallet_tokens::Config for Test {
@@ -148,6 +165,18 @@ impl governance_os_pallet_tokens::Config for Test {
     type CurrencyId = CurrencyId;
     type Balance = Balance;
     type WeightInfo = ();
+    type NativeCurrencyId = ();
+    type NativeExistentialDeposit = ();
+    type AutoCurrencyId = AutoCurrencyIds;
+    type MaxCurrencyIdSkips = MaxCurrencyIdSkips;
+    type CurrencyDeposit = ();
+    type MetadataDepositPerByte = ();
+    type MaxMetadataLength = ();
+    type MaxIssuanceSchedules = ();
+    type MaxBatchSize = ();
+    type MaxCheckpoints = MaxCheckpoints;
+    type OnDust = ();
+    type OnBalanceChanged = ();
     type RoleManager = Bylaws;
     type RoleBuilder = MockRoles;
 }
@@ -156,14 +185,32 @@ parameter_types! {
     pub const Decay: Balance = 1;
 }
 
+parameter_types! {
+    pub const MaxDelegators: u32 = 10;
+}
+
+impl governance_os_pallet_delegation::Config for Test {
+    type Event = Event;
+    type Currencies = Tokens;
+    type OnDelegationChanged = PlcrVoting;
+    type MaxDelegators = MaxDelegators;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const MaxActiveVotes: u32 = 3;
+}
+
 impl governance_os_pallet_plcr_voting::Config for Test {
     type Currencies = Tokens;
+    type Delegations = Delegation;
     type Event = Event;
+    type MaxActiveVotes = MaxActiveVotes;
 }
 
 pub struct ExtBuilder {
     endowed_accounts: Vec<(CurrencyId, AccountId, Balance)>,
-    test_token_details: CurrencyDetails<AccountId>,
+    test_token_details: CurrencyDetails<AccountId, Balance, BlockNumber>,
 }
 
 impl Default for ExtBuilder {
@@ -173,6 +220,10 @@ impl Default for ExtBuilder {
             test_token_details: CurrencyDetails {
                 owner: TEST_TOKEN_OWNER,
                 transferable: true,
+                restricted_receivers: false,
+                existential_deposit: 0,
+                max_supply: None,
+                issuance: None,
             },
         }
     }
@@ -195,13 +246,14 @@ impl ExtBuilder {
 
         governance_os_pallet_bylaws::GenesisConfig::<Test> {
             roles: vec![(MockRoles::CreateCurrencies, None)], // Everybody can create currencies
+            role_settings: vec![],
         }
         .assimilate_storage(&mut t)
         .unwrap();
 
         governance_os_pallet_tokens::GenesisConfig::<Test> {
             endowed_accounts: self.endowed_accounts,
-            currency_details: vec![(TEST_TOKEN_ID, self.test_token_details)],
+            currency_details: vec![(TEST_TOKEN_ID, self.test_token_details, None)],
         }
         .assimilate_storage(&mut t)
         .unwrap();
@@ -219,6 +271,7 @@ pub fn mock_parameters() -> VotingParameters<BlockNumber, CurrencyIdOf<Test>> {
         voting_currency: TEST_TOKEN_ID,
         min_quorum: 50,
         min_participation: 33,
+        snapshot: false,
     }
 }
 
@@ -241,3 +294,17 @@ pub fn advance_blocks(blocks: BlockNumber) {
     let now = System::block_number();
     System::set_block_number(now.saturating_add(blocks));
 }
+
+pub fn mock_snapshot_parameters() -> VotingParameters<BlockNumber, CurrencyIdOf<Test>> {
+    VotingParameters {
+        snapshot: true,
+        ..mock_parameters()
+    }
+}
+
+/// Record checkpoints for the test token from the current block and move to the next one so
+/// that proposals can be created from them.
+pub fn enable_checkpoints() {
+    assert!(Tokens::set_checkpoints(Origin::signed(TEST_TOKEN_OWNER), TEST_TOKEN_ID, true).is_ok());
+    System::set_block_number(System::block_number() + 1);
+}
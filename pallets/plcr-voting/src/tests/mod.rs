@@ -14,5 +14,7 @@
  * limitations under the License.
  */
 
+mod delegation;
 mod mock;
+mod snapshot;
 mod spec;
@@ -0,0 +1,145 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use super::mock::*;
+use crate::{Error, Locks, Proposals};
+use frame_support::{assert_noop, assert_ok, StorageMap};
+use governance_os_support::{
+    testing::{
+        primitives::AccountId, ALICE, BOB, TEST_ORGANIZATION, TEST_TOKEN_ID, TEST_TOKEN_OWNER,
+    },
+    traits::{Currencies, LockableCurrencies, ProposalResult, StandardizedVoting},
+};
+use sp_core::H256;
+
+#[test]
+fn initiate_requires_checkpoints() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                <PlcrVoting as StandardizedVoting>::initiate(
+                    H256::default(),
+                    &TEST_ORGANIZATION,
+                    mock_snapshot_parameters()
+                ),
+                Error::<Test>::NoCheckpoints
+            );
+
+            assert_ok!(Tokens::set_checkpoints(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                true
+            ));
+            assert_noop!(
+                <PlcrVoting as StandardizedVoting>::initiate(
+                    H256::default(),
+                    &TEST_ORGANIZATION,
+                    mock_snapshot_parameters()
+                ),
+                Error::<Test>::NoCheckpoints
+            );
+        })
+}
+
+#[test]
+fn reveals_use_snapshot_balances_without_locks() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let mock_hash = H256::default();
+            enable_checkpoints();
+            assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
+                mock_hash,
+                &TEST_ORGANIZATION,
+                mock_snapshot_parameters()
+            ));
+
+            let (alice_commit, alice_reveal) = mock_vote(100, true, 42);
+            let (bob_commit, bob_reveal) = mock_vote(150, false, 43);
+            assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &ALICE,
+                alice_commit
+            ));
+            assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+                mock_hash, &BOB, bob_commit
+            ));
+
+            // Coins moved after the snapshot can not be used to vote twice.
+            assert_ok!(<Tokens as Currencies<AccountId>>::transfer(
+                TEST_TOKEN_ID,
+                &ALICE,
+                &BOB,
+                100
+            ));
+            advance_blocks(mock_parameters().commit_duration + 1);
+            assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+                mock_hash,
+                &ALICE,
+                alice_reveal
+            ));
+            assert_noop!(
+                <PlcrVoting as StandardizedVoting>::vote(mock_hash, &BOB, bob_reveal),
+                Error::<Test>::NotEnoughBalance
+            );
+
+            assert_eq!(
+                <Tokens as LockableCurrencies<AccountId>>::locked_balance(TEST_TOKEN_ID, &ALICE),
+                0
+            );
+            assert!(!Locks::<Test>::contains_key((TEST_TOKEN_ID, ALICE)));
+            assert_eq!(Proposals::<Test>::get(mock_hash).revealed_favorable, 100);
+        })
+}
+
+#[test]
+fn participation_uses_snapshot_issuance() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let mock_hash = H256::default();
+            enable_checkpoints();
+            assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
+                mock_hash,
+                &TEST_ORGANIZATION,
+                mock_snapshot_parameters()
+            ));
+
+            let (commit, reveal) = mock_vote(100, true, 42);
+            assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+                mock_hash, &ALICE, commit
+            ));
+            // Would bring participation below the 33% threshold if it was counted.
+            assert_ok!(<Tokens as Currencies<AccountId>>::mint(
+                TEST_TOKEN_ID,
+                &BOB,
+                1_000
+            ));
+            advance_blocks(mock_parameters().commit_duration + 1);
+            assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+                mock_hash, &ALICE, reveal
+            ));
+
+            advance_blocks(mock_parameters().reveal_duration);
+            assert_eq!(
+                <PlcrVoting as StandardizedVoting>::close(mock_hash),
+                Ok(ProposalResult::Passing)
+            );
+        })
+}
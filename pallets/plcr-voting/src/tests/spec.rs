@@ -20,8 +20,8 @@ use super::mock::{
 use crate::{Error, Locks, Proposals, Votes};
 use frame_support::{assert_noop, assert_ok, IterableStorageDoubleMap, StorageMap};
 use governance_os_support::{
-    testing::{primitives::AccountId, ALICE, BOB, TEST_TOKEN_ID},
-    traits::{LockableCurrencies, ProposalResult, StandardizedVoting},
+    testing::{primitives::AccountId, ALICE, BOB, TEST_ORGANIZATION, TEST_TOKEN_ID},
+    traits::{LockableCurrencies, ProposalResult, ProposalTally, StandardizedVoting},
 };
 use sp_core::H256;
 
@@ -35,6 +35,7 @@ fn initialize_registers_proposal_hash() {
 
         assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
             mock_hash,
+            &TEST_ORGANIZATION,
             Default::default()
         ));
         assert!(Proposals::<Test>::contains_key(mock_hash));
@@ -51,6 +52,7 @@ fn vote_normal_flow() {
 
             assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_parameters()
             ));
 
@@ -92,6 +94,7 @@ fn vote_cannot_reveal_early() {
 
             assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_parameters()
             ));
 
@@ -114,6 +117,7 @@ fn vote_cannot_commit_late() {
 
             assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_parameters()
             ));
 
@@ -137,6 +141,7 @@ fn vote_cannot_reveal_uncommitted() {
 
             assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_parameters()
             ));
 
@@ -160,6 +165,7 @@ fn cannot_reveal_twice() {
 
             assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_parameters()
             ));
 
@@ -190,6 +196,7 @@ fn cannot_vote_with_tokens_they_do_not_have() {
 
             assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_parameters()
             ));
 
@@ -216,6 +223,7 @@ fn vote_cannot_commit_after_reveal() {
 
             assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_parameters()
             ));
 
@@ -246,6 +254,7 @@ fn vote_cannot_reveal_wrong_hash() {
 
             assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_parameters()
             ));
 
@@ -275,6 +284,7 @@ fn vote_update_commit_change_locks() {
 
             assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_parameters()
             ));
 
@@ -336,6 +346,7 @@ macro_rules! test_close_or_veto {
 
                     assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                         mock_hash,
+                        &TEST_ORGANIZATION,
                         mock_parameters()
                     ));
 
@@ -393,6 +404,7 @@ macro_rules! test_close_or_veto {
 
                     assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                         mock_hash,
+                        &TEST_ORGANIZATION,
                         mock_parameters()
                     ));
 
@@ -446,10 +458,12 @@ macro_rules! test_close_or_veto {
 
                     assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                         mock_hash_1,
+                        &TEST_ORGANIZATION,
                         mock_parameters()
                     ));
                     assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                         mock_hash_2,
+                        &TEST_ORGANIZATION,
                         mock_parameters()
                     ));
 
@@ -534,6 +548,7 @@ fn close_error_if_early() {
 
             assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_parameters()
             ));
 
@@ -567,6 +582,7 @@ fn close_passing() {
 
             assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_parameters()
             ));
 
@@ -598,6 +614,7 @@ fn close_failing() {
 
             assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_parameters()
             ));
 
@@ -629,6 +646,7 @@ fn close_passing_early() {
 
             assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
                 mock_hash,
+                &TEST_ORGANIZATION,
                 mock_parameters()
             ));
 
@@ -647,3 +665,54 @@ fn close_passing_early() {
             assert_eq!(PlcrVoting::close(mock_hash), Ok(ProposalResult::Passing));
         })
 }
+
+#[test]
+fn tally_only_counts_revealed_votes() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let mock_hash = H256::default();
+
+            assert_eq!(PlcrVoting::tally(mock_hash), None);
+            assert_eq!(PlcrVoting::expiry(mock_hash), None);
+            assert_ok!(<PlcrVoting as StandardizedVoting>::initiate(
+                mock_hash,
+                &TEST_ORGANIZATION,
+                mock_parameters()
+            ));
+            assert_eq!(PlcrVoting::expiry(mock_hash), Some(21));
+
+            let (commit, reveal) = mock_vote(90, true, 42);
+            assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+                mock_hash, &ALICE, commit,
+            ));
+            assert_eq!(
+                PlcrVoting::tally(mock_hash)
+                    .expect("proposal exists")
+                    .favorable,
+                0
+            );
+
+            advance_blocks(mock_parameters().commit_duration + 1);
+            assert_ok!(<PlcrVoting as StandardizedVoting>::vote(
+                mock_hash, &ALICE, reveal
+            ));
+
+            assert_eq!(
+                PlcrVoting::tally(mock_hash),
+                Some(ProposalTally {
+                    favorable: 90,
+                    against: 0,
+                    participation: 90,
+                    participation_threshold: 66,
+                    min_participation: 33,
+                    quorum_threshold: 45,
+                    min_quorum: 50,
+                    expiry: 21,
+                    close_result: Some(ProposalResult::Passing),
+                })
+            );
+            assert!(Proposals::<Test>::contains_key(mock_hash));
+        })
+}
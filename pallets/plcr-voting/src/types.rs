@@ -20,6 +20,7 @@ use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{traits::Saturating, RuntimeDebug};
+use sp_std::vec::Vec;
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, Default)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -48,13 +49,21 @@ pub struct VotingParameters<BlockNumber, CurrencyId> {
     /// is set to 33% it means that at least 33% + 1 of the `voting_currency`
     /// total supply need to have been used in votes for or against the proposal.
     pub min_participation: u32,
+
+    /// If set, revealed votes are limited by the balances held at the end of
+    /// the block preceding the proposal's creation and no coins are locked. The
+    /// voting currency must record balance checkpoints.
+    pub snapshot: bool,
 }
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, Default)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct ProposalState<Balance, BlockNumber, CurrencyId> {
+pub struct ProposalState<AccountId, Balance, BlockNumber, CurrencyId> {
     /// Parameters that this proposal was created with.
     pub parameters: VotingParameters<BlockNumber, CurrencyId>,
+    /// Organization the proposal was submitted to. Used to find which
+    /// delegations apply to it.
+    pub organization: AccountId,
     /// How many tokens where staked against this proposal. Computed only
     /// from revealed votes.
     pub revealed_against: Balance,
@@ -64,9 +73,13 @@ pub struct ProposalState<Balance, BlockNumber, CurrencyId> {
 
     /// When this proposal was created. Used to compute phases ending blocks.
     pub created_on: BlockNumber,
+
+    /// Accounts whose delegated voting power was counted in this proposal.
+    /// Used to later clean the associated storage.
+    pub delegators: Vec<AccountId>,
 }
-impl<Balance: Saturating + Copy, BlockNumber, CurrencyId>
-    ProposalState<Balance, BlockNumber, CurrencyId>
+impl<AccountId, Balance: Saturating + Copy, BlockNumber, CurrencyId>
+    ProposalState<AccountId, Balance, BlockNumber, CurrencyId>
 {
     pub fn add_support(&mut self, support: bool, stake: Balance) {
         if support {
@@ -75,6 +88,14 @@ impl<Balance: Saturating + Copy, BlockNumber, CurrencyId>
             self.revealed_against = self.revealed_against.saturating_add(stake);
         }
     }
+
+    pub fn remove_support(&mut self, support: bool, stake: Balance) {
+        if support {
+            self.revealed_favorable = self.revealed_favorable.saturating_sub(stake);
+        } else {
+            self.revealed_against = self.revealed_against.saturating_sub(stake);
+        }
+    }
 }
 
 #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
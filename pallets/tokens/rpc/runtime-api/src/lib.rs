@@ -0,0 +1,29 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Runtime API definition for the tokens pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+    pub trait TokensApi<CurrencyId: Codec, CurrencyMetadata: Codec> {
+        /// Return the name, symbol, decimals and URI of a currency, or `None` if they
+        /// were never set.
+        fn currency_metadata(currency_id: CurrencyId) -> Option<CurrencyMetadata>;
+    }
+}
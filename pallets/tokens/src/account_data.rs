@@ -15,7 +15,11 @@
  */
 
 use codec::{Decode, Encode};
-use sp_runtime::{traits::Saturating, RuntimeDebug};
+use frame_support::traits::WithdrawReasons;
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    RuntimeDebug,
+};
 
 /// All balance information for an account and an associated currency.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
@@ -33,12 +37,63 @@ pub struct AccountCurrencyData<Balance> {
     /// This balance is a 'reserve' balance that other subsystems use in order to set aside tokens
     /// that are still 'owned' by the account holder, but which are suspendable.
     pub reserved: Balance,
-    /// Balance under which the free balance may never drop below.
-    pub frozen: Balance,
+    /// Balance under which the free balance may not drop when paying fees or tips.
+    pub fee_frozen: Balance,
+    /// Balance under which the free balance may not drop when transferring funds.
+    pub transfer_frozen: Balance,
+    /// Balance under which the free balance may not drop when reserving funds.
+    pub reserve_frozen: Balance,
 }
-impl<Balance: Saturating + Copy> AccountCurrencyData<Balance> {
+impl<Balance: Saturating + Zero + Ord + Copy> AccountCurrencyData<Balance> {
     /// Computes and return the total balance, including reserved funds.
     pub fn total(&self) -> Balance {
         self.free.saturating_add(self.reserved)
     }
+
+    /// Balance under which the free balance may not drop when withdrawing funds
+    /// for any of the `reasons`.
+    pub fn frozen(&self, reasons: WithdrawReasons) -> Balance {
+        let mut frozen: Balance = Zero::zero();
+        if reasons.intersects(fee_reasons()) {
+            frozen = frozen.max(self.fee_frozen);
+        }
+        if reasons.contains(WithdrawReasons::TRANSFER) {
+            frozen = frozen.max(self.transfer_frozen);
+        }
+        if reasons.contains(WithdrawReasons::RESERVE) {
+            frozen = frozen.max(self.reserve_frozen);
+        }
+        frozen
+    }
+
+    /// Highest amount frozen for any reason.
+    pub fn max_frozen(&self) -> Balance {
+        self.frozen(WithdrawReasons::all())
+    }
+
+    /// Make sure that at least `amount` is frozen for all the `reasons`.
+    pub fn freeze(&mut self, amount: Balance, reasons: WithdrawReasons) {
+        if reasons.intersects(fee_reasons()) {
+            self.fee_frozen = self.fee_frozen.max(amount);
+        }
+        if reasons.contains(WithdrawReasons::TRANSFER) {
+            self.transfer_frozen = self.transfer_frozen.max(amount);
+        }
+        if reasons.contains(WithdrawReasons::RESERVE) {
+            self.reserve_frozen = self.reserve_frozen.max(amount);
+        }
+    }
+}
+
+/// Reasons tracked by `fee_frozen`.
+fn fee_reasons() -> WithdrawReasons {
+    WithdrawReasons::TRANSACTION_PAYMENT | WithdrawReasons::FEE | WithdrawReasons::TIP
+}
+
+/// A lock on the balance of an account. It only applies to withdrawals made for
+/// one of its `reasons`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TokenLock<Balance> {
+    pub amount: Balance,
+    pub reasons: WithdrawReasons,
 }
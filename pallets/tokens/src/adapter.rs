@@ -64,7 +64,7 @@ where
     }
 
     fn minimum_balance() -> Self::Balance {
-        Zero::zero()
+        Module::<Pallet>::existential_deposit(GetCurrencyId::get())
     }
 
     fn burn(mut amount: Self::Balance) -> Self::PositiveImbalance {
@@ -100,24 +100,31 @@ where
     fn ensure_can_withdraw(
         who: &Pallet::AccountId,
         amount: Self::Balance,
-        _: WithdrawReasons,
+        reasons: WithdrawReasons,
         _: Self::Balance,
     ) -> DispatchResult {
-        Module::<Pallet>::ensure_can_withdraw(GetCurrencyId::get(), who, amount)
+        Module::<Pallet>::ensure_can_withdraw_with_reasons(
+            GetCurrencyId::get(),
+            who,
+            amount,
+            reasons,
+        )
     }
 
     fn transfer(
         source: &Pallet::AccountId,
         dest: &Pallet::AccountId,
         amount: Self::Balance,
-        _: ExistenceRequirement,
+        existence_requirement: ExistenceRequirement,
     ) -> DispatchResult {
-        <Module<Pallet> as Currencies<Pallet::AccountId>>::transfer(
+        Module::<Pallet>::do_transfer(
             GetCurrencyId::get(),
             source,
             dest,
             amount,
+            existence_requirement,
         )
+        .map(|_| ())
     }
 
     fn slash(
@@ -165,14 +172,17 @@ where
     fn withdraw(
         who: &Pallet::AccountId,
         value: Self::Balance,
-        _: WithdrawReasons,
-        _: ExistenceRequirement,
+        reasons: WithdrawReasons,
+        existence_requirement: ExistenceRequirement,
     ) -> Result<Self::NegativeImbalance, DispatchError> {
         // Unlike `Currencies::burn`, this isn't supposed to reduce the total token supply
 
         let mut mutation = Mutation::<Pallet>::new_for_currency(GetCurrencyId::get());
         mutation.ensure_must_be_transferable_for(who)?;
-        mutation.sub_free_balance(who, value)?;
+        mutation.sub_free_balance(who, value, reasons)?;
+        if existence_requirement == ExistenceRequirement::KeepAlive {
+            mutation.ensure_keep_alive(who)?;
+        }
         mutation.forget_issuance_changes();
         mutation.apply()?;
 
@@ -189,9 +199,10 @@ where
         let mut mutation = Mutation::<Pallet>::new_for_currency(GetCurrencyId::get());
         let old_balance = mutation.overwrite_free_balance(who, value);
         mutation.forget_issuance_changes();
-        mutation
-            .apply()
-            .expect("we just forgot issuance changes which were the only error source");
+        if mutation.apply().is_err() {
+            // The account would have been created below the existential deposit
+            return SignedImbalance::Positive(PositiveImbalance::zero());
+        }
 
         if old_balance <= value {
             SignedImbalance::Positive(PositiveImbalance::new(value - old_balance))
@@ -266,13 +277,14 @@ where
         id: LockIdentifier,
         who: &Pallet::AccountId,
         amount: Self::Balance,
-        _reasons: WithdrawReasons,
+        reasons: WithdrawReasons,
     ) {
-        drop(Module::<Pallet>::set_lock(
+        drop(Module::<Pallet>::set_lock_with_reasons(
             GetCurrencyId::get(),
             id,
             who,
             amount,
+            reasons,
         ))
     }
 
@@ -280,13 +292,14 @@ where
         id: LockIdentifier,
         who: &Pallet::AccountId,
         amount: Self::Balance,
-        _reasons: WithdrawReasons,
+        reasons: WithdrawReasons,
     ) {
-        drop(Module::<Pallet>::extend_lock(
+        drop(Module::<Pallet>::extend_lock_with_reasons(
             GetCurrencyId::get(),
             id,
             who,
             amount,
+            reasons,
         ))
     }
 
@@ -0,0 +1,160 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{
+    mutations::Mutation, AirdropClaims, Airdrops, Config, CurrencyAirdrops, Error, Module,
+    NextAirdropId, RawEvent,
+};
+use codec::{Decode, Encode};
+use frame_support::{ensure, traits::WithdrawReasons, StorageDoubleMap, StorageMap, StorageValue};
+use governance_os_support::traits::ReservableCurrencies;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+    traits::{Hash, Saturating},
+    DispatchResult, RuntimeDebug,
+};
+
+/// Longest merkle proof accepted when claiming an airdrop, this is enough for
+/// trees with billions of leaves.
+pub const MAX_AIRDROP_PROOF_LENGTH: u32 = 32;
+
+/// Coins reserved by an account to be claimed by the leaves of a merkle tree.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Airdrop<AccountId, CurrencyId, Balance, Hash> {
+    pub currency_id: CurrencyId,
+
+    /// Account whose coins are reserved, they are given back to it once the
+    /// airdrop is closed.
+    pub creator: AccountId,
+
+    /// Root of the merkle tree of the `(account, amount)` that can be claimed.
+    pub merkle_root: Hash,
+
+    /// Coins that were not claimed yet.
+    pub remaining: Balance,
+}
+
+impl<T: Config> Module<T> {
+    pub fn do_create_airdrop(
+        creator: T::AccountId,
+        currency_id: T::CurrencyId,
+        merkle_root: T::Hash,
+        amount: T::Balance,
+    ) -> DispatchResult {
+        let airdrop_id = Self::next_airdrop_id();
+        let next_airdrop_id = airdrop_id
+            .checked_add(1)
+            .ok_or(Error::<T>::NoAvailableAirdropId)?;
+
+        // Airdrops are a delayed transfer and thus subject to the same permissions.
+        let mut mutation = Mutation::<T>::new_for_currency(currency_id);
+        mutation.ensure_must_be_transferable_for(&creator)?;
+        mutation.sub_free_balance(&creator, amount, WithdrawReasons::RESERVE)?;
+        mutation.add_reserved_balance(&creator, amount)?;
+        mutation.apply()?;
+
+        Airdrops::<T>::insert(
+            airdrop_id,
+            Airdrop {
+                currency_id,
+                creator: creator.clone(),
+                merkle_root,
+                remaining: amount,
+            },
+        );
+        CurrencyAirdrops::<T>::insert(currency_id, airdrop_id, true);
+        NextAirdropId::put(next_airdrop_id);
+
+        Self::deposit_event(RawEvent::AirdropCreated(
+            airdrop_id,
+            currency_id,
+            creator,
+            merkle_root,
+            amount,
+        ));
+        Ok(())
+    }
+
+    pub fn do_claim_airdrop(
+        airdrop_id: u32,
+        who: T::AccountId,
+        amount: T::Balance,
+        proof: &[T::Hash],
+    ) -> DispatchResult {
+        let mut airdrop = Self::airdrops(airdrop_id).ok_or(Error::<T>::UnknownAirdrop)?;
+        ensure!(
+            !AirdropClaims::<T>::get(airdrop_id, &who),
+            Error::<T>::AirdropAlreadyClaimed
+        );
+        ensure!(
+            proof.len() as u32 <= MAX_AIRDROP_PROOF_LENGTH,
+            Error::<T>::InvalidMerkleProof
+        );
+        ensure!(
+            Self::compute_merkle_root(T::Hashing::hash_of(&(&who, amount)), proof)
+                == airdrop.merkle_root,
+            Error::<T>::InvalidMerkleProof
+        );
+        ensure!(airdrop.remaining >= amount, Error::<T>::BalanceTooLow);
+
+        let mut mutation = Mutation::<T>::new_for_currency(airdrop.currency_id);
+        mutation.ensure_can_receive(&who)?;
+        let unreserved = mutation.sub_up_to_reserved_balance(&airdrop.creator, amount);
+        ensure!(unreserved == amount, Error::<T>::BalanceTooLow);
+        mutation.add_free_balance(&who, amount)?;
+        mutation.apply()?;
+
+        airdrop.remaining = airdrop.remaining.saturating_sub(amount);
+        Airdrops::<T>::insert(airdrop_id, airdrop);
+        AirdropClaims::<T>::insert(airdrop_id, &who, true);
+
+        Self::deposit_event(RawEvent::AirdropClaimed(airdrop_id, who, amount));
+        Ok(())
+    }
+
+    pub fn do_close_airdrop(airdrop_id: u32, who: &T::AccountId) -> DispatchResult {
+        let airdrop = Self::airdrops(airdrop_id).ok_or(Error::<T>::UnknownAirdrop)?;
+        ensure!(airdrop.creator == *who, Error::<T>::NotAirdropCreator);
+
+        // If some of the reserved coins were slashed there is nothing more we can give back.
+        let _ = <Self as ReservableCurrencies<T::AccountId>>::unreserve(
+            airdrop.currency_id,
+            who,
+            airdrop.remaining,
+        );
+        Airdrops::<T>::remove(airdrop_id);
+        AirdropClaims::<T>::remove_prefix(airdrop_id);
+        CurrencyAirdrops::<T>::remove(airdrop.currency_id, airdrop_id);
+
+        Self::deposit_event(RawEvent::AirdropClosed(airdrop_id, airdrop.remaining));
+        Ok(())
+    }
+
+    /// Hash `leaf` with every node of `proof` to compute the root of the merkle tree. Pairs
+    /// of nodes are sorted before being hashed so that proofs do not need to include the
+    /// position of the leaf.
+    pub(crate) fn compute_merkle_root(leaf: T::Hash, proof: &[T::Hash]) -> T::Hash {
+        proof.iter().fold(leaf, |node, sibling| {
+            if node <= *sibling {
+                T::Hashing::hash_of(&(node, sibling))
+            } else {
+                T::Hashing::hash_of(&(sibling, node))
+            }
+        })
+    }
+}
@@ -16,28 +16,47 @@
 
 use crate::*;
 use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
 use governance_os_support::traits::Currencies;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::traits::{Bounded, Hash, StaticLookup};
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
 
+/// Return an account with enough native currency to pay for the deposits.
+fn funded_caller<T: Config>() -> T::AccountId {
+    let caller: T::AccountId = whitelisted_caller();
+    let _ = <Module<T> as Currencies<T::AccountId>>::mint(
+        T::NativeCurrencyId::get(),
+        &caller,
+        T::Balance::max_value() / 2u32.into(),
+    );
+    caller
+}
+
 benchmarks! {
     create {
         let token_id: T::CurrencyId = T::CurrencyId::default();
-        let caller: T::AccountId = whitelisted_caller();
-    }: _(RawOrigin::Signed(caller.clone()), token_id, true)
+        let caller: T::AccountId = funded_caller::<T>();
+    }: _(RawOrigin::Signed(caller.clone()), token_id, true, None)
     verify {
         assert_eq!(TotalIssuances::<T>::contains_key(token_id), true);
     }
 
+    create_auto {
+        let caller: T::AccountId = funded_caller::<T>();
+    }: _(RawOrigin::Signed(caller.clone()), true, None)
+    verify {
+        assert_eq!(NextCurrencyIndex::get(), 1);
+    }
+
     mint {
         let token_id: T::CurrencyId = T::CurrencyId::default();
         let coins_to_mint: T::Balance = 10_000_000u32.into();
-        let caller: T::AccountId = whitelisted_caller();
+        let caller: T::AccountId = funded_caller::<T>();
 
-        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true);
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
 
         let to: T::AccountId = account("to", 0, SEED);
         let to_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(to.clone());
@@ -50,12 +69,12 @@ benchmarks! {
     burn {
         let token_id: T::CurrencyId = T::CurrencyId::default();
         let coins_to_burn: T::Balance = 10_000_000u32.into();
-        let caller: T::AccountId = whitelisted_caller();
+        let caller: T::AccountId = funded_caller::<T>();
 
         let to: T::AccountId = account("to", 0, SEED);
         let to_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(to.clone());
 
-        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true);
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
         let _ = <Module<T> as Currencies<T::AccountId>>::mint(token_id, &to, coins_to_burn);
     }: _(RawOrigin::Signed(caller.clone()), token_id, to_lookup, coins_to_burn)
     verify {
@@ -65,28 +84,51 @@ benchmarks! {
 
     update_details {
         let token_id: T::CurrencyId = T::CurrencyId::default();
-        let caller: T::AccountId = whitelisted_caller();
+        let caller: T::AccountId = funded_caller::<T>();
 
-        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true);
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
 
         // Worst case scenario: owner is changed
         let new_owner: T::AccountId = account("owner", 0, SEED);
         let new_details = CurrencyDetails {
             owner: new_owner.clone(),
             transferable: true,
+            restricted_receivers: false,
+            existential_deposit: 0u32.into(),
+            max_supply: None,
+            issuance: None,
         };
     }: _(RawOrigin::Signed(caller.clone()), token_id, new_details)
     verify {
         assert_eq!(RoleManagerOf::<T>::has_role(&new_owner, RoleBuilderOf::<T>::manage_currency(token_id)), true);
     }
 
+    set_metadata {
+        let b in 0 .. T::MaxMetadataLength::get();
+
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let caller: T::AccountId = funded_caller::<T>();
+
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
+
+        let metadata = CurrencyMetadata {
+            name: vec![0; b as usize],
+            symbol: vec![],
+            decimals: 12,
+            uri: None,
+        };
+    }: _(RawOrigin::Signed(caller.clone()), token_id, metadata.clone())
+    verify {
+        assert_eq!(Metadata::<T>::get(token_id), Some(metadata));
+    }
+
     transfer {
         let token_id: T::CurrencyId = T::CurrencyId::default();
         let coins_to_transfer: T::Balance = 10_000_000u32.into();
-        let caller: T::AccountId = whitelisted_caller();
+        let caller: T::AccountId = funded_caller::<T>();
         let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
 
-        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true);
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
         let _ = Module::<T>::mint(RawOrigin::Signed(caller.clone()).into(), token_id, caller_lookup, coins_to_transfer);
 
         let to: T::AccountId = account("to", 0, SEED);
@@ -96,6 +138,230 @@ benchmarks! {
         assert_eq!(<Module<T> as Currencies<T::AccountId>>::free_balance(token_id, &caller), 0u32.into());
         assert_eq!(<Module<T> as Currencies<T::AccountId>>::free_balance(token_id, &to), coins_to_transfer);
     }
+
+    approve {
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let amount: T::Balance = 10_000_000u32.into();
+        let caller: T::AccountId = whitelisted_caller();
+        let spender: T::AccountId = account("spender", 0, SEED);
+        let spender_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(spender.clone());
+    }: _(RawOrigin::Signed(caller.clone()), token_id, spender_lookup, amount)
+    verify {
+        assert_eq!(Allowances::<T>::get(token_id, (&caller, &spender)), amount);
+    }
+
+    transfer_from {
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let coins_to_transfer: T::Balance = 10_000_000u32.into();
+        let owner: T::AccountId = funded_caller::<T>();
+        let owner_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(owner.clone());
+        let spender: T::AccountId = account("spender", 0, SEED);
+        let spender_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(spender.clone());
+
+        let _ = Module::<T>::create(RawOrigin::Signed(owner.clone()).into(), token_id, true, None);
+        let _ = Module::<T>::mint(RawOrigin::Signed(owner.clone()).into(), token_id, owner_lookup.clone(), coins_to_transfer);
+        let _ = Module::<T>::approve(RawOrigin::Signed(owner.clone()).into(), token_id, spender_lookup, coins_to_transfer);
+
+        let to: T::AccountId = account("to", 0, SEED);
+        let to_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(to.clone());
+    }: _(RawOrigin::Signed(spender.clone()), token_id, owner_lookup, to_lookup, coins_to_transfer)
+    verify {
+        assert_eq!(<Module<T> as Currencies<T::AccountId>>::free_balance(token_id, &to), coins_to_transfer);
+        assert_eq!(Allowances::<T>::get(token_id, (&owner, &spender)), 0u32.into());
+    }
+
+    freeze {
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let caller: T::AccountId = funded_caller::<T>();
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
+
+        let who: T::AccountId = account("who", 0, SEED);
+        let who_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(who.clone());
+    }: _(RawOrigin::Signed(caller), token_id, who_lookup)
+    verify {
+        assert!(FrozenAccounts::<T>::get(token_id, &who));
+    }
+
+    thaw {
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let caller: T::AccountId = funded_caller::<T>();
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
+
+        let who: T::AccountId = account("who", 0, SEED);
+        let who_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(who.clone());
+        let _ = Module::<T>::freeze(RawOrigin::Signed(caller.clone()).into(), token_id, who_lookup.clone());
+    }: _(RawOrigin::Signed(caller), token_id, who_lookup)
+    verify {
+        assert!(!FrozenAccounts::<T>::get(token_id, &who));
+    }
+
+    force_transfer {
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let coins_to_transfer: T::Balance = 10_000_000u32.into();
+        let caller: T::AccountId = funded_caller::<T>();
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, false, None);
+
+        let from: T::AccountId = account("from", 0, SEED);
+        let from_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(from.clone());
+        let _ = Module::<T>::mint(RawOrigin::Signed(caller.clone()).into(), token_id, from_lookup.clone(), coins_to_transfer);
+        let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
+    }: _(RawOrigin::Signed(caller.clone()), token_id, from_lookup, caller_lookup, coins_to_transfer)
+    verify {
+        assert_eq!(<Module<T> as Currencies<T::AccountId>>::free_balance(token_id, &caller), coins_to_transfer);
+    }
+
+    transfer_batch {
+        let n in 1 .. T::MaxBatchSize::get();
+
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let coins_to_transfer: T::Balance = 10_000_000u32.into();
+        let caller: T::AccountId = funded_caller::<T>();
+        let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
+
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
+        let _ = Module::<T>::mint(RawOrigin::Signed(caller.clone()).into(), token_id, caller_lookup, coins_to_transfer * n.into());
+
+        let transfers = (0 .. n)
+            .map(|i| (T::Lookup::unlookup(account("to", i, SEED)), coins_to_transfer))
+            .collect::<Vec<_>>();
+    }: _(RawOrigin::Signed(caller.clone()), token_id, transfers)
+    verify {
+        assert_eq!(<Module<T> as Currencies<T::AccountId>>::free_balance(token_id, &caller), 0u32.into());
+    }
+
+    mint_batch {
+        let n in 1 .. T::MaxBatchSize::get();
+
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let coins_to_mint: T::Balance = 10_000_000u32.into();
+        let caller: T::AccountId = funded_caller::<T>();
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
+
+        let mints = (0 .. n)
+            .map(|i| (T::Lookup::unlookup(account("to", i, SEED)), coins_to_mint))
+            .collect::<Vec<_>>();
+    }: _(RawOrigin::Signed(caller), token_id, mints)
+    verify {
+        assert_eq!(TotalIssuances::<T>::get(token_id), coins_to_mint * n.into());
+    }
+
+    create_airdrop {
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let amount: T::Balance = 10_000_000u32.into();
+        let caller: T::AccountId = funded_caller::<T>();
+        let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
+
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
+        let _ = Module::<T>::mint(RawOrigin::Signed(caller.clone()).into(), token_id, caller_lookup, amount);
+    }: _(RawOrigin::Signed(caller.clone()), token_id, T::Hash::default(), amount)
+    verify {
+        assert_eq!(Airdrops::<T>::get(0).unwrap().remaining, amount);
+    }
+
+    claim_airdrop {
+        let p in 0 .. MAX_AIRDROP_PROOF_LENGTH;
+
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let amount: T::Balance = 10_000_000u32.into();
+        let creator: T::AccountId = funded_caller::<T>();
+        let creator_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(creator.clone());
+
+        let _ = Module::<T>::create(RawOrigin::Signed(creator.clone()).into(), token_id, true, None);
+        let _ = Module::<T>::mint(RawOrigin::Signed(creator.clone()).into(), token_id, creator_lookup, amount);
+
+        let claimer: T::AccountId = account("claimer", 0, SEED);
+        let proof = (0 .. p).map(|i| T::Hashing::hash_of(&i)).collect::<Vec<_>>();
+        let root = Module::<T>::compute_merkle_root(T::Hashing::hash_of(&(&claimer, amount)), &proof);
+        let _ = Module::<T>::create_airdrop(RawOrigin::Signed(creator).into(), token_id, root, amount);
+    }: _(RawOrigin::Signed(claimer.clone()), 0, amount, proof)
+    verify {
+        assert_eq!(<Module<T> as Currencies<T::AccountId>>::free_balance(token_id, &claimer), amount);
+    }
+
+    close_airdrop {
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let amount: T::Balance = 10_000_000u32.into();
+        let caller: T::AccountId = funded_caller::<T>();
+        let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
+
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
+        let _ = Module::<T>::mint(RawOrigin::Signed(caller.clone()).into(), token_id, caller_lookup, amount);
+        let _ = Module::<T>::create_airdrop(RawOrigin::Signed(caller.clone()).into(), token_id, T::Hash::default(), amount);
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+    verify {
+        assert_eq!(<Module<T> as Currencies<T::AccountId>>::free_balance(token_id, &caller), amount);
+    }
+
+    start_destroy {
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let caller: T::AccountId = funded_caller::<T>();
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
+    }: _(RawOrigin::Signed(caller), token_id)
+    verify {
+        assert!(Destroying::<T>::get(token_id));
+    }
+
+    continue_destroy {
+        let n in 1 .. 1_000;
+
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let caller: T::AccountId = funded_caller::<T>();
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
+        for i in 0 .. n {
+            let who: T::AccountId = account("holder", i, SEED);
+            let _ = <Module<T> as Currencies<T::AccountId>>::mint(token_id, &who, 10_000_000u32.into());
+        }
+        let _ = Module::<T>::start_destroy(RawOrigin::Signed(caller.clone()).into(), token_id);
+    }: _(RawOrigin::Signed(caller), token_id, n + 1)
+    verify {
+        assert!(!TotalIssuances::<T>::contains_key(token_id));
+    }
+
+    set_fee_rate {
+        let token_id: T::CurrencyId = T::AutoCurrencyId::convert(0);
+        let caller: T::AccountId = whitelisted_caller();
+        let _ = RoleManagerOf::<T>::grant_role(Some(&caller), RoleBuilderOf::<T>::manage_fee_rates());
+    }: _(RawOrigin::Signed(caller), token_id, Some(FixedU128::one()))
+    verify {
+        assert_eq!(FeeRates::<T>::get(token_id), Some(FixedU128::one()));
+    }
+
+    set_checkpoints {
+        let token_id: T::CurrencyId = T::CurrencyId::default();
+        let caller: T::AccountId = funded_caller::<T>();
+        let _ = Module::<T>::create(RawOrigin::Signed(caller.clone()).into(), token_id, true, None);
+    }: _(RawOrigin::Signed(caller), token_id, true)
+    verify {
+        assert!(CheckpointsSince::<T>::contains_key(token_id));
+    }
+
+    on_initialize {
+        let s in 0 .. T::MaxIssuanceSchedules::get();
+
+        let caller: T::AccountId = funded_caller::<T>();
+        for _ in 0 .. s {
+            let _ = Module::<T>::create_auto(RawOrigin::Signed(caller.clone()).into(), true, None);
+            let currency_id = T::AutoCurrencyId::convert(NextCurrencyIndex::get() - 1);
+            let details = CurrencyDetails {
+                owner: caller.clone(),
+                transferable: true,
+                restricted_receivers: false,
+                existential_deposit: 0u32.into(),
+                max_supply: None,
+                issuance: Some(IssuanceSchedule {
+                    beneficiary: caller.clone(),
+                    amount: 1u32.into(),
+                    period: 1u32.into(),
+                }),
+            };
+            let _ = Module::<T>::update_details(RawOrigin::Signed(caller.clone()).into(), currency_id, details);
+        }
+    }: {
+        Module::<T>::on_initialize(1u32.into());
+    }
+    verify {
+        assert_eq!(ScheduledCurrencies::<T>::get().len() as u32, s);
+    }
 }
 
 mod tests {
@@ -0,0 +1,162 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use crate::{
+    BalanceCheckpoints, BalanceCheckpointsStart, Balances, CheckpointsSince, Config, Destroying,
+    Error, IssuanceCheckpoints, IssuanceCheckpointsStart, Module, RawEvent, TotalIssuances,
+};
+use frame_support::{ensure, traits::Get, weights::Weight, StorageDoubleMap, StorageMap};
+use governance_os_support::traits::{CheckpointedCurrencies, Currencies};
+use sp_runtime::DispatchResult;
+use sp_std::prelude::Vec;
+
+impl<T: Config> Module<T> {
+    pub fn do_set_checkpoints(currency_id: T::CurrencyId, enabled: bool) -> DispatchResult {
+        ensure!(
+            TotalIssuances::<T>::contains_key(currency_id),
+            Error::<T>::UnknownCurrency
+        );
+        ensure!(
+            !Destroying::<T>::get(currency_id),
+            Error::<T>::CurrencyDestroying
+        );
+
+        if !enabled {
+            CheckpointsSince::<T>::remove(currency_id);
+        } else if !CheckpointsSince::<T>::contains_key(currency_id) {
+            CheckpointsSince::<T>::insert(currency_id, frame_system::Module::<T>::block_number());
+        }
+        Self::deposit_event(RawEvent::CheckpointsChanged(currency_id, enabled));
+        Ok(())
+    }
+
+    /// Record the total balance `who` held before it changes to `new_total`. Must be called
+    /// before the new balance is saved and only for currencies with checkpoints.
+    pub fn checkpoint_balance(
+        currency_id: T::CurrencyId,
+        who: &T::AccountId,
+        new_total: T::Balance,
+    ) {
+        let old_total = Balances::<T>::get(who, currency_id).total();
+        if old_total != new_total {
+            if let Some(start) = BalanceCheckpoints::<T>::mutate(currency_id, who, |checkpoints| {
+                Self::push_checkpoint(checkpoints, old_total)
+            }) {
+                BalanceCheckpointsStart::<T>::insert(currency_id, who, start);
+            }
+        }
+    }
+
+    /// Record the total issuance of a currency before it changes. Must be called before the
+    /// new issuance is saved and only for currencies with checkpoints.
+    pub fn checkpoint_issuance(currency_id: T::CurrencyId) {
+        let old_issuance = Self::total_issuances(currency_id);
+        if let Some(start) = IssuanceCheckpoints::<T>::mutate(currency_id, |checkpoints| {
+            Self::push_checkpoint(checkpoints, old_issuance)
+        }) {
+            IssuanceCheckpointsStart::<T>::insert(currency_id, start);
+        }
+    }
+
+    /// Worst case weight of recording the checkpoints of `balances` accounts and, if `issuance`
+    /// is set, of the total issuance of a currency.
+    pub fn checkpoints_weight(balances: u32, issuance: bool) -> Weight {
+        let histories = Weight::from(balances).saturating_add(Weight::from(issuance));
+        // `CheckpointsSince` and, for every history, the checkpoints and their start.
+        T::DbWeight::get().reads_writes(
+            histories.saturating_mul(2).saturating_add(1),
+            histories.saturating_mul(2),
+        )
+    }
+
+    /// Checkpoints are the blocks during which a value changed along with the value it had
+    /// before, that is at the end of the previous block. Only the first change of each block
+    /// is recorded. Returns the first block values are known from if older checkpoints had to
+    /// be dropped to keep at most `MaxCheckpoints` of them.
+    fn push_checkpoint(
+        checkpoints: &mut Vec<(T::BlockNumber, T::Balance)>,
+        previous: T::Balance,
+    ) -> Option<T::BlockNumber> {
+        let now = frame_system::Module::<T>::block_number();
+        if checkpoints.last().map(|(block, _)| *block) != Some(now) {
+            checkpoints.push((now, previous));
+        }
+
+        // Values at or after the last dropped block are still recorded by the next checkpoints.
+        let excess = checkpoints
+            .len()
+            .saturating_sub(T::MaxCheckpoints::get() as usize);
+        checkpoints
+            .drain(..excess)
+            .last()
+            .map(|(dropped_on, _)| dropped_on)
+    }
+
+    /// Wether checkpoints were recorded for `currency_id` at the end of `block`.
+    fn checkpointed_at(currency_id: T::CurrencyId, block: T::BlockNumber) -> bool {
+        matches!(Self::checkpoints_since(currency_id), Some(since) if since <= block)
+    }
+
+    /// Value at the end of `block` given its `checkpoints` and `current` value.
+    fn value_at(
+        checkpoints: Vec<(T::BlockNumber, T::Balance)>,
+        current: T::Balance,
+        block: T::BlockNumber,
+    ) -> T::Balance {
+        // The first change made after `block` recorded the value we are looking for.
+        let index = match checkpoints.binary_search_by(|(changed_on, _)| changed_on.cmp(&block)) {
+            Ok(index) => index + 1,
+            Err(index) => index,
+        };
+        checkpoints
+            .get(index)
+            .map_or(current, |(_changed_on, previous)| *previous)
+    }
+}
+
+impl<T: Config> CheckpointedCurrencies<T::AccountId, T::BlockNumber> for Module<T> {
+    fn balance_at(
+        currency_id: Self::CurrencyId,
+        who: &T::AccountId,
+        block: T::BlockNumber,
+    ) -> Option<Self::Balance> {
+        if !Self::checkpointed_at(currency_id, block)
+            || matches!(Self::balance_checkpoints_start(currency_id, who), Some(start) if block < start)
+        {
+            return None;
+        }
+        Some(Self::value_at(
+            BalanceCheckpoints::<T>::get(currency_id, who),
+            Self::total_balance(currency_id, who),
+            block,
+        ))
+    }
+
+    fn total_issuance_at(
+        currency_id: Self::CurrencyId,
+        block: T::BlockNumber,
+    ) -> Option<Self::Balance> {
+        if !Self::checkpointed_at(currency_id, block)
+            || matches!(Self::issuance_checkpoints_start(currency_id), Some(start) if block < start)
+        {
+            return None;
+        }
+        Some(Self::value_at(
+            IssuanceCheckpoints::<T>::get(currency_id),
+            Self::total_issuance(currency_id),
+            block,
+        ))
+    }
+}
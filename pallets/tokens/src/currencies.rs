@@ -14,16 +14,25 @@
  * limitations under the License.
  */
 
-use crate::{mutations::Mutation, Config, Locks, Module, RawEvent};
+use crate::{
+    mutations::Mutation, Allowances, Config, Error, Locks, Module, NamedReserves, RawEvent,
+    TokenLock, TotalAllowances,
+};
 use frame_support::{
-    traits::{BalanceStatus, LockIdentifier},
+    ensure,
+    traits::{BalanceStatus, ExistenceRequirement, LockIdentifier, WithdrawReasons},
+    weights::Weight,
     IterableStorageDoubleMap, StorageDoubleMap,
 };
-use governance_os_support::traits::{Currencies, LockableCurrencies, ReservableCurrencies};
+use governance_os_support::traits::{
+    AllowanceCurrencies, Currencies, LockableCurrencies, NamedReservableCurrencies,
+    ReservableCurrencies, ReserveIdentifier,
+};
 use sp_runtime::{
     traits::{Saturating, Zero},
     DispatchError, DispatchResult,
 };
+use sp_std::prelude::Vec;
 
 impl<T: Config> Currencies<T::AccountId> for Module<T> {
     type CurrencyId = T::CurrencyId;
@@ -39,7 +48,7 @@ impl<T: Config> Currencies<T::AccountId> for Module<T> {
         amount: Self::Balance,
     ) -> DispatchResult {
         let mut mutation = Mutation::<T>::new_for_currency(currency_id);
-        mutation.sub_free_balance(who, amount)?;
+        mutation.sub_free_balance(who, amount, WithdrawReasons::all())?;
         mutation.apply()?;
 
         Self::deposit_event(RawEvent::CurrencyBurned(currency_id, who.clone(), amount));
@@ -71,13 +80,7 @@ impl<T: Config> Currencies<T::AccountId> for Module<T> {
         who: &T::AccountId,
         amount: Self::Balance,
     ) -> DispatchResult {
-        // We simulate a withdrawal but never executes it and rather returns any error that
-        // happens along the way
-        let mut mutation = Mutation::<T>::new_for_currency(currency_id);
-        mutation.ensure_must_be_transferable_for(who)?;
-        mutation.sub_free_balance(who, amount)?;
-
-        Ok(())
+        Self::ensure_can_withdraw_with_reasons(currency_id, who, amount, WithdrawReasons::TRANSFER)
     }
 
     fn transfer(
@@ -86,10 +89,37 @@ impl<T: Config> Currencies<T::AccountId> for Module<T> {
         dest: &T::AccountId,
         amount: Self::Balance,
     ) -> DispatchResult {
+        Self::do_transfer(
+            currency_id,
+            source,
+            dest,
+            amount,
+            ExistenceRequirement::AllowDeath,
+        )
+        .map(|_| ())
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// Transfer `amount` units from `source` to `dest`. If `existence_requirement` is set to
+    /// `KeepAlive` the transfer fails instead of reaping `source`. Returns the weight used
+    /// by the role checks.
+    pub fn do_transfer(
+        currency_id: T::CurrencyId,
+        source: &T::AccountId,
+        dest: &T::AccountId,
+        amount: T::Balance,
+        existence_requirement: ExistenceRequirement,
+    ) -> Result<Weight, DispatchError> {
         let mut mutation = Mutation::<T>::new_for_currency(currency_id);
         mutation.ensure_must_be_transferable_for(source)?;
-        mutation.sub_free_balance(source, amount)?;
+        mutation.ensure_can_receive(dest)?;
+        mutation.sub_free_balance(source, amount, WithdrawReasons::TRANSFER)?;
+        if existence_requirement == ExistenceRequirement::KeepAlive {
+            mutation.ensure_keep_alive(source)?;
+        }
         mutation.add_free_balance(dest, amount)?;
+        let role_checks_weight = mutation.role_checks_weight();
         mutation.apply()?;
 
         Self::deposit_event(RawEvent::CurrencyTransferred(
@@ -98,17 +128,180 @@ impl<T: Config> Currencies<T::AccountId> for Module<T> {
             dest.clone(),
             amount,
         ));
+        Ok(role_checks_weight)
+    }
+
+    /// Transfer units from `source` to every `(dest, amount)` of `transfers`. A single
+    /// `Mutation` is used so that balances are only read and written once. Returns the
+    /// weight used by the role checks.
+    pub fn do_transfer_batch(
+        currency_id: T::CurrencyId,
+        source: &T::AccountId,
+        transfers: &[(T::AccountId, T::Balance)],
+    ) -> Result<Weight, DispatchError> {
+        let mut mutation = Mutation::<T>::new_for_currency(currency_id);
+        mutation.ensure_must_be_transferable_for(source)?;
+        for (dest, amount) in transfers {
+            mutation.ensure_can_receive(dest)?;
+            mutation.sub_free_balance(source, *amount, WithdrawReasons::TRANSFER)?;
+            mutation.add_free_balance(dest, *amount)?;
+        }
+        let role_checks_weight = mutation.role_checks_weight();
+        mutation.apply()?;
+
+        transfers.iter().for_each(|(dest, amount)| {
+            Self::deposit_event(RawEvent::CurrencyTransferred(
+                currency_id,
+                source.clone(),
+                dest.clone(),
+                *amount,
+            ))
+        });
+        Ok(role_checks_weight)
+    }
+
+    /// Create units for every `(dest, amount)` of `mints`, the total issuance is only
+    /// updated once.
+    pub fn do_mint_batch(
+        currency_id: T::CurrencyId,
+        mints: &[(T::AccountId, T::Balance)],
+    ) -> DispatchResult {
+        let mut mutation = Mutation::<T>::new_for_currency(currency_id);
+        for (dest, amount) in mints {
+            mutation.add_free_balance(dest, *amount)?;
+        }
+        mutation.apply()?;
+
+        mints.iter().for_each(|(dest, amount)| {
+            Self::deposit_event(RawEvent::CurrencyMinted(currency_id, dest.clone(), *amount))
+        });
+        Ok(())
+    }
+
+    /// Same as `do_transfer` but ignores locks, frozen accounts and roles.
+    pub fn do_force_transfer(
+        currency_id: T::CurrencyId,
+        source: &T::AccountId,
+        dest: &T::AccountId,
+        amount: T::Balance,
+    ) -> DispatchResult {
+        let mut mutation = Mutation::<T>::new_for_currency(currency_id);
+        // Locks only restrict withdrawals made for one of their reasons.
+        mutation.sub_free_balance(source, amount, WithdrawReasons::empty())?;
+        mutation.add_free_balance(dest, amount)?;
+        mutation.apply()?;
+
+        Self::deposit_event(RawEvent::ForcedTransfer(
+            currency_id,
+            source.clone(),
+            dest.clone(),
+            amount,
+        ));
         Ok(())
     }
 }
 
+impl<T: Config> AllowanceCurrencies<T::AccountId> for Module<T> {
+    fn allowance(
+        currency_id: Self::CurrencyId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+    ) -> Self::Balance {
+        Self::allowances(currency_id, (owner, spender))
+    }
+
+    fn total_allowance(currency_id: Self::CurrencyId, owner: &T::AccountId) -> Self::Balance {
+        Self::total_allowances(currency_id, owner)
+    }
+
+    fn approve(
+        currency_id: Self::CurrencyId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        amount: Self::Balance,
+    ) -> DispatchResult {
+        ensure!(
+            !Self::destroying(currency_id),
+            Error::<T>::CurrencyDestroying
+        );
+        let previous = Allowances::<T>::get(currency_id, (owner, spender));
+        TotalAllowances::<T>::mutate_exists(currency_id, owner, |maybe_total| {
+            let total = maybe_total
+                .unwrap_or_else(Zero::zero)
+                .saturating_sub(previous)
+                .saturating_add(amount);
+            *maybe_total = if total.is_zero() { None } else { Some(total) };
+        });
+        if amount.is_zero() {
+            Allowances::<T>::remove(currency_id, (owner, spender));
+        } else {
+            Allowances::<T>::insert(currency_id, (owner, spender), amount);
+        }
+
+        Self::deposit_event(RawEvent::Approval(
+            currency_id,
+            owner.clone(),
+            spender.clone(),
+            amount,
+        ));
+        Ok(())
+    }
+
+    fn transfer_from(
+        currency_id: Self::CurrencyId,
+        spender: &T::AccountId,
+        owner: &T::AccountId,
+        dest: &T::AccountId,
+        amount: Self::Balance,
+    ) -> DispatchResult {
+        Self::do_transfer_from(currency_id, spender, owner, dest, amount).map(|_| ())
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// Transfer `amount` units from `owner` to `dest` out of the allowance `owner` gave to
+    /// `spender`. Returns the weight used by the role checks.
+    pub fn do_transfer_from(
+        currency_id: T::CurrencyId,
+        spender: &T::AccountId,
+        owner: &T::AccountId,
+        dest: &T::AccountId,
+        amount: T::Balance,
+    ) -> Result<Weight, DispatchError> {
+        let allowance = Self::allowance(currency_id, owner, spender);
+        ensure!(allowance >= amount, Error::<T>::AllowanceTooLow);
+
+        // Goes through the same checks, including the transfer role, as if `owner`
+        // made the transfer.
+        let role_checks_weight = Self::do_transfer(
+            currency_id,
+            owner,
+            dest,
+            amount,
+            ExistenceRequirement::AllowDeath,
+        )?;
+
+        <Self as AllowanceCurrencies<T::AccountId>>::approve(
+            currency_id,
+            owner,
+            spender,
+            allowance.saturating_sub(amount),
+        )?;
+        Ok(role_checks_weight)
+    }
+}
+
 impl<T: Config> ReservableCurrencies<T::AccountId> for Module<T> {
     fn can_reserve(
         currency_id: Self::CurrencyId,
         who: &T::AccountId,
         amount: Self::Balance,
     ) -> bool {
-        Self::ensure_can_withdraw(currency_id, who, amount).is_ok()
+        // Same as `reserve` but without applying the changes
+        let mut mutation = Mutation::<T>::new_for_currency(currency_id);
+        mutation
+            .sub_free_balance(who, amount, WithdrawReasons::RESERVE)
+            .is_ok()
     }
 
     fn slash_reserved(
@@ -138,7 +331,7 @@ impl<T: Config> ReservableCurrencies<T::AccountId> for Module<T> {
         // We do not require the asset to be transferable, it is assumed that it is acceptable
         // to reserve non transferable currencies
         let mut mutation = Mutation::<T>::new_for_currency(currency_id);
-        mutation.sub_free_balance(who, amount)?;
+        mutation.sub_free_balance(who, amount, WithdrawReasons::RESERVE)?;
         mutation.add_reserved_balance(who, amount)?;
         mutation.apply()?;
 
@@ -196,121 +389,221 @@ impl<T: Config> ReservableCurrencies<T::AccountId> for Module<T> {
     }
 }
 
+impl<T: Config> NamedReservableCurrencies<T::AccountId> for Module<T> {
+    fn reserved_balance_named(
+        currency_id: Self::CurrencyId,
+        id: ReserveIdentifier,
+        who: &T::AccountId,
+    ) -> Self::Balance {
+        Self::named_reserves((who, currency_id), id)
+    }
+
+    fn reserve_named(
+        currency_id: Self::CurrencyId,
+        id: ReserveIdentifier,
+        who: &T::AccountId,
+        value: Self::Balance,
+    ) -> DispatchResult {
+        Self::reserve(currency_id, who, value)?;
+        Self::update_named_reserve(currency_id, id, who, |reserved| {
+            reserved.saturating_add(value)
+        });
+        Ok(())
+    }
+
+    fn unreserve_named(
+        currency_id: Self::CurrencyId,
+        id: ReserveIdentifier,
+        who: &T::AccountId,
+        value: Self::Balance,
+    ) -> Self::Balance {
+        let to_unreserve = value.min(Self::reserved_balance_named(currency_id, id, who));
+        let actual = to_unreserve - Self::unreserve(currency_id, who, to_unreserve);
+        Self::update_named_reserve(currency_id, id, who, |reserved| {
+            reserved.saturating_sub(actual)
+        });
+        value - actual
+    }
+
+    fn slash_reserved_named(
+        currency_id: Self::CurrencyId,
+        id: ReserveIdentifier,
+        who: &T::AccountId,
+        value: Self::Balance,
+    ) -> Self::Balance {
+        let to_slash = value.min(Self::reserved_balance_named(currency_id, id, who));
+        let actual = to_slash - Self::slash_reserved(currency_id, who, to_slash);
+        Self::update_named_reserve(currency_id, id, who, |reserved| {
+            reserved.saturating_sub(actual)
+        });
+        value - actual
+    }
+
+    fn repatriate_reserved_named(
+        currency_id: Self::CurrencyId,
+        id: ReserveIdentifier,
+        slashed: &T::AccountId,
+        beneficiary: &T::AccountId,
+        value: Self::Balance,
+        status: BalanceStatus,
+    ) -> Result<Self::Balance, DispatchError> {
+        if slashed == beneficiary {
+            return match status {
+                BalanceStatus::Free => Ok(Self::unreserve_named(currency_id, id, slashed, value)),
+                BalanceStatus::Reserved => Ok(value.saturating_sub(Self::reserved_balance_named(
+                    currency_id,
+                    id,
+                    slashed,
+                ))),
+            };
+        }
+
+        let to_move = value.min(Self::reserved_balance_named(currency_id, id, slashed));
+        let actual = to_move
+            - Self::repatriate_reserved(currency_id, slashed, beneficiary, to_move, status)?;
+        Self::update_named_reserve(currency_id, id, slashed, |reserved| {
+            reserved.saturating_sub(actual)
+        });
+        if status == BalanceStatus::Reserved {
+            Self::update_named_reserve(currency_id, id, beneficiary, |reserved| {
+                reserved.saturating_add(actual)
+            });
+        }
+        Ok(value - actual)
+    }
+}
+
 // Some helper to avoid code repetition when using locks
 impl<T: Config> Module<T> {
-    fn conditionally_write_lock<Cond: FnOnce(T::Balance) -> bool>(
+    /// Apply `f` to the named reserve `id` of `who`, removing it once it reaches zero.
+    fn update_named_reserve(
+        currency_id: T::CurrencyId,
+        id: ReserveIdentifier,
+        who: &T::AccountId,
+        f: impl FnOnce(T::Balance) -> T::Balance,
+    ) {
+        let reserved = f(Self::named_reserves((who, currency_id), id));
+        if reserved.is_zero() {
+            NamedReserves::<T>::remove((who, currency_id), id);
+        } else {
+            NamedReserves::<T>::insert((who, currency_id), id, reserved);
+        }
+    }
+
+    /// Write, or remove if `maybe_lock` is `None`, the lock `lock_id` of `who` and recompute
+    /// the frozen balances of `who` from all of its locks.
+    fn update_lock(
         currency_id: T::CurrencyId,
         lock_id: LockIdentifier,
         who: &T::AccountId,
-        amount: T::Balance,
-        condition: Cond,
+        maybe_lock: Option<TokenLock<T::Balance>>,
     ) -> DispatchResult {
-        Locks::<T>::try_mutate_exists(
-            (who, currency_id),
-            lock_id,
-            |maybe_existing_lock| -> DispatchResult {
-                let mut mutation = Mutation::<T>::new_for_currency(currency_id);
-
-                if maybe_existing_lock.is_none() {
-                    // We are creating a new lock. Add a few checks to handle cases when
-                    // more than one lock is present.
-                    if mutation.frozen(who) < amount {
-                        mutation.add_frozen(who, amount)?;
-                    }
-
-                    // A new lock is being created, inc the system ref
-                    if frame_system::Pallet::<T>::inc_consumers(who).is_err() {
-                        log::warn!(
-                            target: "runtime::tokens",
-                            "Warning: Attempt to introduce lock consumer reference, yet no providers. \
-                            This is unexpected but should be safe."
-                        );
-                    }
-                } else {
-                    // We are overwriting an existing lock
-                    let existing_lock = maybe_existing_lock
-                        .take()
-                        .expect("we just did a is_none check");
-
-                    // If it isn't necessary to change anything we stop here
-                    if !condition(existing_lock) {
-                        return Ok(());
-                    }
-
-                    // We check that it is needed to increase the locked amounts,
-                    // or not.
-                    if mutation.frozen(who).saturating_sub(existing_lock) < amount {
-                        // We use the fact that the mutation helper keeps things in memory,
-                        // so substracting and adding values to a balance does not require
-                        // multiple DB reads.
-                        mutation.sub_frozen(who, existing_lock)?;
-                        mutation.add_frozen(who, amount)?;
-                    }
-                }
-
-                // Locks do not impact total issuance
-                mutation.forget_issuance_changes();
+        let mut locks = Vec::new();
+        let mut existed = false;
+        Locks::<T>::iter_prefix((who, currency_id)).for_each(|(id, lock)| {
+            if id == lock_id {
+                existed = true;
+            } else {
+                locks.push(lock);
+            }
+        });
+        if let Some(lock) = &maybe_lock {
+            locks.push(lock.clone());
+        }
 
-                // Update balance
-                mutation.apply()?;
+        let mut mutation = Mutation::<T>::new_for_currency(currency_id);
+        mutation.overwrite_frozen_balances(who, locks);
+        mutation.apply()?;
 
-                // Write the lock
-                *maybe_existing_lock = Some(amount);
+        match maybe_lock {
+            Some(lock) => {
+                // A new lock is being created, inc the system ref
+                if !existed && frame_system::Pallet::<T>::inc_consumers(who).is_err() {
+                    log::warn!(
+                        target: "runtime::tokens",
+                        "Warning: Attempt to introduce lock consumer reference, yet no providers. \
+                        This is unexpected but should be safe."
+                    );
+                }
+                Locks::<T>::insert((who, currency_id), lock_id, lock);
+            }
+            None => {
+                if existed {
+                    frame_system::Pallet::<T>::dec_consumers(who);
+                }
+                Locks::<T>::remove((who, currency_id), lock_id);
+            }
+        }
 
-                Ok(())
-            },
-        )
+        Ok(())
     }
 }
 
 impl<T: Config> LockableCurrencies<T::AccountId> for Module<T> {
     fn locked_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
-        Self::get_currency_account(currency_id, who).frozen
+        Self::get_currency_account(currency_id, who).max_frozen()
     }
 
-    fn set_lock(
+    fn set_lock_with_reasons(
         currency_id: Self::CurrencyId,
         lock_id: LockIdentifier,
         who: &T::AccountId,
         amount: Self::Balance,
+        reasons: WithdrawReasons,
     ) -> DispatchResult {
-        Self::conditionally_write_lock(currency_id, lock_id, who, amount, |_| true)
+        Self::update_lock(
+            currency_id,
+            lock_id,
+            who,
+            Some(TokenLock { amount, reasons }),
+        )
     }
 
-    fn extend_lock(
+    fn extend_lock_with_reasons(
         currency_id: Self::CurrencyId,
         lock_id: LockIdentifier,
         who: &T::AccountId,
         amount: Self::Balance,
+        reasons: WithdrawReasons,
     ) -> DispatchResult {
-        Self::conditionally_write_lock(currency_id, lock_id, who, amount, |existing_lock| {
-            existing_lock < amount
-        })
+        let lock = match Self::locks((who, currency_id), lock_id) {
+            Some(existing) => {
+                let extended = TokenLock {
+                    amount: existing.amount.max(amount),
+                    reasons: existing.reasons | reasons,
+                };
+                // If it isn't necessary to change anything we stop here
+                if extended == existing {
+                    return Ok(());
+                }
+                extended
+            }
+            None => TokenLock { amount, reasons },
+        };
+
+        Self::update_lock(currency_id, lock_id, who, Some(lock))
     }
 
-    fn remove_lock(
+    fn ensure_can_withdraw_with_reasons(
         currency_id: Self::CurrencyId,
-        lock_id: LockIdentifier,
         who: &T::AccountId,
+        amount: Self::Balance,
+        reasons: WithdrawReasons,
     ) -> DispatchResult {
-        Locks::<T>::remove((who, currency_id), lock_id);
-        frame_system::Pallet::<T>::dec_consumers(who);
-
-        let highest_lock_value = Locks::<T>::iter_prefix((who, currency_id)).fold(
-            Zero::zero(),
-            |acc, (_lock_id, lock_val)| {
-                if acc < lock_val {
-                    lock_val
-                } else {
-                    acc
-                }
-            },
-        );
-
+        // We simulate a withdrawal but never executes it and rather returns any error that
+        // happens along the way
         let mut mutation = Mutation::<T>::new_for_currency(currency_id);
-        mutation.overwrite_frozen_balance(who, highest_lock_value);
-        mutation.apply()?;
+        mutation.ensure_must_be_transferable_for(who)?;
+        mutation.sub_free_balance(who, amount, reasons)?;
 
         Ok(())
     }
+
+    fn remove_lock(
+        currency_id: Self::CurrencyId,
+        lock_id: LockIdentifier,
+        who: &T::AccountId,
+    ) -> DispatchResult {
+        Self::update_lock(currency_id, lock_id, who, None)
+    }
 }
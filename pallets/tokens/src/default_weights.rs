@@ -27,6 +27,11 @@ impl crate::WeightInfo for () {
             .saturating_add(DbWeight::get().reads(7 as Weight))
             .saturating_add(DbWeight::get().writes(5 as Weight))
     }
+    fn create_auto() -> Weight {
+        (128_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(9 as Weight))
+            .saturating_add(DbWeight::get().writes(7 as Weight))
+    }
     fn mint() -> Weight {
         (87_000_000 as Weight)
             .saturating_add(DbWeight::get().reads(8 as Weight))
@@ -42,9 +47,100 @@ impl crate::WeightInfo for () {
             .saturating_add(DbWeight::get().reads(7 as Weight))
             .saturating_add(DbWeight::get().writes(4 as Weight))
     }
+    fn set_metadata(b: u32) -> Weight {
+        (61_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
     fn transfer() -> Weight {
         (97_000_000 as Weight)
-            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().reads(10 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+    fn approve() -> Weight {
+        (25_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn transfer_from() -> Weight {
+        (115_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(11 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+    fn freeze() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn thaw() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn force_transfer() -> Weight {
+        (90_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(6 as Weight))
             .saturating_add(DbWeight::get().writes(4 as Weight))
     }
+    fn transfer_batch(n: u32) -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add((45_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
+    fn mint_batch(n: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((35_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
+    fn create_airdrop() -> Weight {
+        (70_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    fn claim_airdrop(p: u32) -> Weight {
+        (90_000_000 as Weight)
+            .saturating_add((1_500_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+    fn close_airdrop() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    fn start_destroy() -> Weight {
+        (35_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn continue_destroy(n: u32) -> Weight {
+        (150_000_000 as Weight)
+            .saturating_add((40_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(DbWeight::get().reads(12 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes(14 as Weight))
+            .saturating_add(DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+    }
+    fn set_fee_rate() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_checkpoints() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn on_initialize(s: u32) -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add((90_000_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(s as Weight)))
+    }
 }
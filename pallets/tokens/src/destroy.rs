@@ -0,0 +1,193 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{
+    mutations::dec_account_references, AirdropClaims, Airdrops, Allowances, BalanceCheckpoints,
+    BalanceCheckpointsStart, Balances, CheckpointsSince, Config, CurrencyAccounts,
+    CurrencyAirdrops, CurrencyDeposits, Destroying, Details, Error, FeeRates, FrozenAccounts,
+    IssuanceCheckpoints, IssuanceCheckpointsStart, Locks, Metadata, MetadataDeposits, Module,
+    NamedReserves, RawEvent, RoleBuilder, RoleBuilderOf, RoleManagerOf, ScheduledCurrencies,
+    TotalAllowances, TotalIssuances,
+};
+use frame_support::{
+    ensure, traits::Get, IterableStorageDoubleMap, StorageDoubleMap, StorageMap, StorageValue,
+};
+use governance_os_support::traits::{OnBalanceChanged, ReservableCurrencies, RoleManager};
+use sp_runtime::{traits::Zero, DispatchResult};
+use sp_std::prelude::Vec;
+
+impl<T: Config> Module<T> {
+    pub fn do_start_destroy(currency_id: T::CurrencyId) -> DispatchResult {
+        ensure!(
+            currency_id != T::NativeCurrencyId::get(),
+            Error::<T>::CannotDestroyNativeCurrency
+        );
+        ensure!(
+            TotalIssuances::<T>::contains_key(currency_id),
+            Error::<T>::UnknownCurrency
+        );
+        ensure!(
+            !Self::destroying(currency_id),
+            Error::<T>::CurrencyDestroying
+        );
+
+        Destroying::<T>::insert(currency_id, true);
+        // Balances are about to be removed without being checkpointed.
+        CheckpointsSince::<T>::remove(currency_id);
+        Self::deposit_event(RawEvent::DestroyStarted(currency_id));
+        Ok(())
+    }
+
+    /// Remove up to `max_items` storage entries related to `currency_id` and remove the
+    /// currency itself once there are none left.
+    pub fn do_continue_destroy(currency_id: T::CurrencyId, max_items: u32) -> DispatchResult {
+        ensure!(Self::destroying(currency_id), Error::<T>::NotDestroying);
+
+        let mut budget = max_items as usize;
+
+        // Keys are collected first since we can not remove them while iterating.
+        let accounts = CurrencyAccounts::<T>::iter_prefix(currency_id)
+            .take(budget)
+            .map(|(who, _)| who)
+            .collect::<Vec<_>>();
+        for who in accounts.iter() {
+            // Locks and named reserves count against the budget too, the account itself is
+            // only removed once they are all gone.
+            let locks = Locks::<T>::iter_prefix((who, currency_id))
+                .take(budget)
+                .map(|(lock_id, _)| lock_id)
+                .collect::<Vec<_>>();
+            locks.iter().for_each(|lock_id| {
+                frame_system::Pallet::<T>::dec_consumers(who);
+                Locks::<T>::remove((who, currency_id), lock_id);
+            });
+            budget -= locks.len();
+
+            let reserves = NamedReserves::<T>::iter_prefix((who, currency_id))
+                .take(budget)
+                .map(|(reserve_id, _)| reserve_id)
+                .collect::<Vec<_>>();
+            reserves
+                .iter()
+                .for_each(|reserve_id| NamedReserves::<T>::remove((who, currency_id), reserve_id));
+            budget -= reserves.len();
+
+            if budget == 0 {
+                break;
+            }
+            dec_account_references::<T>(who);
+            Balances::<T>::remove(who, currency_id);
+            FrozenAccounts::<T>::remove(currency_id, who);
+            CurrencyAccounts::<T>::remove(currency_id, who);
+            T::OnBalanceChanged::on_balance_changed(currency_id, who, Zero::zero(), Zero::zero());
+            budget -= 1;
+        }
+
+        let allowances = Allowances::<T>::iter_prefix(currency_id)
+            .take(budget)
+            .map(|(owner_and_spender, _)| owner_and_spender)
+            .collect::<Vec<_>>();
+        allowances.iter().for_each(|owner_and_spender| {
+            Allowances::<T>::remove(currency_id, owner_and_spender);
+            TotalAllowances::<T>::remove(currency_id, &owner_and_spender.0);
+        });
+        budget -= allowances.len();
+
+        let checkpointed = BalanceCheckpoints::<T>::iter_prefix(currency_id)
+            .take(budget)
+            .map(|(who, _)| who)
+            .collect::<Vec<_>>();
+        // Histories are always started along with their checkpoints.
+        checkpointed.iter().for_each(|who| {
+            BalanceCheckpoints::<T>::remove(currency_id, who);
+            BalanceCheckpointsStart::<T>::remove(currency_id, who);
+        });
+        budget -= checkpointed.len();
+
+        // Frozen accounts without any balance left.
+        let frozen = FrozenAccounts::<T>::iter_prefix(currency_id)
+            .take(budget)
+            .map(|(who, _)| who)
+            .collect::<Vec<_>>();
+        frozen
+            .iter()
+            .for_each(|who| FrozenAccounts::<T>::remove(currency_id, who));
+        budget -= frozen.len();
+
+        // Reserved coins were removed with the balances, nothing can be claimed anymore.
+        let airdrops = CurrencyAirdrops::<T>::iter_prefix(currency_id)
+            .take(budget)
+            .map(|(airdrop_id, _)| airdrop_id)
+            .collect::<Vec<_>>();
+        airdrops.iter().for_each(|airdrop_id| {
+            Airdrops::<T>::remove(airdrop_id);
+            AirdropClaims::<T>::remove_prefix(airdrop_id);
+            CurrencyAirdrops::<T>::remove(currency_id, airdrop_id);
+        });
+        budget -= airdrops.len();
+
+        let removed = max_items.saturating_sub(budget as u32);
+        if removed > 0 {
+            Self::deposit_event(RawEvent::DestroyProgressed(currency_id, removed));
+        }
+        // All the iterators ran out of entries before exhausting the budget.
+        if budget > 0 {
+            Self::finish_destroy(currency_id);
+        }
+
+        Ok(())
+    }
+
+    fn finish_destroy(currency_id: T::CurrencyId) {
+        TotalIssuances::<T>::remove(currency_id);
+        IssuanceCheckpoints::<T>::remove(currency_id);
+        IssuanceCheckpointsStart::<T>::remove(currency_id);
+        Metadata::<T>::remove(currency_id);
+        FeeRates::<T>::remove(currency_id);
+        ScheduledCurrencies::<T>::mutate(|scheduled| {
+            if let Ok(index) = scheduled.binary_search(&currency_id) {
+                scheduled.remove(index);
+            }
+        });
+
+        // Grants made to other accounts are left to the role managers.
+        if let Some(details) = Details::<T>::take(currency_id) {
+            let _ = RoleManagerOf::<T>::revoke_role(
+                Some(&details.owner),
+                RoleBuilderOf::<T>::manage_currency(currency_id),
+            );
+        }
+        let _ = RoleManagerOf::<T>::revoke_role(
+            None,
+            RoleBuilderOf::<T>::transfer_currency(currency_id),
+        );
+        let _ = RoleManagerOf::<T>::revoke_role(
+            None,
+            RoleBuilderOf::<T>::receive_currency(currency_id),
+        );
+
+        let native = T::NativeCurrencyId::get();
+        MetadataDeposits::<T>::take(currency_id)
+            .into_iter()
+            .chain(CurrencyDeposits::<T>::take(currency_id))
+            .for_each(|(depositor, deposit)| {
+                Self::unreserve(native, &depositor, deposit);
+            });
+
+        Destroying::<T>::remove(currency_id);
+        Self::deposit_event(RawEvent::CurrencyDestroyed(currency_id));
+    }
+}
@@ -19,12 +19,13 @@ use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::RuntimeDebug;
+use sp_std::prelude::Vec;
 
 /// This structure is used to encode metadata about a currency, for instance,
 /// which account is its "owner" and thus can mint or burn units.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct CurrencyDetails<AccountId> {
+pub struct CurrencyDetails<AccountId, Balance, BlockNumber> {
     /// The owner of the currency, typically it can mint or burn units of this
     /// currency.
     pub owner: AccountId,
@@ -32,4 +33,62 @@ pub struct CurrencyDetails<AccountId> {
     /// Wether the currency is transferrable. If set to false no account will be
     /// able to transfer the tokens to each other.
     pub transferable: bool,
+
+    /// Wether accounts need the `receive_currency` role to be credited units of this
+    /// currency through transfers, for instance to maintain an allowlist of investors.
+    pub restricted_receivers: bool,
+
+    /// Minimum balance an account must hold. Accounts going below it are reaped
+    /// and their remaining balance is handled as dust.
+    pub existential_deposit: Balance,
+
+    /// Optional cap on the total issuance of the currency.
+    pub max_supply: Option<Balance>,
+
+    /// Optional schedule used to mint new units automatically.
+    pub issuance: Option<IssuanceSchedule<AccountId, Balance, BlockNumber>>,
+}
+
+/// Units of a currency minted at a regular interval for a given account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct IssuanceSchedule<AccountId, Balance, BlockNumber> {
+    /// Account credited with the new units, for instance the treasury of an
+    /// organization.
+    pub beneficiary: AccountId,
+
+    /// How many units are minted every period.
+    pub amount: Balance,
+
+    /// Number of blocks between two issuances, one means every block.
+    pub period: BlockNumber,
+}
+
+/// Human readable information about a currency, mostly used by wallets and user
+/// interfaces.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CurrencyMetadata {
+    pub name: Vec<u8>,
+    pub symbol: Vec<u8>,
+    /// Number of decimals used to display the balances.
+    pub decimals: u8,
+    /// Optional link to more information about the currency, for instance an IPFS CID.
+    pub uri: Option<Vec<u8>>,
+}
+
+impl CurrencyMetadata {
+    /// Number of bytes stored, used to compute the metadata deposit.
+    pub fn byte_len(&self) -> usize {
+        self.name.len() + self.symbol.len() + self.uri.as_ref().map_or(0, |uri| uri.len())
+    }
+
+    /// Make sure none of the variable length fields is longer than `max_length`.
+    pub fn is_within(&self, max_length: u32) -> bool {
+        let max_length = max_length as usize;
+        [Some(&self.name), Some(&self.symbol), self.uri.as_ref()]
+            .iter()
+            .flatten()
+            .all(|field| field.len() <= max_length)
+    }
 }
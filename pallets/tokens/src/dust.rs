@@ -0,0 +1,51 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{Config, Module};
+use frame_support::traits::Get;
+use governance_os_support::traits::Currencies;
+use sp_std::marker;
+
+/// Handle the remaining balance of an account that was reaped because it went below
+/// the existential deposit of a currency. By the time it is called the dust was already
+/// removed from the total issuance.
+pub trait OnDust<AccountId, CurrencyId, Balance> {
+    fn on_dust(currency_id: CurrencyId, who: &AccountId, amount: Balance);
+}
+
+/// Simply burn the dust.
+impl<AccountId, CurrencyId, Balance> OnDust<AccountId, CurrencyId, Balance> for () {
+    fn on_dust(_currency_id: CurrencyId, _who: &AccountId, _amount: Balance) {}
+}
+
+/// Credit the dust to the account returned by `GetAccountId`, typically an organization's
+/// treasury. If the dust can not be credited, for instance because it is too small to create
+/// the account, it is burned.
+pub struct TransferDust<T, GetAccountId>(marker::PhantomData<(T, GetAccountId)>);
+impl<T, GetAccountId> OnDust<T::AccountId, T::CurrencyId, T::Balance>
+    for TransferDust<T, GetAccountId>
+where
+    T: Config,
+    GetAccountId: Get<T::AccountId>,
+{
+    fn on_dust(currency_id: T::CurrencyId, _who: &T::AccountId, amount: T::Balance) {
+        let _ = <Module<T> as Currencies<T::AccountId>>::mint(
+            currency_id,
+            &GetAccountId::get(),
+            amount,
+        );
+    }
+}
@@ -0,0 +1,215 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{mutations::Mutation, Config, FeeRates, Module, NextFeeCurrency};
+use codec::{Decode, Encode};
+use frame_support::{
+    traits::{Get, WithdrawReasons},
+    StorageMap, StorageValue,
+};
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_runtime::{
+    traits::{DispatchInfoOf, PostDispatchInfoOf, Saturating, SignedExtension, Zero},
+    transaction_validity::{
+        InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
+    DispatchResult, FixedPointNumber, FixedPointOperand, FixedU128,
+};
+use sp_std::marker::PhantomData;
+
+impl<T: Config> Module<T> {
+    /// How many units of `currency_id` are worth one unit of native currency when paying
+    /// for fees. Returns `None` if no rate was set for this currency.
+    pub fn fee_rate(currency_id: T::CurrencyId) -> Option<FixedU128> {
+        if currency_id == T::NativeCurrencyId::get() {
+            return Some(FixedU128::one());
+        }
+        FeeRates::<T>::get(currency_id)
+    }
+}
+
+impl<T: Config> Module<T>
+where
+    T::Balance: FixedPointOperand,
+{
+    /// Convert a fee expressed in native currency into the currency identified by
+    /// `currency_id`.
+    pub fn fee_in_currency(currency_id: T::CurrencyId, fee: T::Balance) -> Option<T::Balance> {
+        Self::fee_rate(currency_id).map(|rate| rate.saturating_mul_int(fee))
+    }
+}
+
+/// An `OnChargeTransaction` implementation withdrawing the fees in the currency chosen by
+/// the signer through the `SetFeeCurrency` signed extension, the native currency by default.
+/// Collected fees are credited to `Beneficiary`.
+pub struct MultiCurrencyFees<Beneficiary>(PhantomData<Beneficiary>);
+
+impl<T, Beneficiary> OnChargeTransaction<T> for MultiCurrencyFees<Beneficiary>
+where
+    T: Config + pallet_transaction_payment::Config,
+    T::Balance: FixedPointOperand,
+    Beneficiary: Get<T::AccountId>,
+{
+    type Balance = T::Balance;
+    /// Currency, conversion rate and amount that was withdrawn.
+    type LiquidityInfo = Option<(T::CurrencyId, FixedU128, T::Balance)>;
+
+    fn withdraw_fee(
+        who: &T::AccountId,
+        _call: &T::Call,
+        _info: &DispatchInfoOf<T::Call>,
+        fee: Self::Balance,
+        tip: Self::Balance,
+    ) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+        // Always consumed so that the choice does not leak to the next transaction.
+        let currency_id = NextFeeCurrency::<T>::take().unwrap_or_else(T::NativeCurrencyId::get);
+        if fee.is_zero() {
+            return Ok(None);
+        }
+
+        let rate = Module::<T>::fee_rate(currency_id).ok_or(InvalidTransaction::Payment)?;
+        let amount = rate.saturating_mul_int(fee);
+        let reasons = if tip.is_zero() {
+            WithdrawReasons::TRANSACTION_PAYMENT
+        } else {
+            WithdrawReasons::TRANSACTION_PAYMENT | WithdrawReasons::TIP
+        };
+
+        let mut mutation = Mutation::<T>::new_for_currency(currency_id);
+        if currency_id != T::NativeCurrencyId::get() {
+            mutation
+                .ensure_must_be_transferable_for(who)
+                .map_err(|_| InvalidTransaction::Payment)?;
+        }
+        mutation
+            .sub_free_balance(who, amount, reasons)
+            .map_err(|_| InvalidTransaction::Payment)?;
+        mutation
+            .ensure_keep_alive(who)
+            .map_err(|_| InvalidTransaction::Payment)?;
+        mutation.apply().map_err(|_| InvalidTransaction::Payment)?;
+
+        Ok(Some((currency_id, rate, amount)))
+    }
+
+    fn correct_and_deposit_fee(
+        who: &T::AccountId,
+        _dispatch_info: &DispatchInfoOf<T::Call>,
+        _post_info: &PostDispatchInfoOf<T::Call>,
+        corrected_fee: Self::Balance,
+        _tip: Self::Balance,
+        already_withdrawn: Self::LiquidityInfo,
+    ) -> Result<(), TransactionValidityError> {
+        if let Some((currency_id, rate, paid)) = already_withdrawn {
+            let collected = rate.saturating_mul_int(corrected_fee).min(paid);
+            let refund = paid.saturating_sub(collected);
+
+            // The fees were already withdrawn, failing to credit them only burns them.
+            if !refund.is_zero() {
+                let mut mutation = Mutation::<T>::new_for_currency(currency_id);
+                let _ = mutation
+                    .add_free_balance(who, refund)
+                    .and_then(|_| mutation.apply());
+            }
+            if !collected.is_zero() {
+                let mut mutation = Mutation::<T>::new_for_currency(currency_id);
+                let _ = mutation
+                    .add_free_balance(&Beneficiary::get(), collected)
+                    .and_then(|_| mutation.apply());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Signed extension letting the signer pay the fees of its transaction in another currency
+/// than the native one. It must be placed before `ChargeTransactionPayment`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct SetFeeCurrency<T: Config + Send + Sync>(pub Option<T::CurrencyId>);
+
+impl<T: Config + Send + Sync> SetFeeCurrency<T> {
+    fn set_fee_currency(&self) -> Result<(), TransactionValidityError> {
+        match self.0 {
+            Some(currency_id) => {
+                if Module::<T>::fee_rate(currency_id).is_none() {
+                    return Err(InvalidTransaction::Payment.into());
+                }
+                NextFeeCurrency::<T>::put(currency_id);
+            }
+            None => NextFeeCurrency::<T>::kill(),
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for SetFeeCurrency<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "SetFeeCurrency({:?})", self.0)
+    }
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Config + Send + Sync> SignedExtension for SetFeeCurrency<T> {
+    const IDENTIFIER: &'static str = "SetFeeCurrency";
+    type AccountId = T::AccountId;
+    type Call = T::Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        _who: &Self::AccountId,
+        _call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        self.set_fee_currency()?;
+        Ok(ValidTransaction::default())
+    }
+
+    fn pre_dispatch(
+        self,
+        _who: &Self::AccountId,
+        _call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> Result<(), TransactionValidityError> {
+        self.set_fee_currency()
+    }
+
+    /// The fees may not have been withdrawn through `MultiCurrencyFees`, for instance when the
+    /// transaction is sponsored, make sure the choice does not leak to the next transaction.
+    fn post_dispatch(
+        _pre: Self::Pre,
+        _info: &DispatchInfoOf<Self::Call>,
+        _post_info: &PostDispatchInfoOf<Self::Call>,
+        _len: usize,
+        _result: &DispatchResult,
+    ) -> Result<(), TransactionValidityError> {
+        NextFeeCurrency::<T>::kill();
+        Ok(())
+    }
+}
@@ -17,24 +17,62 @@
 //! This pallet implements the code to support a multi currency runtime.
 //! Along with compatibility with the `Currency` trait through the use
 //! of `NativeCurrencyAdapter`.
-//! Caveats:
-//! - for now, we do not support `reasons` and `existence_requirements`
-//! - for now, we do not support `ExistentialDeposit`
-//! - for now, we do not support locking or reserving funds
+//! Accounts going below the existential deposit of a currency are reaped and their
+//! remaining balance is given to the `OnDust` handler.
+//! Every balance change is reported to the `OnBalanceChanged` hooks so that other pallets
+//! can keep data derived from balances up to date.
+//! Currencies can be given a name, a symbol and a number of decimals, their owners
+//! pay a deposit proportional to the size of this metadata.
+//! Currencies may have a capped supply and be issued automatically according to a
+//! schedule set by their owners.
+//! Locks only apply to the withdrawals made for one of their `WithdrawReasons`, for
+//! instance a voting lock may still let its owner pay for transaction fees.
+//! Reserves can be named so that each subsystem only moves the coins it reserved itself,
+//! named reserves are included in the `reserved` balance of the accounts.
+//! Accounts can let others spend some of their coins through allowances, the
+//! transfers made this way are still subject to the owner's transfer permissions.
+//! Currency managers can enforce compliance rules on their currencies: they may freeze
+//! individual accounts, only let accounts holding the `receive_currency` role be credited
+//! through transfers, and force transfers out of any account.
+//! Coins can be sent to, or minted for, many accounts at once with `transfer_batch` and
+//! `mint_batch`. Larger distributions can use airdrops: the creator reserves the coins
+//! and posts the root of a merkle tree of `(account, amount)` leaves, each recipient then
+//! claims their coins with a proof.
+//! Currencies can be destroyed by their managers: they are frozen first and their accounts
+//! are then removed in bounded batches, across as many calls as needed, before the id is
+//! freed.
+//! Transaction fees can be paid in any currency with a fee rate through `MultiCurrencyFees`,
+//! signers pick the currency with the `SetFeeCurrency` signed extension.
+//! Currency managers can record checkpoints of the balances and total issuance of their
+//! currencies, they are exposed through `CheckpointedCurrencies` so that votes can be
+//! counted from the balances held at a given block.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage, traits::LockIdentifier, weights::Weight,
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::DispatchResultWithPostInfo,
+    ensure,
+    traits::{ExistenceRequirement, Get, LockIdentifier},
+    weights::Weight,
     Parameter,
 };
 use frame_system::ensure_signed;
-use governance_os_support::traits::{Currencies, RoleManager};
+use governance_os_support::traits::{
+    AllowanceCurrencies, Currencies, OnBalanceChanged, ReservableCurrencies, ReserveIdentifier,
+    RoleManager,
+};
 use sp_runtime::{
-    traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, StaticLookup, Zero},
-    DispatchResult,
+    traits::{
+        AtLeast32BitUnsigned, Convert, MaybeSerializeDeserialize, Member, Saturating, StaticLookup,
+        Zero,
+    },
+    DispatchError, DispatchResult, FixedPointNumber, FixedU128,
+};
+use sp_std::{
+    cmp::{Eq, PartialEq},
+    prelude::Vec,
 };
-use sp_std::cmp::{Eq, PartialEq};
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
@@ -43,23 +81,50 @@ mod tests;
 
 mod account_data;
 mod adapter;
+mod airdrops;
+mod checkpoints;
 mod currencies;
 mod default_weights;
+mod destroy;
 mod details;
+mod dust;
+mod fees;
 mod imbalances;
+mod migrations;
 mod mutations;
 
-pub use account_data::AccountCurrencyData;
+pub use account_data::{AccountCurrencyData, TokenLock};
 pub use adapter::NativeCurrencyAdapter;
-pub use details::CurrencyDetails;
+pub use airdrops::{Airdrop, MAX_AIRDROP_PROOF_LENGTH};
+pub use details::{CurrencyDetails, CurrencyMetadata, IssuanceSchedule};
+pub use dust::{OnDust, TransferDust};
+pub use fees::{MultiCurrencyFees, SetFeeCurrency};
 pub use imbalances::{NegativeImbalance, PositiveImbalance};
+pub use migrations::Releases;
 
 pub trait WeightInfo {
     fn create() -> Weight;
+    fn create_auto() -> Weight;
     fn mint() -> Weight;
     fn burn() -> Weight;
     fn update_details() -> Weight;
+    fn set_metadata(b: u32) -> Weight;
     fn transfer() -> Weight;
+    fn approve() -> Weight;
+    fn transfer_from() -> Weight;
+    fn freeze() -> Weight;
+    fn thaw() -> Weight;
+    fn force_transfer() -> Weight;
+    fn transfer_batch(n: u32) -> Weight;
+    fn mint_batch(n: u32) -> Weight;
+    fn create_airdrop() -> Weight;
+    fn claim_airdrop(p: u32) -> Weight;
+    fn close_airdrop() -> Weight;
+    fn start_destroy() -> Weight;
+    fn continue_destroy(n: u32) -> Weight;
+    fn set_fee_rate() -> Weight;
+    fn set_checkpoints() -> Weight;
+    fn on_initialize(s: u32) -> Weight;
 }
 
 pub trait RoleBuilder {
@@ -71,11 +136,18 @@ pub trait RoleBuilder {
     /// in the future.
     fn transfer_currency(id: Self::CurrencyId) -> Self::Role;
 
+    /// Role required to be credited units of currencies whose `restricted_receivers`
+    /// flag is set. It is granted to everybody otherwise.
+    fn receive_currency(id: Self::CurrencyId) -> Self::Role;
+
     /// Role for the account(s) that are allowed to `mint` or `burn` units of currency.
     fn manage_currency(id: Self::CurrencyId) -> Self::Role;
 
     /// Role for creating currencies.
     fn create_currencies() -> Self::Role;
+
+    /// Role for setting the rates at which currencies can be used to pay for fees.
+    fn manage_fee_rates() -> Self::Role;
 }
 type RoleBuilderOf<T> = <T as Config>::RoleBuilder;
 
@@ -97,6 +169,49 @@ pub trait Config: frame_system::Config {
     /// Weight values for this pallet
     type WeightInfo: WeightInfo;
 
+    /// The currency used to pay for fees and storage.
+    type NativeCurrencyId: Get<Self::CurrencyId>;
+
+    /// Existential deposit of the native currency, used when it was not configured in its
+    /// `CurrencyDetails`.
+    type NativeExistentialDeposit: Get<Self::Balance>;
+
+    /// Builds the id of a currency created through `create_auto` from its index.
+    type AutoCurrencyId: Convert<u32, Self::CurrencyId>;
+
+    /// Maximum number of ids already in use that a call to `create_auto` skips before giving
+    /// up. The skipped ids are remembered so that the next calls can go further.
+    type MaxCurrencyIdSkips: Get<u32>;
+
+    /// Amount of native currency reserved from the creator of a currency, this prevents
+    /// squatting the currency ids.
+    type CurrencyDeposit: Get<Self::Balance>;
+
+    /// Amount of native currency reserved from the account setting the metadata of a
+    /// currency for each byte it occupies.
+    type MetadataDepositPerByte: Get<Self::Balance>;
+
+    /// Maximum length of each of the name, symbol and URI of a currency.
+    type MaxMetadataLength: Get<u32>;
+
+    /// Maximum number of currencies with an issuance schedule.
+    type MaxIssuanceSchedules: Get<u32>;
+
+    /// Maximum number of recipients of a `transfer_batch` or `mint_batch` call.
+    type MaxBatchSize: Get<u32>;
+
+    /// Maximum number of checkpoints kept per account and per currency, older ones are dropped
+    /// and the balances they recorded can no longer be queried.
+    type MaxCheckpoints: Get<u32>;
+
+    /// Handler for the remaining balance of the accounts that were reaped.
+    type OnDust: OnDust<Self::AccountId, Self::CurrencyId, Self::Balance>;
+
+    /// Hooks called for every account whose balance changed. They run as part of the
+    /// dispatchables of this pallet, benchmarks have to be run with the runtime's hooks
+    /// to account for their weight.
+    type OnBalanceChanged: OnBalanceChanged<Self::AccountId, Self::CurrencyId, Self::Balance>;
+
     /// Pallet that is in charge of managing the roles based ACL.
     type RoleManager: RoleManager<AccountId = Self::AccountId>;
 
@@ -109,6 +224,27 @@ pub trait Config: frame_system::Config {
     >;
 }
 
+type CurrencyDetailsOf<T> = CurrencyDetails<
+    <T as frame_system::Config>::AccountId,
+    <T as Config>::Balance,
+    <T as frame_system::Config>::BlockNumber,
+>;
+type IssuanceScheduleOf<T> = IssuanceSchedule<
+    <T as frame_system::Config>::AccountId,
+    <T as Config>::Balance,
+    <T as frame_system::Config>::BlockNumber,
+>;
+type AirdropOf<T> = Airdrop<
+    <T as frame_system::Config>::AccountId,
+    <T as Config>::CurrencyId,
+    <T as Config>::Balance,
+    <T as frame_system::Config>::Hash,
+>;
+type BatchOf<T> = Vec<(
+    <T as frame_system::Config>::AccountId,
+    <T as Config>::Balance,
+)>;
+type LookupSourceOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
 type RoleManagerOf<T> = <T as Config>::RoleManager;
 
 decl_storage! {
@@ -116,15 +252,72 @@ decl_storage! {
         /// Store the balances holded by an account. By storing the balances under an account (VS storing
         /// the accounts under the currency ids) we can enumerate the tokens holded by an account if needed.
         pub Balances get(fn balances): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::CurrencyId => AccountCurrencyData<T::Balance>;
-        pub Locks get(fn locks): double_map hasher(blake2_128_concat) (T::AccountId, T::CurrencyId), hasher(blake2_128_concat) LockIdentifier => T::Balance;
+        /// Accounts with a balance in each currency, used to find them when the currency is destroyed.
+        pub CurrencyAccounts get(fn currency_accounts): double_map hasher(blake2_128_concat) T::CurrencyId, hasher(blake2_128_concat) T::AccountId => ();
+        /// Set once `CurrencyAccounts` was filled with the balances that existed before it was introduced.
+        pub CurrencyAccountsMigrated build(|_config: &GenesisConfig<T>| true): bool;
+        /// Layout of the balances and locks, used to know which migrations still need to run.
+        pub StorageVersion build(|_config: &GenesisConfig<T>| Releases::V2): Releases;
+        /// Currencies being destroyed, no balance can be changed until they are fully removed.
+        pub Destroying get(fn destroying): map hasher(blake2_128_concat) T::CurrencyId => bool;
+        pub Locks get(fn locks): double_map hasher(blake2_128_concat) (T::AccountId, T::CurrencyId), hasher(blake2_128_concat) LockIdentifier => Option<TokenLock<T::Balance>>;
+        /// Part of the reserved balance of an account held under a given identifier.
+        pub NamedReserves get(fn named_reserves): double_map hasher(blake2_128_concat) (T::AccountId, T::CurrencyId), hasher(blake2_128_concat) ReserveIdentifier => T::Balance;
         pub TotalIssuances get(fn total_issuances): map hasher(blake2_128_concat) T::CurrencyId => T::Balance;
+        pub Details get(fn details): map hasher(blake2_128_concat) T::CurrencyId => Option<CurrencyDetailsOf<T>>;
+        /// Account that created a currency and how much it reserved for it.
+        pub CurrencyDeposits get(fn currency_deposits): map hasher(blake2_128_concat) T::CurrencyId => Option<(T::AccountId, T::Balance)>;
+        /// Currencies with an issuance schedule, they are checked on every block.
+        pub ScheduledCurrencies get(fn scheduled_currencies): Vec<T::CurrencyId>;
+        /// Index from which `create_auto` looks for the next free currency id.
+        pub NextCurrencyIndex get(fn next_currency_index): u32;
+        pub Metadata get(fn metadata): map hasher(blake2_128_concat) T::CurrencyId => Option<CurrencyMetadata>;
+        /// Account that paid for the metadata of a currency and how much was reserved.
+        pub MetadataDeposits get(fn metadata_deposits): map hasher(blake2_128_concat) T::CurrencyId => Option<(T::AccountId, T::Balance)>;
+        /// Accounts that were frozen by the managers of a currency and can not transfer it.
+        pub FrozenAccounts get(fn frozen_accounts): double_map hasher(blake2_128_concat) T::CurrencyId, hasher(blake2_128_concat) T::AccountId => bool;
+        /// Airdrops that can still be claimed.
+        pub Airdrops get(fn airdrops): map hasher(twox_64_concat) u32 => Option<AirdropOf<T>>;
+        /// Accounts that already claimed their part of an airdrop.
+        pub AirdropClaims get(fn airdrop_claims): double_map hasher(twox_64_concat) u32, hasher(blake2_128_concat) T::AccountId => bool;
+        /// Airdrops of each currency, so that they can be removed along with it.
+        pub CurrencyAirdrops get(fn currency_airdrops): double_map hasher(blake2_128_concat) T::CurrencyId, hasher(twox_64_concat) u32 => bool;
+        /// Identifier of the next airdrop.
+        pub NextAirdropId get(fn next_airdrop_id): u32;
+        /// How many units of a currency are worth one unit of native currency when paying for fees.
+        /// Currencies without a rate can not be used to pay for fees.
+        pub FeeRates get(fn fee_rates): map hasher(blake2_128_concat) T::CurrencyId => Option<FixedU128>;
+        /// Currency chosen through `SetFeeCurrency` to pay for the fees of the transaction being
+        /// processed.
+        pub NextFeeCurrency: Option<T::CurrencyId>;
+        /// Block from which the balances and total issuance of a currency are checkpointed, unset if
+        /// they are not.
+        pub CheckpointsSince get(fn checkpoints_since): map hasher(blake2_128_concat) T::CurrencyId => Option<T::BlockNumber>;
+        /// Blocks during which the total balance of an account changed along with the balance it held
+        /// at the end of the previous block. Only recorded for currencies with checkpoints.
+        pub BalanceCheckpoints get(fn balance_checkpoints): double_map hasher(blake2_128_concat) T::CurrencyId, hasher(blake2_128_concat) T::AccountId => Vec<(T::BlockNumber, T::Balance)>;
+        /// First block whose balance can still be computed from `BalanceCheckpoints`, only set once
+        /// checkpoints were dropped to respect `MaxCheckpoints`.
+        pub BalanceCheckpointsStart get(fn balance_checkpoints_start): double_map hasher(blake2_128_concat) T::CurrencyId, hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+        /// Same as `BalanceCheckpoints` but for the total issuance of a currency.
+        pub IssuanceCheckpoints get(fn issuance_checkpoints): map hasher(blake2_128_concat) T::CurrencyId => Vec<(T::BlockNumber, T::Balance)>;
+        /// Same as `BalanceCheckpointsStart` but for the total issuance of a currency.
+        pub IssuanceCheckpointsStart get(fn issuance_checkpoints_start): map hasher(blake2_128_concat) T::CurrencyId => Option<T::BlockNumber>;
+        /// How many coins a spender can transfer on behalf of an owner, keyed by currency and `(owner, spender)`.
+        pub Allowances get(fn allowances): double_map hasher(blake2_128_concat) T::CurrencyId, hasher(blake2_128_concat) (T::AccountId, T::AccountId) => T::Balance;
+        /// Sum of the allowances given by an account for a given currency.
+        pub TotalAllowances get(fn total_allowances): double_map hasher(blake2_128_concat) T::CurrencyId, hasher(blake2_128_concat) T::AccountId => T::Balance;
     }
     add_extra_genesis {
         config(endowed_accounts): Vec<(T::CurrencyId, T::AccountId, T::Balance)>;
-        config(currency_details): Vec<(T::CurrencyId, CurrencyDetails<T::AccountId>)>;
+        config(currency_details): Vec<(T::CurrencyId, CurrencyDetailsOf<T>, Option<CurrencyMetadata>)>;
         build(|config: &GenesisConfig<T>| {
-            config.currency_details.iter().cloned().for_each(|(currency_id, currency_details)| {
-                Module::<T>::set_currency_acl(currency_id, currency_details, None);
+            config.currency_details.iter().cloned().for_each(|(currency_id, currency_details, maybe_metadata)| {
+                Module::<T>::set_currency_details(currency_id, currency_details, None);
+                // Genesis metadata is not backed by any deposit.
+                if let Some(metadata) = maybe_metadata {
+                    Metadata::<T>::insert(currency_id, metadata);
+                }
                 // If we have an error it means that the currency had some coins issued earlier in
                 // the genesis block, thus we ignore it.
                 drop(Module::<T>::maybe_create_zero_issuance(currency_id));
@@ -143,7 +336,9 @@ decl_event!(
         AccountId = <T as frame_system::Config>::AccountId,
         Balance = <T as Config>::Balance,
         CurrencyId = <T as Config>::CurrencyId,
-        CurrencyDetails = CurrencyDetails<<T as frame_system::Config>::AccountId>,
+        CurrencyDetails = CurrencyDetailsOf<T>,
+        IssuanceSchedule = IssuanceScheduleOf<T>,
+        Hash = <T as frame_system::Config>::Hash,
     {
         /// A new currency has been created. \[currency id, details\]
         CurrencyCreated(CurrencyId, CurrencyDetails),
@@ -153,8 +348,50 @@ decl_event!(
         CurrencyBurned(CurrencyId, AccountId, Balance),
         /// Some details about a currency were changed. \[currency_id, details\]
         CurrencyDetailsChanged(CurrencyId, CurrencyDetails),
+        /// The metadata of a currency was changed. \[currency_id, metadata\]
+        CurrencyMetadataChanged(CurrencyId, CurrencyMetadata),
+        /// The maximum supply of a currency was changed. \[currency_id, max_supply\]
+        MaxSupplyChanged(CurrencyId, Option<Balance>),
+        /// The issuance schedule of a currency was changed. \[currency_id, schedule\]
+        IssuanceScheduleChanged(CurrencyId, Option<IssuanceSchedule>),
+        /// Some units of currency were minted according to its issuance schedule.
+        /// \[currency_id, beneficiary, amount\]
+        ScheduledIssuance(CurrencyId, AccountId, Balance),
         /// Some units of currency were transferred. \[currency_id, source, dest, amount\]
         CurrencyTransferred(CurrencyId, AccountId, AccountId, Balance),
+        /// The allowance given by an account to a spender was changed.
+        /// \[currency_id, owner, spender, amount\]
+        Approval(CurrencyId, AccountId, AccountId, Balance),
+        /// An account was frozen by the managers of a currency. \[currency_id, account\]
+        AccountFrozen(CurrencyId, AccountId),
+        /// A frozen account can transfer a currency again. \[currency_id, account\]
+        AccountThawed(CurrencyId, AccountId),
+        /// Some units of currency were moved by its managers regardless of locks and
+        /// permissions. \[currency_id, source, dest, amount\]
+        ForcedTransfer(CurrencyId, AccountId, AccountId, Balance),
+        /// Some units of currency were reserved to be claimed by the accounts listed in
+        /// the merkle tree whose root is given. \[airdrop_id, currency_id, creator, root, amount\]
+        AirdropCreated(u32, CurrencyId, AccountId, Hash, Balance),
+        /// An account claimed its part of an airdrop. \[airdrop_id, account, amount\]
+        AirdropClaimed(u32, AccountId, Balance),
+        /// An airdrop was closed and its unclaimed coins returned to its creator.
+        /// \[airdrop_id, unclaimed\]
+        AirdropClosed(u32, Balance),
+        /// A currency was frozen and its accounts can now be removed. \[currency_id\]
+        DestroyStarted(CurrencyId),
+        /// Some accounts and allowances of a currency being destroyed were removed.
+        /// \[currency_id, removed\]
+        DestroyProgressed(CurrencyId, u32),
+        /// A currency was fully removed and its id can be used again. \[currency_id\]
+        CurrencyDestroyed(CurrencyId),
+        /// The rate at which a currency can be used to pay for fees was changed.
+        /// \[currency_id, rate\]
+        FeeRateChanged(CurrencyId, Option<FixedU128>),
+        /// Checkpoints were enabled or disabled for a currency. \[currency_id, enabled\]
+        CheckpointsChanged(CurrencyId, bool),
+        /// An account was reaped and its remaining balance was given to the dust
+        /// handler. \[currency_id, account, amount\]
+        DustLost(CurrencyId, AccountId, Balance),
     }
 );
 
@@ -175,6 +412,50 @@ decl_error! {
         CurrencyAlreadyExists,
         /// This owner(s) of this currency have disabled transfers
         UnTransferableCurrency,
+        /// The account would be created with a balance below the currency's existential
+        /// deposit.
+        ExistentialDeposit,
+        /// The operation would reap an account that is required to stay alive.
+        KeepAlive,
+        /// One of the fields of the currency metadata is longer than `MaxMetadataLength`.
+        MetadataTooLong,
+        /// All the indexes usable by `create_auto` have been consumed, or we skipped more than
+        /// `MaxCurrencyIdSkips` ids already in use.
+        NoAvailableCurrencyId,
+        /// This operation would bring the total issuance above the currency's maximum supply.
+        MaxSupplyExceeded,
+        /// Issuance schedules must have a non zero period.
+        InvalidIssuanceSchedule,
+        /// There are already `MaxIssuanceSchedules` currencies with an issuance schedule.
+        TooManyIssuanceSchedules,
+        /// This account was frozen by the managers of the currency.
+        AccountFrozen,
+        /// The recipient lacks the role needed to receive this currency.
+        CannotReceiveCurrency,
+        /// Batches can not have more than `MaxBatchSize` entries.
+        BatchTooLarge,
+        /// All the airdrop identifiers have been consumed.
+        NoAvailableAirdropId,
+        /// There is no airdrop with this identifier.
+        UnknownAirdrop,
+        /// This account already claimed its part of the airdrop.
+        AirdropAlreadyClaimed,
+        /// The merkle proof does not match the root of the airdrop.
+        InvalidMerkleProof,
+        /// Only the creator of an airdrop can close it.
+        NotAirdropCreator,
+        /// The currency does not exist.
+        UnknownCurrency,
+        /// The native currency can not be destroyed.
+        CannotDestroyNativeCurrency,
+        /// The currency is being destroyed and its balances can not be changed.
+        CurrencyDestroying,
+        /// The currency is not being destroyed.
+        NotDestroying,
+        /// The spender is not allowed to transfer that many coins on behalf of the owner.
+        AllowanceTooLow,
+        /// Fee rates can not be zero nor be set for the native currency.
+        InvalidFeeRate,
     }
 }
 
@@ -184,32 +465,76 @@ decl_module! {
 
         fn deposit_event() = default;
 
+        fn on_runtime_upgrade() -> Weight {
+            // Balances must be readable before being indexed.
+            migrations::migrate_to_v2::<T>()
+                .saturating_add(migrations::migrate_currency_accounts::<T>())
+        }
+
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let scheduled = Self::scheduled_currencies();
+            let weight = T::WeightInfo::on_initialize(scheduled.len() as u32);
+            scheduled.into_iter().for_each(|currency_id| Self::issue_scheduled(currency_id, now));
+            weight
+        }
+
         /// Creates a new currency with 0 units, to issue units to people one would have to call
         /// `issue`. This will register the caller of this dispatchable as the owner of the currency
         /// so they can issue or burn units. This will produce an error if `currency_id` is already
         /// used by another currency. Use `transferable` to determine if the created asset can be
         /// transferred between accounts. If not, the only way to move it would be to either be root
-        /// or burn then mint the tokens again.
+        /// or burn then mint the tokens again. `metadata` can be provided to set the currency's name,
+        /// symbol and decimals, see `set_metadata` for the associated deposit. `CurrencyDeposit`
+        /// is reserved from the caller.
         ///
         /// NOTE: by default, everybody can create new currencies, if it is not wanted you can use the
         /// `bylaws` pallet to restrict access to this dispatchable.
-        #[weight = T::WeightInfo::create()]
-        pub fn create(origin, currency_id: T::CurrencyId, transferable: bool) {
+        #[weight = T::WeightInfo::create()
+            .saturating_add(metadata.as_ref().map_or(0, |m| T::WeightInfo::set_metadata(m.byte_len() as u32)))
+            .saturating_add(RoleManagerOf::<T>::has_role_weight())
+        ]
+        pub fn create(origin, currency_id: T::CurrencyId, transferable: bool, metadata: Option<CurrencyMetadata>) {
             let who = RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::create_currencies())?;
+            Self::do_create(who, currency_id, transferable, metadata)?;
+        }
 
-            Self::maybe_create_zero_issuance(currency_id)?;
+        /// Same as `create` but the currency id is allocated by the runtime and returned in the
+        /// `CurrencyCreated` event. This prevents other accounts from front-running the creation
+        /// of a given currency id.
+        #[weight = T::WeightInfo::create_auto()
+            .saturating_add(metadata.as_ref().map_or(0, |m| T::WeightInfo::set_metadata(m.byte_len() as u32)))
+            .saturating_add(RoleManagerOf::<T>::has_role_weight())
+            .saturating_add(T::DbWeight::get().reads_writes(T::MaxCurrencyIdSkips::get().into(), 1))
+        ]
+        pub fn create_auto(origin, transferable: bool, metadata: Option<CurrencyMetadata>) {
+            let who = RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::create_currencies())?;
 
-            let details = CurrencyDetails {
-                owner: who.clone(),
-                transferable,
+            let mut index = Self::next_currency_index();
+            let mut skipped = 0;
+            let currency_id = loop {
+                let candidate = T::AutoCurrencyId::convert(index);
+                index = index.checked_add(1).ok_or(Error::<T>::NoAvailableCurrencyId)?;
+                // Some ids may have been used by the genesis or by calls to `create`.
+                if !TotalIssuances::<T>::contains_key(candidate) {
+                    break candidate;
+                }
+                if skipped >= T::MaxCurrencyIdSkips::get() {
+                    NextCurrencyIndex::put(index);
+                    return Err(Error::<T>::NoAvailableCurrencyId.into());
+                }
+                skipped += 1;
             };
-            Self::set_currency_acl(currency_id, details.clone(), None);
-            Self::deposit_event(RawEvent::CurrencyCreated(currency_id, details));
+
+            Self::do_create(who, currency_id, transferable, metadata)?;
+            NextCurrencyIndex::put(index);
         }
 
         /// Issue some units of the currency identified by `currency_id` and credit them to `dest`.
         /// Can only be called by the owner of the currency.
-        #[weight = T::WeightInfo::mint()]
+        #[weight = T::WeightInfo::mint()
+            .saturating_add(RoleManagerOf::<T>::has_role_weight())
+            .saturating_add(Module::<T>::checkpoints_weight(1, true))
+        ]
         pub fn mint(origin, currency_id: T::CurrencyId, dest: <T::Lookup as StaticLookup>::Source, amount: T::Balance) {
             RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::manage_currency(currency_id))?;
             let to = T::Lookup::lookup(dest)?;
@@ -219,7 +544,10 @@ decl_module! {
 
         /// Destroy some units of the currency identified by `currency_id` from `from`.
         /// Can only be called by the owner of the currency.
-        #[weight = T::WeightInfo::burn()]
+        #[weight = T::WeightInfo::burn()
+            .saturating_add(RoleManagerOf::<T>::has_role_weight())
+            .saturating_add(Module::<T>::checkpoints_weight(1, true))
+        ]
         pub fn burn(origin, currency_id: T::CurrencyId, from: <T::Lookup as StaticLookup>::Source, amount: T::Balance) {
             RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::manage_currency(currency_id))?;
             let source = T::Lookup::lookup(from)?;
@@ -227,31 +555,268 @@ decl_module! {
         }
 
         /// Update details about the currency identified by `currency_id`. For instance, this
-        /// can be used to change the owner of the currency. Can only be called by the owner.
+        /// can be used to change the owner of the currency, its existential deposit, maximum supply
+        /// or issuance schedule. Can only be called by the owner. The maximum supply can not be
+        /// set below the current total issuance.
         ///
         /// **NOTE**: this will remove ownership / management access from the caller for the given
         /// currency if a new owner is specified. However, if other accounts have been granted
         /// management access to the same currency (for instance through a root action) this will
         /// not change it.
-        #[weight = T::WeightInfo::update_details()]
-        pub fn update_details(origin, currency_id: T::CurrencyId, details: CurrencyDetails<T::AccountId>) {
+        #[weight = T::WeightInfo::update_details().saturating_add(RoleManagerOf::<T>::has_role_weight())]
+        pub fn update_details(origin, currency_id: T::CurrencyId, details: CurrencyDetailsOf<T>) {
             let who = RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::manage_currency(currency_id))?;
-            Self::set_currency_acl(currency_id, details.clone(), Some(who));
+
+            if let Some(max_supply) = details.max_supply {
+                ensure!(Self::total_issuances(currency_id) <= max_supply, Error::<T>::MaxSupplyExceeded);
+            }
+            if let Some(schedule) = &details.issuance {
+                ensure!(!schedule.period.is_zero(), Error::<T>::InvalidIssuanceSchedule);
+                ensure!(
+                    Self::scheduled_currencies().contains(&currency_id)
+                        || (Self::scheduled_currencies().len() as u32) < T::MaxIssuanceSchedules::get(),
+                    Error::<T>::TooManyIssuanceSchedules
+                );
+            }
+
+            let (old_max_supply, old_issuance) = Self::details(currency_id)
+                .map(|old| (old.max_supply, old.issuance))
+                .unwrap_or_default();
+            Self::set_currency_details(currency_id, details.clone(), Some(who));
+
+            if old_max_supply != details.max_supply {
+                Self::deposit_event(RawEvent::MaxSupplyChanged(currency_id, details.max_supply));
+            }
+            if old_issuance != details.issuance {
+                Self::deposit_event(RawEvent::IssuanceScheduleChanged(currency_id, details.issuance.clone()));
+            }
             Self::deposit_event(RawEvent::CurrencyDetailsChanged(currency_id, details));
         }
 
+        /// Set the name, symbol, decimals and URI of the currency identified by `currency_id`.
+        /// A deposit of `MetadataDepositPerByte` native currency units per byte is reserved from
+        /// the caller, the deposit of the previous metadata, if any, is returned to whoever paid
+        /// for it. Can only be called by the owner.
+        #[weight = T::WeightInfo::set_metadata(metadata.byte_len() as u32).saturating_add(RoleManagerOf::<T>::has_role_weight())]
+        pub fn set_metadata(origin, currency_id: T::CurrencyId, metadata: CurrencyMetadata) {
+            let who = RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::manage_currency(currency_id))?;
+            Self::do_set_metadata(currency_id, &who, metadata)?;
+        }
+
         /// Transfer `amount` units of the currency identified by `currency_id` from the origin's
         /// account to the balance of `dest`.
-        #[weight = T::WeightInfo::transfer()]
-        pub fn transfer(origin, currency_id: T::CurrencyId, dest: <T::Lookup as StaticLookup>::Source, amount: T::Balance) {
+        #[weight = T::WeightInfo::transfer()
+            .saturating_add(RoleManagerOf::<T>::has_role_weight().saturating_mul(2))
+            .saturating_add(Module::<T>::checkpoints_weight(2, false))
+        ]
+        pub fn transfer(origin, currency_id: T::CurrencyId, dest: <T::Lookup as StaticLookup>::Source, amount: T::Balance) -> DispatchResultWithPostInfo {
             let from = ensure_signed(origin)?;
             let to = T::Lookup::lookup(dest)?;
-            <Self as Currencies<T::AccountId>>::transfer(currency_id, &from, &to, amount)?;
+            let role_checks_weight = Self::do_transfer(currency_id, &from, &to, amount, ExistenceRequirement::AllowDeath)?;
+            // Refund what the role checks did not use
+            Ok(Some(T::WeightInfo::transfer()
+                .saturating_add(role_checks_weight)
+                .saturating_add(Self::checkpoints_weight(2, false))
+            ).into())
+        }
+
+        /// Let `spender` transfer up to `amount` units of the currency identified by `currency_id`
+        /// from the origin's account. This replaces any existing allowance.
+        #[weight = T::WeightInfo::approve()]
+        pub fn approve(origin, currency_id: T::CurrencyId, spender: <T::Lookup as StaticLookup>::Source, amount: T::Balance) {
+            let owner = ensure_signed(origin)?;
+            let spender = T::Lookup::lookup(spender)?;
+            <Self as AllowanceCurrencies<T::AccountId>>::approve(currency_id, &owner, &spender, amount)?;
+        }
+
+        /// Raise the allowance given to `spender` by `amount`.
+        #[weight = T::WeightInfo::approve()]
+        pub fn increase_allowance(origin, currency_id: T::CurrencyId, spender: <T::Lookup as StaticLookup>::Source, amount: T::Balance) {
+            let owner = ensure_signed(origin)?;
+            let spender = T::Lookup::lookup(spender)?;
+            let new_allowance = Self::allowances(currency_id, (&owner, &spender)).saturating_add(amount);
+            <Self as AllowanceCurrencies<T::AccountId>>::approve(currency_id, &owner, &spender, new_allowance)?;
+        }
+
+        /// Lower the allowance given to `spender` by `amount`, down to zero.
+        #[weight = T::WeightInfo::approve()]
+        pub fn decrease_allowance(origin, currency_id: T::CurrencyId, spender: <T::Lookup as StaticLookup>::Source, amount: T::Balance) {
+            let owner = ensure_signed(origin)?;
+            let spender = T::Lookup::lookup(spender)?;
+            let new_allowance = Self::allowances(currency_id, (&owner, &spender)).saturating_sub(amount);
+            <Self as AllowanceCurrencies<T::AccountId>>::approve(currency_id, &owner, &spender, new_allowance)?;
+        }
+
+        /// Transfer `amount` units of the currency identified by `currency_id` from `owner` to
+        /// `dest` using the allowance `owner` gave to the origin. The transfer is subject to
+        /// the same permissions as if it was made by `owner`.
+        #[weight = T::WeightInfo::transfer_from()
+            .saturating_add(RoleManagerOf::<T>::has_role_weight().saturating_mul(2))
+            .saturating_add(Module::<T>::checkpoints_weight(2, false))
+        ]
+        pub fn transfer_from(origin, currency_id: T::CurrencyId, owner: <T::Lookup as StaticLookup>::Source, dest: <T::Lookup as StaticLookup>::Source, amount: T::Balance) -> DispatchResultWithPostInfo {
+            let spender = ensure_signed(origin)?;
+            let owner = T::Lookup::lookup(owner)?;
+            let to = T::Lookup::lookup(dest)?;
+            let role_checks_weight = Self::do_transfer_from(currency_id, &spender, &owner, &to, amount)?;
+            Ok(Some(T::WeightInfo::transfer_from()
+                .saturating_add(role_checks_weight)
+                .saturating_add(Self::checkpoints_weight(2, false))
+            ).into())
+        }
+
+        /// Prevent `who` from transferring units of the currency identified by `currency_id`.
+        /// Can only be called by the managers of the currency.
+        #[weight = T::WeightInfo::freeze().saturating_add(RoleManagerOf::<T>::has_role_weight())]
+        pub fn freeze(origin, currency_id: T::CurrencyId, who: <T::Lookup as StaticLookup>::Source) {
+            RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::manage_currency(currency_id))?;
+            let who = T::Lookup::lookup(who)?;
+            FrozenAccounts::<T>::insert(currency_id, &who, true);
+            Self::deposit_event(RawEvent::AccountFrozen(currency_id, who));
+        }
+
+        /// Let a previously frozen account transfer units of the currency identified by
+        /// `currency_id` again. Can only be called by the managers of the currency.
+        #[weight = T::WeightInfo::thaw().saturating_add(RoleManagerOf::<T>::has_role_weight())]
+        pub fn thaw(origin, currency_id: T::CurrencyId, who: <T::Lookup as StaticLookup>::Source) {
+            RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::manage_currency(currency_id))?;
+            let who = T::Lookup::lookup(who)?;
+            FrozenAccounts::<T>::remove(currency_id, &who);
+            Self::deposit_event(RawEvent::AccountThawed(currency_id, who));
+        }
+
+        /// Move `amount` units of the currency identified by `currency_id` from `source` to `dest`.
+        /// This ignores locks, frozen accounts and transfer permissions, which makes it possible
+        /// to claw back coins. Can only be called by the managers of the currency.
+        #[weight = T::WeightInfo::force_transfer()
+            .saturating_add(RoleManagerOf::<T>::has_role_weight())
+            .saturating_add(Module::<T>::checkpoints_weight(2, false))
+        ]
+        pub fn force_transfer(origin, currency_id: T::CurrencyId, source: <T::Lookup as StaticLookup>::Source, dest: <T::Lookup as StaticLookup>::Source, amount: T::Balance) {
+            RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::manage_currency(currency_id))?;
+            let from = T::Lookup::lookup(source)?;
+            let to = T::Lookup::lookup(dest)?;
+            Self::do_force_transfer(currency_id, &from, &to, amount)?;
+        }
+
+        /// Transfer units of the currency identified by `currency_id` from the origin's account
+        /// to every `(dest, amount)` of `transfers`. Either all the transfers succeed or none.
+        #[weight = T::WeightInfo::transfer_batch(transfers.len() as u32)
+            // One role check for the sender and one per receiver
+            .saturating_add(RoleManagerOf::<T>::has_role_weight().saturating_mul(transfers.len() as Weight + 1))
+            .saturating_add(Module::<T>::checkpoints_weight(transfers.len() as u32 + 1, false))
+        ]
+        pub fn transfer_batch(origin, currency_id: T::CurrencyId, transfers: Vec<(<T::Lookup as StaticLookup>::Source, T::Balance)>) -> DispatchResultWithPostInfo {
+            let from = ensure_signed(origin)?;
+            let transfers = Self::lookup_batch(transfers)?;
+            let role_checks_weight = Self::do_transfer_batch(currency_id, &from, &transfers)?;
+            Ok(Some(T::WeightInfo::transfer_batch(transfers.len() as u32)
+                .saturating_add(role_checks_weight)
+                .saturating_add(Self::checkpoints_weight(transfers.len() as u32 + 1, false))
+            ).into())
+        }
+
+        /// Create units of the currency identified by `currency_id` for every `(dest, amount)`
+        /// of `mints`. Either all the mints succeed or none. Can only be called by the owner
+        /// of the currency.
+        #[weight = T::WeightInfo::mint_batch(mints.len() as u32)
+            .saturating_add(RoleManagerOf::<T>::has_role_weight())
+            .saturating_add(Module::<T>::checkpoints_weight(mints.len() as u32, true))
+        ]
+        pub fn mint_batch(origin, currency_id: T::CurrencyId, mints: Vec<(<T::Lookup as StaticLookup>::Source, T::Balance)>) {
+            RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::manage_currency(currency_id))?;
+            let mints = Self::lookup_batch(mints)?;
+            Self::do_mint_batch(currency_id, &mints)?;
+        }
+
+        /// Reserve `amount` units of the currency identified by `currency_id` from the origin's
+        /// account so that they can be claimed by the accounts listed in the merkle tree whose
+        /// root is `merkle_root`. Leaves are the hash of the SCALE encoded `(account, amount)`
+        /// tuples and pairs of nodes are sorted before being hashed together.
+        #[weight = T::WeightInfo::create_airdrop().saturating_add(RoleManagerOf::<T>::has_role_weight())]
+        pub fn create_airdrop(origin, currency_id: T::CurrencyId, merkle_root: T::Hash, amount: T::Balance) {
+            let who = ensure_signed(origin)?;
+            Self::do_create_airdrop(who, currency_id, merkle_root, amount)?;
+        }
+
+        /// Claim `amount` units from the airdrop `airdrop_id`. `proof` is the list of the sibling
+        /// nodes from the leaf of the origin to the root of the airdrop.
+        #[weight = T::WeightInfo::claim_airdrop(proof.len() as u32).saturating_add(RoleManagerOf::<T>::has_role_weight())]
+        pub fn claim_airdrop(origin, airdrop_id: u32, amount: T::Balance, proof: Vec<T::Hash>) {
+            let who = ensure_signed(origin)?;
+            Self::do_claim_airdrop(airdrop_id, who, amount, &proof)?;
+        }
+
+        /// Close the airdrop `airdrop_id` and return its unclaimed coins. Can only be called
+        /// by the creator of the airdrop.
+        #[weight = T::WeightInfo::close_airdrop()]
+        pub fn close_airdrop(origin, airdrop_id: u32) {
+            let who = ensure_signed(origin)?;
+            Self::do_close_airdrop(airdrop_id, &who)?;
+        }
+
+        /// Freeze the currency identified by `currency_id` so that it can be destroyed with
+        /// `continue_destroy`. Can only be called by the managers of the currency.
+        #[weight = T::WeightInfo::start_destroy().saturating_add(RoleManagerOf::<T>::has_role_weight())]
+        pub fn start_destroy(origin, currency_id: T::CurrencyId) {
+            RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::manage_currency(currency_id))?;
+            Self::do_start_destroy(currency_id)?;
+        }
+
+        /// Remove up to `max_items` accounts, allowances and balance checkpoints of the currency
+        /// identified by `currency_id`. Once none are left the currency itself is removed, its deposits are
+        /// refunded, its roles revoked and its id freed. Can be called by anybody.
+        #[weight = T::WeightInfo::continue_destroy(*max_items)]
+        pub fn continue_destroy(origin, currency_id: T::CurrencyId, max_items: u32) {
+            ensure_signed(origin)?;
+            Self::do_continue_destroy(currency_id, max_items)?;
+        }
+
+        /// Let the currency identified by `currency_id` be used to pay for fees, `rate` being
+        /// how many of its units are worth one unit of native currency. Passing `None` prevents
+        /// the currency from being used for fees.
+        #[weight = T::WeightInfo::set_fee_rate().saturating_add(RoleManagerOf::<T>::has_role_weight())]
+        pub fn set_fee_rate(origin, currency_id: T::CurrencyId, rate: Option<FixedU128>) {
+            RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::manage_fee_rates())?;
+            ensure!(
+                currency_id != T::NativeCurrencyId::get() && rate != Some(FixedU128::zero()),
+                Error::<T>::InvalidFeeRate
+            );
+
+            match rate {
+                Some(rate) => FeeRates::<T>::insert(currency_id, rate),
+                None => FeeRates::<T>::remove(currency_id),
+            }
+            Self::deposit_event(RawEvent::FeeRateChanged(currency_id, rate));
+        }
+
+        /// Start or stop recording checkpoints of the balances and total issuance of the currency
+        /// identified by `currency_id`. Past values can only be queried for the blocks following
+        /// the one during which checkpoints were last enabled. Can only be called by the managers
+        /// of the currency.
+        #[weight = T::WeightInfo::set_checkpoints().saturating_add(RoleManagerOf::<T>::has_role_weight())]
+        pub fn set_checkpoints(origin, currency_id: T::CurrencyId, enabled: bool) {
+            RoleManagerOf::<T>::ensure_has_role(origin, RoleBuilderOf::<T>::manage_currency(currency_id))?;
+            Self::do_set_checkpoints(currency_id, enabled)?;
         }
     }
 }
 
 impl<T: Config> Module<T> {
+    /// Resolve the accounts of a `transfer_batch` or `mint_batch` call.
+    fn lookup_batch(
+        batch: Vec<(LookupSourceOf<T>, T::Balance)>,
+    ) -> Result<BatchOf<T>, DispatchError> {
+        ensure!(
+            batch.len() as u32 <= T::MaxBatchSize::get(),
+            Error::<T>::BatchTooLarge
+        );
+        batch
+            .into_iter()
+            .map(|(dest, amount)| Ok((T::Lookup::lookup(dest)?, amount)))
+            .collect()
+    }
+
     /// Return the `AccountCurrencyData` for the `who` and `currency_id`.
     fn get_currency_account(
         currency_id: T::CurrencyId,
@@ -260,10 +825,169 @@ impl<T: Config> Module<T> {
         Balances::<T>::get(who, currency_id)
     }
 
+    /// Minimum balance an account must hold in the currency identified by `currency_id`.
+    /// Defaults to `NativeExistentialDeposit` for the native currency and to zero for the
+    /// other currencies.
+    pub fn existential_deposit(currency_id: T::CurrencyId) -> T::Balance {
+        match Self::details(currency_id) {
+            Some(details) => details.existential_deposit,
+            None if currency_id == T::NativeCurrencyId::get() => T::NativeExistentialDeposit::get(),
+            None => Zero::zero(),
+        }
+    }
+
+    /// Save the details of a currency and update its ACL roles.
+    fn set_currency_details(
+        currency_id: T::CurrencyId,
+        details: CurrencyDetailsOf<T>,
+        maybe_no_longer_owner: Option<T::AccountId>,
+    ) {
+        ScheduledCurrencies::<T>::mutate(|scheduled| {
+            match (scheduled.binary_search(&currency_id), &details.issuance) {
+                (Err(index), Some(_)) => scheduled.insert(index, currency_id),
+                (Ok(index), None) => {
+                    scheduled.remove(index);
+                }
+                _ => {}
+            }
+        });
+        Details::<T>::insert(currency_id, &details);
+        Self::set_currency_acl(currency_id, details, maybe_no_longer_owner);
+    }
+
+    /// Mint the units due by the issuance schedule of a currency, if any. Issuance stops
+    /// once the maximum supply is reached.
+    fn issue_scheduled(currency_id: T::CurrencyId, now: T::BlockNumber) {
+        let details = match Self::details(currency_id) {
+            Some(details) => details,
+            None => return,
+        };
+        let schedule = match details.issuance {
+            Some(schedule) if (now % schedule.period).is_zero() => schedule,
+            _ => return,
+        };
+
+        let amount = match details.max_supply {
+            Some(max_supply) => schedule
+                .amount
+                .min(max_supply.saturating_sub(Self::total_issuances(currency_id))),
+            None => schedule.amount,
+        };
+        if amount.is_zero() {
+            return;
+        }
+
+        if <Self as Currencies<T::AccountId>>::mint(currency_id, &schedule.beneficiary, amount)
+            .is_ok()
+        {
+            Self::deposit_event(RawEvent::ScheduledIssuance(
+                currency_id,
+                schedule.beneficiary,
+                amount,
+            ));
+        }
+    }
+
+    /// Register a new currency owned by `who` after reserving its deposits.
+    fn do_create(
+        who: T::AccountId,
+        currency_id: T::CurrencyId,
+        transferable: bool,
+        metadata: Option<CurrencyMetadata>,
+    ) -> DispatchResult {
+        ensure!(
+            !TotalIssuances::<T>::contains_key(currency_id),
+            Error::<T>::CurrencyAlreadyExists
+        );
+        // Nothing must be reserved if the metadata is going to be refused.
+        if let Some(metadata) = &metadata {
+            ensure!(
+                metadata.is_within(T::MaxMetadataLength::get()),
+                Error::<T>::MetadataTooLong
+            );
+        }
+        let deposit = T::CurrencyDeposit::get();
+        let total_deposit = metadata.as_ref().map_or(deposit, |metadata| {
+            deposit.saturating_add(Self::metadata_deposit(metadata))
+        });
+        ensure!(
+            Self::can_reserve(T::NativeCurrencyId::get(), &who, total_deposit),
+            Error::<T>::BalanceTooLow
+        );
+
+        if !deposit.is_zero() {
+            Self::reserve(T::NativeCurrencyId::get(), &who, deposit)?;
+            CurrencyDeposits::<T>::insert(currency_id, (who.clone(), deposit));
+        }
+        if let Some(metadata) = metadata {
+            Self::do_set_metadata(currency_id, &who, metadata)?;
+        }
+        Self::maybe_create_zero_issuance(currency_id)?;
+
+        let details = CurrencyDetails {
+            owner: who,
+            transferable,
+            restricted_receivers: false,
+            existential_deposit: Zero::zero(),
+            max_supply: None,
+            issuance: None,
+        };
+        Self::set_currency_details(currency_id, details.clone(), None);
+        Self::deposit_event(RawEvent::CurrencyCreated(currency_id, details));
+        Ok(())
+    }
+
+    /// Amount of native currency to reserve for storing `metadata`.
+    fn metadata_deposit(metadata: &CurrencyMetadata) -> T::Balance {
+        T::MetadataDepositPerByte::get().saturating_mul((metadata.byte_len() as u32).into())
+    }
+
+    /// Save the metadata of a currency after reserving its deposit from `who` and returning
+    /// the one of the previous metadata.
+    fn do_set_metadata(
+        currency_id: T::CurrencyId,
+        who: &T::AccountId,
+        metadata: CurrencyMetadata,
+    ) -> DispatchResult {
+        ensure!(
+            metadata.is_within(T::MaxMetadataLength::get()),
+            Error::<T>::MetadataTooLong
+        );
+
+        let native = T::NativeCurrencyId::get();
+        let deposit = Self::metadata_deposit(&metadata);
+        match Self::metadata_deposits(currency_id) {
+            Some((depositor, old_deposit)) if &depositor == who => {
+                if deposit > old_deposit {
+                    Self::reserve(native, who, deposit.saturating_sub(old_deposit))?;
+                } else if deposit < old_deposit {
+                    Self::unreserve(native, who, old_deposit.saturating_sub(deposit));
+                }
+            }
+            maybe_previous => {
+                if !deposit.is_zero() {
+                    Self::reserve(native, who, deposit)?;
+                }
+                if let Some((depositor, old_deposit)) = maybe_previous {
+                    Self::unreserve(native, &depositor, old_deposit);
+                }
+            }
+        }
+
+        if deposit.is_zero() {
+            MetadataDeposits::<T>::remove(currency_id);
+        } else {
+            MetadataDeposits::<T>::insert(currency_id, (who.clone(), deposit));
+        }
+        Metadata::<T>::insert(currency_id, &metadata);
+        Self::deposit_event(RawEvent::CurrencyMetadataChanged(currency_id, metadata));
+        Ok(())
+    }
+
     /// Register the ACL roles accordingly for a given currency.
     fn set_currency_acl(
         currency_id: T::CurrencyId,
-        details: CurrencyDetails<T::AccountId>,
+        details: CurrencyDetailsOf<T>,
         maybe_no_longer_owner: Option<T::AccountId>,
     ) {
         if let Some(previous_owner) = maybe_no_longer_owner {
@@ -299,6 +1023,13 @@ impl<T: Config> Module<T> {
                 RoleBuilderOf::<T>::transfer_currency(currency_id),
             ));
         }
+
+        let receive_role = RoleBuilderOf::<T>::receive_currency(currency_id);
+        let _ = if details.restricted_receivers {
+            RoleManagerOf::<T>::revoke_role(None, receive_role)
+        } else {
+            RoleManagerOf::<T>::grant_role(None, receive_role)
+        };
     }
 
     /// Just set the total issuance to 0. This will write to the storage. Use only when
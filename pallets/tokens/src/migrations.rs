@@ -0,0 +1,92 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{
+    AccountCurrencyData, Balances, Config, CurrencyAccounts, CurrencyAccountsMigrated, Locks,
+    StorageVersion, TokenLock,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+    traits::{Get, WithdrawReasons},
+    weights::Weight,
+    IterableStorageDoubleMap, StorageDoubleMap, StorageValue,
+};
+use sp_runtime::RuntimeDebug;
+
+/// Storage layouts of the pallet.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub enum Releases {
+    /// A single frozen balance per account and locks without withdraw reasons.
+    #[default]
+    V1,
+    /// Frozen balances and locks per withdraw reason.
+    V2,
+}
+
+/// `AccountCurrencyData` as stored in `Releases::V1`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct OldAccountCurrencyData<Balance> {
+    pub free: Balance,
+    pub reserved: Balance,
+    pub frozen: Balance,
+}
+
+/// Split the frozen balances per withdraw reason and turn lock amounts into `TokenLock`s.
+/// Old frozen balances and locks applied to every withdrawal, so they keep doing so.
+pub fn migrate_to_v2<T: Config>() -> Weight {
+    if StorageVersion::get() != Releases::V1 {
+        return T::DbWeight::get().reads(1);
+    }
+
+    let mut entries: u64 = 0;
+    Balances::<T>::translate::<OldAccountCurrencyData<T::Balance>, _>(|_who, _currency_id, old| {
+        entries = entries.saturating_add(1);
+        Some(AccountCurrencyData {
+            free: old.free,
+            reserved: old.reserved,
+            fee_frozen: old.frozen,
+            transfer_frozen: old.frozen,
+            reserve_frozen: old.frozen,
+        })
+    });
+    Locks::<T>::translate::<T::Balance, _>(|_account_and_currency, _lock_id, amount| {
+        entries = entries.saturating_add(1);
+        Some(TokenLock {
+            amount,
+            reasons: WithdrawReasons::all(),
+        })
+    });
+    StorageVersion::put(Releases::V2);
+
+    T::DbWeight::get().reads_writes(entries.saturating_add(1), entries.saturating_add(1))
+}
+
+/// Index the accounts holding a balance before `CurrencyAccounts` was introduced, otherwise
+/// they would be left behind when their currency is destroyed. Only runs once.
+pub fn migrate_currency_accounts<T: Config>() -> Weight {
+    if CurrencyAccountsMigrated::get() {
+        return T::DbWeight::get().reads(1);
+    }
+
+    let mut accounts: u64 = 0;
+    Balances::<T>::iter().for_each(|(who, currency_id, _)| {
+        CurrencyAccounts::<T>::insert(currency_id, who, ());
+        accounts = accounts.saturating_add(1);
+    });
+    CurrencyAccountsMigrated::put(true);
+
+    T::DbWeight::get().reads_writes(accounts.saturating_add(1), accounts.saturating_add(1))
+}
@@ -15,16 +15,30 @@
  */
 
 use crate::{
-    AccountCurrencyData, Balances, Config, Error, RoleBuilder, RoleBuilderOf, RoleManagerOf,
-    TotalIssuances,
+    AccountCurrencyData, Balances, Config, CurrencyAccounts, Destroying, Error, FrozenAccounts,
+    Module, OnDust, RawEvent, RoleBuilder, RoleBuilderOf, RoleManagerOf, TokenLock, TotalIssuances,
 };
-use frame_support::{StorageDoubleMap, StorageMap};
-use governance_os_support::traits::RoleManager;
+use frame_support::{
+    ensure, traits::WithdrawReasons, weights::Weight, StorageDoubleMap, StorageMap,
+};
+use governance_os_support::traits::{OnBalanceChanged, RoleManager};
 use sp_runtime::{
     traits::{CheckedAdd, CheckedSub, Saturating, Zero},
     DispatchResult,
 };
-use sp_std::{collections::btree_map::BTreeMap, marker};
+use sp_std::{collections::btree_map::BTreeMap, marker, prelude::Vec};
+
+/// Release the references held on `who` by one of its currency balances.
+pub fn dec_account_references<T: Config>(who: &T::AccountId) {
+    if frame_system::Pallet::<T>::dec_providers(who).is_err() {
+        log::warn!(
+            target: "runtime::tokens",
+            "Warning: Attempt to remove lock provider reference failed. \
+            This is unexpected but should be safe."
+        );
+    }
+    frame_system::Pallet::<T>::dec_consumers(who);
+}
 
 /// An internal helper to represent balance changes. It is used to express in a better manner
 /// operations done on balances while saving on weight costs by fetching the required data
@@ -36,6 +50,9 @@ pub struct Mutation<T: Config> {
     balances: BTreeMap<T::AccountId, (AccountCurrencyData<T::Balance>, bool, bool)>,
     coins_created: T::Balance,
     coins_burned: T::Balance,
+    existential_deposit: Option<T::Balance>,
+    // Weight actually used by the role checks.
+    role_checks_weight: Weight,
     _phantom: marker::PhantomData<T>,
 }
 impl<T: Config> Mutation<T> {
@@ -45,6 +62,8 @@ impl<T: Config> Mutation<T> {
             balances: BTreeMap::new(),
             coins_created: Zero::zero(),
             coins_burned: Zero::zero(),
+            existential_deposit: None,
+            role_checks_weight: 0,
             _phantom: marker::PhantomData,
         }
     }
@@ -80,14 +99,57 @@ impl<T: Config> Mutation<T> {
         );
     }
 
+    /// Return the existential deposit of the currency, fetching it only once.
+    pub fn existential_deposit(&mut self) -> T::Balance {
+        let currency_id = self.currency_id;
+        *self
+            .existential_deposit
+            .get_or_insert_with(|| Module::<T>::existential_deposit(currency_id))
+    }
+
+    /// Verify that `who` would not be reaped when applying the mutation.
+    pub fn ensure_keep_alive(&mut self, who: &T::AccountId) -> DispatchResult {
+        let existential_deposit = self.existential_deposit();
+        if self.get_or_fetch_balance(who).total() < existential_deposit {
+            return Err(Error::<T>::KeepAlive.into());
+        }
+
+        Ok(())
+    }
+
+    /// Weight used by the role checks made so far, at most `has_role_weight` per check.
+    pub fn role_checks_weight(&self) -> Weight {
+        self.role_checks_weight
+    }
+
+    /// Check that `who` holds `role`, keeping track of the weight it used.
+    fn has_role(
+        &mut self,
+        who: &T::AccountId,
+        role: <RoleManagerOf<T> as RoleManager>::Role,
+    ) -> bool {
+        let (has_role, weight) = RoleManagerOf::<T>::has_role_with_weight(who, role);
+        self.role_checks_weight = self.role_checks_weight.saturating_add(weight);
+        has_role
+    }
+
     /// Verify that the currency is transferable
     pub fn ensure_must_be_transferable_for(&mut self, who: &T::AccountId) -> DispatchResult {
-        if !RoleManagerOf::<T>::has_role(
-            who,
-            RoleBuilderOf::<T>::transfer_currency(self.currency_id),
-        ) {
+        if !self.has_role(who, RoleBuilderOf::<T>::transfer_currency(self.currency_id)) {
             return Err(Error::<T>::UnTransferableCurrency.into());
         }
+        if FrozenAccounts::<T>::get(self.currency_id, who) {
+            return Err(Error::<T>::AccountFrozen.into());
+        }
+
+        Ok(())
+    }
+
+    /// Verify that `who` is allowed to be credited units of the currency through a transfer.
+    pub fn ensure_can_receive(&mut self, who: &T::AccountId) -> DispatchResult {
+        if !self.has_role(who, RoleBuilderOf::<T>::receive_currency(self.currency_id)) {
+            return Err(Error::<T>::CannotReceiveCurrency.into());
+        }
 
         Ok(())
     }
@@ -108,17 +170,20 @@ impl<T: Config> Mutation<T> {
         Ok(())
     }
 
+    /// Withdraw `decrement` from the free balance of `who`. Only the locks applying to one
+    /// of the `reasons` are taken into account.
     pub fn sub_free_balance(
         &mut self,
         who: &T::AccountId,
         decrement: T::Balance,
+        reasons: WithdrawReasons,
     ) -> DispatchResult {
         let mut balance = self.get_or_fetch_balance(who);
         balance.free = balance
             .free
             .checked_sub(&decrement)
             .ok_or(Error::<T>::BalanceTooLow)?;
-        if balance.free < balance.frozen {
+        if balance.free < balance.frozen(reasons) {
             return Err(Error::<T>::BalanceLockTriggered.into());
         }
         self.coins_burned = self.coins_burned.saturating_add(decrement);
@@ -164,8 +229,11 @@ impl<T: Config> Mutation<T> {
         decrement: T::Balance,
     ) -> T::Balance {
         let mut balance = self.get_or_fetch_balance(who);
-        let actual_subed = balance.free.saturating_sub(balance.frozen).min(decrement);
-        // We just capped `actual_subed` to `balance.free - balance.frozen` itself.
+        let actual_subed = balance
+            .free
+            .saturating_sub(balance.max_frozen())
+            .min(decrement);
+        // We just capped `actual_subed` to `balance.free - balance.max_frozen()` itself.
         balance.free -= actual_subed;
         self.coins_burned = self.coins_burned.saturating_add(actual_subed);
         self.save_balance(who, balance);
@@ -173,58 +241,21 @@ impl<T: Config> Mutation<T> {
         actual_subed
     }
 
-    pub fn frozen(&mut self, who: &T::AccountId) -> T::Balance {
-        let balance = self.get_or_fetch_balance(who);
-        balance.frozen
-    }
-
-    pub fn add_frozen(&mut self, who: &T::AccountId, increment: T::Balance) -> DispatchResult {
-        let mut balance = self.get_or_fetch_balance(who);
-        balance.frozen = balance
-            .frozen
-            .checked_add(&increment)
-            .ok_or(Error::<T>::BalanceOverflow)?;
-        self.coins_created = self.coins_created.saturating_add(increment);
-        self.save_balance(who, balance);
-
-        Ok(())
-    }
-
-    pub fn sub_frozen(&mut self, who: &T::AccountId, decrement: T::Balance) -> DispatchResult {
-        let mut balance = self.get_or_fetch_balance(who);
-        balance.frozen = balance
-            .frozen
-            .checked_sub(&decrement)
-            .ok_or(Error::<T>::BalanceTooLow)?;
-        self.coins_burned = self.coins_burned.saturating_sub(decrement);
-        self.save_balance(who, balance);
-
-        Ok(())
-    }
-
-    /// Does what it says and return the old balance.
-    pub fn overwrite_frozen_balance(
+    /// Recompute the frozen balances of `who` from all of its `locks`.
+    pub fn overwrite_frozen_balances(
         &mut self,
         who: &T::AccountId,
-        new_balance: T::Balance,
-    ) -> T::Balance {
+        locks: impl IntoIterator<Item = TokenLock<T::Balance>>,
+    ) {
         let mut balance = self.get_or_fetch_balance(who);
-        if balance.frozen < new_balance {
-            self.coins_created = self
-                .coins_created
-                .saturating_add(new_balance.saturating_sub(balance.frozen));
-        } else {
-            self.coins_burned = self
-                .coins_burned
-                .saturating_add(balance.frozen.saturating_sub(new_balance));
-        }
-
-        let frozen_balance_bak = balance.frozen;
-        balance.frozen = new_balance;
+        balance.fee_frozen = Zero::zero();
+        balance.transfer_frozen = Zero::zero();
+        balance.reserve_frozen = Zero::zero();
+        locks
+            .into_iter()
+            .for_each(|lock| balance.freeze(lock.amount, lock.reasons));
 
         self.save_balance(who, balance);
-
-        frozen_balance_bak
     }
 
     /// Does what it says and return the old balance.
@@ -259,8 +290,59 @@ impl<T: Config> Mutation<T> {
         self.coins_burned = Zero::zero();
     }
 
-    /// Commit all the changes to the chain state or error.
-    pub fn apply(self) -> DispatchResult {
+    /// Commit all the changes to the chain state or error. Accounts that went below the
+    /// existential deposit are reaped unless they have frozen or reserved funds, their remaining balance
+    /// is burned and given to the `OnDust` handler.
+    pub fn apply(mut self) -> DispatchResult {
+        ensure!(
+            !Destroying::<T>::get(self.currency_id),
+            Error::<T>::CurrencyDestroying
+        );
+
+        let existential_deposit = self.existential_deposit();
+        let mut dust = Vec::new();
+        for (account, (balance, _changed, snapshot_was_0)) in self
+            .balances
+            .iter_mut()
+            .filter(|(_account, (_bal, changed, _snapshot_was_0))| *changed)
+        {
+            let total = balance.total();
+            if total.is_zero() || total >= existential_deposit {
+                continue;
+            }
+            // We check this before writing anything to avoid partial changes
+            if *snapshot_was_0 {
+                return Err(Error::<T>::ExistentialDeposit.into());
+            }
+            if balance.max_frozen().is_zero() && balance.reserved.is_zero() {
+                dust.push((account.clone(), total));
+                *balance = Default::default();
+            }
+        }
+        for (_account, amount) in dust.iter() {
+            self.coins_burned = self.coins_burned.saturating_add(*amount);
+        }
+
+        let maybe_new_issuance = if self.coins_created != self.coins_burned {
+            let new_issuance = Module::<T>::total_issuances(self.currency_id)
+                .checked_sub(&self.coins_burned)
+                .ok_or(Error::<T>::TotalIssuanceUnderflow)?
+                .checked_add(&self.coins_created)
+                .ok_or(Error::<T>::TotalIssuanceOverflow)?;
+            if self.coins_created > self.coins_burned {
+                if let Some(max_supply) =
+                    Module::<T>::details(self.currency_id).and_then(|details| details.max_supply)
+                {
+                    ensure!(new_issuance <= max_supply, Error::<T>::MaxSupplyExceeded);
+                }
+            }
+            Some(new_issuance)
+        } else {
+            None
+        };
+
+        // Checkpoints are read from the chain state before it is overwritten.
+        let checkpointed = Module::<T>::checkpoints_since(self.currency_id).is_some();
         self.balances
             .iter()
             .filter(|(_account, (_bal, changed, _snapshot_was_0))| *changed)
@@ -268,17 +350,14 @@ impl<T: Config> Mutation<T> {
                 (account, balance, snapshot_was_0)
             })
             .for_each(|(account, balance, snapshot_0)| {
+                if checkpointed {
+                    Module::<T>::checkpoint_balance(self.currency_id, account, balance.total());
+                }
                 if balance.total() == Zero::zero() {
                     if !*snapshot_0 {
-                        if frame_system::Pallet::<T>::dec_providers(account).is_err() {
-                            log::warn!(
-                                target: "runtime::tokens",
-                                "Warning: Attempt to remove lock provider reference failed. \
-                                This is unexpected but should be safe."
-                            );
-                        }
-                        frame_system::Pallet::<T>::dec_consumers(account);
+                        dec_account_references::<T>(account);
                         Balances::<T>::remove(account, self.currency_id);
+                        CurrencyAccounts::<T>::remove(self.currency_id, account);
                     }
                 } else {
                     if *snapshot_0 {
@@ -290,24 +369,40 @@ impl<T: Config> Mutation<T> {
                                 This is unexpected but should be safe."
                             );
                         }
+                        CurrencyAccounts::<T>::insert(self.currency_id, account, ());
                     }
                     Balances::<T>::insert(account, self.currency_id, balance);
                 }
             });
 
-        if self.coins_created != self.coins_burned {
-            TotalIssuances::<T>::try_mutate(self.currency_id, |d| -> DispatchResult {
-                *d = d
-                    .checked_sub(&self.coins_burned)
-                    .ok_or(Error::<T>::TotalIssuanceUnderflow)?;
-                *d = d
-                    .checked_add(&self.coins_created)
-                    .ok_or(Error::<T>::TotalIssuanceOverflow)?;
-
-                Ok(())
-            })?;
+        if let Some(new_issuance) = maybe_new_issuance {
+            if checkpointed {
+                Module::<T>::checkpoint_issuance(self.currency_id);
+            }
+            TotalIssuances::<T>::insert(self.currency_id, new_issuance);
         }
 
+        self.balances
+            .iter()
+            .filter(|(_account, (_bal, changed, _snapshot_was_0))| *changed)
+            .for_each(|(account, (balance, _changed, _snapshot_was_0))| {
+                T::OnBalanceChanged::on_balance_changed(
+                    self.currency_id,
+                    account,
+                    balance.free,
+                    balance.reserved,
+                )
+            });
+
+        dust.into_iter().for_each(|(account, amount)| {
+            Module::<T>::deposit_event(RawEvent::DustLost(
+                self.currency_id,
+                account.clone(),
+                amount,
+            ));
+            T::OnDust::on_dust(self.currency_id, &account, amount);
+        });
+
         Ok(())
     }
 }
@@ -179,6 +179,63 @@ fn withdraw() {
         })
 }
 
+#[test]
+fn transfer_keep_alive_fails_if_source_would_be_reaped() {
+    ExtBuilder::default()
+        .existential_deposit(10)
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                TokensCurrencyAdapter::transfer(&ALICE, &BOB, 95, ExistenceRequirement::KeepAlive),
+                Error::<Test>::KeepAlive
+            );
+            assert_ok!(TokensCurrencyAdapter::transfer(
+                &ALICE,
+                &BOB,
+                95,
+                ExistenceRequirement::AllowDeath
+            ));
+            assert_eq!(TokensCurrencyAdapter::total_balance(&ALICE), 0);
+        })
+}
+
+#[test]
+fn withdraw_keep_alive_fails_if_account_would_be_reaped() {
+    ExtBuilder::default()
+        .existential_deposit(10)
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert!(TokensCurrencyAdapter::withdraw(
+                &ALICE,
+                95,
+                WithdrawReasons::all(),
+                ExistenceRequirement::KeepAlive
+            )
+            .is_err());
+            assert_eq!(TokensCurrencyAdapter::free_balance(&ALICE), 100);
+
+            assert!(TokensCurrencyAdapter::withdraw(
+                &ALICE,
+                90,
+                WithdrawReasons::all(),
+                ExistenceRequirement::KeepAlive
+            )
+            .is_ok());
+        })
+}
+
+#[test]
+fn minimum_balance() {
+    ExtBuilder::default()
+        .existential_deposit(10)
+        .build()
+        .execute_with(|| {
+            assert_eq!(TokensCurrencyAdapter::minimum_balance(), 10);
+        })
+}
+
 #[test]
 fn ensure_can_withdraw() {
     ExtBuilder::default()
@@ -452,7 +509,7 @@ fn set_lock() {
         .execute_with(|| {
             TokensCurrencyAdapter::set_lock(*b"testtest", &ALICE, 25, WithdrawReasons::all());
             assert_eq!(
-                Tokens::get_currency_account(TEST_TOKEN_ID, &ALICE).frozen,
+                Tokens::get_currency_account(TEST_TOKEN_ID, &ALICE).max_frozen(),
                 25
             );
         })
@@ -467,7 +524,7 @@ fn extend_lock() {
             TokensCurrencyAdapter::set_lock(*b"testtest", &ALICE, 25, WithdrawReasons::all());
             TokensCurrencyAdapter::extend_lock(*b"testtest", &ALICE, 30, WithdrawReasons::all());
             assert_eq!(
-                Tokens::get_currency_account(TEST_TOKEN_ID, &ALICE).frozen,
+                Tokens::get_currency_account(TEST_TOKEN_ID, &ALICE).max_frozen(),
                 30
             );
         })
@@ -482,7 +539,7 @@ fn remove_lock() {
             TokensCurrencyAdapter::set_lock(*b"testtest", &ALICE, 25, WithdrawReasons::all());
             TokensCurrencyAdapter::remove_lock(*b"testtest", &ALICE);
             assert_eq!(
-                Tokens::get_currency_account(TEST_TOKEN_ID, &ALICE).frozen,
+                Tokens::get_currency_account(TEST_TOKEN_ID, &ALICE).max_frozen(),
                 0
             );
         })
@@ -0,0 +1,202 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{Error, RawEvent};
+use frame_support::{assert_noop, assert_ok};
+use governance_os_support::{
+    testing::{
+        primitives::{AccountId, Balance},
+        ALICE, BOB, CHARLIE, EVE, TEST_TOKEN_ID,
+    },
+    traits::{Currencies, ReservableCurrencies},
+};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+fn leaf(who: AccountId, amount: Balance) -> H256 {
+    BlakeTwo256::hash_of(&(who, amount))
+}
+
+fn node(a: H256, b: H256) -> H256 {
+    if a <= b {
+        BlakeTwo256::hash_of(&(a, b))
+    } else {
+        BlakeTwo256::hash_of(&(b, a))
+    }
+}
+
+/// Tree with `(BOB, 10)`, `(CHARLIE, 20)` and `(EVE, 30)`.
+fn tree() -> (H256, Vec<H256>) {
+    let leaves = vec![leaf(BOB, 10), leaf(CHARLIE, 20), leaf(EVE, 30)];
+    let left = node(leaves[0], leaves[1]);
+    (node(left, leaves[2]), leaves)
+}
+
+fn create_airdrop() {
+    let (root, _) = tree();
+    assert_ok!(Tokens::create_airdrop(
+        Origin::signed(ALICE),
+        TEST_TOKEN_ID,
+        root,
+        60
+    ));
+}
+
+#[test]
+fn create_airdrop_reserves_coins() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            create_airdrop();
+
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), 40);
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE), 60);
+            assert_eq!(Tokens::next_airdrop_id(), 1);
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_tokens(RawEvent::AirdropCreated(
+                    0,
+                    TEST_TOKEN_ID,
+                    ALICE,
+                    tree().0,
+                    60
+                ))));
+        })
+}
+
+#[test]
+fn create_airdrop_fails_if_balance_too_low() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Tokens::create_airdrop(Origin::signed(ALICE), TEST_TOKEN_ID, tree().0, 101),
+                Error::<Test>::BalanceTooLow
+            );
+        })
+}
+
+#[test]
+fn claim_airdrop_works() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            create_airdrop();
+            let (_, leaves) = tree();
+
+            assert_ok!(Tokens::claim_airdrop(
+                Origin::signed(CHARLIE),
+                0,
+                20,
+                vec![leaves[0], leaves[2]]
+            ));
+            assert_ok!(Tokens::claim_airdrop(
+                Origin::signed(EVE),
+                0,
+                30,
+                vec![node(leaves[0], leaves[1])]
+            ));
+
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &CHARLIE), 20);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &EVE), 30);
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE), 10);
+            assert_eq!(Tokens::airdrops(0).unwrap().remaining, 10);
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_tokens(RawEvent::AirdropClaimed(0, EVE, 30))));
+        })
+}
+
+#[test]
+fn claim_airdrop_only_once() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            create_airdrop();
+            let (_, leaves) = tree();
+
+            let proof = vec![leaves[1], leaves[2]];
+            assert_ok!(Tokens::claim_airdrop(
+                Origin::signed(BOB),
+                0,
+                10,
+                proof.clone()
+            ));
+            assert_noop!(
+                Tokens::claim_airdrop(Origin::signed(BOB), 0, 10, proof),
+                Error::<Test>::AirdropAlreadyClaimed
+            );
+        })
+}
+
+#[test]
+fn claim_airdrop_fails_with_invalid_proof() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            create_airdrop();
+            let (_, leaves) = tree();
+
+            // Wrong amount
+            assert_noop!(
+                Tokens::claim_airdrop(Origin::signed(BOB), 0, 20, vec![leaves[1], leaves[2]]),
+                Error::<Test>::InvalidMerkleProof
+            );
+            // Wrong account
+            assert_noop!(
+                Tokens::claim_airdrop(Origin::signed(ALICE), 0, 10, vec![leaves[1], leaves[2]]),
+                Error::<Test>::InvalidMerkleProof
+            );
+            assert_noop!(
+                Tokens::claim_airdrop(Origin::signed(BOB), 1, 10, vec![leaves[1], leaves[2]]),
+                Error::<Test>::UnknownAirdrop
+            );
+        })
+}
+
+#[test]
+fn close_airdrop_returns_unclaimed_coins() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            create_airdrop();
+            let (_, leaves) = tree();
+            assert_ok!(Tokens::claim_airdrop(
+                Origin::signed(BOB),
+                0,
+                10,
+                vec![leaves[1], leaves[2]]
+            ));
+
+            assert_noop!(
+                Tokens::close_airdrop(Origin::signed(BOB), 0),
+                Error::<Test>::NotAirdropCreator
+            );
+            assert_ok!(Tokens::close_airdrop(Origin::signed(ALICE), 0));
+
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), 90);
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE), 0);
+            assert_eq!(Tokens::airdrops(0), None);
+            assert!(!Tokens::airdrop_claims(0, BOB));
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_tokens(RawEvent::AirdropClosed(0, 50))));
+        })
+}
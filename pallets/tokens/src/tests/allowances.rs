@@ -0,0 +1,263 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{Allowances, Error, RawEvent, RoleBuilder, TotalAllowances};
+use frame_support::{assert_noop, assert_ok, StorageDoubleMap};
+use governance_os_support::{
+    testing::{primitives::AccountId, ALICE, BOB, CHARLIE, TEST_TOKEN_ID, TEST_TOKEN_OWNER},
+    traits::{AllowanceCurrencies, Currencies, RoleManager},
+};
+
+#[test]
+fn approve_sets_allowance() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Tokens::approve(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            BOB,
+            50
+        ));
+        assert_eq!(Tokens::allowance(TEST_TOKEN_ID, &ALICE, &BOB), 50);
+        assert_eq!(Tokens::allowance(TEST_TOKEN_ID, &BOB, &ALICE), 0);
+        assert!(System::events().iter().any(|record| record.event
+            == Event::governance_os_pallet_tokens(RawEvent::Approval(
+                TEST_TOKEN_ID,
+                ALICE,
+                BOB,
+                50
+            ))));
+
+        // Overwrites the previous allowance
+        assert_ok!(Tokens::approve(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            BOB,
+            20
+        ));
+        assert_eq!(Tokens::allowance(TEST_TOKEN_ID, &ALICE, &BOB), 20);
+    })
+}
+
+#[test]
+fn approve_zero_clears_storage() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Tokens::approve(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            BOB,
+            50
+        ));
+        assert_ok!(Tokens::approve(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            BOB,
+            0
+        ));
+        assert!(!Allowances::<Test>::contains_key(
+            TEST_TOKEN_ID,
+            (ALICE, BOB)
+        ));
+    })
+}
+
+#[test]
+fn total_allowance_tracks_all_spenders() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::approve(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                50
+            ));
+            assert_ok!(Tokens::approve(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                CHARLIE,
+                30
+            ));
+            assert_eq!(Tokens::total_allowance(TEST_TOKEN_ID, &ALICE), 80);
+
+            assert_ok!(Tokens::approve(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                10
+            ));
+            assert_eq!(Tokens::total_allowance(TEST_TOKEN_ID, &ALICE), 40);
+
+            assert_ok!(Tokens::transfer_from(
+                Origin::signed(CHARLIE),
+                TEST_TOKEN_ID,
+                ALICE,
+                CHARLIE,
+                30
+            ));
+            assert_eq!(Tokens::total_allowance(TEST_TOKEN_ID, &ALICE), 10);
+
+            assert_ok!(Tokens::approve(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                0
+            ));
+            assert!(!TotalAllowances::<Test>::contains_key(TEST_TOKEN_ID, ALICE));
+        })
+}
+
+#[test]
+fn increase_and_decrease_allowance() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Tokens::increase_allowance(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            BOB,
+            30
+        ));
+        assert_ok!(Tokens::increase_allowance(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            BOB,
+            20
+        ));
+        assert_eq!(Tokens::allowance(TEST_TOKEN_ID, &ALICE, &BOB), 50);
+
+        assert_ok!(Tokens::decrease_allowance(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            BOB,
+            10
+        ));
+        assert_eq!(Tokens::allowance(TEST_TOKEN_ID, &ALICE, &BOB), 40);
+
+        // Saturates at zero
+        assert_ok!(Tokens::decrease_allowance(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            BOB,
+            100
+        ));
+        assert_eq!(Tokens::allowance(TEST_TOKEN_ID, &ALICE, &BOB), 0);
+    })
+}
+
+#[test]
+fn transfer_from_spends_allowance() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::approve(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                50
+            ));
+            assert_ok!(Tokens::transfer_from(
+                Origin::signed(BOB),
+                TEST_TOKEN_ID,
+                ALICE,
+                CHARLIE,
+                30
+            ));
+
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), 70);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &BOB), 100);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &CHARLIE), 30);
+            assert_eq!(Tokens::allowance(TEST_TOKEN_ID, &ALICE, &BOB), 20);
+        })
+}
+
+#[test]
+fn transfer_from_fails_if_allowance_too_low() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::approve(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                20
+            ));
+            assert_noop!(
+                Tokens::transfer_from(Origin::signed(BOB), TEST_TOKEN_ID, ALICE, CHARLIE, 30),
+                Error::<Test>::AllowanceTooLow
+            );
+            assert_noop!(
+                Tokens::transfer_from(Origin::signed(CHARLIE), TEST_TOKEN_ID, ALICE, CHARLIE, 10),
+                Error::<Test>::AllowanceTooLow
+            );
+        })
+}
+
+#[test]
+fn transfer_from_keeps_allowance_if_transfer_fails() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::approve(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                500
+            ));
+            assert_noop!(
+                Tokens::transfer_from(Origin::signed(BOB), TEST_TOKEN_ID, ALICE, CHARLIE, 200),
+                Error::<Test>::BalanceTooLow
+            );
+            assert_eq!(Tokens::allowance(TEST_TOKEN_ID, &ALICE, &BOB), 500);
+        })
+}
+
+#[test]
+fn transfer_from_checks_owner_transfer_role() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Tokens::create(
+            Origin::signed(TEST_TOKEN_OWNER),
+            42,
+            false,
+            None
+        ));
+        assert_ok!(<Tokens as Currencies<AccountId>>::mint(42, &ALICE, 100));
+        assert_ok!(<Tokens as Currencies<AccountId>>::mint(42, &CHARLIE, 100));
+
+        assert_ok!(Tokens::approve(Origin::signed(ALICE), 42, BOB, 50));
+        assert_ok!(Tokens::approve(Origin::signed(CHARLIE), 42, BOB, 50));
+        assert_noop!(
+            Tokens::transfer_from(Origin::signed(BOB), 42, ALICE, BOB, 50),
+            Error::<Test>::UnTransferableCurrency
+        );
+
+        // Only the owner of the coins needs to be allowed to transfer them
+        assert_ok!(Bylaws::grant_role(
+            Some(&CHARLIE),
+            MockRoles::transfer_currency(42)
+        ));
+        assert_ok!(Tokens::transfer_from(
+            Origin::signed(BOB),
+            42,
+            CHARLIE,
+            BOB,
+            50
+        ));
+        assert_eq!(Tokens::free_balance(42, &BOB), 50);
+    })
+}
@@ -0,0 +1,131 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{Error, RawEvent};
+use frame_support::{assert_noop, assert_ok};
+use governance_os_support::{
+    errors::AclError,
+    testing::{ALICE, BOB, CHARLIE, EVE, TEST_TOKEN_ID, TEST_TOKEN_OWNER},
+    traits::Currencies,
+};
+
+#[test]
+fn transfer_batch_works() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::transfer_batch(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                vec![(BOB, 10), (CHARLIE, 20), (EVE, 30)]
+            ));
+
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), 40);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &BOB), 110);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &CHARLIE), 20);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &EVE), 30);
+            assert_eq!(Tokens::total_issuance(TEST_TOKEN_ID), 200);
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_tokens(RawEvent::CurrencyTransferred(
+                    TEST_TOKEN_ID,
+                    ALICE,
+                    EVE,
+                    30
+                ))));
+        })
+}
+
+#[test]
+fn transfer_batch_is_all_or_nothing() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            // The first two transfers are fine but the third one is not
+            assert_noop!(
+                Tokens::transfer_batch(
+                    Origin::signed(ALICE),
+                    TEST_TOKEN_ID,
+                    vec![(BOB, 40), (CHARLIE, 40), (EVE, 40)]
+                ),
+                Error::<Test>::BalanceTooLow
+            );
+        })
+}
+
+#[test]
+fn transfer_batch_fails_if_too_large() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Tokens::transfer_batch(
+                    Origin::signed(ALICE),
+                    TEST_TOKEN_ID,
+                    vec![(BOB, 1), (CHARLIE, 1), (EVE, 1), (TEST_TOKEN_OWNER, 1)]
+                ),
+                Error::<Test>::BatchTooLarge
+            );
+        })
+}
+
+#[test]
+fn mint_batch_works() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Tokens::mint_batch(
+            Origin::signed(TEST_TOKEN_OWNER),
+            TEST_TOKEN_ID,
+            vec![(ALICE, 10), (BOB, 20), (ALICE, 5)]
+        ));
+
+        assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), 15);
+        assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &BOB), 20);
+        assert_eq!(Tokens::total_issuance(TEST_TOKEN_ID), 35);
+        assert!(System::events().iter().any(|record| record.event
+            == Event::governance_os_pallet_tokens(RawEvent::CurrencyMinted(
+                TEST_TOKEN_ID,
+                BOB,
+                20
+            ))));
+    })
+}
+
+#[test]
+fn mint_batch_fails_if_not_owner() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Tokens::mint_batch(Origin::signed(ALICE), TEST_TOKEN_ID, vec![(ALICE, 10)]),
+            AclError::MissingRole
+        );
+    })
+}
+
+#[test]
+fn mint_batch_fails_if_too_large() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Tokens::mint_batch(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                vec![(ALICE, 1), (BOB, 1), (CHARLIE, 1), (EVE, 1)]
+            ),
+            Error::<Test>::BatchTooLarge
+        );
+    })
+}
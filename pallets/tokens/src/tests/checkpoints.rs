@@ -0,0 +1,260 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use super::mock::*;
+use crate::{BalanceCheckpoints, Error, IssuanceCheckpoints, RawEvent, RoleBuilder};
+use frame_support::{assert_noop, assert_ok, StorageDoubleMap, StorageMap};
+use governance_os_support::{
+    errors::AclError,
+    testing::{primitives::AccountId, ALICE, BOB, CHARLIE, TEST_TOKEN_ID, TEST_TOKEN_OWNER},
+    traits::{CheckpointedCurrencies, Currencies, ReservableCurrencies, RoleManager},
+};
+
+fn enable_checkpoints() {
+    assert_ok!(Tokens::set_checkpoints(
+        Origin::signed(TEST_TOKEN_OWNER),
+        TEST_TOKEN_ID,
+        true
+    ));
+}
+
+#[test]
+fn set_checkpoints_works() {
+    ExtBuilder::default().build().execute_with(|| {
+        enable_checkpoints();
+        assert_eq!(Tokens::checkpoints_since(TEST_TOKEN_ID), Some(1));
+        assert!(System::events().iter().any(|record| record.event
+            == Event::governance_os_pallet_tokens(RawEvent::CheckpointsChanged(
+                TEST_TOKEN_ID,
+                true
+            ))));
+
+        // Enabling them again keeps the history.
+        System::set_block_number(5);
+        enable_checkpoints();
+        assert_eq!(Tokens::checkpoints_since(TEST_TOKEN_ID), Some(1));
+
+        assert_ok!(Tokens::set_checkpoints(
+            Origin::signed(TEST_TOKEN_OWNER),
+            TEST_TOKEN_ID,
+            false
+        ));
+        assert_eq!(Tokens::checkpoints_since(TEST_TOKEN_ID), None);
+    })
+}
+
+#[test]
+fn set_checkpoints_requires_role() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Tokens::set_checkpoints(Origin::signed(ALICE), TEST_TOKEN_ID, true),
+            AclError::MissingRole
+        );
+    })
+}
+
+#[test]
+fn set_checkpoints_fails_for_unknown_currencies() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Bylaws::grant_role(
+            Some(&ALICE),
+            MockRoles::manage_currency(42)
+        ));
+        assert_noop!(
+            Tokens::set_checkpoints(Origin::signed(ALICE), 42, true),
+            Error::<Test>::UnknownCurrency
+        );
+    })
+}
+
+#[test]
+fn past_balances_are_returned() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            enable_checkpoints();
+
+            System::set_block_number(2);
+            assert_ok!(Tokens::transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                40
+            ));
+            // Only the first change of a block is recorded.
+            assert_ok!(Tokens::transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                10
+            ));
+            System::set_block_number(4);
+            assert_ok!(Tokens::reserve(TEST_TOKEN_ID, &ALICE, 20));
+            assert_ok!(Tokens::transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                CHARLIE,
+                20
+            ));
+            System::set_block_number(6);
+
+            assert_eq!(
+                BalanceCheckpoints::<Test>::get(TEST_TOKEN_ID, ALICE),
+                vec![(2, 100), (4, 50)]
+            );
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &ALICE, 1), Some(100));
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &ALICE, 2), Some(50));
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &ALICE, 3), Some(50));
+            // Reserved coins are part of the total balance.
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &ALICE, 4), Some(30));
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &BOB, 1), Some(100));
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &BOB, 5), Some(150));
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &CHARLIE, 3), Some(0));
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &CHARLIE, 4), Some(20));
+        })
+}
+
+#[test]
+fn past_issuances_are_returned() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            enable_checkpoints();
+
+            System::set_block_number(2);
+            assert_ok!(<Tokens as Currencies<AccountId>>::mint(
+                TEST_TOKEN_ID,
+                &CHARLIE,
+                50
+            ));
+            System::set_block_number(3);
+            assert_ok!(<Tokens as Currencies<AccountId>>::burn(
+                TEST_TOKEN_ID,
+                &ALICE,
+                100
+            ));
+
+            assert_eq!(
+                IssuanceCheckpoints::<Test>::get(TEST_TOKEN_ID),
+                vec![(2, 200), (3, 250)]
+            );
+            assert_eq!(Tokens::total_issuance_at(TEST_TOKEN_ID, 1), Some(200));
+            assert_eq!(Tokens::total_issuance_at(TEST_TOKEN_ID, 2), Some(250));
+            assert_eq!(Tokens::total_issuance_at(TEST_TOKEN_ID, 3), Some(150));
+        })
+}
+
+#[test]
+fn oldest_checkpoints_are_dropped() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            enable_checkpoints();
+
+            for block in 2..6 {
+                System::set_block_number(block);
+                assert_ok!(<Tokens as Currencies<AccountId>>::mint(
+                    TEST_TOKEN_ID,
+                    &ALICE,
+                    10
+                ));
+            }
+
+            // `MaxCheckpoints` is 3 in the mock.
+            assert_eq!(
+                BalanceCheckpoints::<Test>::get(TEST_TOKEN_ID, ALICE),
+                vec![(3, 110), (4, 120), (5, 130)]
+            );
+            assert_eq!(
+                Tokens::balance_checkpoints_start(TEST_TOKEN_ID, ALICE),
+                Some(2)
+            );
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &ALICE, 1), None);
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &ALICE, 2), Some(110));
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &ALICE, 5), Some(140));
+            assert_eq!(
+                IssuanceCheckpoints::<Test>::get(TEST_TOKEN_ID),
+                vec![(3, 210), (4, 220), (5, 230)]
+            );
+            assert_eq!(Tokens::issuance_checkpoints_start(TEST_TOKEN_ID), Some(2));
+            assert_eq!(Tokens::total_issuance_at(TEST_TOKEN_ID, 1), None);
+            assert_eq!(Tokens::total_issuance_at(TEST_TOKEN_ID, 4), Some(230));
+
+            // Untouched accounts keep their full history.
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &BOB, 1), Some(100));
+        })
+}
+
+#[test]
+fn nothing_is_returned_without_checkpoints() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                40
+            ));
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &ALICE, 1), None);
+            assert_eq!(Tokens::total_issuance_at(TEST_TOKEN_ID, 1), None);
+            assert!(!BalanceCheckpoints::<Test>::contains_key(
+                TEST_TOKEN_ID,
+                ALICE
+            ));
+
+            System::set_block_number(3);
+            enable_checkpoints();
+            // Balances prior to the checkpoints are unknown.
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &ALICE, 2), None);
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &ALICE, 3), Some(60));
+        })
+}
+
+#[test]
+fn destroy_removes_checkpoints() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            enable_checkpoints();
+            System::set_block_number(2);
+            assert_ok!(<Tokens as Currencies<AccountId>>::burn(
+                TEST_TOKEN_ID,
+                &ALICE,
+                100
+            ));
+
+            assert_ok!(Tokens::start_destroy(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID
+            ));
+            assert_eq!(Tokens::balance_at(TEST_TOKEN_ID, &ALICE, 1), None);
+            assert_ok!(Tokens::continue_destroy(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                10
+            ));
+            assert!(!BalanceCheckpoints::<Test>::contains_key(
+                TEST_TOKEN_ID,
+                ALICE
+            ));
+            assert!(!IssuanceCheckpoints::<Test>::contains_key(TEST_TOKEN_ID));
+        })
+}
@@ -0,0 +1,234 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{CurrencyDetails, Error, RawEvent, RoleBuilder};
+use frame_support::{assert_noop, assert_ok};
+use governance_os_support::{
+    errors::AclError,
+    testing::{primitives::AccountId, ALICE, BOB, CHARLIE, TEST_TOKEN_ID, TEST_TOKEN_OWNER},
+    traits::{Currencies, LockableCurrencies, RoleManager},
+};
+
+fn restrict_receivers(restricted_receivers: bool) {
+    assert_ok!(Tokens::update_details(
+        Origin::signed(TEST_TOKEN_OWNER),
+        TEST_TOKEN_ID,
+        CurrencyDetails {
+            owner: TEST_TOKEN_OWNER,
+            transferable: true,
+            restricted_receivers,
+            existential_deposit: 0,
+            max_supply: None,
+            issuance: None,
+        }
+    ));
+}
+
+#[test]
+fn restricted_receivers_need_role() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            restrict_receivers(true);
+            assert_noop!(
+                Tokens::transfer(Origin::signed(ALICE), TEST_TOKEN_ID, CHARLIE, 10),
+                Error::<Test>::CannotReceiveCurrency
+            );
+
+            assert_ok!(Bylaws::grant_role(
+                Some(&CHARLIE),
+                MockRoles::receive_currency(TEST_TOKEN_ID)
+            ));
+            assert_ok!(Tokens::transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                CHARLIE,
+                10
+            ));
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &CHARLIE), 10);
+        })
+}
+
+#[test]
+fn lifting_receivers_restriction_lets_anybody_receive() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            restrict_receivers(true);
+            restrict_receivers(false);
+            assert_ok!(Tokens::transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                CHARLIE,
+                10
+            ));
+        })
+}
+
+#[test]
+fn frozen_accounts_can_not_send() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::freeze(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                ALICE
+            ));
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_tokens(RawEvent::AccountFrozen(
+                    TEST_TOKEN_ID,
+                    ALICE
+                ))));
+
+            assert_noop!(
+                Tokens::transfer(Origin::signed(ALICE), TEST_TOKEN_ID, BOB, 10),
+                Error::<Test>::AccountFrozen
+            );
+            assert_ok!(Tokens::approve(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                10
+            ));
+            assert_noop!(
+                Tokens::transfer_from(Origin::signed(BOB), TEST_TOKEN_ID, ALICE, BOB, 10),
+                Error::<Test>::AccountFrozen
+            );
+
+            // Frozen accounts may still receive coins
+            assert_ok!(Tokens::transfer(
+                Origin::signed(BOB),
+                TEST_TOKEN_ID,
+                ALICE,
+                10
+            ));
+        })
+}
+
+#[test]
+fn thawed_accounts_can_send_again() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::freeze(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                ALICE
+            ));
+            assert_ok!(Tokens::thaw(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                ALICE
+            ));
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_tokens(RawEvent::AccountThawed(
+                    TEST_TOKEN_ID,
+                    ALICE
+                ))));
+
+            assert_ok!(Tokens::transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                10
+            ));
+        })
+}
+
+#[test]
+fn compliance_calls_require_manager() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Tokens::freeze(Origin::signed(ALICE), TEST_TOKEN_ID, BOB),
+                AclError::MissingRole
+            );
+            assert_noop!(
+                Tokens::thaw(Origin::signed(ALICE), TEST_TOKEN_ID, BOB),
+                AclError::MissingRole
+            );
+            assert_noop!(
+                Tokens::force_transfer(Origin::signed(ALICE), TEST_TOKEN_ID, BOB, ALICE, 10),
+                AclError::MissingRole
+            );
+        })
+}
+
+#[test]
+fn force_transfer_ignores_locks_freezes_and_roles() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            restrict_receivers(true);
+            assert_ok!(<Tokens as LockableCurrencies<AccountId>>::set_lock(
+                TEST_TOKEN_ID,
+                *b"testlock",
+                &ALICE,
+                100
+            ));
+            assert_ok!(Tokens::freeze(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                ALICE
+            ));
+
+            assert_ok!(Tokens::force_transfer(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                ALICE,
+                CHARLIE,
+                60
+            ));
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), 40);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &CHARLIE), 60);
+            assert_eq!(Tokens::total_issuance(TEST_TOKEN_ID), 200);
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_tokens(RawEvent::ForcedTransfer(
+                    TEST_TOKEN_ID,
+                    ALICE,
+                    CHARLIE,
+                    60
+                ))));
+        })
+}
+
+#[test]
+fn force_transfer_fails_if_balance_too_low() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Tokens::force_transfer(
+                    Origin::signed(TEST_TOKEN_OWNER),
+                    TEST_TOKEN_ID,
+                    ALICE,
+                    CHARLIE,
+                    101
+                ),
+                Error::<Test>::BalanceTooLow
+            );
+        })
+}
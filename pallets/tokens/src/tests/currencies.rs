@@ -15,14 +15,14 @@
  */
 
 use super::mock::*;
-use crate::Error;
+use crate::{Error, TokenLock};
 use frame_support::{
-    traits::BalanceStatus,
+    traits::{BalanceStatus, WithdrawReasons},
     {assert_noop, assert_ok},
 };
 use governance_os_support::{
     testing::{primitives::AccountId, ALICE, BOB, TEST_TOKEN_ID, TEST_TOKEN_OWNER},
-    traits::{Currencies, LockableCurrencies, ReservableCurrencies},
+    traits::{voting_lock_reasons, Currencies, LockableCurrencies, ReservableCurrencies},
 };
 
 #[test]
@@ -301,7 +301,12 @@ fn repatriate_reserved_reserved_balance() {
 #[test]
 fn ensure_can_withdraw_refuse_if_non_transferable_currency() {
     ExtBuilder::default().build().execute_with(|| {
-        assert_ok!(Tokens::create(Origin::signed(TEST_TOKEN_OWNER), 42, false));
+        assert_ok!(Tokens::create(
+            Origin::signed(TEST_TOKEN_OWNER),
+            42,
+            false,
+            None
+        ));
         assert_noop!(
             <Tokens as Currencies<AccountId>>::transfer(42, &ALICE, &BOB, 50),
             Error::<Test>::UnTransferableCurrency
@@ -826,3 +831,77 @@ fn remove_lock_dec_ref() {
             );
         })
 }
+
+#[test]
+fn locks_only_apply_to_their_reasons() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(
+                <Tokens as LockableCurrencies<AccountId>>::set_lock_with_reasons(
+                    TEST_TOKEN_ID,
+                    *b"testtest",
+                    &ALICE,
+                    100,
+                    voting_lock_reasons()
+                )
+            );
+
+            assert_noop!(
+                <Tokens as Currencies<AccountId>>::transfer(TEST_TOKEN_ID, &ALICE, &BOB, 10),
+                Error::<Test>::BalanceLockTriggered
+            );
+            assert_noop!(
+                <Tokens as ReservableCurrencies<AccountId>>::reserve(TEST_TOKEN_ID, &ALICE, 10),
+                Error::<Test>::BalanceLockTriggered
+            );
+            assert_ok!(
+                <Tokens as LockableCurrencies<AccountId>>::ensure_can_withdraw_with_reasons(
+                    TEST_TOKEN_ID,
+                    &ALICE,
+                    10,
+                    WithdrawReasons::TRANSACTION_PAYMENT
+                )
+            );
+        })
+}
+
+#[test]
+fn extend_lock_merges_reasons() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(
+                <Tokens as LockableCurrencies<AccountId>>::set_lock_with_reasons(
+                    TEST_TOKEN_ID,
+                    *b"testtest",
+                    &ALICE,
+                    50,
+                    WithdrawReasons::TRANSFER
+                )
+            );
+            assert_ok!(
+                <Tokens as LockableCurrencies<AccountId>>::extend_lock_with_reasons(
+                    TEST_TOKEN_ID,
+                    *b"testtest",
+                    &ALICE,
+                    30,
+                    WithdrawReasons::FEE
+                )
+            );
+
+            assert_eq!(
+                Tokens::locks((ALICE, TEST_TOKEN_ID), *b"testtest"),
+                Some(TokenLock {
+                    amount: 50,
+                    reasons: WithdrawReasons::TRANSFER | WithdrawReasons::FEE,
+                })
+            );
+            let account = Tokens::get_currency_account(TEST_TOKEN_ID, &ALICE);
+            assert_eq!(account.transfer_frozen, 50);
+            assert_eq!(account.fee_frozen, 50);
+            assert_eq!(account.reserve_frozen, 0);
+        })
+}
@@ -0,0 +1,293 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{
+    CurrencyAccounts, CurrencyAccountsMigrated, Error, NamedReserves, RawEvent, RoleBuilder,
+};
+use frame_support::{
+    assert_noop, assert_ok, traits::OnRuntimeUpgrade, IterableStorageDoubleMap, StorageDoubleMap,
+    StorageValue,
+};
+use governance_os_support::{
+    errors::AclError,
+    testing::{primitives::AccountId, ALICE, BOB, CHARLIE, TEST_TOKEN_ID, TEST_TOKEN_OWNER},
+    traits::{
+        Currencies, LockableCurrencies, NamedReservableCurrencies, ReservableCurrencies,
+        RoleManager,
+    },
+};
+use sp_core::H256;
+use sp_runtime::{FixedPointNumber, FixedU128};
+
+#[test]
+fn accounts_are_indexed_per_currency() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert!(CurrencyAccounts::<Test>::contains_key(TEST_TOKEN_ID, ALICE));
+            assert_ok!(Tokens::transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                CHARLIE,
+                100
+            ));
+            assert!(!CurrencyAccounts::<Test>::contains_key(
+                TEST_TOKEN_ID,
+                ALICE
+            ));
+            assert!(CurrencyAccounts::<Test>::contains_key(
+                TEST_TOKEN_ID,
+                CHARLIE
+            ));
+        })
+}
+
+#[test]
+fn start_destroy_freezes_currency() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::start_destroy(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID
+            ));
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_tokens(RawEvent::DestroyStarted(TEST_TOKEN_ID))));
+
+            assert_noop!(
+                Tokens::transfer(Origin::signed(ALICE), TEST_TOKEN_ID, BOB, 10),
+                Error::<Test>::CurrencyDestroying
+            );
+            assert_noop!(
+                Tokens::mint(Origin::signed(TEST_TOKEN_OWNER), TEST_TOKEN_ID, BOB, 10),
+                Error::<Test>::CurrencyDestroying
+            );
+            assert_noop!(
+                Tokens::approve(Origin::signed(ALICE), TEST_TOKEN_ID, BOB, 10),
+                Error::<Test>::CurrencyDestroying
+            );
+            assert_noop!(
+                Tokens::start_destroy(Origin::signed(TEST_TOKEN_OWNER), TEST_TOKEN_ID),
+                Error::<Test>::CurrencyDestroying
+            );
+        })
+}
+
+#[test]
+fn start_destroy_checks() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Tokens::start_destroy(Origin::signed(ALICE), TEST_TOKEN_ID),
+            AclError::MissingRole
+        );
+        assert_ok!(Bylaws::grant_role(
+            Some(&ALICE),
+            MockRoles::manage_currency(NATIVE_CURRENCY_ID)
+        ));
+        assert_noop!(
+            Tokens::start_destroy(Origin::signed(ALICE), NATIVE_CURRENCY_ID),
+            Error::<Test>::CannotDestroyNativeCurrency
+        );
+        assert_ok!(Bylaws::grant_role(
+            Some(&ALICE),
+            MockRoles::manage_currency(42)
+        ));
+        assert_noop!(
+            Tokens::start_destroy(Origin::signed(ALICE), 42),
+            Error::<Test>::UnknownCurrency
+        );
+    })
+}
+
+#[test]
+fn continue_destroy_fails_if_not_destroying() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Tokens::continue_destroy(Origin::signed(ALICE), TEST_TOKEN_ID, 10),
+            Error::<Test>::NotDestroying
+        );
+    })
+}
+
+#[test]
+fn continue_destroy_removes_accounts_in_batches() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(<Tokens as LockableCurrencies<AccountId>>::set_lock(
+                TEST_TOKEN_ID,
+                *b"testlock",
+                &ALICE,
+                50
+            ));
+            assert_ok!(
+                <Tokens as NamedReservableCurrencies<AccountId>>::reserve_named(
+                    TEST_TOKEN_ID,
+                    *b"deposit ",
+                    &BOB,
+                    20
+                )
+            );
+            assert_ok!(Tokens::approve(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                CHARLIE,
+                10
+            ));
+            let alice_consumers = System::consumers(&ALICE);
+            assert_ok!(Tokens::start_destroy(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID
+            ));
+
+            // The lock and the named reserve count as items
+            assert_ok!(Tokens::continue_destroy(
+                Origin::signed(CHARLIE),
+                TEST_TOKEN_ID,
+                3
+            ));
+            assert_eq!(
+                CurrencyAccounts::<Test>::iter_prefix(TEST_TOKEN_ID).count(),
+                1
+            );
+            assert!(Tokens::destroying(TEST_TOKEN_ID));
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_tokens(RawEvent::DestroyProgressed(
+                    TEST_TOKEN_ID,
+                    3
+                ))));
+
+            // One account and one allowance left
+            assert_ok!(Tokens::continue_destroy(
+                Origin::signed(CHARLIE),
+                TEST_TOKEN_ID,
+                2
+            ));
+            assert!(Tokens::destroying(TEST_TOKEN_ID));
+            assert_ok!(Tokens::continue_destroy(
+                Origin::signed(CHARLIE),
+                TEST_TOKEN_ID,
+                1
+            ));
+
+            assert!(!Tokens::destroying(TEST_TOKEN_ID));
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), 0);
+            assert_eq!(Tokens::total_balance(TEST_TOKEN_ID, &BOB), 0);
+            assert_eq!(Tokens::locks((ALICE, TEST_TOKEN_ID), *b"testlock"), None);
+            assert_eq!(Tokens::allowances(TEST_TOKEN_ID, (ALICE, CHARLIE)), 0);
+            assert_eq!(
+                NamedReserves::<Test>::iter_prefix((BOB, TEST_TOKEN_ID)).count(),
+                0
+            );
+            // The balance and the lock references are released
+            assert_eq!(System::consumers(&ALICE), alice_consumers - 2);
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_tokens(RawEvent::CurrencyDestroyed(TEST_TOKEN_ID))));
+        })
+}
+
+#[test]
+fn destroyed_currency_frees_id_roles_and_deposits() {
+    ExtBuilder::default()
+        .currency_deposit(10)
+        .balances(vec![(NATIVE_CURRENCY_ID, ALICE, 100)])
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::create(Origin::signed(ALICE), 42, true, None));
+            assert_eq!(Tokens::reserved_balance(NATIVE_CURRENCY_ID, &ALICE), 10);
+            assert_ok!(Tokens::mint(Origin::signed(ALICE), 42, BOB, 10));
+
+            assert_ok!(Tokens::start_destroy(Origin::signed(ALICE), 42));
+            assert_ok!(Tokens::continue_destroy(Origin::signed(ALICE), 42, 10));
+
+            assert_eq!(Tokens::reserved_balance(NATIVE_CURRENCY_ID, &ALICE), 0);
+            assert_eq!(Tokens::details(42), None);
+            assert!(!Bylaws::has_role(&ALICE, MockRoles::manage_currency(42)));
+            assert!(!Bylaws::has_role(&BOB, MockRoles::transfer_currency(42)));
+
+            // The id can be used again
+            assert_ok!(Tokens::create(Origin::signed(ALICE), 42, true, None));
+            assert_eq!(Tokens::free_balance(42, &BOB), 0);
+        })
+}
+
+#[test]
+fn destroyed_currency_removes_airdrops_and_fee_rate() {
+    ExtBuilder::default()
+        .balances(vec![(NATIVE_CURRENCY_ID, ALICE, 100)])
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::create(Origin::signed(ALICE), 42, true, None));
+            assert_ok!(Tokens::mint(Origin::signed(ALICE), 42, ALICE, 100));
+            assert_ok!(Tokens::create_airdrop(
+                Origin::signed(ALICE),
+                42,
+                H256::repeat_byte(1),
+                60
+            ));
+            assert_ok!(Bylaws::grant_role(Some(&ALICE), MockRoles::Root));
+            assert_ok!(Tokens::set_fee_rate(
+                Origin::signed(ALICE),
+                42,
+                Some(FixedU128::saturating_from_integer(2))
+            ));
+
+            assert_ok!(Tokens::start_destroy(Origin::signed(ALICE), 42));
+            assert_ok!(Tokens::continue_destroy(Origin::signed(ALICE), 42, 10));
+            assert_eq!(Tokens::details(42), None);
+            assert_eq!(Tokens::airdrops(0), None);
+            assert!(!Tokens::currency_airdrops(42, 0));
+            assert_eq!(Tokens::fee_rates(42), None);
+
+            // Reusing the id does not bring back the airdrop nor the fee rate
+            assert_ok!(Tokens::create(Origin::signed(ALICE), 42, true, None));
+            assert_ok!(Tokens::mint(Origin::signed(ALICE), 42, ALICE, 100));
+            assert_noop!(
+                Tokens::close_airdrop(Origin::signed(ALICE), 0),
+                Error::<Test>::UnknownAirdrop
+            );
+            assert_eq!(Tokens::reserved_balance(42, &ALICE), 0);
+            assert_eq!(Tokens::fee_rates(42), None);
+        })
+}
+
+#[test]
+fn migration_indexes_existing_accounts() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            CurrencyAccounts::<Test>::remove_prefix(TEST_TOKEN_ID);
+            CurrencyAccountsMigrated::kill();
+
+            Tokens::on_runtime_upgrade();
+            assert!(CurrencyAccounts::<Test>::contains_key(TEST_TOKEN_ID, ALICE));
+            assert!(CurrencyAccounts::<Test>::contains_key(TEST_TOKEN_ID, BOB));
+            assert!(CurrencyAccountsMigrated::get());
+
+            // Does not run twice
+            CurrencyAccounts::<Test>::remove(TEST_TOKEN_ID, ALICE);
+            Tokens::on_runtime_upgrade();
+            assert!(!CurrencyAccounts::<Test>::contains_key(
+                TEST_TOKEN_ID,
+                ALICE
+            ));
+        })
+}
@@ -15,18 +15,23 @@
  */
 
 use super::mock::*;
-use crate::{CurrencyDetails, Error, RoleBuilder};
-use frame_support::{assert_noop, assert_ok};
+use crate::{CurrencyDetails, CurrencyMetadata, Error, RawEvent, RoleBuilder, WeightInfo};
+use frame_support::{assert_noop, assert_ok, weights::GetDispatchInfo};
 use governance_os_support::{
     errors::AclError,
     testing::{ALICE, BOB, TEST_TOKEN_ID, TEST_TOKEN_OWNER},
-    traits::{Currencies, RoleManager},
+    traits::{Currencies, ReservableCurrencies, RoleManager},
 };
 
 #[test]
 fn create_transferrable_works() {
     ExtBuilder::default().build().execute_with(|| {
-        assert_ok!(Tokens::create(Origin::signed(TEST_TOKEN_OWNER), 42, true));
+        assert_ok!(Tokens::create(
+            Origin::signed(TEST_TOKEN_OWNER),
+            42,
+            true,
+            None
+        ));
         assert_eq!(
             Bylaws::has_role(&TEST_TOKEN_OWNER, MockRoles::manage_currency(42)),
             true
@@ -41,7 +46,12 @@ fn create_transferrable_works() {
 #[test]
 fn create_non_transferable_works() {
     ExtBuilder::default().build().execute_with(|| {
-        assert_ok!(Tokens::create(Origin::signed(TEST_TOKEN_OWNER), 42, false));
+        assert_ok!(Tokens::create(
+            Origin::signed(TEST_TOKEN_OWNER),
+            42,
+            false,
+            None
+        ));
         assert_eq!(
             Bylaws::has_role(&TEST_TOKEN_OWNER, MockRoles::manage_currency(42)),
             true
@@ -57,12 +67,112 @@ fn create_non_transferable_works() {
 fn create_duplicate_currency_id_fails() {
     ExtBuilder::default().build().execute_with(|| {
         assert_noop!(
-            Tokens::create(Origin::signed(TEST_TOKEN_OWNER), TEST_TOKEN_ID, true),
+            Tokens::create(Origin::signed(TEST_TOKEN_OWNER), TEST_TOKEN_ID, true, None),
             Error::<Test>::CurrencyAlreadyExists
         );
     })
 }
 
+#[test]
+fn create_reserves_currency_deposit() {
+    ExtBuilder::default()
+        .currency_deposit(10)
+        .balances(vec![(NATIVE_CURRENCY_ID, TEST_TOKEN_OWNER, 100)])
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::create(
+                Origin::signed(TEST_TOKEN_OWNER),
+                42,
+                true,
+                None
+            ));
+            assert_eq!(Tokens::currency_deposits(42), Some((TEST_TOKEN_OWNER, 10)));
+            assert_eq!(
+                Tokens::reserved_balance(NATIVE_CURRENCY_ID, &TEST_TOKEN_OWNER),
+                10
+            );
+        })
+}
+
+#[test]
+fn create_fails_if_currency_deposit_can_not_be_paid() {
+    ExtBuilder::default()
+        .currency_deposit(10)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Tokens::create(Origin::signed(TEST_TOKEN_OWNER), 42, true, None),
+                Error::<Test>::BalanceTooLow
+            );
+        })
+}
+
+#[test]
+fn create_auto_skips_used_ids() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Tokens::create(
+            Origin::signed(TEST_TOKEN_OWNER),
+            2,
+            true,
+            None
+        ));
+
+        assert_ok!(Tokens::create_auto(Origin::signed(ALICE), true, None));
+        assert_ok!(Tokens::create_auto(Origin::signed(ALICE), true, None));
+
+        // 1 is used by the genesis and 2 was created manually
+        assert_eq!(Tokens::next_currency_index(), 4);
+        assert_eq!(Tokens::details(0).unwrap().owner, ALICE);
+        assert_eq!(Tokens::details(3).unwrap().owner, ALICE);
+        assert!(System::events().iter().any(|record| record.event
+            == Event::governance_os_pallet_tokens(RawEvent::CurrencyCreated(
+                3,
+                Tokens::details(3).unwrap()
+            ))));
+    })
+}
+
+#[test]
+fn create_auto_gives_up_after_max_skips() {
+    ExtBuilder::default().build().execute_with(|| {
+        for currency_id in 2..5 {
+            assert_ok!(Tokens::create(
+                Origin::signed(TEST_TOKEN_OWNER),
+                currency_id,
+                true,
+                None
+            ));
+        }
+
+        assert_ok!(Tokens::create_auto(Origin::signed(ALICE), true, None));
+        // 1 to 3 are used, that is one more than `MaxCurrencyIdSkips`
+        assert_eq!(
+            Tokens::create_auto(Origin::signed(ALICE), true, None),
+            Err(Error::<Test>::NoAvailableCurrencyId.into())
+        );
+        // The ids we skipped are not checked again
+        assert_eq!(Tokens::next_currency_index(), 4);
+
+        assert_ok!(Tokens::create_auto(Origin::signed(ALICE), true, None));
+        assert_eq!(Tokens::details(5).unwrap().owner, ALICE);
+        assert_eq!(Tokens::next_currency_index(), 6);
+    })
+}
+
+#[test]
+fn create_auto_does_not_consume_index_on_failure() {
+    ExtBuilder::default()
+        .currency_deposit(10)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Tokens::create_auto(Origin::signed(ALICE), true, None),
+                Error::<Test>::BalanceTooLow
+            );
+            assert_eq!(Tokens::next_currency_index(), 0);
+        })
+}
+
 #[test]
 fn mint_fails_if_not_owner() {
     ExtBuilder::default().build().execute_with(|| {
@@ -127,7 +237,11 @@ fn update_details_fails_if_not_owner() {
                     TEST_TOKEN_ID,
                     CurrencyDetails {
                         owner: ALICE,
-                        transferable: true
+                        transferable: true,
+                        restricted_receivers: false,
+                        existential_deposit: 0,
+                        max_supply: None,
+                        issuance: None,
                     }
                 ),
                 AclError::MissingRole
@@ -146,9 +260,14 @@ fn update_details_works() {
                 TEST_TOKEN_ID,
                 CurrencyDetails {
                     owner: ALICE,
-                    transferable: false
+                    transferable: false,
+                    restricted_receivers: false,
+                    existential_deposit: 5,
+                    max_supply: None,
+                    issuance: None,
                 }
             ));
+            assert_eq!(Tokens::existential_deposit(TEST_TOKEN_ID), 5);
             assert_eq!(
                 Bylaws::has_role(&TEST_TOKEN_OWNER, MockRoles::manage_currency(TEST_TOKEN_ID)),
                 false
@@ -180,3 +299,189 @@ fn transfer_works() {
             assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &BOB), 150);
         })
 }
+
+#[test]
+fn transfer_only_charges_the_role_checks_made() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let declared = Call::Tokens(crate::Call::transfer(TEST_TOKEN_ID, BOB, 50))
+                .get_dispatch_info()
+                .weight;
+            let post_info =
+                Tokens::transfer(Origin::signed(ALICE), TEST_TOKEN_ID, BOB, 50).unwrap();
+
+            // Both roles are granted to everyone, only the grants are read
+            let reads = <Test as frame_system::Config>::DbWeight::get().reads(4);
+            assert_eq!(
+                post_info.actual_weight,
+                Some(<() as WeightInfo>::transfer() + reads + Tokens::checkpoints_weight(2, false))
+            );
+            assert!(post_info.actual_weight.unwrap() < declared);
+        })
+}
+
+fn test_metadata() -> CurrencyMetadata {
+    CurrencyMetadata {
+        name: b"Test".to_vec(),
+        symbol: b"TST".to_vec(),
+        decimals: 12,
+        uri: None,
+    }
+}
+
+#[test]
+fn create_with_metadata_reserves_deposit() {
+    ExtBuilder::default()
+        .balances(vec![(NATIVE_CURRENCY_ID, TEST_TOKEN_OWNER, 100)])
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::create(
+                Origin::signed(TEST_TOKEN_OWNER),
+                42,
+                true,
+                Some(test_metadata())
+            ));
+            assert_eq!(Tokens::metadata(42), Some(test_metadata()));
+            assert_eq!(Tokens::metadata_deposits(42), Some((TEST_TOKEN_OWNER, 7)));
+            assert_eq!(
+                Tokens::reserved_balance(NATIVE_CURRENCY_ID, &TEST_TOKEN_OWNER),
+                7
+            );
+        })
+}
+
+#[test]
+fn create_fails_if_metadata_deposit_can_not_be_paid() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Tokens::create(
+                Origin::signed(TEST_TOKEN_OWNER),
+                42,
+                true,
+                Some(test_metadata())
+            ),
+            Error::<Test>::BalanceTooLow
+        );
+    })
+}
+
+#[test]
+fn create_fails_if_metadata_too_long() {
+    ExtBuilder::default()
+        .currency_deposit(10)
+        .balances(vec![(NATIVE_CURRENCY_ID, TEST_TOKEN_OWNER, 100)])
+        .build()
+        .execute_with(|| {
+            let mut metadata = test_metadata();
+            metadata.uri = Some(vec![0; 17]);
+            assert_noop!(
+                Tokens::create(Origin::signed(TEST_TOKEN_OWNER), 42, true, Some(metadata)),
+                Error::<Test>::MetadataTooLong
+            );
+            assert_eq!(
+                Tokens::reserved_balance(NATIVE_CURRENCY_ID, &TEST_TOKEN_OWNER),
+                0
+            );
+            assert_eq!(Tokens::currency_deposits(42), None);
+        })
+}
+
+#[test]
+fn set_metadata_fails_if_not_owner() {
+    ExtBuilder::default()
+        .balances(vec![(NATIVE_CURRENCY_ID, ALICE, 100)])
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Tokens::set_metadata(Origin::signed(ALICE), TEST_TOKEN_ID, test_metadata()),
+                AclError::MissingRole
+            );
+        })
+}
+
+#[test]
+fn set_metadata_fails_if_too_long() {
+    ExtBuilder::default()
+        .balances(vec![(NATIVE_CURRENCY_ID, TEST_TOKEN_OWNER, 100)])
+        .build()
+        .execute_with(|| {
+            let mut metadata = test_metadata();
+            metadata.uri = Some(vec![0; 17]);
+            assert_noop!(
+                Tokens::set_metadata(Origin::signed(TEST_TOKEN_OWNER), TEST_TOKEN_ID, metadata),
+                Error::<Test>::MetadataTooLong
+            );
+        })
+}
+
+#[test]
+fn set_metadata_adjusts_deposit() {
+    ExtBuilder::default()
+        .balances(vec![(NATIVE_CURRENCY_ID, TEST_TOKEN_OWNER, 100)])
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::set_metadata(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                test_metadata()
+            ));
+
+            let mut metadata = test_metadata();
+            metadata.uri = Some(b"ipfs".to_vec());
+            assert_ok!(Tokens::set_metadata(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                metadata.clone()
+            ));
+            assert_eq!(Tokens::metadata(TEST_TOKEN_ID), Some(metadata));
+            assert_eq!(
+                Tokens::reserved_balance(NATIVE_CURRENCY_ID, &TEST_TOKEN_OWNER),
+                11
+            );
+
+            assert_ok!(Tokens::set_metadata(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                test_metadata()
+            ));
+            assert_eq!(
+                Tokens::reserved_balance(NATIVE_CURRENCY_ID, &TEST_TOKEN_OWNER),
+                7
+            );
+        })
+}
+
+#[test]
+fn set_metadata_returns_deposit_of_previous_depositor() {
+    ExtBuilder::default()
+        .balances(vec![
+            (NATIVE_CURRENCY_ID, TEST_TOKEN_OWNER, 100),
+            (NATIVE_CURRENCY_ID, ALICE, 100),
+        ])
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::set_metadata(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                test_metadata()
+            ));
+            assert_ok!(Bylaws::grant_role(
+                Some(&ALICE),
+                MockRoles::manage_currency(TEST_TOKEN_ID)
+            ));
+            assert_ok!(Tokens::set_metadata(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                test_metadata()
+            ));
+
+            assert_eq!(Tokens::metadata_deposits(TEST_TOKEN_ID), Some((ALICE, 7)));
+            assert_eq!(
+                Tokens::reserved_balance(NATIVE_CURRENCY_ID, &TEST_TOKEN_OWNER),
+                0
+            );
+            assert_eq!(Tokens::reserved_balance(NATIVE_CURRENCY_ID, &ALICE), 7);
+        })
+}
@@ -0,0 +1,258 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{Error, MultiCurrencyFees, NextFeeCurrency, RawEvent, SetFeeCurrency};
+use frame_support::{
+    assert_noop, assert_ok,
+    weights::{DispatchInfo, PostDispatchInfo},
+    StorageValue,
+};
+use governance_os_support::{
+    errors::AclError,
+    testing::{ALICE, TEST_ORGANIZATION, TEST_TOKEN_ID},
+    traits::{Currencies, RoleManager},
+};
+use pallet_transaction_payment::{ChargeTransactionPayment, OnChargeTransaction};
+use sp_runtime::{
+    traits::SignedExtension,
+    transaction_validity::{InvalidTransaction, TransactionValidityError},
+    FixedPointNumber, FixedU128,
+};
+
+type Fees = MultiCurrencyFees<FeeBeneficiary>;
+
+fn call() -> Call {
+    Call::System(frame_system::Call::remark(vec![]))
+}
+
+fn set_test_token_rate(rate: u128) {
+    assert_ok!(Bylaws::grant_role(Some(&ALICE), MockRoles::Root));
+    assert_ok!(Tokens::set_fee_rate(
+        Origin::signed(ALICE),
+        TEST_TOKEN_ID,
+        Some(FixedU128::saturating_from_integer(rate))
+    ));
+}
+
+#[test]
+fn set_fee_rate_works() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_test_token_rate(2);
+        assert_eq!(
+            Tokens::fee_rates(TEST_TOKEN_ID),
+            Some(FixedU128::saturating_from_integer(2))
+        );
+        assert!(System::events().iter().any(|record| record.event
+            == Event::governance_os_pallet_tokens(RawEvent::FeeRateChanged(
+                TEST_TOKEN_ID,
+                Some(FixedU128::saturating_from_integer(2))
+            ))));
+
+        assert_ok!(Tokens::set_fee_rate(
+            Origin::signed(ALICE),
+            TEST_TOKEN_ID,
+            None
+        ));
+        assert_eq!(Tokens::fee_rates(TEST_TOKEN_ID), None);
+    })
+}
+
+#[test]
+fn set_fee_rate_requires_role() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Tokens::set_fee_rate(Origin::signed(ALICE), TEST_TOKEN_ID, Some(FixedU128::one())),
+            AclError::MissingRole
+        );
+    })
+}
+
+#[test]
+fn set_fee_rate_rejects_invalid_rates() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Bylaws::grant_role(Some(&ALICE), MockRoles::Root));
+        assert_noop!(
+            Tokens::set_fee_rate(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                Some(FixedU128::zero())
+            ),
+            Error::<Test>::InvalidFeeRate
+        );
+        assert_noop!(
+            Tokens::set_fee_rate(
+                Origin::signed(ALICE),
+                NATIVE_CURRENCY_ID,
+                Some(FixedU128::one())
+            ),
+            Error::<Test>::InvalidFeeRate
+        );
+    })
+}
+
+#[test]
+fn fees_default_to_native_currency() {
+    ExtBuilder::default()
+        .balances(vec![(NATIVE_CURRENCY_ID, ALICE, 1_000)])
+        .build()
+        .execute_with(|| {
+            let info = DispatchInfo::default();
+            let paid =
+                <Fees as OnChargeTransaction<Test>>::withdraw_fee(&ALICE, &call(), &info, 100, 0)
+                    .unwrap();
+            assert_eq!(Tokens::free_balance(NATIVE_CURRENCY_ID, &ALICE), 900);
+
+            assert_ok!(
+                <Fees as OnChargeTransaction<Test>>::correct_and_deposit_fee(
+                    &ALICE,
+                    &info,
+                    &PostDispatchInfo::default(),
+                    60,
+                    0,
+                    paid
+                )
+            );
+            assert_eq!(Tokens::free_balance(NATIVE_CURRENCY_ID, &ALICE), 940);
+            assert_eq!(
+                Tokens::free_balance(NATIVE_CURRENCY_ID, &TEST_ORGANIZATION),
+                60
+            );
+            assert_eq!(Tokens::total_issuance(NATIVE_CURRENCY_ID), 1_000);
+        })
+}
+
+#[test]
+fn fees_can_be_paid_in_chosen_currency() {
+    ExtBuilder::default()
+        .balances(vec![(TEST_TOKEN_ID, ALICE, 1_000_000_000_000)])
+        .build()
+        .execute_with(|| {
+            set_test_token_rate(2);
+
+            let info = DispatchInfo::default();
+            let fee = TransactionPayment::compute_fee(10, &info, 0);
+            let pre = (
+                SetFeeCurrency::<Test>(Some(TEST_TOKEN_ID)),
+                ChargeTransactionPayment::<Test>::from(0),
+            )
+                .pre_dispatch(&ALICE, &call(), &info, 10)
+                .unwrap();
+            assert_eq!(
+                Tokens::free_balance(TEST_TOKEN_ID, &ALICE),
+                1_000_000_000_000 - 2 * fee
+            );
+            assert_eq!(NextFeeCurrency::<Test>::get(), None);
+
+            // Part of the fee is refunded when the actual weight was lower
+            let post_info = PostDispatchInfo {
+                actual_weight: Some(0),
+                pays_fee: Default::default(),
+            };
+            let actual_fee = TransactionPayment::compute_actual_fee(10, &info, &post_info, 0);
+            assert_ok!(
+                <(SetFeeCurrency<Test>, ChargeTransactionPayment<Test>)>::post_dispatch(
+                    pre,
+                    &info,
+                    &post_info,
+                    10,
+                    &Ok(())
+                )
+            );
+            assert_eq!(
+                Tokens::free_balance(TEST_TOKEN_ID, &ALICE),
+                1_000_000_000_000 - 2 * actual_fee
+            );
+            assert_eq!(
+                Tokens::free_balance(TEST_TOKEN_ID, &TEST_ORGANIZATION),
+                2 * actual_fee
+            );
+        })
+}
+
+#[test]
+fn fee_currency_is_cleared_if_fees_were_not_withdrawn() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            set_test_token_rate(2);
+
+            // Without `ChargeTransactionPayment`, as when the transaction is sponsored
+            let info = DispatchInfo::default();
+            assert_ok!(SetFeeCurrency::<Test>(Some(TEST_TOKEN_ID)).pre_dispatch(
+                &ALICE,
+                &call(),
+                &info,
+                10
+            ));
+            assert_eq!(NextFeeCurrency::<Test>::get(), Some(TEST_TOKEN_ID));
+
+            assert_ok!(SetFeeCurrency::<Test>::post_dispatch(
+                (),
+                &info,
+                &PostDispatchInfo::default(),
+                10,
+                &Ok(())
+            ));
+            assert_eq!(NextFeeCurrency::<Test>::get(), None);
+        })
+}
+
+#[test]
+fn fees_can_not_be_paid_in_currencies_without_rate() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_eq!(
+                SetFeeCurrency::<Test>(Some(TEST_TOKEN_ID))
+                    .validate(&ALICE, &call(), &DispatchInfo::default(), 10)
+                    .unwrap_err(),
+                TransactionValidityError::Invalid(InvalidTransaction::Payment)
+            );
+        })
+}
+
+#[test]
+fn fees_withdrawal_fails_if_balance_too_low() {
+    ExtBuilder::default()
+        .balances(vec![
+            (NATIVE_CURRENCY_ID, ALICE, 100),
+            (TEST_TOKEN_ID, ALICE, 100),
+        ])
+        .build()
+        .execute_with(|| {
+            set_test_token_rate(2);
+            let info = DispatchInfo::default();
+
+            // The native currency has an existential deposit of 10
+            assert_eq!(
+                <Fees as OnChargeTransaction<Test>>::withdraw_fee(&ALICE, &call(), &info, 95, 0)
+                    .unwrap_err(),
+                TransactionValidityError::Invalid(InvalidTransaction::Payment)
+            );
+
+            NextFeeCurrency::<Test>::put(TEST_TOKEN_ID);
+            assert_eq!(
+                <Fees as OnChargeTransaction<Test>>::withdraw_fee(&ALICE, &call(), &info, 60, 0)
+                    .unwrap_err(),
+                TransactionValidityError::Invalid(InvalidTransaction::Payment)
+            );
+            assert_eq!(NextFeeCurrency::<Test>::get(), None);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), 100);
+        })
+}
@@ -0,0 +1,115 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::Error;
+use frame_support::{assert_noop, assert_ok};
+use governance_os_support::{
+    testing::{ALICE, BOB, CHARLIE, TEST_TOKEN_ID, TEST_TOKEN_OWNER},
+    traits::ReservableCurrencies,
+};
+
+#[test]
+fn transfers_report_both_accounts() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                CHARLIE,
+                40
+            ));
+            assert_eq!(
+                take_balance_changes(),
+                vec![
+                    (TEST_TOKEN_ID, ALICE, 60, 0),
+                    (TEST_TOKEN_ID, CHARLIE, 40, 0)
+                ]
+            );
+        })
+}
+
+#[test]
+fn reserves_are_reported() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::reserve(TEST_TOKEN_ID, &ALICE, 30));
+            assert_eq!(take_balance_changes(), vec![(TEST_TOKEN_ID, ALICE, 70, 30)]);
+        })
+}
+
+#[test]
+fn reaped_accounts_are_reported_empty() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .existential_deposit(10)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                95
+            ));
+            assert_eq!(
+                take_balance_changes(),
+                vec![(TEST_TOKEN_ID, ALICE, 0, 0), (TEST_TOKEN_ID, BOB, 195, 0)]
+            );
+        })
+}
+
+#[test]
+fn failed_mutations_are_not_reported() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Tokens::transfer(Origin::signed(ALICE), TEST_TOKEN_ID, BOB, 101),
+                Error::<Test>::BalanceTooLow
+            );
+            assert_eq!(take_balance_changes(), vec![]);
+        })
+}
+
+#[test]
+fn destroyed_accounts_are_reported_empty() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::start_destroy(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID
+            ));
+            assert_ok!(Tokens::continue_destroy(
+                Origin::signed(CHARLIE),
+                TEST_TOKEN_ID,
+                10
+            ));
+
+            let mut changes = take_balance_changes();
+            changes.sort();
+            assert_eq!(
+                changes,
+                vec![(TEST_TOKEN_ID, ALICE, 0, 0), (TEST_TOKEN_ID, BOB, 0, 0)]
+            );
+        })
+}
@@ -0,0 +1,226 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{CurrencyDetails, Error, IssuanceSchedule, RawEvent};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use governance_os_support::{
+    testing::{
+        primitives::{AccountId, Balance, BlockNumber},
+        ALICE, BOB, TEST_TOKEN_ID, TEST_TOKEN_OWNER,
+    },
+    traits::Currencies,
+};
+
+fn details_with(
+    max_supply: Option<Balance>,
+    issuance: Option<IssuanceSchedule<AccountId, Balance, BlockNumber>>,
+) -> CurrencyDetails<AccountId, Balance, BlockNumber> {
+    CurrencyDetails {
+        owner: TEST_TOKEN_OWNER,
+        transferable: true,
+        restricted_receivers: false,
+        existential_deposit: 0,
+        max_supply,
+        issuance,
+    }
+}
+
+fn schedule(
+    amount: Balance,
+    period: BlockNumber,
+) -> IssuanceSchedule<AccountId, Balance, BlockNumber> {
+    IssuanceSchedule {
+        beneficiary: BOB,
+        amount,
+        period,
+    }
+}
+
+#[test]
+fn mint_fails_above_max_supply() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::update_details(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                details_with(Some(250), None)
+            ));
+
+            assert_noop!(
+                Tokens::mint(Origin::signed(TEST_TOKEN_OWNER), TEST_TOKEN_ID, ALICE, 51),
+                Error::<Test>::MaxSupplyExceeded
+            );
+            assert_ok!(Tokens::mint(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                ALICE,
+                50
+            ));
+            assert_eq!(Tokens::total_issuances(TEST_TOKEN_ID), 250);
+        })
+}
+
+#[test]
+fn can_not_set_max_supply_below_total_issuance() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Tokens::update_details(
+                    Origin::signed(TEST_TOKEN_OWNER),
+                    TEST_TOKEN_ID,
+                    details_with(Some(199), None)
+                ),
+                Error::<Test>::MaxSupplyExceeded
+            );
+        })
+}
+
+#[test]
+fn update_details_emits_dedicated_events() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Tokens::update_details(
+            Origin::signed(TEST_TOKEN_OWNER),
+            TEST_TOKEN_ID,
+            details_with(Some(1_000), Some(schedule(10, 2)))
+        ));
+
+        assert!(System::events().iter().any(|record| record.event
+            == Event::governance_os_pallet_tokens(RawEvent::MaxSupplyChanged(
+                TEST_TOKEN_ID,
+                Some(1_000)
+            ))));
+        assert!(System::events().iter().any(|record| record.event
+            == Event::governance_os_pallet_tokens(RawEvent::IssuanceScheduleChanged(
+                TEST_TOKEN_ID,
+                Some(schedule(10, 2))
+            ))));
+    })
+}
+
+#[test]
+fn scheduled_issuance_mints_every_period() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Tokens::update_details(
+            Origin::signed(TEST_TOKEN_OWNER),
+            TEST_TOKEN_ID,
+            details_with(None, Some(schedule(10, 2)))
+        ));
+
+        Tokens::on_initialize(2);
+        assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &BOB), 10);
+        Tokens::on_initialize(3);
+        assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &BOB), 10);
+        Tokens::on_initialize(4);
+        assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &BOB), 20);
+    })
+}
+
+#[test]
+fn scheduled_issuance_stops_at_max_supply() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Tokens::update_details(
+            Origin::signed(TEST_TOKEN_OWNER),
+            TEST_TOKEN_ID,
+            details_with(Some(15), Some(schedule(10, 1)))
+        ));
+
+        Tokens::on_initialize(2);
+        Tokens::on_initialize(3);
+        Tokens::on_initialize(4);
+        assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &BOB), 15);
+        assert_eq!(Tokens::total_issuances(TEST_TOKEN_ID), 15);
+    })
+}
+
+#[test]
+fn removing_schedule_stops_issuance() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Tokens::update_details(
+            Origin::signed(TEST_TOKEN_OWNER),
+            TEST_TOKEN_ID,
+            details_with(None, Some(schedule(10, 1)))
+        ));
+        assert_eq!(Tokens::scheduled_currencies(), vec![TEST_TOKEN_ID]);
+
+        assert_ok!(Tokens::update_details(
+            Origin::signed(TEST_TOKEN_OWNER),
+            TEST_TOKEN_ID,
+            details_with(None, None)
+        ));
+        assert!(Tokens::scheduled_currencies().is_empty());
+
+        Tokens::on_initialize(2);
+        assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &BOB), 0);
+    })
+}
+
+#[test]
+fn schedule_period_must_not_be_zero() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Tokens::update_details(
+                Origin::signed(TEST_TOKEN_OWNER),
+                TEST_TOKEN_ID,
+                details_with(None, Some(schedule(10, 0)))
+            ),
+            Error::<Test>::InvalidIssuanceSchedule
+        );
+    })
+}
+
+#[test]
+fn can_not_have_too_many_schedules() {
+    ExtBuilder::default().build().execute_with(|| {
+        for currency_id in &[42, 43, 44] {
+            assert_ok!(Tokens::create(
+                Origin::signed(TEST_TOKEN_OWNER),
+                *currency_id,
+                true,
+                None
+            ));
+        }
+        assert_ok!(Tokens::update_details(
+            Origin::signed(TEST_TOKEN_OWNER),
+            42,
+            details_with(None, Some(schedule(10, 1)))
+        ));
+        assert_ok!(Tokens::update_details(
+            Origin::signed(TEST_TOKEN_OWNER),
+            43,
+            details_with(None, Some(schedule(10, 1)))
+        ));
+
+        assert_noop!(
+            Tokens::update_details(
+                Origin::signed(TEST_TOKEN_OWNER),
+                44,
+                details_with(None, Some(schedule(10, 1)))
+            ),
+            Error::<Test>::TooManyIssuanceSchedules
+        );
+        // Updating an existing schedule is still possible
+        assert_ok!(Tokens::update_details(
+            Origin::signed(TEST_TOKEN_OWNER),
+            43,
+            details_with(None, Some(schedule(20, 1)))
+        ));
+    })
+}
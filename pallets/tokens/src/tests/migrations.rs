@@ -0,0 +1,74 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{
+    migrations::OldAccountCurrencyData, AccountCurrencyData, Balances, Locks, Releases,
+    StorageVersion, TokenLock,
+};
+use frame_support::{
+    storage::unhashed,
+    traits::{OnRuntimeUpgrade, WithdrawReasons},
+    StorageDoubleMap, StorageValue,
+};
+use governance_os_support::testing::{primitives::Balance, ALICE, TEST_TOKEN_ID};
+
+#[test]
+fn genesis_uses_latest_storage_version() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(StorageVersion::get(), Releases::V2);
+    })
+}
+
+#[test]
+fn migration_to_v2_decodes_old_balances_and_locks() {
+    ExtBuilder::default().build().execute_with(|| {
+        StorageVersion::put(Releases::V1);
+        unhashed::put(
+            &Balances::<Test>::hashed_key_for(ALICE, TEST_TOKEN_ID),
+            &OldAccountCurrencyData::<Balance> {
+                free: 100,
+                reserved: 20,
+                frozen: 50,
+            },
+        );
+        unhashed::put(
+            &Locks::<Test>::hashed_key_for((ALICE, TEST_TOKEN_ID), *b"testlock"),
+            &50u128,
+        );
+
+        Tokens::on_runtime_upgrade();
+
+        assert_eq!(StorageVersion::get(), Releases::V2);
+        assert_eq!(
+            Tokens::balances(ALICE, TEST_TOKEN_ID),
+            AccountCurrencyData {
+                free: 100,
+                reserved: 20,
+                fee_frozen: 50,
+                transfer_frozen: 50,
+                reserve_frozen: 50,
+            }
+        );
+        assert_eq!(
+            Tokens::locks((ALICE, TEST_TOKEN_ID), *b"testlock"),
+            Some(TokenLock {
+                amount: 50,
+                reasons: WithdrawReasons::all(),
+            })
+        );
+    })
+}
@@ -15,12 +15,12 @@
  */
 
 use super::mock::*;
-use crate::Balances;
-use frame_support::assert_ok;
+use crate::{Balances, Error, RawEvent};
 use frame_support::StorageDoubleMap;
+use frame_support::{assert_noop, assert_ok};
 use governance_os_support::{
-    testing::{primitives::AccountId, ALICE, BOB, TEST_TOKEN_ID},
-    traits::Currencies,
+    testing::{primitives::AccountId, ALICE, BOB, EVE, TEST_TOKEN_ID},
+    traits::{Currencies, LockableCurrencies},
 };
 
 #[test]
@@ -45,3 +45,104 @@ fn kill_currency_data_if_balance_down_to_zero() {
             );
         })
 }
+
+#[test]
+fn reap_account_below_existential_deposit_and_transfer_dust() {
+    ExtBuilder::default()
+        .existential_deposit(10)
+        .balances(vec![
+            (TEST_TOKEN_ID, ALICE, 100),
+            (TEST_TOKEN_ID, BOB, 100),
+            (TEST_TOKEN_ID, DUST_RECEIVER, 100),
+        ])
+        .build()
+        .execute_with(|| {
+            assert_ok!(<Tokens as Currencies<AccountId>>::transfer(
+                TEST_TOKEN_ID,
+                &ALICE,
+                &BOB,
+                95
+            ));
+
+            assert!(!Balances::<Test>::contains_key(ALICE, TEST_TOKEN_ID));
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &DUST_RECEIVER), 105);
+            assert_eq!(Tokens::total_issuance(TEST_TOKEN_ID), 300);
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_tokens(RawEvent::DustLost(
+                    TEST_TOKEN_ID,
+                    ALICE,
+                    5
+                ))));
+        })
+}
+
+#[test]
+fn dust_is_burned_if_it_can_not_be_transferred() {
+    ExtBuilder::default()
+        .existential_deposit(10)
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(<Tokens as Currencies<AccountId>>::transfer(
+                TEST_TOKEN_ID,
+                &ALICE,
+                &BOB,
+                95
+            ));
+
+            // The dust is too small to create the receiver's account
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &DUST_RECEIVER), 0);
+            assert_eq!(Tokens::total_issuance(TEST_TOKEN_ID), 195);
+        })
+}
+
+#[test]
+fn do_not_reap_accounts_with_frozen_funds() {
+    ExtBuilder::default()
+        .existential_deposit(10)
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::set_lock(TEST_TOKEN_ID, *b"testlock", &ALICE, 5));
+            assert_ok!(<Tokens as Currencies<AccountId>>::transfer(
+                TEST_TOKEN_ID,
+                &ALICE,
+                &BOB,
+                95
+            ));
+
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), 5);
+        })
+}
+
+#[test]
+fn can_not_create_account_below_existential_deposit() {
+    ExtBuilder::default()
+        .existential_deposit(10)
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                <Tokens as Currencies<AccountId>>::transfer(TEST_TOKEN_ID, &ALICE, &EVE, 5),
+                Error::<Test>::ExistentialDeposit
+            );
+            assert_ok!(<Tokens as Currencies<AccountId>>::transfer(
+                TEST_TOKEN_ID,
+                &ALICE,
+                &EVE,
+                10
+            ));
+        })
+}
+
+#[test]
+fn existential_deposit_defaults_for_native_currency() {
+    ExtBuilder::default()
+        .existential_deposit(5)
+        .build()
+        .execute_with(|| {
+            assert_eq!(Tokens::existential_deposit(TEST_TOKEN_ID), 5);
+            assert_eq!(Tokens::existential_deposit(NATIVE_CURRENCY_ID), 10);
+            assert_eq!(Tokens::existential_deposit(TEST_TOKEN_ID + 1), 0);
+        })
+}
@@ -14,20 +14,33 @@
  * limitations under the License.
  */
 
-use crate::{self as governance_os_pallet_tokens, CurrencyDetails, NativeCurrencyAdapter};
+use crate::{
+    self as governance_os_pallet_tokens, CurrencyDetails, MultiCurrencyFees, NativeCurrencyAdapter,
+    TransferDust,
+};
 use codec::{Decode, Encode};
-use frame_support::{construct_runtime, parameter_types, traits::GenesisBuild};
+use frame_support::{
+    construct_runtime, parameter_types,
+    traits::GenesisBuild,
+    weights::{constants::RocksDbWeight, IdentityFee},
+};
 use governance_os_support::{
     impl_enum_default,
     testing::{
         primitives::{AccountId, Balance, BlockNumber, CurrencyId},
-        ALICE, BOB, TEST_TOKEN_ID, TEST_TOKEN_OWNER,
+        AutoCurrencyIds, ALICE, BOB, TEST_ORGANIZATION, TEST_TOKEN_ID, TEST_TOKEN_OWNER,
     },
+    traits::OnBalanceChanged,
 };
 use serde::{Deserialize, Serialize};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup, RuntimeDebug};
 
+/// A currency with no details configured in the genesis, it uses `NativeExistentialDeposit`.
+pub const NATIVE_CURRENCY_ID: CurrencyId = 100;
+/// Receives the dust of the reaped accounts.
+pub const DUST_RECEIVER: AccountId = 9;
+
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -40,6 +53,7 @@ construct_runtime!(
         System: frame_system::{Module, Call, Config, Storage, Event<T>},
         Bylaws: governance_os_pallet_bylaws::{Module, Call, Storage, Event<T>},
         Tokens: governance_os_pallet_tokens::{Module, Call, Storage, Event<T>},
+        TransactionPayment: pallet_transaction_payment::{Module, Storage},
     }
 );
 
@@ -53,7 +67,7 @@ impl frame_system::Config for Test {
     type BaseCallFilter = ();
     type BlockWeights = BlockWeights;
     type BlockLength = ();
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type Origin = Origin;
     type Index = u64;
     type BlockNumber = BlockNumber;
@@ -90,6 +104,7 @@ pub enum MockRoles {
     Root,
     CreateCurrencies,
     TransferCurrency(CurrencyId),
+    ReceiveCurrency(CurrencyId),
     ManageCurrency(CurrencyId),
 }
 impl_enum_default!(MockRoles, Root);
@@ -108,6 +123,7 @@ impl governance_os_pallet_bylaws::RoleBuilder for MockRoles {
 parameter_types! {
     pub const RootRole: MockRoles = MockRoles::Root;
     pub const MaxRoles: u32 = 5;
+    pub const MaxRoleAncestors: u32 = 4;
 }
 
 impl governance_os_pallet_bylaws::Config for Test {
@@ -115,6 +131,7 @@ impl governance_os_pallet_bylaws::Config for Test {
     type Role = MockRoles;
     type WeightInfo = ();
     type MaxRoles = MaxRoles;
+    type MaxRoleAncestors = MaxRoleAncestors;
     type RoleBuilder = MockRoles;
 }
 
@@ -126,6 +143,10 @@ impl governance_os_pallet_tokens::RoleBuilder for MockRoles {
         Self::TransferCurrency(id)
     }
 
+    fn receive_currency(id: CurrencyId) -> Self {
+        Self::ReceiveCurrency(id)
+    }
+
     fn manage_currency(id: CurrencyId) -> Self {
         Self::ManageCurrency(id)
     }
@@ -133,6 +154,53 @@ impl governance_os_pallet_tokens::RoleBuilder for MockRoles {
     fn create_currencies() -> Self {
         Self::CreateCurrencies
     }
+
+    fn manage_fee_rates() -> Self {
+        Self::Root
+    }
+}
+
+parameter_types! {
+    pub static BalanceChanges: Vec<(CurrencyId, AccountId, Balance, Balance)> = vec![];
+}
+
+/// Keeps track of the calls to the `OnBalanceChanged` hook.
+pub struct RecordBalanceChanges;
+impl OnBalanceChanged<AccountId, CurrencyId, Balance> for RecordBalanceChanges {
+    fn on_balance_changed(
+        currency_id: CurrencyId,
+        who: &AccountId,
+        free: Balance,
+        reserved: Balance,
+    ) {
+        let mut changes = BalanceChanges::get();
+        changes.push((currency_id, *who, free, reserved));
+        BalanceChanges::set(changes);
+    }
+}
+
+/// Return and forget the `(currency_id, account, free, reserved)` reported to the
+/// `OnBalanceChanged` hook so far.
+pub fn take_balance_changes() -> Vec<(CurrencyId, AccountId, Balance, Balance)> {
+    let changes = BalanceChanges::get();
+    BalanceChanges::set(vec![]);
+    changes
+}
+
+parameter_types! {
+    pub static CurrencyDeposit: Balance = 0;
+}
+
+parameter_types! {
+    pub const NativeCurrencyId: CurrencyId = NATIVE_CURRENCY_ID;
+    pub const NativeExistentialDeposit: Balance = 10;
+    pub const DustReceiver: AccountId = DUST_RECEIVER;
+    pub const MetadataDepositPerByte: Balance = 1;
+    pub const MaxIssuanceSchedules: u32 = 2;
+    pub const MaxBatchSize: u32 = 3;
+    pub const MaxCurrencyIdSkips: u32 = 2;
+    pub const MaxCheckpoints: u32 = 3;
+    pub const MaxMetadataLength: u32 = 16;
 }
 
 impl governance_os_pallet_tokens::Config for Test {
@@ -140,10 +208,34 @@ impl governance_os_pallet_tokens::Config for Test {
     type CurrencyId = CurrencyId;
     type Balance = Balance;
     type WeightInfo = ();
+    type NativeCurrencyId = NativeCurrencyId;
+    type NativeExistentialDeposit = NativeExistentialDeposit;
+    type AutoCurrencyId = AutoCurrencyIds;
+    type MaxCurrencyIdSkips = MaxCurrencyIdSkips;
+    type CurrencyDeposit = CurrencyDeposit;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type MaxMetadataLength = MaxMetadataLength;
+    type MaxIssuanceSchedules = MaxIssuanceSchedules;
+    type MaxBatchSize = MaxBatchSize;
+    type MaxCheckpoints = MaxCheckpoints;
+    type OnDust = TransferDust<Test, DustReceiver>;
+    type OnBalanceChanged = RecordBalanceChanges;
     type RoleManager = Bylaws;
     type RoleBuilder = MockRoles;
 }
 
+parameter_types! {
+    pub const TransactionByteFee: Balance = 1;
+    pub const FeeBeneficiary: AccountId = TEST_ORGANIZATION;
+}
+
+impl pallet_transaction_payment::Config for Test {
+    type OnChargeTransaction = MultiCurrencyFees<FeeBeneficiary>;
+    type TransactionByteFee = TransactionByteFee;
+    type WeightToFee = IdentityFee<Balance>;
+    type FeeMultiplierUpdate = ();
+}
+
 parameter_types! {
     pub const GetTestTokenId: CurrencyId = TEST_TOKEN_ID;
 }
@@ -151,7 +243,7 @@ pub type TokensCurrencyAdapter = NativeCurrencyAdapter<Test, GetTestTokenId>;
 
 pub struct ExtBuilder {
     endowed_accounts: Vec<(CurrencyId, AccountId, Balance)>,
-    test_token_details: CurrencyDetails<AccountId>,
+    test_token_details: CurrencyDetails<AccountId, Balance, BlockNumber>,
 }
 
 impl Default for ExtBuilder {
@@ -161,6 +253,10 @@ impl Default for ExtBuilder {
             test_token_details: CurrencyDetails {
                 owner: TEST_TOKEN_OWNER,
                 transferable: true,
+                restricted_receivers: false,
+                existential_deposit: 0,
+                max_supply: None,
+                issuance: None,
             },
         }
     }
@@ -172,6 +268,16 @@ impl ExtBuilder {
         self
     }
 
+    pub fn existential_deposit(mut self, existential_deposit: Balance) -> Self {
+        self.test_token_details.existential_deposit = existential_deposit;
+        self
+    }
+
+    pub fn currency_deposit(self, deposit: Balance) -> Self {
+        CurrencyDeposit::set(deposit);
+        self
+    }
+
     pub fn one_hundred_for_alice_n_bob(self) -> Self {
         self.balances(vec![(TEST_TOKEN_ID, ALICE, 100), (TEST_TOKEN_ID, BOB, 100)])
     }
@@ -183,19 +289,22 @@ impl ExtBuilder {
 
         governance_os_pallet_bylaws::GenesisConfig::<Test> {
             roles: vec![(MockRoles::CreateCurrencies, None)], // Everybody can create currencies
+            role_settings: vec![],
         }
         .assimilate_storage(&mut t)
         .unwrap();
 
         governance_os_pallet_tokens::GenesisConfig::<Test> {
             endowed_accounts: self.endowed_accounts,
-            currency_details: vec![(TEST_TOKEN_ID, self.test_token_details)],
+            currency_details: vec![(TEST_TOKEN_ID, self.test_token_details, None)],
         }
         .assimilate_storage(&mut t)
         .unwrap();
 
         let mut ext = sp_io::TestExternalities::new(t);
         ext.execute_with(|| System::set_block_number(1));
+        // Ignore the changes made by the genesis endowments.
+        take_balance_changes();
         ext
     }
 }
@@ -15,9 +15,20 @@
  */
 
 mod adapter;
+mod airdrops;
+mod allowances;
+mod batch;
+mod checkpoints;
+mod compliance;
 mod currencies;
+mod destroy;
 mod dispatchable;
+mod fees;
 mod genesis;
+mod hooks;
+mod issuance;
+mod migrations;
 mod misc;
 pub mod mock;
 mod mutations;
+mod named_reserves;
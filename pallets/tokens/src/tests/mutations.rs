@@ -15,8 +15,8 @@
  */
 
 use super::mock::*;
-use crate::{mutations::Mutation, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{mutations::Mutation, Error, TokenLock};
+use frame_support::{assert_noop, assert_ok, traits::WithdrawReasons};
 use governance_os_support::testing::{ALICE, TEST_TOKEN_ID};
 
 #[test]
@@ -26,7 +26,13 @@ fn sub_up_to_free_balance_honors_frozen() {
         .build()
         .execute_with(|| {
             let mut mutation = Mutation::<Test>::new_for_currency(TEST_TOKEN_ID);
-            assert_ok!(mutation.add_frozen(&ALICE, 40));
+            mutation.overwrite_frozen_balances(
+                &ALICE,
+                vec![TokenLock {
+                    amount: 40,
+                    reasons: WithdrawReasons::all(),
+                }],
+            );
             assert_eq!(mutation.sub_up_to_free_balance(&ALICE, 100), 60);
         })
 }
@@ -38,10 +44,38 @@ fn sub_free_balance_honors_frozen() {
         .build()
         .execute_with(|| {
             let mut mutation = Mutation::<Test>::new_for_currency(TEST_TOKEN_ID);
-            assert_ok!(mutation.add_frozen(&ALICE, 40));
+            mutation.overwrite_frozen_balances(
+                &ALICE,
+                vec![TokenLock {
+                    amount: 40,
+                    reasons: WithdrawReasons::all(),
+                }],
+            );
+            assert_noop!(
+                mutation.sub_free_balance(&ALICE, 100, WithdrawReasons::all()),
+                Error::<Test>::BalanceLockTriggered
+            );
+        })
+}
+
+#[test]
+fn sub_free_balance_only_checks_relevant_reasons() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let mut mutation = Mutation::<Test>::new_for_currency(TEST_TOKEN_ID);
+            mutation.overwrite_frozen_balances(
+                &ALICE,
+                vec![TokenLock {
+                    amount: 40,
+                    reasons: WithdrawReasons::TRANSFER,
+                }],
+            );
             assert_noop!(
-                mutation.sub_free_balance(&ALICE, 100),
+                mutation.sub_free_balance(&ALICE, 100, WithdrawReasons::TRANSFER),
                 Error::<Test>::BalanceLockTriggered
             );
+            assert_ok!(mutation.sub_free_balance(&ALICE, 100, WithdrawReasons::FEE));
         })
 }
@@ -0,0 +1,203 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{Error, NamedReserves};
+use frame_support::{assert_noop, assert_ok, traits::BalanceStatus, StorageDoubleMap};
+use governance_os_support::{
+    testing::{primitives::AccountId, ALICE, BOB, TEST_TOKEN_ID},
+    traits::{Currencies, NamedReservableCurrencies, ReservableCurrencies, ReserveIdentifier},
+};
+
+const DEPOSIT: ReserveIdentifier = *b"deposit ";
+const ESCROW: ReserveIdentifier = *b"escrow  ";
+
+#[test]
+fn reserve_named_adds_to_reserved_balance() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::reserve_named(TEST_TOKEN_ID, DEPOSIT, &ALICE, 30));
+            assert_ok!(Tokens::reserve_named(TEST_TOKEN_ID, ESCROW, &ALICE, 20));
+            assert_ok!(Tokens::reserve_named(TEST_TOKEN_ID, DEPOSIT, &ALICE, 5));
+
+            assert_eq!(
+                Tokens::reserved_balance_named(TEST_TOKEN_ID, DEPOSIT, &ALICE),
+                35
+            );
+            assert_eq!(
+                Tokens::reserved_balance_named(TEST_TOKEN_ID, ESCROW, &ALICE),
+                20
+            );
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE), 55);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), 45);
+        })
+}
+
+#[test]
+fn reserve_named_fails_if_balance_too_low() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Tokens::reserve_named(TEST_TOKEN_ID, DEPOSIT, &ALICE, 101),
+                Error::<Test>::BalanceTooLow
+            );
+            assert_eq!(
+                Tokens::reserved_balance_named(TEST_TOKEN_ID, DEPOSIT, &ALICE),
+                0
+            );
+        })
+}
+
+#[test]
+fn unreserve_named_only_moves_its_own_funds() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::reserve_named(TEST_TOKEN_ID, DEPOSIT, &ALICE, 30));
+            assert_ok!(Tokens::reserve_named(TEST_TOKEN_ID, ESCROW, &ALICE, 20));
+
+            assert_eq!(
+                Tokens::unreserve_named(TEST_TOKEN_ID, DEPOSIT, &ALICE, 40),
+                10
+            );
+            assert_eq!(
+                Tokens::reserved_balance_named(TEST_TOKEN_ID, DEPOSIT, &ALICE),
+                0
+            );
+            assert_eq!(
+                Tokens::reserved_balance_named(TEST_TOKEN_ID, ESCROW, &ALICE),
+                20
+            );
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE), 20);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), 80);
+            assert!(!NamedReserves::<Test>::contains_key(
+                (ALICE, TEST_TOKEN_ID),
+                DEPOSIT
+            ));
+        })
+}
+
+#[test]
+fn slash_reserved_named_only_slashes_its_own_funds() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::reserve_named(TEST_TOKEN_ID, DEPOSIT, &ALICE, 30));
+            assert_ok!(Tokens::reserve_named(TEST_TOKEN_ID, ESCROW, &ALICE, 20));
+
+            assert_eq!(
+                Tokens::slash_reserved_named(TEST_TOKEN_ID, ESCROW, &ALICE, 25),
+                5
+            );
+            assert_eq!(
+                Tokens::reserved_balance_named(TEST_TOKEN_ID, DEPOSIT, &ALICE),
+                30
+            );
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE), 30);
+            assert_eq!(Tokens::total_issuance(TEST_TOKEN_ID), 180);
+        })
+}
+
+#[test]
+fn repatriate_reserved_named_to_free() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::reserve_named(TEST_TOKEN_ID, DEPOSIT, &ALICE, 30));
+            assert_ok!(Tokens::reserve(TEST_TOKEN_ID, &ALICE, 10));
+
+            assert_eq!(
+                Tokens::repatriate_reserved_named(
+                    TEST_TOKEN_ID,
+                    DEPOSIT,
+                    &ALICE,
+                    &BOB,
+                    40,
+                    BalanceStatus::Free
+                ),
+                Ok(10)
+            );
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE), 10);
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &BOB), 130);
+            assert_eq!(
+                Tokens::reserved_balance_named(TEST_TOKEN_ID, DEPOSIT, &BOB),
+                0
+            );
+        })
+}
+
+#[test]
+fn repatriate_reserved_named_to_reserved_keeps_name() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::reserve_named(TEST_TOKEN_ID, DEPOSIT, &ALICE, 30));
+
+            assert_eq!(
+                <Tokens as NamedReservableCurrencies<AccountId>>::repatriate_reserved_named(
+                    TEST_TOKEN_ID,
+                    DEPOSIT,
+                    &ALICE,
+                    &BOB,
+                    20,
+                    BalanceStatus::Reserved
+                ),
+                Ok(0)
+            );
+            assert_eq!(
+                Tokens::reserved_balance_named(TEST_TOKEN_ID, DEPOSIT, &ALICE),
+                10
+            );
+            assert_eq!(
+                Tokens::reserved_balance_named(TEST_TOKEN_ID, DEPOSIT, &BOB),
+                20
+            );
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &BOB), 20);
+        })
+}
+
+#[test]
+fn do_not_reap_accounts_with_reserved_funds() {
+    ExtBuilder::default()
+        .existential_deposit(10)
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Tokens::reserve_named(TEST_TOKEN_ID, DEPOSIT, &ALICE, 5));
+            assert_ok!(<Tokens as Currencies<AccountId>>::transfer(
+                TEST_TOKEN_ID,
+                &ALICE,
+                &BOB,
+                93
+            ));
+
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &ALICE), 2);
+            assert_eq!(Tokens::reserved_balance(TEST_TOKEN_ID, &ALICE), 5);
+            assert_eq!(
+                NamedReserves::<Test>::get((ALICE, TEST_TOKEN_ID), DEPOSIT),
+                5
+            );
+            assert_eq!(Tokens::total_issuance(TEST_TOKEN_ID), 200);
+        })
+}
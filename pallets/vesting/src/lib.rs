@@ -0,0 +1,237 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! This pallet lets accounts transfer coins that unlock progressively over time, for
+//! instance to distribute tokens to contributors. Each vesting schedule starts at a
+//! given block, may have a cliff during which nothing unlocks, and then unlocks a fixed
+//! amount of coins every block.
+//!
+//! An account can have several schedules per currency, they are all backed by a single
+//! lock maintained through the `LockableCurrencies` trait. The lock is only reduced when
+//! the account claims the coins that were released. Vesting coins can still be used to
+//! pay for transaction fees.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    storage::{with_transaction, TransactionOutcome},
+    traits::{Get, LockIdentifier, WithdrawReasons},
+    weights::Weight,
+};
+use frame_system::ensure_signed;
+use governance_os_support::traits::{Currencies, LockableCurrencies};
+use sp_runtime::{
+    traits::{Convert, Saturating, StaticLookup, Zero},
+    DispatchError,
+};
+use sp_std::prelude::*;
+
+mod default_weights;
+#[cfg(test)]
+mod tests;
+mod types;
+
+pub use types::VestingSchedule;
+
+pub const VESTING_LOCK_ID: LockIdentifier = *b"vesting ";
+
+pub trait WeightInfo {
+    fn vested_transfer(s: u32) -> Weight;
+    fn claim(s: u32) -> Weight;
+}
+
+pub trait Config: frame_system::Config {
+    /// Because this pallet emits events, it depends on the runtime's definition of an event.
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
+
+    /// Pallet in charge of currencies. Used so that we can lock the vesting coins.
+    type Currencies: LockableCurrencies<Self::AccountId>;
+
+    /// Used to compute how many coins were released since the start of a schedule.
+    type BlockNumberToBalance: Convert<Self::BlockNumber, BalanceOf<Self>>;
+
+    /// Maximum number of vesting schedules an account can have for a given currency.
+    type MaxVestingSchedules: Get<u32>;
+
+    /// Minimum amount of coins that can be transferred under a vesting schedule, this
+    /// prevents filling the schedules of an account with dust.
+    type MinVestedTransfer: Get<BalanceOf<Self>>;
+
+    /// Weight values for this pallet.
+    type WeightInfo: WeightInfo;
+}
+
+type BalanceOf<T> =
+    <<T as Config>::Currencies as Currencies<<T as frame_system::Config>::AccountId>>::Balance;
+type CurrencyIdOf<T> =
+    <<T as Config>::Currencies as Currencies<<T as frame_system::Config>::AccountId>>::CurrencyId;
+type VestingScheduleOf<T> = VestingSchedule<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+decl_storage! {
+    trait Store for Module<T: Config> as Vesting {
+        /// Vesting schedules of an account for a given currency. Schedules are removed once all
+        /// their coins have been claimed.
+        pub Schedules get(fn schedules): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) CurrencyIdOf<T> => Vec<VestingScheduleOf<T>>;
+    }
+    add_extra_genesis {
+        /// Schedules locking coins endowed by the tokens pallet's genesis.
+        config(vesting): Vec<(CurrencyIdOf<T>, T::AccountId, VestingScheduleOf<T>)>;
+        build(|config: &GenesisConfig<T>| {
+            config.vesting.iter().cloned().for_each(|(currency_id, who, schedule)| {
+                assert!(schedule.is_valid(), "invalid vesting schedule");
+                assert!(
+                    (Schedules::<T>::decode_len(&who, currency_id).unwrap_or(0) as u32) < T::MaxVestingSchedules::get(),
+                    "too many vesting schedules"
+                );
+                Schedules::<T>::append(&who, currency_id, schedule);
+                assert!(Module::<T>::update_lock(currency_id, &who).is_ok());
+            });
+        })
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        AccountId = <T as frame_system::Config>::AccountId,
+        Balance = BalanceOf<T>,
+        BlockNumber = <T as frame_system::Config>::BlockNumber,
+        CurrencyId = CurrencyIdOf<T>,
+    {
+        /// Some coins were transferred under a vesting schedule.
+        /// \[from, to, currency, schedule\]
+        VestingScheduleAdded(
+            AccountId,
+            AccountId,
+            CurrencyId,
+            VestingSchedule<Balance, BlockNumber>,
+        ),
+        /// An account claimed its released coins. \[who, currency, still locked\]
+        Claimed(AccountId, CurrencyId, Balance),
+    }
+);
+
+decl_error! {
+    pub enum Error for Module<T: Config> {
+        /// Vesting schedules must lock some coins and release some every block.
+        InvalidSchedule,
+        /// The amount transferred is below `MinVestedTransfer`.
+        AmountLow,
+        /// The account already has `MaxVestingSchedules` schedules for this currency.
+        TooManySchedules,
+        /// The account has no vesting schedule for this currency.
+        NotVesting,
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Config> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+
+        fn deposit_event() = default;
+
+        /// Transfer `schedule.total` coins of `currency` from the caller to `dest` and lock them
+        /// until they are released according to `schedule`.
+        #[weight = T::WeightInfo::vested_transfer(T::MaxVestingSchedules::get())]
+        pub fn vested_transfer(
+            origin,
+            currency: CurrencyIdOf<T>,
+            dest: <T::Lookup as StaticLookup>::Source,
+            schedule: VestingScheduleOf<T>,
+        ) {
+            let who = ensure_signed(origin)?;
+            let dest = T::Lookup::lookup(dest)?;
+
+            ensure!(schedule.is_valid(), Error::<T>::InvalidSchedule);
+            ensure!(schedule.total >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+            ensure!(
+                (Schedules::<T>::decode_len(&dest, currency).unwrap_or(0) as u32) < T::MaxVestingSchedules::get(),
+                Error::<T>::TooManySchedules
+            );
+
+            // The coins must not be left unlocked in `dest` if we fail to lock them.
+            with_transaction(|| {
+                let res = T::Currencies::transfer(currency, &who, &dest, schedule.total)
+                    .and_then(|_| {
+                        Schedules::<T>::append(&dest, currency, schedule.clone());
+                        Self::update_lock(currency, &dest).map(|_| ())
+                    });
+                match res {
+                    Ok(()) => TransactionOutcome::Commit(Ok(())),
+                    Err(e) => TransactionOutcome::Rollback(Err(e)),
+                }
+            })?;
+
+            Self::deposit_event(RawEvent::VestingScheduleAdded(who, dest, currency, schedule));
+        }
+
+        /// Unlock the coins of `currency` released by the caller's vesting schedules so far.
+        #[weight = T::WeightInfo::claim(T::MaxVestingSchedules::get())]
+        pub fn claim(origin, currency: CurrencyIdOf<T>) {
+            let who = ensure_signed(origin)?;
+            ensure!(Schedules::<T>::contains_key(&who, currency), Error::<T>::NotVesting);
+
+            let locked = Self::update_lock(currency, &who)?;
+
+            Self::deposit_event(RawEvent::Claimed(who, currency, locked));
+        }
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// Amount of coins of `currency` still locked by the vesting schedules of `who`.
+    pub fn locked_balance(currency: CurrencyIdOf<T>, who: &T::AccountId) -> BalanceOf<T> {
+        let now = frame_system::Pallet::<T>::block_number();
+        Self::schedules(who, currency)
+            .iter()
+            .fold(Zero::zero(), |acc: BalanceOf<T>, schedule| {
+                acc.saturating_add(schedule.locked_at::<T::BlockNumberToBalance>(now))
+            })
+    }
+
+    /// Remove the schedules that were fully released and lock the coins of the other ones.
+    /// Returns the amount of coins that are still locked.
+    fn update_lock(
+        currency: CurrencyIdOf<T>,
+        who: &T::AccountId,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let now = frame_system::Pallet::<T>::block_number();
+        let mut locked: BalanceOf<T> = Zero::zero();
+        let mut schedules = Self::schedules(who, currency);
+        schedules.retain(|schedule| {
+            let schedule_locked = schedule.locked_at::<T::BlockNumberToBalance>(now);
+            locked = locked.saturating_add(schedule_locked);
+            !schedule_locked.is_zero()
+        });
+
+        if schedules.is_empty() {
+            Schedules::<T>::remove(who, currency);
+            T::Currencies::remove_lock(currency, VESTING_LOCK_ID, who)?;
+        } else {
+            Schedules::<T>::insert(who, currency, schedules);
+            T::Currencies::set_lock_with_reasons(
+                currency,
+                VESTING_LOCK_ID,
+                who,
+                locked,
+                WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE,
+            )?;
+        }
+
+        Ok(locked)
+    }
+}
@@ -0,0 +1,255 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::{Error, RawEvent, VestingSchedule, VESTING_LOCK_ID};
+use frame_support::{assert_noop, assert_ok, traits::WithdrawReasons};
+use governance_os_support::{
+    testing::{
+        primitives::{AccountId, Balance, BlockNumber},
+        ALICE, BOB, TEST_TOKEN_ID,
+    },
+    traits::{Currencies, LockableCurrencies},
+};
+
+fn schedule(
+    start: BlockNumber,
+    cliff: BlockNumber,
+    per_block: Balance,
+    total: Balance,
+) -> VestingSchedule<Balance, BlockNumber> {
+    VestingSchedule {
+        start,
+        cliff,
+        per_block,
+        total,
+    }
+}
+
+fn can_transfer(who: AccountId, amount: Balance) -> bool {
+    Tokens::ensure_can_withdraw(TEST_TOKEN_ID, &who, amount).is_ok()
+}
+
+#[test]
+fn vested_transfer_locks_coins() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Vesting::vested_transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                schedule(1, 0, 10, 50)
+            ));
+
+            assert_eq!(Tokens::free_balance(TEST_TOKEN_ID, &BOB), 150);
+            assert_eq!(Vesting::locked_balance(TEST_TOKEN_ID, &BOB), 50);
+            assert!(can_transfer(BOB, 100));
+            assert!(!can_transfer(BOB, 101));
+            assert_eq!(
+                Tokens::locks((BOB, TEST_TOKEN_ID), VESTING_LOCK_ID)
+                    .unwrap()
+                    .amount,
+                50
+            );
+        })
+}
+
+#[test]
+fn vested_transfer_fails_with_invalid_schedule() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Vesting::vested_transfer(
+                    Origin::signed(ALICE),
+                    TEST_TOKEN_ID,
+                    BOB,
+                    schedule(1, 0, 0, 50)
+                ),
+                Error::<Test>::InvalidSchedule
+            );
+            assert_noop!(
+                Vesting::vested_transfer(
+                    Origin::signed(ALICE),
+                    TEST_TOKEN_ID,
+                    BOB,
+                    schedule(1, 0, 10, 0)
+                ),
+                Error::<Test>::InvalidSchedule
+            );
+        })
+}
+
+#[test]
+fn vested_transfer_fails_if_amount_too_low() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Vesting::vested_transfer(
+                    Origin::signed(ALICE),
+                    TEST_TOKEN_ID,
+                    BOB,
+                    schedule(1, 0, 1, 4)
+                ),
+                Error::<Test>::AmountLow
+            );
+        })
+}
+
+#[test]
+fn vested_transfer_fails_with_too_many_schedules() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            for _ in 0..2 {
+                assert_ok!(Vesting::vested_transfer(
+                    Origin::signed(ALICE),
+                    TEST_TOKEN_ID,
+                    BOB,
+                    schedule(1, 0, 1, 10)
+                ));
+            }
+            assert_noop!(
+                Vesting::vested_transfer(
+                    Origin::signed(ALICE),
+                    TEST_TOKEN_ID,
+                    BOB,
+                    schedule(1, 0, 1, 10)
+                ),
+                Error::<Test>::TooManySchedules
+            );
+        })
+}
+
+#[test]
+fn claim_releases_unlocked_coins() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Vesting::vested_transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                schedule(1, 0, 10, 50)
+            ));
+
+            System::set_block_number(3);
+            // Nothing is unlocked until claimed
+            assert!(!can_transfer(BOB, 101));
+
+            assert_ok!(Vesting::claim(Origin::signed(BOB), TEST_TOKEN_ID));
+            assert!(can_transfer(BOB, 120));
+            assert!(!can_transfer(BOB, 121));
+            assert!(System::events().iter().any(|record| record.event
+                == Event::governance_os_pallet_vesting(RawEvent::Claimed(BOB, TEST_TOKEN_ID, 30))));
+        })
+}
+
+#[test]
+fn nothing_is_released_before_the_cliff() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Vesting::vested_transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                schedule(1, 5, 10, 100)
+            ));
+
+            System::set_block_number(5);
+            assert_eq!(Vesting::locked_balance(TEST_TOKEN_ID, &BOB), 100);
+
+            // Coins released during the cliff are available at once
+            System::set_block_number(6);
+            assert_eq!(Vesting::locked_balance(TEST_TOKEN_ID, &BOB), 50);
+        })
+}
+
+#[test]
+fn fully_released_schedules_are_removed() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Vesting::vested_transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                schedule(1, 0, 10, 50)
+            ));
+            assert_ok!(Vesting::vested_transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                schedule(1, 0, 5, 50)
+            ));
+
+            System::set_block_number(6);
+            assert_ok!(Vesting::claim(Origin::signed(BOB), TEST_TOKEN_ID));
+            assert_eq!(
+                Vesting::schedules(BOB, TEST_TOKEN_ID),
+                vec![schedule(1, 0, 5, 50)]
+            );
+            assert_eq!(Vesting::locked_balance(TEST_TOKEN_ID, &BOB), 25);
+
+            System::set_block_number(11);
+            assert_ok!(Vesting::claim(Origin::signed(BOB), TEST_TOKEN_ID));
+            assert!(Vesting::schedules(BOB, TEST_TOKEN_ID).is_empty());
+            assert_eq!(Tokens::locks((BOB, TEST_TOKEN_ID), VESTING_LOCK_ID), None);
+            assert!(can_transfer(BOB, 200));
+        })
+}
+
+#[test]
+fn claim_fails_if_not_vesting() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Vesting::claim(Origin::signed(BOB), TEST_TOKEN_ID),
+            Error::<Test>::NotVesting
+        );
+    })
+}
+
+#[test]
+fn vesting_coins_can_pay_fees() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Vesting::vested_transfer(
+                Origin::signed(ALICE),
+                TEST_TOKEN_ID,
+                BOB,
+                schedule(1, 0, 10, 50)
+            ));
+
+            assert_ok!(Tokens::ensure_can_withdraw_with_reasons(
+                TEST_TOKEN_ID,
+                &BOB,
+                150,
+                WithdrawReasons::TRANSACTION_PAYMENT
+            ));
+        })
+}
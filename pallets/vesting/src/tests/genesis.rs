@@ -0,0 +1,63 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::mock::*;
+use crate::VestingSchedule;
+use governance_os_support::{
+    testing::{ALICE, TEST_TOKEN_ID},
+    traits::Currencies,
+};
+
+#[test]
+fn genesis_schedules_lock_endowed_coins() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .vesting(vec![(
+            TEST_TOKEN_ID,
+            ALICE,
+            VestingSchedule {
+                start: 10,
+                cliff: 0,
+                per_block: 1,
+                total: 60,
+            },
+        )])
+        .build()
+        .execute_with(|| {
+            assert_eq!(Vesting::locked_balance(TEST_TOKEN_ID, &ALICE), 60);
+            assert!(Tokens::ensure_can_withdraw(TEST_TOKEN_ID, &ALICE, 40).is_ok());
+            assert!(Tokens::ensure_can_withdraw(TEST_TOKEN_ID, &ALICE, 41).is_err());
+        })
+}
+
+#[test]
+#[should_panic(expected = "too many vesting schedules")]
+fn genesis_fails_with_too_many_schedules() {
+    let schedule = VestingSchedule {
+        start: 10,
+        cliff: 0,
+        per_block: 1,
+        total: 10,
+    };
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .vesting(vec![
+            (TEST_TOKEN_ID, ALICE, schedule.clone()),
+            (TEST_TOKEN_ID, ALICE, schedule.clone()),
+            (TEST_TOKEN_ID, ALICE, schedule),
+        ])
+        .build();
+}
@@ -0,0 +1,282 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate as governance_os_pallet_vesting;
+use crate::VestingSchedule;
+use codec::{Decode, Encode};
+use frame_support::{construct_runtime, parameter_types, traits::GenesisBuild};
+use governance_os_pallet_tokens::CurrencyDetails;
+use governance_os_support::{
+    impl_enum_default,
+    testing::{
+        primitives::{AccountId, Balance, BlockNumber, CurrencyId},
+        AutoCurrencyIds, ALICE, BOB, TEST_TOKEN_ID, TEST_TOKEN_OWNER,
+    },
+};
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{ConvertInto, IdentityLookup},
+    RuntimeDebug,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Module, Call, Config, Storage, Event<T>},
+        Bylaws: governance_os_pallet_bylaws::{Module, Call, Storage, Event<T>},
+        Tokens: governance_os_pallet_tokens::{Module, Call, Storage, Event<T>},
+        Vesting: governance_os_pallet_vesting::{Module, Call, Storage, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub BlockWeights: frame_system::limits::BlockWeights =
+        frame_system::limits::BlockWeights::simple_max(1024);
+    pub static ExistentialDeposit: u64 = 0;
+}
+impl frame_system::Config for Test {
+    type BaseCallFilter = ();
+    type BlockWeights = BlockWeights;
+    type BlockLength = ();
+    type DbWeight = ();
+    type Origin = Origin;
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Call = Call;
+    type Hash = H256;
+    type Hashing = ::sp_runtime::traits::BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+}
+#[derive(
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    Encode,
+    Decode,
+    Copy,
+    Clone,
+    Serialize,
+    Deserialize,
+    Ord,
+    PartialOrd,
+)]
+pub enum MockRoles {
+    Root,
+    CreateCurrencies,
+    TransferCurrency(CurrencyId),
+    ReceiveCurrency(CurrencyId),
+    ManageCurrency(CurrencyId),
+}
+impl_enum_default!(MockRoles, Root);
+impl governance_os_pallet_bylaws::RoleBuilder for MockRoles {
+    type Role = MockRoles;
+
+    fn manage_roles() -> MockRoles {
+        Self::root()
+    }
+
+    fn root() -> MockRoles {
+        MockRoles::Root
+    }
+}
+
+parameter_types! {
+    pub const RootRole: MockRoles = MockRoles::Root;
+    pub const MaxRoles: u32 = 5;
+    pub const MaxRoleAncestors: u32 = 4;
+}
+
+impl governance_os_pallet_bylaws::Config for Test {
+    type Event = Event;
+    type Role = MockRoles;
+    type WeightInfo = ();
+    type MaxRoles = MaxRoles;
+    type MaxRoleAncestors = MaxRoleAncestors;
+    type RoleBuilder = MockRoles;
+}
+
+impl governance_os_pallet_tokens::RoleBuilder for MockRoles {
+    type CurrencyId = CurrencyId;
+    type Role = Self;
+
+    fn transfer_currency(id: CurrencyId) -> Self {
+        Self::TransferCurrency(id)
+    }
+
+    fn receive_currency(id: CurrencyId) -> Self {
+        Self::ReceiveCurrency(id)
+    }
+
+    fn manage_currency(id: CurrencyId) -> Self {
+        Self::ManageCurrency(id)
+    }
+
+    fn create_currencies() -> Self {
+        Self::CreateCurrencies
+    }
+
+    fn manage_fee_rates() -> Self {
+        Self::Root
+    }
+}
+
+parameter_types! {
+    pub const MaxCurrencyIdSkips: u32 = 16;
+    pub const MaxCheckpoints: u32 = 16;
+}
+
+impl governance_os_pallet_tokens::Config for Test {
+    type Event = Event;
+    type CurrencyId = CurrencyId;
+    type Balance = Balance;
+    type WeightInfo = ();
+    type NativeCurrencyId = ();
+    type NativeExistentialDeposit = ();
+    type AutoCurrencyId = AutoCurrencyIds;
+    type MaxCurrencyIdSkips = MaxCurrencyIdSkips;
+    type CurrencyDeposit = ();
+    type MetadataDepositPerByte = ();
+    type MaxMetadataLength = ();
+    type MaxIssuanceSchedules = ();
+    type MaxBatchSize = ();
+    type MaxCheckpoints = MaxCheckpoints;
+    type OnDust = ();
+    type OnBalanceChanged = ();
+    type RoleManager = Bylaws;
+    type RoleBuilder = MockRoles;
+}
+
+parameter_types! {
+    pub const MaxVestingSchedules: u32 = 2;
+    pub const MinVestedTransfer: Balance = 5;
+}
+
+impl governance_os_pallet_vesting::Config for Test {
+    type Event = Event;
+    type Currencies = Tokens;
+    type BlockNumberToBalance = ConvertInto;
+    type MaxVestingSchedules = MaxVestingSchedules;
+    type MinVestedTransfer = MinVestedTransfer;
+    type WeightInfo = ();
+}
+
+pub struct ExtBuilder {
+    endowed_accounts: Vec<(CurrencyId, AccountId, Balance)>,
+    vesting: Vec<(CurrencyId, AccountId, VestingSchedule<Balance, BlockNumber>)>,
+    test_token_details: CurrencyDetails<AccountId, Balance, BlockNumber>,
+}
+
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        Self {
+            endowed_accounts: vec![],
+            vesting: vec![],
+            test_token_details: CurrencyDetails {
+                owner: TEST_TOKEN_OWNER,
+                transferable: true,
+                restricted_receivers: false,
+                existential_deposit: 0,
+                max_supply: None,
+                issuance: None,
+            },
+        }
+    }
+}
+
+impl ExtBuilder {
+    pub fn balances(mut self, endowed_accounts: Vec<(CurrencyId, AccountId, Balance)>) -> Self {
+        self.endowed_accounts = endowed_accounts;
+        self
+    }
+
+    pub fn vesting(
+        mut self,
+        vesting: Vec<(CurrencyId, AccountId, VestingSchedule<Balance, BlockNumber>)>,
+    ) -> Self {
+        self.vesting = vesting;
+        self
+    }
+
+    pub fn one_hundred_for_alice_n_bob(self) -> Self {
+        self.balances(vec![(TEST_TOKEN_ID, ALICE, 100), (TEST_TOKEN_ID, BOB, 100)])
+    }
+
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+
+        governance_os_pallet_bylaws::GenesisConfig::<Test> {
+            roles: vec![(MockRoles::CreateCurrencies, None)], // Everybody can create currencies
+            role_settings: vec![],
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        governance_os_pallet_tokens::GenesisConfig::<Test> {
+            endowed_accounts: self.endowed_accounts,
+            currency_details: vec![(TEST_TOKEN_ID, self.test_token_details, None)],
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        governance_os_pallet_vesting::GenesisConfig::<Test> {
+            vesting: self.vesting,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        let mut ext = sp_io::TestExternalities::new(t);
+        ext.execute_with(|| System::set_block_number(1));
+        ext
+    }
+}
@@ -0,0 +1,63 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Type definitions for the vesting pallet.
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, Convert},
+    RuntimeDebug,
+};
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VestingSchedule<Balance, BlockNumber> {
+    /// Block from which the coins start to unlock.
+    pub start: BlockNumber,
+    /// Number of blocks after `start` during which nothing can be claimed. Coins that
+    /// unlocked during the cliff can all be claimed once it is over.
+    pub cliff: BlockNumber,
+    /// Amount of coins unlocked every block.
+    pub per_block: Balance,
+    /// Amount of coins locked by the schedule when it is created.
+    pub total: Balance,
+}
+
+impl<Balance, BlockNumber> VestingSchedule<Balance, BlockNumber>
+where
+    Balance: AtLeast32BitUnsigned + Copy,
+    BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+    /// Schedules locking nothing or never unlocking anything are refused.
+    pub fn is_valid(&self) -> bool {
+        !self.total.is_zero() && !self.per_block.is_zero()
+    }
+
+    /// Amount of coins still locked at block `now`.
+    pub fn locked_at<BlockNumberToBalance: Convert<BlockNumber, Balance>>(
+        &self,
+        now: BlockNumber,
+    ) -> Balance {
+        if now < self.start.saturating_add(self.cliff) {
+            return self.total;
+        }
+        let elapsed = BlockNumberToBalance::convert(now.saturating_sub(self.start));
+        self.total
+            .saturating_sub(self.per_block.saturating_mul(elapsed))
+    }
+}
@@ -73,6 +73,7 @@ pub enum Role {
     CreateOrganizations,
     ManageCurrency(CurrencyId),
     ManageRoles,
+    ReceiveCurrency(CurrencyId),
     Root,
     TransferCurrency(CurrencyId),
 }
@@ -87,6 +88,10 @@ impl governance_os_pallet_tokens::RoleBuilder for Role {
         Role::TransferCurrency(id)
     }
 
+    fn receive_currency(id: CurrencyId) -> Role {
+        Role::ReceiveCurrency(id)
+    }
+
     fn manage_currency(id: CurrencyId) -> Role {
         Role::ManageCurrency(id)
     }
@@ -94,6 +99,10 @@ impl governance_os_pallet_tokens::RoleBuilder for Role {
     fn create_currencies() -> Role {
         Role::CreateCurrencies
     }
+
+    fn manage_fee_rates() -> Role {
+        Role::Root
+    }
 }
 impl governance_os_pallet_bylaws::RoleBuilder for Role {
     type Role = Role;
@@ -28,7 +28,10 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use frame_support::{construct_runtime, traits::Randomness};
-use governance_os_primitives::{AccountId, Balance, BlockNumber, Index, Signature};
+use governance_os_pallet_tokens::CurrencyMetadata;
+use governance_os_primitives::{
+    AccountId, Balance, BlockNumber, CurrencyId, Hash, Index, Signature,
+};
 use pallet_grandpa::fg_primitives;
 use pallet_grandpa::AuthorityList as GrandpaAuthorityList;
 use sp_api::impl_runtime_apis;
@@ -51,12 +54,13 @@ mod version;
 mod voting_router;
 
 pub use pallets_consensus::{AuraId, GrandpaId, SessionKeys};
-pub use pallets_economics::{NativeCurrency, NativeCurrencyId};
+pub use pallets_economics::{NativeCurrency, NativeCurrencyId, NativeExistentialDeposit};
 #[cfg(feature = "std")]
 pub use version::native_version;
 pub use version::VERSION;
 pub use voting_router::{
-    CoinVotingParameters, PlcrVotingParameters, RuntimeVotingParameters, RuntimeVotingSystemId,
+    CoinVotingParameters, PlcrVotingParameters, RuntimeProposalTally, RuntimeVotingParameters,
+    RuntimeVotingSystemId,
 };
 
 construct_runtime!(
@@ -79,9 +83,11 @@ construct_runtime!(
         // Economics
         Tokens: governance_os_pallet_tokens::{Module, Call, Storage, Config<T>, Event<T>},
         TransactionPayment: pallet_transaction_payment::{Module, Storage},
+        Vesting: governance_os_pallet_vesting::{Module, Call, Storage, Config<T>, Event<T>},
 
         // dOrgs
         Bylaws: governance_os_pallet_bylaws::{Module, Call, Storage, Config<T>, Event<T>},
+        Delegation: governance_os_pallet_delegation::{Module, Call, Storage, Event<T>},
         CoinVoting: governance_os_pallet_coin_voting::{Module, Call, Storage},
         ConvictionVoting: governance_os_pallet_conviction_voting::{Module, Call, Storage},
         PlcrVoting: governance_os_pallet_plcr_voting::{Module, Call, Storage, Event<T>},
@@ -114,7 +120,8 @@ pub type SignedExtra = (
     frame_system::CheckEra<Runtime>,
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
-    pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    governance_os_pallet_tokens::SetFeeCurrency<Runtime>,
+    governance_os_pallet_organizations::ChargeSponsoredTransaction<Runtime>,
 );
 
 impl_runtime_apis! {
@@ -248,6 +255,18 @@ impl_runtime_apis! {
         }
     }
 
+    impl governance_os_pallet_organizations_rpc_runtime_api::VotingApi<Block, Hash, RuntimeProposalTally> for Runtime {
+        fn proposal_tally(proposal: Hash) -> Option<RuntimeProposalTally> {
+            Organizations::proposal_tally(proposal)
+        }
+    }
+
+    impl governance_os_pallet_tokens_rpc_runtime_api::TokensApi<Block, CurrencyId, CurrencyMetadata> for Runtime {
+        fn currency_metadata(currency_id: CurrencyId) -> Option<CurrencyMetadata> {
+            Tokens::metadata(currency_id)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn dispatch_benchmark(
@@ -14,12 +14,23 @@
  * limitations under the License.
  */
 
-use crate::{voting_router::RuntimeVotingRouter, Bylaws, Call, Event, Runtime, Tokens};
-use frame_support::parameter_types;
+use crate::{
+    pallets_core::BlockWeights, pallets_economics::CoreOrganization,
+    voting_router::RuntimeVotingRouter, Bylaws, Call, CoinVoting, ConvictionVoting, Delegation,
+    Event, NativeCurrency, NativeCurrencyId, PlcrVoting, Runtime, Tokens,
+};
+use frame_support::{
+    parameter_types,
+    traits::{Currency, OnUnbalanced},
+    weights::Weight,
+};
+use governance_os_pallet_tokens::NegativeImbalance;
 use governance_os_primitives::{Balance, Role};
+use sp_runtime::Perbill;
 
 parameter_types! {
     pub const MaxRoles: u32 = 50;
+    pub const MaxRoleAncestors: u32 = 16;
 }
 
 impl governance_os_pallet_bylaws::Config for Runtime {
@@ -27,11 +38,30 @@ impl governance_os_pallet_bylaws::Config for Runtime {
     type Role = Role;
     type WeightInfo = ();
     type MaxRoles = MaxRoles;
+    type MaxRoleAncestors = MaxRoleAncestors;
     type RoleBuilder = Role;
 }
 
+parameter_types! {
+    pub const MaxDelegators: u32 = 100;
+}
+
+impl governance_os_pallet_delegation::Config for Runtime {
+    type Event = Event;
+    type Currencies = Tokens;
+    type OnDelegationChanged = (CoinVoting, ConvictionVoting, PlcrVoting);
+    type MaxDelegators = MaxDelegators;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const MaxActiveVotes: u32 = 50;
+}
+
 impl governance_os_pallet_coin_voting::Config for Runtime {
     type Currencies = Tokens;
+    type Delegations = Delegation;
+    type MaxActiveVotes = MaxActiveVotes;
 }
 
 parameter_types! {
@@ -41,19 +71,47 @@ parameter_types! {
 impl governance_os_pallet_conviction_voting::Config for Runtime {
     type Currencies = Tokens;
     type Decay = Decay;
+    type Delegations = Delegation;
+    type MaxActiveVotes = MaxActiveVotes;
 }
 
 impl governance_os_pallet_plcr_voting::Config for Runtime {
     type Event = Event;
     type Currencies = Tokens;
+    type Delegations = Delegation;
+    type MaxActiveVotes = MaxActiveVotes;
 }
 
 parameter_types! {
+    // Kept in line with `TransactionByteFee` until the native currency units are settled.
+    pub const ProposalDepositPerByte: Balance = 1;
+    pub const MaxTitleLength: u32 = 128;
+    // Enough for a short text or an IPFS CID.
+    pub const MaxDescriptionLength: u32 = 1024;
     // For now we keep this value small to avoid extrinsics with abusive weights.
     // Future upgrades should bring this value up without increasing the maximum
     // block weight.
     pub const MaxVotes: u32 = 100;
     pub const MaxExecutors: u32 = 100;
+    pub const MaxGuardians: u32 = 100;
+    // Every limited currency adds a few storage accesses to `apply_as`.
+    pub const MaxSpendingLimits: u32 = 16;
+    // Leave most of the block to extrinsics, expired proposals that do not fit will
+    // be closed in the next blocks.
+    pub MaxAutoCloseWeight: Weight = Perbill::from_percent(20) * BlockWeights::get().max_block;
+    // Enough to vote, change one's vote and close the proposal.
+    pub const MaxSponsoredCallsPerProposal: u32 = 3;
+}
+
+/// Fees paid by organizations for others go to the core organization, like the other
+/// transaction fees.
+pub struct SponsoredFeesToCoreOrganization;
+impl OnUnbalanced<NegativeImbalance<Runtime, NativeCurrencyId>>
+    for SponsoredFeesToCoreOrganization
+{
+    fn on_nonzero_unbalanced(amount: NegativeImbalance<Runtime, NativeCurrencyId>) {
+        NativeCurrency::resolve_creating(&CoreOrganization::get(), amount);
+    }
 }
 
 impl governance_os_pallet_organizations::Config for Runtime {
@@ -62,7 +120,18 @@ impl governance_os_pallet_organizations::Config for Runtime {
     type RoleManager = Bylaws;
     type RoleBuilder = Role;
     type VotingRouter = RuntimeVotingRouter;
+    type Currencies = Tokens;
+    type DepositCurrencyId = NativeCurrencyId;
+    type ProposalDepositPerByte = ProposalDepositPerByte;
+    type MaxTitleLength = MaxTitleLength;
+    type MaxDescriptionLength = MaxDescriptionLength;
     type MaxVotes = MaxVotes;
     type MaxExecutors = MaxExecutors;
+    type MaxGuardians = MaxGuardians;
+    type MaxSpendingLimits = MaxSpendingLimits;
+    type MaxAutoCloseWeight = MaxAutoCloseWeight;
+    type MaxSponsoredCallsPerProposal = MaxSponsoredCallsPerProposal;
+    type NativeCurrency = NativeCurrency;
+    type OnSponsoredFees = SponsoredFeesToCoreOrganization;
     type WeightInfo = ();
 }
@@ -14,11 +14,35 @@
  * limitations under the License.
  */
 
-use crate::{Bylaws, Event, Runtime};
+use crate::{Bylaws, Event, Organizations, Runtime, Tokens};
 use frame_support::{parameter_types, weights::IdentityFee};
-use governance_os_pallet_tokens::NativeCurrencyAdapter;
-use governance_os_primitives::{Balance, CurrencyId, Role};
-use pallet_transaction_payment::CurrencyAdapter;
+use governance_os_pallet_tokens::{MultiCurrencyFees, NativeCurrencyAdapter, TransferDust};
+use governance_os_primitives::{AccountId, Balance, CurrencyId, Role};
+use sp_runtime::traits::{Convert, ConvertInto};
+
+parameter_types! {
+    pub const NativeCurrencyId: CurrencyId = CurrencyId::Native;
+    // Keeps creating accounts costly enough to avoid filling the storage with dust.
+    pub const NativeExistentialDeposit: Balance = 1_000;
+    // The core organization, created first, collects the dust of reaped accounts and the
+    // transaction fees.
+    pub CoreOrganization: AccountId = Organizations::org_id_for(0);
+    pub const CurrencyDeposit: Balance = 10_000;
+    pub const MetadataDepositPerByte: Balance = 1;
+    pub const MaxIssuanceSchedules: u32 = 32;
+    pub const MaxBatchSize: u32 = 256;
+    pub const MaxCurrencyIdSkips: u32 = 64;
+    pub const MaxCheckpoints: u32 = 512;
+    pub const MaxMetadataLength: u32 = 128;
+}
+
+/// Currencies created with `create_auto` are given `CurrencyId::Custom` ids.
+pub struct CustomCurrencyIds;
+impl Convert<u32, CurrencyId> for CustomCurrencyIds {
+    fn convert(index: u32) -> CurrencyId {
+        CurrencyId::Custom(index)
+    }
+}
 
 impl governance_os_pallet_tokens::Config for Runtime {
     type Event = Event;
@@ -27,10 +51,32 @@ impl governance_os_pallet_tokens::Config for Runtime {
     type WeightInfo = ();
     type RoleBuilder = Role;
     type RoleManager = Bylaws;
+    type NativeCurrencyId = NativeCurrencyId;
+    type NativeExistentialDeposit = NativeExistentialDeposit;
+    type AutoCurrencyId = CustomCurrencyIds;
+    type MaxCurrencyIdSkips = MaxCurrencyIdSkips;
+    type CurrencyDeposit = CurrencyDeposit;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type MaxMetadataLength = MaxMetadataLength;
+    type MaxIssuanceSchedules = MaxIssuanceSchedules;
+    type MaxBatchSize = MaxBatchSize;
+    type MaxCheckpoints = MaxCheckpoints;
+    type OnDust = TransferDust<Runtime, CoreOrganization>;
+    type OnBalanceChanged = ();
 }
 
 parameter_types! {
-    pub const NativeCurrencyId: CurrencyId = CurrencyId::Native;
+    pub const MaxVestingSchedules: u32 = 16;
+    pub const MinVestedTransfer: Balance = 10_000;
+}
+
+impl governance_os_pallet_vesting::Config for Runtime {
+    type Event = Event;
+    type Currencies = Tokens;
+    type BlockNumberToBalance = ConvertInto;
+    type MaxVestingSchedules = MaxVestingSchedules;
+    type MinVestedTransfer = MinVestedTransfer;
+    type WeightInfo = ();
 }
 
 /// The system's native currency, typically used to pay for fees.
@@ -41,7 +87,7 @@ parameter_types! {
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-    type OnChargeTransaction = CurrencyAdapter<NativeCurrency, ()>;
+    type OnChargeTransaction = MultiCurrencyFees<CoreOrganization>;
     type TransactionByteFee = TransactionByteFee;
     type WeightToFee = IdentityFee<Balance>;
     type FeeMultiplierUpdate = ();
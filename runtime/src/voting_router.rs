@@ -16,6 +16,7 @@
 
 use crate::{CoinVoting, ConvictionVoting, PlcrVoting};
 use codec::{Decode, Encode};
+use frame_support::weights::Weight;
 pub use governance_os_pallet_coin_voting::{
     VoteData as CoinVoteData, VotingParameters as CoinVotingParameters,
 };
@@ -26,7 +27,9 @@ pub use governance_os_pallet_plcr_voting::{
     VoteData as PlcrVoteData, VotingParameters as PlcrVotingParameters,
 };
 use governance_os_primitives::{AccountId, Balance, BlockNumber, CurrencyId, Hash};
-use governance_os_support::traits::{ProposalResult, StandardizedVoting, VotingRouter};
+use governance_os_support::traits::{
+    ProposalResult, ProposalTally, StandardizedVoting, VotingRouter,
+};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{DispatchError, DispatchResult, RuntimeDebug};
@@ -63,6 +66,9 @@ pub enum RuntimeVotingSystemId {
     PlcrVoting,
 }
 
+/// How the runtime reports the state of a proposal, whatever its voting system.
+pub type RuntimeProposalTally = ProposalTally<Balance, BlockNumber>;
+
 pub struct RuntimeVotingRouter;
 impl VotingRouter for RuntimeVotingRouter {
     type AccountId = AccountId;
@@ -70,22 +76,25 @@ impl VotingRouter for RuntimeVotingRouter {
     type Parameters = RuntimeVotingParameters;
     type ProposalId = Hash;
     type VoteData = RuntimeVoteData;
+    type Tally = RuntimeProposalTally;
+    type BlockNumber = BlockNumber;
 
     fn initiate(
         voting_system: Self::VotingSystemId,
         proposal: Self::ProposalId,
+        organization: &Self::AccountId,
         parameters: Self::Parameters,
     ) -> DispatchResult {
         match (voting_system, parameters) {
             (RuntimeVotingSystemId::CoinVoting, RuntimeVotingParameters::CoinVoting(params)) => {
-                CoinVoting::initiate(proposal, params)
+                CoinVoting::initiate(proposal, organization, params)
             }
             (
                 RuntimeVotingSystemId::ConvictionVoting,
                 RuntimeVotingParameters::ConvictionVoting(params),
-            ) => ConvictionVoting::initiate(proposal, params),
+            ) => ConvictionVoting::initiate(proposal, organization, params),
             (RuntimeVotingSystemId::PlcrVoting, RuntimeVotingParameters::PlcrVoting(params)) => {
-                PlcrVoting::initiate(proposal, params)
+                PlcrVoting::initiate(proposal, organization, params)
             }
             _ => Err("wrong voting system, voting parameters pair".into()),
         }
@@ -129,4 +138,44 @@ impl VotingRouter for RuntimeVotingRouter {
             RuntimeVotingSystemId::PlcrVoting => PlcrVoting::close(proposal),
         }
     }
+
+    fn tally(
+        voting_system: Self::VotingSystemId,
+        proposal: Self::ProposalId,
+    ) -> Option<Self::Tally> {
+        match voting_system {
+            RuntimeVotingSystemId::CoinVoting => CoinVoting::tally(proposal),
+            RuntimeVotingSystemId::ConvictionVoting => ConvictionVoting::tally(proposal),
+            RuntimeVotingSystemId::PlcrVoting => PlcrVoting::tally(proposal),
+        }
+    }
+
+    fn expiry(
+        voting_system: Self::VotingSystemId,
+        proposal: Self::ProposalId,
+    ) -> Option<Self::BlockNumber> {
+        match voting_system {
+            RuntimeVotingSystemId::CoinVoting => CoinVoting::expiry(proposal),
+            RuntimeVotingSystemId::ConvictionVoting => ConvictionVoting::expiry(proposal),
+            RuntimeVotingSystemId::PlcrVoting => PlcrVoting::expiry(proposal),
+        }
+    }
+
+    fn can_vote(
+        voting_system: Self::VotingSystemId,
+        proposal: Self::ProposalId,
+        voter: &Self::AccountId,
+    ) -> bool {
+        match voting_system {
+            RuntimeVotingSystemId::CoinVoting => CoinVoting::can_vote(proposal, voter),
+            RuntimeVotingSystemId::ConvictionVoting => ConvictionVoting::can_vote(proposal, voter),
+            RuntimeVotingSystemId::PlcrVoting => PlcrVoting::can_vote(proposal, voter),
+        }
+    }
+
+    fn vote_weight() -> Weight {
+        CoinVoting::vote_weight()
+            .max(ConvictionVoting::vote_weight())
+            .max(PlcrVoting::vote_weight())
+    }
 }
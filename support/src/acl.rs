@@ -17,6 +17,7 @@
 //! A set of common traits to define Access Control lists between pallets and
 //! runtime users.
 
+use frame_support::weights::Weight;
 use frame_system::{ensure_signed, RawOrigin};
 use sp_runtime::{DispatchError, DispatchResult};
 use sp_std::convert::Into;
@@ -40,14 +41,37 @@ impl Into<DispatchError> for AclError {
 pub trait RoleManager {
     type AccountId;
     type Role;
+    type BlockNumber;
 
     /// Should return `true` if `traget` has the role `role`. This can be the case
     /// if the role was granted directly to the target or if it was granted to all accounts.
     fn has_role(target: &Self::AccountId, role: Self::Role) -> bool;
 
+    /// Worst case weight of a call to `has_role`. Pallets calling `has_role` or
+    /// `ensure_has_role` should account for it in their own weights.
+    fn has_role_weight() -> Weight;
+
+    /// Same as `has_role` but also returns the weight the check actually used, which is at
+    /// most `has_role_weight`. Lets pallets refund what they charged in excess.
+    fn has_role_with_weight(target: &Self::AccountId, role: Self::Role) -> (bool, Weight) {
+        (Self::has_role(target, role), Self::has_role_weight())
+    }
+
     /// Grants `target` the role `role`. If target is `None` then it should give the role to
-    /// every account that exists or may exists on the chain.
-    fn grant_role(target: Option<&Self::AccountId>, role: Self::Role) -> DispatchResult;
+    /// every account that exists or may exists on the chain. The grant never expires.
+    fn grant_role(target: Option<&Self::AccountId>, role: Self::Role) -> DispatchResult {
+        Self::grant_role_with_validity(target, role, None, None)
+    }
+
+    /// Same as `grant_role` but the role is only effective from the block `valid_from` to the
+    /// block `valid_until`, both included. Leaving one of the bounds to `None` makes the grant
+    /// effective immediately or forever respectively.
+    fn grant_role_with_validity(
+        target: Option<&Self::AccountId>,
+        role: Self::Role,
+        valid_from: Option<Self::BlockNumber>,
+        valid_until: Option<Self::BlockNumber>,
+    ) -> DispatchResult;
 
     /// Should revoke the role `role` for `target`. If the role wasn't granted to `target` this
     /// should error.
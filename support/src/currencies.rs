@@ -20,7 +20,7 @@
 //! this one.
 
 use codec::FullCodec;
-use frame_support::traits::{BalanceStatus, LockIdentifier};
+use frame_support::traits::{BalanceStatus, LockIdentifier, WithdrawReasons};
 use sp_runtime::{
     traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize},
     DispatchError, DispatchResult,
@@ -163,6 +163,93 @@ pub trait ReservableCurrencies<AccountId>: Currencies<AccountId> {
     ) -> result::Result<Self::Balance, DispatchError>;
 }
 
+/// Identifier of a named reserve, similar to a `LockIdentifier`.
+pub type ReserveIdentifier = [u8; 8];
+
+/// An extension of the `ReservableCurrencies` trait to let subsystems reserve
+/// funds under their own identifier. Named reserves are part of the reserved
+/// balance of an account but each subsystem can only move the coins it reserved.
+pub trait NamedReservableCurrencies<AccountId>: ReservableCurrencies<AccountId> {
+    /// The amount of the reserved balance of `who` held under `id`.
+    fn reserved_balance_named(
+        currency_id: Self::CurrencyId,
+        id: ReserveIdentifier,
+        who: &AccountId,
+    ) -> Self::Balance;
+
+    /// Same as `reserve` but the funds are held under `id`.
+    fn reserve_named(
+        currency_id: Self::CurrencyId,
+        id: ReserveIdentifier,
+        who: &AccountId,
+        value: Self::Balance,
+    ) -> DispatchResult;
+
+    /// Same as `unreserve` but only moves funds held under `id`. Returns the amount
+    /// that could not be unreserved.
+    fn unreserve_named(
+        currency_id: Self::CurrencyId,
+        id: ReserveIdentifier,
+        who: &AccountId,
+        value: Self::Balance,
+    ) -> Self::Balance;
+
+    /// Same as `slash_reserved` but only slashes funds held under `id`. Returns the
+    /// amount that could not be slashed.
+    fn slash_reserved_named(
+        currency_id: Self::CurrencyId,
+        id: ReserveIdentifier,
+        who: &AccountId,
+        value: Self::Balance,
+    ) -> Self::Balance;
+
+    /// Same as `repatriate_reserved` but only moves funds held under `id`. If `status`
+    /// is `Reserved` the funds are held under `id` for `beneficiary` too.
+    fn repatriate_reserved_named(
+        currency_id: Self::CurrencyId,
+        id: ReserveIdentifier,
+        slashed: &AccountId,
+        beneficiary: &AccountId,
+        value: Self::Balance,
+        status: BalanceStatus,
+    ) -> result::Result<Self::Balance, DispatchError>;
+}
+
+/// An extension of the `Currencies` trait to let accounts spend coins on behalf
+/// of their owners, similar to ERC20 allowances. This can be used to pull coins
+/// from an account, for instance to collect subscription payments.
+pub trait AllowanceCurrencies<AccountId>: Currencies<AccountId> {
+    /// How many coins `spender` can still transfer out of the balance of `owner`.
+    fn allowance(
+        currency_id: Self::CurrencyId,
+        owner: &AccountId,
+        spender: &AccountId,
+    ) -> Self::Balance;
+
+    /// Sum of the allowances `owner` gave to other accounts.
+    fn total_allowance(currency_id: Self::CurrencyId, owner: &AccountId) -> Self::Balance;
+
+    /// Let `spender` transfer up to `amount` coins from the balance of `owner`.
+    /// This overwrites any existing allowance.
+    fn approve(
+        currency_id: Self::CurrencyId,
+        owner: &AccountId,
+        spender: &AccountId,
+        amount: Self::Balance,
+    ) -> DispatchResult;
+
+    /// Transfer `amount` coins from `owner` to `dest` and deduct them from the
+    /// allowance of `spender`. The transfer is subject to the same checks as if
+    /// it was made by `owner`.
+    fn transfer_from(
+        currency_id: Self::CurrencyId,
+        spender: &AccountId,
+        owner: &AccountId,
+        dest: &AccountId,
+        amount: Self::Balance,
+    ) -> DispatchResult;
+}
+
 /// An extension of the `Currencies` trait to allow the runtime to lock funds
 /// from the token holders. Locks should be combinable. Creating a lock shall
 /// increment any existing reference count, deleting one should decrement it.
@@ -171,7 +258,8 @@ pub trait LockableCurrencies<AccountId>: Currencies<AccountId> {
     fn locked_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance;
 
     /// Create a new lock on the balance of `who`. You can lock more coins
-    /// than the total balance of a user.
+    /// than the total balance of a user. The lock applies to all withdraw
+    /// reasons.
     ///
     /// NOTE: this should overwrite any existing lock with the same id.
     fn set_lock(
@@ -179,16 +267,50 @@ pub trait LockableCurrencies<AccountId>: Currencies<AccountId> {
         lock_id: LockIdentifier,
         who: &AccountId,
         amount: Self::Balance,
+    ) -> DispatchResult {
+        Self::set_lock_with_reasons(currency_id, lock_id, who, amount, WithdrawReasons::all())
+    }
+
+    /// Same as `set_lock` but the lock only applies to withdrawals made for
+    /// one of the `reasons`.
+    fn set_lock_with_reasons(
+        currency_id: Self::CurrencyId,
+        lock_id: LockIdentifier,
+        who: &AccountId,
+        amount: Self::Balance,
+        reasons: WithdrawReasons,
     ) -> DispatchResult;
 
     /// Extend an existing lock for `who` and `currency_id`. If the lock
     /// already exists it will take the maximum value between `amount` and
-    /// the existing one.
+    /// the existing one. The lock applies to all withdraw reasons.
     fn extend_lock(
         currency_id: Self::CurrencyId,
         lock_id: LockIdentifier,
         who: &AccountId,
         amount: Self::Balance,
+    ) -> DispatchResult {
+        Self::extend_lock_with_reasons(currency_id, lock_id, who, amount, WithdrawReasons::all())
+    }
+
+    /// Same as `extend_lock` but the lock only applies to withdrawals made for
+    /// one of the `reasons`. If the lock already exists its reasons are merged
+    /// with `reasons`.
+    fn extend_lock_with_reasons(
+        currency_id: Self::CurrencyId,
+        lock_id: LockIdentifier,
+        who: &AccountId,
+        amount: Self::Balance,
+        reasons: WithdrawReasons,
+    ) -> DispatchResult;
+
+    /// Same as `ensure_can_withdraw` but only the locks applying to one of the
+    /// `reasons` are taken into account.
+    fn ensure_can_withdraw_with_reasons(
+        currency_id: Self::CurrencyId,
+        who: &AccountId,
+        amount: Self::Balance,
+        reasons: WithdrawReasons,
     ) -> DispatchResult;
 
     /// Remove an existing lock.
@@ -198,3 +320,51 @@ pub trait LockableCurrencies<AccountId>: Currencies<AccountId> {
         who: &AccountId,
     ) -> DispatchResult;
 }
+
+/// An extension of the `Currencies` trait for implementations recording the past balances
+/// of the accounts, for instance so that votes can be counted from the coins held when a
+/// proposal was created instead of locking them.
+pub trait CheckpointedCurrencies<AccountId, BlockNumber>: Currencies<AccountId> {
+    /// The total balance of `who` at the end of `block`. Returns `None` if no checkpoints
+    /// were recorded for the currency at the time.
+    fn balance_at(
+        currency_id: Self::CurrencyId,
+        who: &AccountId,
+        block: BlockNumber,
+    ) -> Option<Self::Balance>;
+
+    /// The total issuance of the currency at the end of `block`. Returns `None` if no
+    /// checkpoints were recorded for the currency at the time.
+    fn total_issuance_at(
+        currency_id: Self::CurrencyId,
+        block: BlockNumber,
+    ) -> Option<Self::Balance>;
+}
+
+/// Hook called whenever the balance of an account changes in a currency, after the change
+/// was saved. Pallets keeping data derived from balances, such as delegations or balance
+/// checkpoints, implement it to stay up to date when coins move.
+pub trait OnBalanceChanged<AccountId, CurrencyId, Balance> {
+    /// `free` and `reserved` are the new balances of `who`, both are zero if the account
+    /// was removed.
+    fn on_balance_changed(
+        currency_id: CurrencyId,
+        who: &AccountId,
+        free: Balance,
+        reserved: Balance,
+    );
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AccountId, CurrencyId: Clone, Balance: Clone> OnBalanceChanged<AccountId, CurrencyId, Balance>
+    for Tuple
+{
+    fn on_balance_changed(
+        currency_id: CurrencyId,
+        who: &AccountId,
+        free: Balance,
+        reserved: Balance,
+    ) {
+        for_tuples!( #( Tuple::on_balance_changed(currency_id.clone(), who, free.clone(), reserved.clone()); )* );
+    }
+}
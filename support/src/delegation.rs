@@ -0,0 +1,112 @@
+/*
+ * Copyright 2020 Nuclei Studio OÜ
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A set of common traits to let accounts delegate their voting power to
+//! other accounts.
+
+use frame_support::weights::Weight;
+use sp_runtime::DispatchResult;
+use sp_std::prelude::Vec;
+
+/// Implemented by a pallet keeping track of voting power delegations. Voting
+/// pallets rely on it to count the voting power delegated to the accounts that
+/// vote on their proposals.
+pub trait Delegations<AccountId, CurrencyId, Balance> {
+    /// Returns the account to which `delegator` delegated its `currency` voting
+    /// power in the context of `organization`, along with the delegated amount.
+    /// Delegations made for `organization` take precedence over the ones made
+    /// for all the organizations.
+    fn delegation_of(
+        currency: CurrencyId,
+        organization: &AccountId,
+        delegator: &AccountId,
+    ) -> Option<(AccountId, Balance)>;
+
+    /// Returns all the accounts whose `currency` voting power is delegated to
+    /// `delegate` in the context of `organization`, along with the delegated
+    /// amounts.
+    fn delegators_of(
+        currency: CurrencyId,
+        organization: &AccountId,
+        delegate: &AccountId,
+    ) -> Vec<(AccountId, Balance)>;
+
+    /// Maximum number of accounts `delegators_of` can return.
+    fn max_delegators_of() -> u32;
+
+    /// Worst case weight of a call to `delegators_of`.
+    fn delegators_of_weight() -> Weight;
+}
+
+impl<AccountId, CurrencyId, Balance> Delegations<AccountId, CurrencyId, Balance> for () {
+    fn delegation_of(
+        _currency: CurrencyId,
+        _organization: &AccountId,
+        _delegator: &AccountId,
+    ) -> Option<(AccountId, Balance)> {
+        None
+    }
+
+    fn delegators_of(
+        _currency: CurrencyId,
+        _organization: &AccountId,
+        _delegate: &AccountId,
+    ) -> Vec<(AccountId, Balance)> {
+        Vec::new()
+    }
+
+    fn max_delegators_of() -> u32 {
+        0
+    }
+
+    fn delegators_of_weight() -> Weight {
+        0
+    }
+}
+
+/// Hook called whenever an account creates, updates or removes one of its
+/// delegations. Voting pallets implement it to recompute the tallies of the
+/// proposals voted on by `delegates`, the accounts whose votes the power of
+/// `delegator` may have been or may now be counted with.
+pub trait OnDelegationChanged<AccountId, CurrencyId> {
+    fn on_delegation_changed(
+        currency: CurrencyId,
+        delegator: &AccountId,
+        delegates: &[AccountId],
+    ) -> DispatchResult;
+
+    /// Worst case weight of a call to `on_delegation_changed` with `delegates`
+    /// delegates.
+    fn on_delegation_changed_weight(delegates: u32) -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AccountId, CurrencyId: Clone> OnDelegationChanged<AccountId, CurrencyId> for Tuple {
+    fn on_delegation_changed(
+        currency: CurrencyId,
+        delegator: &AccountId,
+        delegates: &[AccountId],
+    ) -> DispatchResult {
+        for_tuples!( #( Tuple::on_delegation_changed(currency.clone(), delegator, delegates)?; )* );
+        Ok(())
+    }
+
+    fn on_delegation_changed_weight(delegates: u32) -> Weight {
+        let mut weight: Weight = 0;
+        for_tuples!( #( weight = weight.saturating_add(Tuple::on_delegation_changed_weight(delegates)); )* );
+        weight
+    }
+}
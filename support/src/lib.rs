@@ -20,6 +20,7 @@
 
 mod acl;
 mod currencies;
+mod delegation;
 pub mod errors;
 pub mod testing;
 pub mod traits;
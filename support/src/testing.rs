@@ -16,6 +16,8 @@
 
 //! A series of helpers and common values used for tests.
 
+use sp_runtime::traits::Convert;
+
 pub const ROOT: primitives::AccountId = 0;
 pub const TEST_TOKEN_ID: primitives::CurrencyId = 1;
 pub const TEST_TOKEN_OWNER: primitives::AccountId = 2;
@@ -23,6 +25,7 @@ pub const ALICE: primitives::AccountId = 3;
 pub const BOB: primitives::AccountId = 4;
 pub const CHARLIE: primitives::AccountId = 5;
 pub const EVE: primitives::AccountId = 6;
+pub const TEST_ORGANIZATION: primitives::AccountId = 7;
 
 pub mod primitives {
     pub type AccountId = u128;
@@ -30,3 +33,12 @@ pub mod primitives {
     pub type CurrencyId = u8;
     pub type BlockNumber = u64;
 }
+
+/// Builds the currency ids allocated automatically by the tokens pallet by truncating
+/// their index, which is good enough for tests.
+pub struct AutoCurrencyIds;
+impl Convert<u32, primitives::CurrencyId> for AutoCurrencyIds {
+    fn convert(index: u32) -> primitives::CurrencyId {
+        index as primitives::CurrencyId
+    }
+}
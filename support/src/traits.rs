@@ -15,5 +15,11 @@
  */
 
 pub use crate::acl::RoleManager;
-pub use crate::currencies::{Currencies, LockableCurrencies, ReservableCurrencies};
-pub use crate::voting::{ProposalResult, StandardizedVoting, VotingRouter};
+pub use crate::currencies::{
+    AllowanceCurrencies, CheckpointedCurrencies, Currencies, LockableCurrencies,
+    NamedReservableCurrencies, OnBalanceChanged, ReservableCurrencies, ReserveIdentifier,
+};
+pub use crate::delegation::{Delegations, OnDelegationChanged};
+pub use crate::voting::{
+    voting_lock_reasons, ProposalResult, ProposalTally, StandardizedVoting, VotingRouter,
+};
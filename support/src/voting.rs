@@ -17,12 +17,21 @@
 //! A set of common traits to voting systems.
 
 use codec::{Decode, Encode};
-use frame_support::Parameter;
+use frame_support::{traits::WithdrawReasons, weights::Weight, Parameter};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_runtime::{DispatchError, DispatchResult, RuntimeDebug};
+use sp_runtime::{
+    traits::AtLeast32BitUnsigned, DispatchError, DispatchResult, Perbill, RuntimeDebug,
+};
 use sp_std::result;
 
+/// Reasons used by voting systems when locking the funds of voters. Locked funds
+/// can not be transferred or reserved but can still pay for transaction fees, so
+/// that voters are always able to close the proposals they voted on.
+pub fn voting_lock_reasons() -> WithdrawReasons {
+    WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE
+}
+
 /// End result of a proposal being closed.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -31,6 +40,76 @@ pub enum ProposalResult {
     Failing,
 }
 
+/// A normalized, read only view of how a proposal is doing. Returned by the
+/// different voting implementations so that clients do not have to decode
+/// each pallet's own storage.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProposalTally<Balance, BlockNumber> {
+    /// Voting power in favor of the proposal.
+    pub favorable: Balance,
+    /// Voting power against the proposal.
+    pub against: Balance,
+    /// Total voting power that participated in the proposal.
+    pub participation: Balance,
+    /// Participation that has to be exceeded for the proposal to pass.
+    pub participation_threshold: Balance,
+    /// Minimum participation, as a percentage of the total supply.
+    pub min_participation: u32,
+    /// Favorable power that has to be exceeded for the proposal to pass.
+    pub quorum_threshold: Balance,
+    /// Minimum quorum, as a percentage of the participation.
+    pub min_quorum: u32,
+    /// Block after which the proposal can be closed even if it is failing.
+    pub expiry: BlockNumber,
+    /// What `close` would return if it was called now, or `None` if the
+    /// proposal cannot be closed yet.
+    pub close_result: Option<ProposalResult>,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy, BlockNumber: PartialOrd>
+    ProposalTally<Balance, BlockNumber>
+{
+    /// Build a tally out of the raw vote counts of a proposal. A proposal passes if its
+    /// participation exceeds `min_participation` percent of `total_supply` and if its
+    /// favorable votes exceed `min_quorum` percent of the participation. It can be closed
+    /// as soon as it passes or once `now` is past `expiry`.
+    pub fn new(
+        favorable: Balance,
+        against: Balance,
+        total_supply: Balance,
+        min_participation: u32,
+        min_quorum: u32,
+        expiry: BlockNumber,
+        now: BlockNumber,
+    ) -> Self {
+        let participation = favorable.saturating_add(against);
+        let participation_threshold = Perbill::from_percent(min_participation) * total_supply;
+        let quorum_threshold = Perbill::from_percent(min_quorum) * participation;
+
+        let passing = participation > participation_threshold && favorable > quorum_threshold;
+        let close_result = if passing {
+            Some(ProposalResult::Passing)
+        } else if now > expiry {
+            Some(ProposalResult::Failing)
+        } else {
+            None
+        };
+
+        Self {
+            favorable,
+            against,
+            participation,
+            participation_threshold,
+            min_participation,
+            quorum_threshold,
+            min_quorum,
+            expiry,
+            close_result,
+        }
+    }
+}
+
 /// A common trait accross all voting implementations to make it easy to change
 /// between voting models or implementations.
 /// A pallet implementing this trait is not necessarily in charge of storing
@@ -51,11 +130,22 @@ pub trait StandardizedVoting {
     /// How accounts are represented, used to identify voters.
     type AccountId;
 
+    /// How the current state of a proposal is reported, typically a `ProposalTally`.
+    type Tally;
+
+    /// How block numbers are represented, used to report when proposals expire.
+    type BlockNumber;
+
     /// A proposal is being created. Handle any eventual registration and trigger
     /// an error if any preconditions are not met. Shall be called before any other
     /// state changes so that it is safe to fail here. It is the caller's responsibility
-    /// to try and prevent overwrites or duplicated proposals.
-    fn initiate(proposal: Self::ProposalId, parameters: Self::Parameters) -> DispatchResult;
+    /// to try and prevent overwrites or duplicated proposals. `organization` is the
+    /// organization the proposal was submitted to, used to resolve vote delegations.
+    fn initiate(
+        proposal: Self::ProposalId,
+        organization: &Self::AccountId,
+        parameters: Self::Parameters,
+    ) -> DispatchResult;
 
     /// Special function to handle the case when a proposal is being vetoed. This
     /// should clean any storage or state associated to the given proposal.
@@ -73,6 +163,22 @@ pub trait StandardizedVoting {
     /// some conditions are not met. Shall return an indicator on wether the proposal is
     /// passing (should be executed) or not (should be discarded).
     fn close(proposal: Self::ProposalId) -> result::Result<ProposalResult, DispatchError>;
+
+    /// Report the current state of a proposal without mutating any storage, including
+    /// what `close` would return if called now. Returns `None` if the proposal is unknown.
+    fn tally(proposal: Self::ProposalId) -> Option<Self::Tally>;
+
+    /// Return the last block of the voting period of a proposal. Once this block is over
+    /// `close` is expected to succeed. Returns `None` if the proposal is unknown.
+    fn expiry(proposal: Self::ProposalId) -> Option<Self::BlockNumber>;
+
+    /// Whether `voter` holds anything it could vote on the proposal with, without checking if
+    /// it already voted. Returns `false` if the proposal is unknown.
+    fn can_vote(proposal: Self::ProposalId, voter: &Self::AccountId) -> bool;
+
+    /// Worst case weight of a call to `vote`, including the voting power delegated to the
+    /// voter that has to be counted.
+    fn vote_weight() -> Weight;
 }
 
 /// Used to route votes and related actions between different voting system implementations.
@@ -96,11 +202,19 @@ pub trait VotingRouter {
     /// Typically an `enum` to account for all the different voting systems.
     type VoteData: Parameter;
 
+    /// How the runtime reports the state of a proposal accross all voting systems.
+    /// Typically a `ProposalTally`.
+    type Tally: Parameter;
+
+    /// How block numbers are represented, used to report when proposals expire.
+    type BlockNumber: Parameter;
+
     /// Route the `initiate` call to the right `StandardizedVoting` implementation based
     /// on the value of `voting_systems`.
     fn initiate(
         voting_system: Self::VotingSystemId,
         proposal: Self::ProposalId,
+        organization: &Self::AccountId,
         parameters: Self::Parameters,
     ) -> DispatchResult;
 
@@ -123,4 +237,30 @@ pub trait VotingRouter {
         voting_system: Self::VotingSystemId,
         proposal: Self::ProposalId,
     ) -> result::Result<ProposalResult, DispatchError>;
+
+    /// Route the `tally` call to the right `StandardizedVoting` implementation based
+    /// on the value of `voting_systems`.
+    fn tally(
+        voting_system: Self::VotingSystemId,
+        proposal: Self::ProposalId,
+    ) -> Option<Self::Tally>;
+
+    /// Route the `expiry` call to the right `StandardizedVoting` implementation based
+    /// on the value of `voting_systems`.
+    fn expiry(
+        voting_system: Self::VotingSystemId,
+        proposal: Self::ProposalId,
+    ) -> Option<Self::BlockNumber>;
+
+    /// Route the `can_vote` call to the right `StandardizedVoting` implementation based
+    /// on the value of `voting_systems`.
+    fn can_vote(
+        voting_system: Self::VotingSystemId,
+        proposal: Self::ProposalId,
+        voter: &Self::AccountId,
+    ) -> bool;
+
+    /// Worst case weight of a call to `vote` accross all the `StandardizedVoting`
+    /// implementations we route to.
+    fn vote_weight() -> Weight;
 }